@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 use clap::{
+    ArgAction,
     ArgMatches,
     Command,
     arg,
@@ -9,16 +10,18 @@ use clap::{
 use img::prelude::*;
 
 use crate::io::{
-    read_image,
+    read_image_with_metadata,
     write_image,
 };
 
 use crate::param::{
+    background,
     channel_flags::{
         self,
         ChannelFlags,
     },
     input,
+    metadata,
     output,
 };
 
@@ -80,6 +83,10 @@ fn gaussian_subcommand() -> Command {
                     .default_value("3")
                     .value_parser(value_parser!(f32)),
             )
+            .arg(
+                arg!(-p --premultiplied "blur with color premultiplied by alpha")
+                    .action(ArgAction::SetTrue),
+            )
             .arg(channel_flags::arg())
     }
 
@@ -100,6 +107,10 @@ fn gaussian_subcommand() -> Command {
                     .default_value("3")
                     .value_parser(value_parser!(f32)),
             )
+            .arg(
+                arg!(-p --premultiplied "blur with color premultiplied by alpha")
+                    .action(ArgAction::SetTrue),
+            )
             .arg(channel_flags::arg())
             .arg(threads::arg())
     }
@@ -114,13 +125,18 @@ pub fn subcommand() -> Command {
 }
 
 pub fn action(matches: &ArgMatches) -> anyhow::Result<()> {
-    let image = read_image(matches.get_one::<PathBuf>(input::ARG_NAME).unwrap())?;
+    let input_path = matches.get_one::<PathBuf>(input::ARG_NAME).unwrap();
+    let output_path = matches.get_one::<PathBuf>(output::ARG_NAME).unwrap();
+    let (image, metadata) = read_image_with_metadata(
+        input_path,
+        metadata::from_matches(matches, input_path, output_path),
+    )?;
     let image = match matches.subcommand().ok_or(anyhow::anyhow!("no subcommand provided"))? {
         (MEAN_CMD_NAME | MEAN_CMD_ALIAS1 | MEAN_CMD_ALIAS2, m) => apply_mean(&image, m)?,
         (GAUSSIAN_CMD_NAME | GAUSSIAN_CMD_ALIAS1, m) => apply_gauss(&image, m)?,
         _ => unreachable!(),
     };
-    write_image(&image, matches.get_one::<PathBuf>(output::ARG_NAME).unwrap())?;
+    write_image(&image, output_path, background::from_matches(matches), &metadata)?;
     Ok(())
 }
 
@@ -149,9 +165,13 @@ fn apply_gauss(image: &Image, matches: &ArgMatches) -> anyhow::Result<Image> {
     let target_radius = matches.get_one::<usize>("radius").unwrap();
     let sigma = matches.get_one::<f32>("sigma").unwrap();
     let channel_flags = *matches.get_one::<ChannelFlags>("flags").unwrap();
+    let alpha_mode = match matches.get_flag("premultiplied") {
+        true => AlphaMode::Premultiplied,
+        false => AlphaMode::Straight,
+    };
 
     #[cfg(not(feature = "parallel"))]
-    let image = gaussian_blur(image, *target_radius, *sigma, channel_flags.into())?;
+    let image = gaussian_blur(image, *target_radius, *sigma, channel_flags.into(), alpha_mode)?;
 
     #[cfg(feature = "parallel")]
     let image = {
@@ -161,7 +181,14 @@ fn apply_gauss(image: &Image, matches: &ArgMatches) -> anyhow::Result<Image> {
         };
 
         let threads = matches.get_one::<Threads>(threads::ARG_NAME).unwrap();
-        gaussian_blur_par(image, threads.number(), *target_radius, *sigma, channel_flags.into())?
+        gaussian_blur_par(
+            image,
+            threads.number(),
+            *target_radius,
+            *sigma,
+            channel_flags.into(),
+            alpha_mode,
+        )?
     };
 
     Ok(image)
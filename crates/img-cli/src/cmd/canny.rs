@@ -8,11 +8,13 @@ use img::prelude::*;
 
 use crate::{
     io::{
-        read_image,
+        read_image_with_metadata,
         write_image,
     },
     param::{
+        background,
         input,
+        metadata,
         output,
     },
 };
@@ -34,11 +36,15 @@ pub fn subcommand() -> Command {
 }
 
 pub fn action(matches: &ArgMatches) -> anyhow::Result<()> {
-    let image = read_image(matches.get_one::<PathBuf>(input::ARG_NAME).unwrap())?;
-    let image = canny(&image);
+    let input_path = matches.get_one::<PathBuf>(input::ARG_NAME).unwrap();
+    let output_path = matches.get_one::<PathBuf>(output::ARG_NAME).unwrap();
+    let (image, metadata) = read_image_with_metadata(
+        input_path,
+        metadata::from_matches(matches, input_path, output_path),
+    )?;
 
     #[cfg(not(feature = "parallel"))]
-    let image = canny(&image);
+    let image = canny(&image, CannyLensOptions::default())?;
 
     #[cfg(feature = "parallel")]
     let image = {
@@ -48,9 +54,9 @@ pub fn action(matches: &ArgMatches) -> anyhow::Result<()> {
         };
 
         let threads = matches.get_one::<Threads>(threads::ARG_NAME).unwrap();
-        canny_par(&image, threads.number())
+        canny_par(&image, threads.number(), CannyLensOptions::default())?
     };
 
-    write_image(&image, matches.get_one::<PathBuf>(output::ARG_NAME).unwrap())?;
+    write_image(&image, output_path, background::from_matches(matches), &metadata)?;
     Ok(())
 }
@@ -13,11 +13,13 @@ use img::prelude::*;
 
 use crate::{
     io::{
-        read_image,
+        read_image_with_metadata,
         write_image,
     },
     param::{
+        background,
         input,
+        metadata,
         output,
         size_offset::SizeOffset,
     },
@@ -52,7 +54,12 @@ pub fn subcommand() -> Command {
 }
 
 pub fn action(matches: &ArgMatches) -> anyhow::Result<()> {
-    let image = read_image(matches.get_one::<PathBuf>(input::ARG_NAME).unwrap())?;
+    let input_path = matches.get_one::<PathBuf>(input::ARG_NAME).unwrap();
+    let output_path = matches.get_one::<PathBuf>(output::ARG_NAME).unwrap();
+    let (image, metadata) = read_image_with_metadata(
+        input_path,
+        metadata::from_matches(matches, input_path, output_path),
+    )?;
     let target_size_offset = matches.get_one::<SizeOffset>("size").unwrap();
 
     let old_size = image.size();
@@ -87,6 +94,6 @@ pub fn action(matches: &ArgMatches) -> anyhow::Result<()> {
         let threads = matches.get_one::<Threads>(threads::ARG_NAME).unwrap();
         crop_par(&image, threads.number(), margin)?
     };
-    write_image(&image, matches.get_one::<PathBuf>(output::ARG_NAME).unwrap())?;
+    write_image(&image, output_path, background::from_matches(matches), &metadata)?;
     Ok(())
 }
@@ -10,15 +10,17 @@ use img::prelude::*;
 
 use crate::{
     io::{
-        read_image,
+        read_image_with_metadata,
         write_image,
     },
     param::{
+        background,
         channel_flags::{
             self,
             ChannelFlags,
         },
         input,
+        metadata,
         output,
     },
 };
@@ -57,7 +59,12 @@ pub fn subcommand() -> Command {
 }
 
 pub fn action(matches: &ArgMatches) -> anyhow::Result<()> {
-    let image = read_image(matches.get_one::<PathBuf>(input::ARG_NAME).unwrap())?;
+    let input_path = matches.get_one::<PathBuf>(input::ARG_NAME).unwrap();
+    let output_path = matches.get_one::<PathBuf>(output::ARG_NAME).unwrap();
+    let (image, metadata) = read_image_with_metadata(
+        input_path,
+        metadata::from_matches(matches, input_path, output_path),
+    )?;
     let gamma = matches.get_one::<f32>("gamma").unwrap();
     let channel_flags = *matches.get_one::<ChannelFlags>(channel_flags::ARG_NAME).unwrap();
 
@@ -75,6 +82,6 @@ pub fn action(matches: &ArgMatches) -> anyhow::Result<()> {
         gamma_correction_par(&image, threads.number(), *gamma, channel_flags.into())
     };
 
-    write_image(&image, matches.get_one::<PathBuf>(output::ARG_NAME).unwrap())?;
+    write_image(&image, output_path, background::from_matches(matches), &metadata)?;
     Ok(())
 }
@@ -8,15 +8,17 @@ use img::prelude::*;
 
 use crate::{
     io::{
-        read_image,
+        read_image_with_metadata,
         write_image,
     },
     param::{
+        background,
         channel_flags::{
             self,
             ChannelFlags,
         },
         input,
+        metadata,
         output,
     },
 };
@@ -41,7 +43,12 @@ pub fn subcommand() -> Command {
 }
 
 pub fn action(matches: &ArgMatches) -> anyhow::Result<()> {
-    let image = read_image(matches.get_one::<PathBuf>(input::ARG_NAME).unwrap())?;
+    let input_path = matches.get_one::<PathBuf>(input::ARG_NAME).unwrap();
+    let output_path = matches.get_one::<PathBuf>(output::ARG_NAME).unwrap();
+    let (image, metadata) = read_image_with_metadata(
+        input_path,
+        metadata::from_matches(matches, input_path, output_path),
+    )?;
     let channel_flags = *matches.get_one::<ChannelFlags>(channel_flags::ARG_NAME).unwrap();
 
     #[cfg(not(feature = "parallel"))]
@@ -58,6 +65,6 @@ pub fn action(matches: &ArgMatches) -> anyhow::Result<()> {
         grayscale_par(&image, threads.number(), channel_flags.into())
     };
 
-    write_image(&image, matches.get_one::<PathBuf>(output::ARG_NAME).unwrap())?;
+    write_image(&image, output_path, background::from_matches(matches), &metadata)?;
     Ok(())
 }
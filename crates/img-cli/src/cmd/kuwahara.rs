@@ -8,11 +8,13 @@ use img::prelude::*;
 
 use crate::{
     io::{
-        read_image,
+        read_image_with_metadata,
         write_image,
     },
     param::{
+        background,
         input,
+        metadata,
         output,
     },
 };
@@ -34,7 +36,12 @@ pub fn subcommand() -> Command {
 }
 
 pub fn action(matches: &ArgMatches) -> anyhow::Result<()> {
-    let image = read_image(matches.get_one::<PathBuf>(input::ARG_NAME).unwrap())?;
+    let input_path = matches.get_one::<PathBuf>(input::ARG_NAME).unwrap();
+    let output_path = matches.get_one::<PathBuf>(output::ARG_NAME).unwrap();
+    let (image, metadata) = read_image_with_metadata(
+        input_path,
+        metadata::from_matches(matches, input_path, output_path),
+    )?;
     let image = kuwahara(&image);
 
     #[cfg(not(feature = "parallel"))]
@@ -51,6 +58,6 @@ pub fn action(matches: &ArgMatches) -> anyhow::Result<()> {
         kuwahara_par(&image, threads.number())
     };
 
-    write_image(&image, matches.get_one::<PathBuf>(output::ARG_NAME).unwrap())?;
+    write_image(&image, output_path, background::from_matches(matches), &metadata)?;
     Ok(())
 }
@@ -6,4 +6,6 @@ pub mod grayscale;
 pub mod kuwahara;
 pub mod negative;
 pub mod resize;
+pub mod rotate;
 pub mod sepia;
+pub mod show;
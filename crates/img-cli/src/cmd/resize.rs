@@ -12,11 +12,13 @@ use img::prelude::*;
 
 use crate::{
     io::{
-        read_image,
+        read_image_with_metadata,
         write_image,
     },
     param::{
+        background,
         input,
+        metadata,
         output,
         size::Size,
     },
@@ -46,7 +48,12 @@ pub fn subcommand() -> Command {
 }
 
 pub fn action(matches: &ArgMatches) -> anyhow::Result<()> {
-    let image = read_image(matches.get_one::<PathBuf>(input::ARG_NAME).unwrap())?;
+    let input_path = matches.get_one::<PathBuf>(input::ARG_NAME).unwrap();
+    let output_path = matches.get_one::<PathBuf>(output::ARG_NAME).unwrap();
+    let (image, metadata) = read_image_with_metadata(
+        input_path,
+        metadata::from_matches(matches, input_path, output_path),
+    )?;
     let target_size = matches.get_one::<Size>("size").unwrap();
     let scale = Scale::new(
         target_size.width as f32 / image.size().width() as f32,
@@ -67,6 +74,6 @@ pub fn action(matches: &ArgMatches) -> anyhow::Result<()> {
         resize_par(&image, threads.number(), scale)?
     };
 
-    write_image(&image, matches.get_one::<PathBuf>(output::ARG_NAME).unwrap())?;
+    write_image(&image, output_path, background::from_matches(matches), &metadata)?;
     Ok(())
 }
@@ -0,0 +1,79 @@
+use std::path::PathBuf;
+
+use clap::{
+    ArgMatches,
+    Command,
+    arg,
+    value_parser,
+};
+use img::prelude::*;
+
+use crate::{
+    io::{
+        read_image_with_metadata,
+        write_image,
+    },
+    param::{
+        background,
+        input,
+        metadata,
+        output,
+    },
+};
+
+pub const CMD_NAME: &str = "rotate";
+
+pub fn subcommand() -> Command {
+    #[cfg(not(feature = "parallel"))]
+    {
+        Command::new(CMD_NAME).arg(input::arg()).arg(output::arg()).arg(
+            arg!(-a --angle <angle> "clockwise rotation angle in degrees")
+                .required(true)
+                .allow_negative_numbers(true)
+                .value_parser(value_parser!(f32)),
+        )
+    }
+
+    #[cfg(feature = "parallel")]
+    {
+        use crate::param::threads;
+
+        Command::new(CMD_NAME)
+            .arg(input::arg())
+            .arg(output::arg())
+            .arg(
+                arg!(-a --angle <angle> "clockwise rotation angle in degrees")
+                    .required(true)
+                    .allow_negative_numbers(true)
+                    .value_parser(value_parser!(f32)),
+            )
+            .arg(threads::arg())
+    }
+}
+
+pub fn action(matches: &ArgMatches) -> anyhow::Result<()> {
+    let input_path = matches.get_one::<PathBuf>(input::ARG_NAME).unwrap();
+    let output_path = matches.get_one::<PathBuf>(output::ARG_NAME).unwrap();
+    let (image, metadata) = read_image_with_metadata(
+        input_path,
+        metadata::from_matches(matches, input_path, output_path),
+    )?;
+    let angle = matches.get_one::<f32>("angle").unwrap().to_radians();
+
+    #[cfg(not(feature = "parallel"))]
+    let image = rotate(&image, angle);
+
+    #[cfg(feature = "parallel")]
+    let image = {
+        use crate::param::threads::{
+            self,
+            Threads,
+        };
+
+        let threads = matches.get_one::<Threads>(threads::ARG_NAME).unwrap();
+        rotate_par(&image, threads.number(), angle)
+    };
+
+    write_image(&image, output_path, background::from_matches(matches), &metadata)?;
+    Ok(())
+}
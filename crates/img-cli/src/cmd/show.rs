@@ -0,0 +1,114 @@
+use std::{
+    env,
+    io::Write,
+    path::PathBuf,
+    str::FromStr,
+};
+
+use anyhow::bail;
+use clap::{
+    ArgMatches,
+    Command,
+    arg,
+};
+use img::prelude::*;
+
+use crate::{
+    io::{
+        flatten,
+        read_image,
+    },
+    param::{
+        background,
+        input,
+    },
+    printing::{
+        half_block,
+        sixel,
+    },
+};
+
+pub const CMD_NAME: &str = "show";
+
+/// Terminals known to support Sixel, matched against `TERM` and `TERM_PROGRAM`.
+const SIXEL_TERMINALS: [&str; 6] = ["sixel", "foot", "mlterm", "contour", "wezterm", "konsole"];
+
+pub fn subcommand() -> Command {
+    Command::new(CMD_NAME)
+        .about("render image in the terminal")
+        .arg(input::arg())
+        .arg(
+            arg!(-p --protocol <protocol> "output protocol: auto, sixel or half-block")
+                .required(false)
+                .default_value("auto")
+                .value_parser(Protocol::from_str),
+        )
+        .arg(
+            arg!(-w --width <width> "maximum width in pixels (half-block: columns)")
+                .required(false)
+                .value_parser(clap::value_parser!(usize)),
+        )
+}
+
+pub fn action(matches: &ArgMatches) -> anyhow::Result<()> {
+    let image = read_image(matches.get_one::<PathBuf>(input::ARG_NAME).unwrap())?;
+    let background = background::from_matches(matches).unwrap_or(Pixel::new([0, 0, 0, 255]));
+    let image = flatten(&image, background);
+
+    let image = match matches.get_one::<usize>("width") {
+        Some(width) if *width < image.size().width() => {
+            let factor = *width as f32 / image.size().width() as f32;
+            resize(&image, Scale::new(factor, factor)?)?
+        }
+        _ => image,
+    };
+
+    let text = match matches.get_one::<Protocol>("protocol").unwrap().resolve() {
+        Protocol::Sixel => sixel::encode(&image),
+        _ => half_block::encode(&image),
+    };
+
+    let mut stdout = std::io::stdout().lock();
+    stdout.write_all(text.as_bytes())?;
+    stdout.flush()?;
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Auto,
+    Sixel,
+    HalfBlock,
+}
+
+impl Protocol {
+    /// Detect protocol supported by the terminal if set to [`Protocol::Auto`], falling back to
+    /// half-block output which only needs truecolor support.
+    fn resolve(self) -> Self {
+        if self != Self::Auto {
+            return self;
+        }
+
+        let supports_sixel = ["TERM", "TERM_PROGRAM"].iter().any(|name| {
+            env::var(name).is_ok_and(|value| {
+                let value = value.to_lowercase();
+                SIXEL_TERMINALS.iter().any(|terminal| value.contains(terminal))
+            })
+        });
+
+        if supports_sixel { Self::Sixel } else { Self::HalfBlock }
+    }
+}
+
+impl FromStr for Protocol {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Self::Auto),
+            "sixel" => Ok(Self::Sixel),
+            "half-block" => Ok(Self::HalfBlock),
+            _ => bail!("protocol must be one of auto, sixel or half-block"),
+        }
+    }
+}
@@ -1,7 +1,16 @@
 use std::path::Path;
 
 use anyhow::bail;
-use img::prelude::Image;
+use img::{
+    io::metadata::JpegMetadata,
+    prelude::{
+        Image,
+        Pixel,
+    },
+};
+
+#[cfg(any(feature = "png", feature = "jpeg"))]
+use crate::printing::print_warning;
 
 #[cfg(feature = "jpeg")]
 use img::io::jpeg::{
@@ -16,34 +25,71 @@ use img::io::png::{
 };
 
 /// Read an image from a file specified in path (supports png or/and jpeg based on enabled features)
-#[cfg(any(feature = "png", feature = "jpeg"))]
 pub fn read_image(path: impl AsRef<Path>) -> anyhow::Result<Image> {
-    use std::fs::File;
+    read_image_with_metadata(path, false).map(|(image, _)| image)
+}
+
+/// Read an image and metadata worth preserving from a file specified in path, reading the file
+/// only once. Metadata is only read from jpeg files and only if `keep_metadata` is set.
+/// Metadata that can't be parsed is reported as a warning and dropped.
+#[cfg(any(feature = "png", feature = "jpeg"))]
+pub fn read_image_with_metadata(
+    path: impl AsRef<Path>,
+    keep_metadata: bool,
+) -> anyhow::Result<(Image, JpegMetadata)> {
     let path = path.as_ref();
     let extension = path.extension().ok_or(anyhow::anyhow!("No file extension found"))?;
-    let file = File::open(path)?;
+    let data = std::fs::read(path)?;
 
     let image = match extension.to_string_lossy().as_ref() {
         #[cfg(feature = "png")]
-        "png" => Image::read_png(file)?,
+        "png" => Image::read_png(data.as_slice())?,
         #[cfg(feature = "jpeg")]
-        "jpg" | "jpeg" => Image::read_jpeg(file)?,
+        "jpg" | "jpeg" => Image::read_jpeg(data.as_slice())?,
         _ => bail!("Invalid file - unsupported file format"),
     };
 
-    Ok(image)
+    let metadata = match keep_metadata && is_jpeg(path) {
+        true => JpegMetadata::read(&data).unwrap_or_else(|error| {
+            print_warning(format!("failed to read metadata, it won't be copied: {error}"));
+            JpegMetadata::default()
+        }),
+        false => JpegMetadata::default(),
+    };
+
+    Ok((image, metadata))
 }
 
 #[cfg(not(any(feature = "png", feature = "jpeg")))]
-pub fn read_image(_path: impl AsRef<Path>) -> anyhow::Result<Image> {
+pub fn read_image_with_metadata(
+    _path: impl AsRef<Path>,
+    _keep_metadata: bool,
+) -> anyhow::Result<(Image, JpegMetadata)> {
     bail!("No image format support compiled in (enable the `png` or `jpeg` feature)")
 }
 
+/// Check if path has a jpeg file extension.
+pub fn is_jpeg(path: impl AsRef<Path>) -> bool {
+    path.as_ref()
+        .extension()
+        .is_some_and(|extension| matches!(extension.to_string_lossy().as_ref(), "jpg" | "jpeg"))
+}
+
 /// Write an image to a file specified in path (supports png or/and jpeg based on enabled features)
+/// If background is given, image is composited over it first, producing opaque output.
+/// Metadata is embedded if the format supports it (jpeg only).
 #[cfg(any(feature = "png", feature = "jpeg"))]
-pub fn write_image(image: &Image, path: impl AsRef<Path>) -> anyhow::Result<()> {
+#[cfg_attr(not(feature = "jpeg"), allow(unused_variables))]
+pub fn write_image(
+    image: &Image,
+    path: impl AsRef<Path>,
+    background: Option<Pixel>,
+    metadata: &JpegMetadata,
+) -> anyhow::Result<()> {
     use std::fs::File;
     let path = path.as_ref();
+    let flattened = background.map(|background| flatten(image, background));
+    let image = flattened.as_ref().unwrap_or(image);
     let extension = path.extension().ok_or(anyhow::anyhow!("No file extension found"))?;
 
     match extension.to_string_lossy().as_ref() {
@@ -54,8 +100,11 @@ pub fn write_image(image: &Image, path: impl AsRef<Path>) -> anyhow::Result<()>
         }
         #[cfg(feature = "jpeg")]
         "jpg" | "jpeg" => {
-            let file = File::create(path)?;
-            image.write_jpeg(file, Default::default(), Default::default())?;
+            use std::io::Write;
+
+            let mut jpeg = Vec::new();
+            image.write_jpeg(&mut jpeg, Default::default(), Default::default())?;
+            File::create(path)?.write_all(&metadata.embed(&jpeg)?)?;
         }
         _ => bail!("Invalid file - unsupported file format"),
     };
@@ -64,6 +113,24 @@ pub fn write_image(image: &Image, path: impl AsRef<Path>) -> anyhow::Result<()>
 }
 
 #[cfg(not(any(feature = "png", feature = "jpeg")))]
-pub fn write_image(_image: &Image, _path: impl AsRef<Path>) -> anyhow::Result<()> {
+pub fn write_image(
+    _image: &Image,
+    _path: impl AsRef<Path>,
+    _background: Option<Pixel>,
+    _metadata: &JpegMetadata,
+) -> anyhow::Result<()> {
     bail!("No image format support compiled in (enable the `png` or `jpeg` feature)")
 }
+
+/// Composite image over a solid background color, dropping its alpha.
+pub fn flatten(image: &Image, background: Pixel) -> Image {
+    use img::lens::{
+        FromLens,
+        Lens,
+    };
+
+    let mut background = background;
+    background.set_a(255);
+
+    Image::from_lens(image.lens().map(move |px| px.blend_over(background)))
+}
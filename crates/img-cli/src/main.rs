@@ -14,7 +14,13 @@ use cmd::{
     gamma_correction,
     grayscale,
     resize,
+    rotate,
     sepia,
+    show,
+};
+use param::{
+    background,
+    metadata,
 };
 use printing::print_error;
 
@@ -28,15 +34,20 @@ use crate::cmd::{
 fn main() {
     let command = command!()
         .subcommand_required(true)
+        .arg(background::no_alpha_arg())
+        .arg(background::arg())
+        .arg(metadata::strip_arg())
         .subcommand(grayscale::subcommand())
         .subcommand(sepia::subcommand())
         .subcommand(resize::subcommand())
+        .subcommand(rotate::subcommand())
         .subcommand(crop::subcommand())
         .subcommand(blur::subcommand())
         .subcommand(gamma_correction::subcommand())
         .subcommand(canny::subcommand())
         .subcommand(kuwahara::subcommand())
-        .subcommand(negative::subcommand());
+        .subcommand(negative::subcommand())
+        .subcommand(show::subcommand());
 
     if let Err(e) = execute_command(command) {
         print_error(e.to_string());
@@ -50,12 +61,14 @@ fn execute_command(command: Command) -> anyhow::Result<()> {
         (grayscale::CMD_NAME, m) => grayscale::action(m),
         (sepia::CMD_NAME, m) => sepia::action(m),
         (resize::CMD_NAME, m) => resize::action(m),
+        (rotate::CMD_NAME, m) => rotate::action(m),
         (crop::CMD_NAME, m) => crop::action(m),
         (blur::CMD_NAME, m) => blur::action(m),
         (gamma_correction::CMD_NAME, m) => gamma_correction::action(m),
         (canny::CMD_NAME, m) => canny::action(m),
         (kuwahara::CMD_NAME, m) => kuwahara::action(m),
         (negative::CMD_NAME, m) => negative::action(m),
+        (show::CMD_NAME, m) => show::action(m),
         _ => unreachable!(),
     }
 }
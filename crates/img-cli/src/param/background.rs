@@ -0,0 +1,79 @@
+use std::str::FromStr;
+
+use anyhow::{
+    anyhow,
+    bail,
+};
+use clap::{
+    Arg,
+    ArgAction,
+    ArgMatches,
+    arg,
+};
+use img::prelude::Pixel;
+
+pub const NO_ALPHA_ARG_NAME: &str = "no-alpha";
+pub fn no_alpha_arg() -> Arg {
+    arg!(--"no-alpha" "composite output over a solid background, dropping alpha")
+        .action(ArgAction::SetTrue)
+        .global(true)
+}
+
+pub const ARG_NAME: &str = "background";
+pub fn arg() -> Arg {
+    arg!(--background <hex> "background color in [#]RRGGBB format, implies --no-alpha")
+        .required(false)
+        .value_parser(Background::from_str)
+        .global(true)
+}
+
+/// Background to flatten output over, if requested with `--no-alpha` or `--background`.
+/// Defaults to white when only `--no-alpha` is given.
+pub fn from_matches(matches: &ArgMatches) -> Option<Pixel> {
+    match matches.get_one::<Background>(ARG_NAME) {
+        Some(background) => Some(background.0),
+        None if matches.get_flag(NO_ALPHA_ARG_NAME) => Some(Background::default().0),
+        None => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Background(pub Pixel);
+
+impl Default for Background {
+    fn default() -> Self {
+        Self(Pixel::new([255, 255, 255, 255]))
+    }
+}
+
+impl FromStr for Background {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.strip_prefix('#').unwrap_or(s);
+        if hex.len() != 6 || !hex.chars().all(|ch| ch.is_ascii_hexdigit()) {
+            bail!("background must be in [#]RRGGBB format")
+        }
+
+        let channel = |index: usize| {
+            u8::from_str_radix(&hex[index * 2..index * 2 + 2], 16)
+                .map_err(|_| anyhow!("invalid background channel"))
+        };
+
+        Ok(Background(Pixel::new([channel(0)?, channel(1)?, channel(2)?, 255])))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_background_from_str() {
+        assert_eq!("ff0000".parse::<Background>().unwrap().0, Pixel::new([255, 0, 0, 255]));
+        assert_eq!("#0A0b0C".parse::<Background>().unwrap().0, Pixel::new([10, 11, 12, 255]));
+        assert!("fff".parse::<Background>().is_err());
+        assert!("gg0000".parse::<Background>().is_err());
+        assert!("+f0000".parse::<Background>().is_err());
+    }
+}
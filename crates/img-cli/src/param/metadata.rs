@@ -0,0 +1,26 @@
+use std::path::Path;
+
+use crate::io::is_jpeg;
+use clap::{
+    Arg,
+    ArgAction,
+    ArgMatches,
+    arg,
+};
+
+pub const STRIP_ARG_NAME: &str = "strip-metadata";
+pub fn strip_arg() -> Arg {
+    arg!(--"strip-metadata" "do not copy EXIF and ICC profile metadata from input to output")
+        .action(ArgAction::SetTrue)
+        .global(true)
+}
+
+/// Check if metadata should be copied from input file to output. It is only copied between jpeg
+/// files and never if `--strip-metadata` is given.
+pub fn from_matches(
+    matches: &ArgMatches,
+    input: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+) -> bool {
+    !matches.get_flag(STRIP_ARG_NAME) && is_jpeg(input) && is_jpeg(output)
+}
@@ -1,5 +1,7 @@
+pub mod background;
 pub mod channel_flags;
 pub mod input;
+pub mod metadata;
 pub mod output;
 pub mod size;
 pub mod size_offset;
@@ -0,0 +1,77 @@
+use std::fmt::Write;
+
+use img::prelude::{
+    Image,
+    Pixel,
+    Point,
+};
+
+/// Upper half block, its foreground color paints the top pixel and background the bottom one.
+const UPPER_HALF_BLOCK: char = '▀';
+
+/// Encode image as text using truecolor escape sequences, two pixel rows per line.
+///
+/// Pixels are expected to be opaque, alpha is ignored. Bottom half of the last line of an image
+/// with odd height uses the default terminal background.
+pub fn encode(image: &Image) -> String {
+    let (width, height) = (image.size().width(), image.size().height());
+    let mut text = String::new();
+
+    for y in (0..height).step_by(2) {
+        for x in 0..width {
+            let top = pixel(image, x, y);
+            write_color(&mut text, 38, top);
+            match (y + 1 < height).then(|| pixel(image, x, y + 1)) {
+                Some(bottom) => write_color(&mut text, 48, bottom),
+                None => text.push_str("\x1b[49m"),
+            }
+            text.push(UPPER_HALF_BLOCK);
+        }
+        text.push_str("\x1b[0m\n");
+    }
+
+    text
+}
+
+fn pixel(image: &Image, x: usize, y: usize) -> Pixel {
+    // SAFETY: callers only pass coordinates within the image, so both the point and the pixel
+    // are valid.
+    let point = Point::new(x, y).expect("unexpected error in Point::new");
+    *image.pixel(point).expect("unexpected error in Image::pixel")
+}
+
+/// Write SGR sequence setting foreground (38) or background (48) color.
+fn write_color(text: &mut String, target: u8, px: Pixel) {
+    // Writing to a String never fails.
+    let _ = write!(text, "\x1b[{};2;{};{};{}m", target, px.r(), px.g(), px.b());
+}
+
+#[cfg(test)]
+mod tests {
+    use img::prelude::Size;
+
+    use super::*;
+
+    #[test]
+    fn test_rows() {
+        for (height, rows) in [(1, 1), (2, 1), (5, 3), (8, 4)] {
+            let image = Image::solid(Size::new(3, height).unwrap(), Pixel::new([1, 2, 3, 255]));
+
+            let text = encode(&image);
+
+            assert_eq!(text.lines().count(), rows);
+            assert!(text.lines().all(|line| line.matches(UPPER_HALF_BLOCK).count() == 3));
+        }
+    }
+
+    #[test]
+    fn test_colors() {
+        let image = Image::new(
+            Size::new(1, 2).unwrap(),
+            Box::new([Pixel::new([255, 0, 0, 255]), Pixel::new([0, 0, 255, 255])]),
+        )
+        .unwrap();
+
+        assert_eq!(encode(&image), "\x1b[38;2;255;0;0m\x1b[48;2;0;0;255m▀\x1b[0m\n");
+    }
+}
@@ -0,0 +1,12 @@
+pub mod half_block;
+pub mod sixel;
+
+use colored::Colorize;
+
+pub fn print_error(message: impl AsRef<str>) {
+    eprintln!("{} {}", "error:".red(), message.as_ref());
+}
+
+pub fn print_warning(message: impl AsRef<str>) {
+    eprintln!("{} {}", "warning:".yellow(), message.as_ref());
+}
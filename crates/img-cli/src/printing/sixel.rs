@@ -0,0 +1,120 @@
+use std::fmt::Write;
+
+use img::prelude::Image;
+
+/// Number of levels of every channel in the fixed color cube palette.
+const LEVELS: usize = 6;
+
+/// Number of pixel rows encoded by one sixel character.
+const BAND_HEIGHT: usize = 6;
+
+/// Encode image with the Sixel protocol.
+///
+/// Colors are reduced to a fixed 6x6x6 color cube, which every Sixel terminal supports without
+/// computing a palette per image. Pixels are expected to be opaque, alpha is ignored.
+pub fn encode(image: &Image) -> String {
+    let (width, height) = (image.size().width(), image.size().height());
+    let indices = image.pixels().iter().map(|px| {
+        let [r, g, b] = [px.r(), px.g(), px.b()].map(level);
+        (r * LEVELS + g) * LEVELS + b
+    });
+    let indices = indices.collect::<Vec<_>>();
+
+    // Writing to a String never fails.
+    let mut text = String::new();
+    let _ = write!(text, "\x1bPq\"1;1;{width};{height}");
+    for index in 0..LEVELS * LEVELS * LEVELS {
+        let [r, g, b] = [index / (LEVELS * LEVELS), index / LEVELS % LEVELS, index % LEVELS]
+            .map(|level| level * 100 / (LEVELS - 1));
+        let _ = write!(text, "#{index};2;{r};{g};{b}");
+    }
+
+    for band in (0..height).step_by(BAND_HEIGHT) {
+        let rows = band..(band + BAND_HEIGHT).min(height);
+        let mut used = indices[rows.start * width..rows.end * width].to_vec();
+        used.sort_unstable();
+        used.dedup();
+
+        for (position, color) in used.iter().enumerate() {
+            if position > 0 {
+                // Return to the start of the band to overlay next color.
+                text.push('$');
+            }
+            let _ = write!(text, "#{color}");
+
+            let sixels = (0..width).map(|x| {
+                rows.clone()
+                    .filter(|y| indices[y * width + x] == *color)
+                    .fold(0u8, |bits, y| bits | 1 << (y - band))
+            });
+            write_run_length(&mut text, sixels);
+        }
+        text.push('-');
+    }
+    text.push_str("\x1b\\");
+
+    text
+}
+
+/// Map channel value to the nearest palette level.
+fn level(value: u8) -> usize {
+    (value as usize * (LEVELS - 1) + 127) / 255
+}
+
+/// Write sixel characters, compressing repeats with `!<count><char>`.
+fn write_run_length(text: &mut String, sixels: impl Iterator<Item = u8>) {
+    let mut run: Option<(u8, usize)> = None;
+    for sixel in sixels.chain(std::iter::once(u8::MAX)) {
+        match run {
+            Some((current, count)) if current == sixel => run = Some((current, count + 1)),
+            _ => {
+                if let Some((current, count)) = run {
+                    let character = (current + 63) as char;
+                    if count > 3 {
+                        let _ = write!(text, "!{count}{character}");
+                    } else {
+                        (0..count).for_each(|_| text.push(character));
+                    }
+                }
+                run = Some((sixel, 1));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use img::prelude::{
+        Pixel,
+        Size,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_encode_solid() {
+        let image = Image::solid(Size::new(10, 8).unwrap(), Pixel::new([255, 0, 0, 255]));
+
+        let text = encode(&image);
+
+        assert!(text.starts_with("\x1bPq\"1;1;10;8"));
+        assert!(text.ends_with("\x1b\\"));
+        assert_eq!(text.matches('-').count(), 2);
+        // Red is the 180th color of the cube, full band and the remaining 2 rows.
+        assert!(text.contains("#180!10~-#180!10B-"));
+    }
+
+    #[test]
+    fn test_encode_two_colors() {
+        let black = Pixel::new([0, 0, 0, 255]);
+        let white = Pixel::new([255, 255, 255, 255]);
+        let image = Image::new(Size::new(2, 1).unwrap(), Box::new([black, white])).unwrap();
+
+        let text = encode(&image);
+
+        assert!(text.ends_with("#0@?$#215?@-\x1b\\"));
+        assert_eq!(level(0), 0);
+        assert_eq!(level(128), 3);
+        assert_eq!(level(255), 5);
+    }
+}
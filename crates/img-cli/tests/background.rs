@@ -0,0 +1,46 @@
+#![cfg(feature = "png")]
+
+use std::{
+    fs::File,
+    path::PathBuf,
+    process::Command,
+};
+
+use img::{
+    io::png::{
+        ReadPng,
+        WritePng,
+    },
+    prelude::*,
+};
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("img-cli-{}-{}", std::process::id(), name))
+}
+
+#[test]
+fn test_no_alpha_flattens_over_background() -> anyhow::Result<()> {
+    let input = temp_path("background-input.png");
+    let output = temp_path("background-output.png");
+
+    let size = Size::new(2, 2)?;
+    let image = Image::new(size, vec![Pixel::new([0, 0, 255, 128]); size.area()].into())?;
+    image.write_png(File::create(&input)?)?;
+
+    let status = Command::new(env!("CARGO_BIN_EXE_img-cli"))
+        .args(["crop", "-i"])
+        .arg(&input)
+        .arg("-o")
+        .arg(&output)
+        .args(["--size", "2x2+0x0", "--no-alpha", "--background", "ff0000"])
+        .status()?;
+    assert!(status.success());
+
+    let result = Image::read_png(File::open(&output)?)?;
+    assert_eq!(*result.pixel(Point::new(1, 1)?)?, Pixel::new([127, 0, 128, 255]));
+
+    std::fs::remove_file(input)?;
+    std::fs::remove_file(output)?;
+
+    Ok(())
+}
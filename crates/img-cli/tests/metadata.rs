@@ -0,0 +1,70 @@
+#![cfg(feature = "jpeg")]
+
+use std::{
+    path::PathBuf,
+    process::Command,
+};
+
+use img::{
+    io::{
+        jpeg::WriteJpeg,
+        metadata::JpegMetadata,
+    },
+    prelude::*,
+};
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("img-cli-{}-{}", std::process::id(), name))
+}
+
+/// APP1 segment with big endian EXIF data holding only orientation tag.
+fn exif_segment(orientation: u16) -> Vec<u8> {
+    let mut payload = b"Exif\0\0MM\0\x2A\0\0\0\x08\0\x01\x01\x12\0\x03\0\0\0\x01".to_vec();
+    payload.extend_from_slice(&orientation.to_be_bytes());
+    payload.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+
+    let length = (payload.len() as u16 + 2).to_be_bytes();
+    [&[0xFF, 0xE1], &length[..], &payload].concat()
+}
+
+fn run_negative(extra_args: &[&str]) -> anyhow::Result<JpegMetadata> {
+    let input = temp_path(&format!("metadata-input{}.jpg", extra_args.len()));
+    let output = temp_path(&format!("metadata-output{}.jpg", extra_args.len()));
+
+    let image = Image::solid(Size::new(8, 8)?, Pixel::new([10, 120, 200, 255]));
+    let mut jpeg = Vec::new();
+    image.write_jpeg(&mut jpeg, Default::default(), Default::default())?;
+    // Insert EXIF right after SOI marker.
+    let jpeg = [&jpeg[..2], &exif_segment(6), &jpeg[2..]].concat();
+    std::fs::write(&input, jpeg)?;
+
+    let status = Command::new(env!("CARGO_BIN_EXE_img-cli"))
+        .args(["negative", "-i"])
+        .arg(&input)
+        .arg("-o")
+        .arg(&output)
+        .args(extra_args)
+        .status()?;
+    assert!(status.success());
+
+    let metadata = JpegMetadata::read(&std::fs::read(&output)?)?;
+
+    std::fs::remove_file(input)?;
+    std::fs::remove_file(output)?;
+
+    Ok(metadata)
+}
+
+#[test]
+fn test_jpeg_round_trip_keeps_orientation() -> anyhow::Result<()> {
+    assert_eq!(run_negative(&[])?.orientation(), Some(6));
+
+    Ok(())
+}
+
+#[test]
+fn test_strip_metadata() -> anyhow::Result<()> {
+    assert!(run_negative(&["--strip-metadata"])?.is_empty());
+
+    Ok(())
+}
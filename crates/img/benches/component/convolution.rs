@@ -0,0 +1,48 @@
+use std::{
+    hint::black_box,
+    time::Duration,
+};
+
+use criterion::{
+    Criterion,
+    criterion_group,
+    criterion_main,
+};
+use img::{
+    component::kernel::convolution::ConvolutionKernel,
+    lens::{
+        FromLens,
+        Lens,
+    },
+    prelude::*,
+};
+use rand::{
+    Rng,
+    SeedableRng,
+    rngs::SmallRng,
+};
+
+fn random_kernel(size: Size, rng: &mut SmallRng) -> ConvolutionKernel {
+    let buffer: Vec<f32> = (0..size.area()).map(|_| rng.random_range(-1f32..1f32)).collect();
+    ConvolutionKernel::new(size, buffer, ChannelFlags::RGBA).unwrap()
+}
+
+/// Compare 3x3 kernels, which take the specialized path, with the generic path used by other
+/// sizes.
+fn convolution_benchmark(criterion: &mut Criterion) {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let image = black_box(Image::random(Size::new(512, 512).unwrap(), &mut rng));
+
+    let mut group = criterion.benchmark_group("convolution_512x512");
+    group.sample_size(20).measurement_time(Duration::from_secs(5));
+    for (name, size) in [("3x3", Size::new(3, 3)), ("5x5", Size::new(5, 5))] {
+        let kernel = random_kernel(size.unwrap(), &mut rng);
+        group.bench_function(name, |b| {
+            b.iter(|| Image::from_lens(image.lens().kernel(kernel.clone()).unwrap()))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, convolution_benchmark);
+criterion_main!(benches);
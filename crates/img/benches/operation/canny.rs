@@ -8,7 +8,7 @@ use img::prelude::*;
 
 mod common;
 
-operation_bench!(canny []);
+operation_bench!(canny[CannyLensOptions::default()]);
 
 criterion_group!(benches, canny_benchmark);
 criterion_main!(benches);
@@ -8,7 +8,7 @@ use img::prelude::*;
 
 mod common;
 
-operation_bench!(gaussian_blur [2, 3.0, ChannelFlags::RGB]);
+operation_bench!(gaussian_blur [2, 3.0, ChannelFlags::RGB, AlphaMode::Straight]);
 
 criterion_group!(benches, gaussian_blur_benchmark);
 criterion_main!(benches);
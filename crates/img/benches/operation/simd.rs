@@ -0,0 +1,49 @@
+use std::{
+    hint::black_box,
+    time::Duration,
+};
+
+use criterion::{
+    Criterion,
+    criterion_group,
+    criterion_main,
+};
+use img::{
+    operation::color::{
+        brightness_simd,
+        grayscale_simd,
+        negative_simd,
+    },
+    prelude::*,
+};
+use rand::{
+    SeedableRng,
+    rngs::SmallRng,
+};
+
+/// Compare vectorized operations with their scalar counterparts on the same image.
+fn simd_benchmark(criterion: &mut Criterion) {
+    let image =
+        black_box(Image::random(Size::new(1000, 1000).unwrap(), &mut SmallRng::seed_from_u64(0)));
+
+    let mut group = criterion.benchmark_group("negative_1000x1000");
+    group.sample_size(20).measurement_time(Duration::from_secs(5));
+    group.bench_function("scalar", |b| b.iter(|| negative(&image, ChannelFlags::RGB)));
+    group.bench_function("simd", |b| b.iter(|| negative_simd(&image, ChannelFlags::RGB)));
+    group.finish();
+
+    let mut group = criterion.benchmark_group("brightness_1000x1000");
+    group.sample_size(20).measurement_time(Duration::from_secs(5));
+    group.bench_function("scalar", |b| b.iter(|| brightness(&image, 40, ChannelFlags::RGB)));
+    group.bench_function("simd", |b| b.iter(|| brightness_simd(&image, 40, ChannelFlags::RGB)));
+    group.finish();
+
+    let mut group = criterion.benchmark_group("grayscale_1000x1000");
+    group.sample_size(20).measurement_time(Duration::from_secs(5));
+    group.bench_function("scalar", |b| b.iter(|| grayscale(&image, ChannelFlags::RGB)));
+    group.bench_function("simd", |b| b.iter(|| grayscale_simd(&image, ChannelFlags::RGB)));
+    group.finish();
+}
+
+criterion_group!(benches, simd_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,121 @@
+use crate::lens::{
+    FromLens,
+    Lens,
+};
+
+/// Number of bins in a [`Histogram`], one for each `u8` value.
+pub const HISTOGRAM_BINS: usize = u8::MAX as usize + 1;
+
+/// A `struct` representing histogram of single channel `u8` values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Histogram {
+    bins: [usize; HISTOGRAM_BINS],
+    total: usize,
+}
+
+impl Histogram {
+    /// Get number of occurences of `value`.
+    pub fn count(&self, value: u8) -> usize {
+        self.bins[value as usize]
+    }
+
+    /// Get all bins of [`Histogram`], indexed by value.
+    pub fn bins(&self) -> &[usize; HISTOGRAM_BINS] {
+        &self.bins
+    }
+
+    /// Get total number of values counted.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Calculate normalized cumulative distribution function.
+    ///
+    /// Returns array where value at index `i` is a fraction of values less than or equal to `i`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::{
+    ///     component::histogram::Histogram,
+    ///     lens::{
+    ///         FromLens,
+    ///         value::ValueLens,
+    ///     },
+    ///     prelude::*,
+    /// };
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let histogram = Histogram::from_lens(ValueLens::new(100u8, Size::new(2, 2)?));
+    /// let cdf = histogram.cdf();
+    ///
+    /// assert_eq!(cdf[99], 0.0);
+    /// assert_eq!(cdf[100], 1.0);
+    /// assert_eq!(cdf[255], 1.0);
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn cdf(&self) -> [f32; HISTOGRAM_BINS] {
+        let mut cdf = [0f32; HISTOGRAM_BINS];
+        let mut sum = 0;
+        for (value, count) in cdf.iter_mut().zip(self.bins) {
+            sum += count;
+            *value = sum as f32 / self.total as f32;
+        }
+
+        cdf
+    }
+}
+
+impl FromLens<u8> for Histogram {
+    /// Collect [`Lens`] into a [`Histogram`].
+    fn from_lens<S>(source: S) -> Self
+    where
+        S: Lens<Item = u8>,
+    {
+        let mut bins = [0; HISTOGRAM_BINS];
+        source.elements().for_each(|value| bins[value as usize] += 1);
+
+        Self { bins, total: source.size().area() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        component::primitive::Size,
+        lens::{
+            Lens,
+            value::ValueLens,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_from_lens() {
+        let size = Size::new(4, 2).unwrap();
+        let lens = ValueLens::new(0u8, size).remap(|s, p| s.look(p).map(|_| p.x() as u8), size);
+        let histogram = Histogram::from_lens(lens);
+
+        assert_eq!(histogram.total(), 8);
+        assert_eq!(histogram.count(0), 2);
+        assert_eq!(histogram.count(3), 2);
+        assert_eq!(histogram.count(4), 0);
+    }
+
+    #[test]
+    fn test_cdf() {
+        let size = Size::new(4, 1).unwrap();
+        let lens =
+            ValueLens::new(0u8, size).remap(|s, p| s.look(p).map(|_| p.x() as u8 * 10), size);
+        let cdf = Histogram::from_lens(lens).cdf();
+
+        assert_eq!(cdf[0], 0.25);
+        assert_eq!(cdf[9], 0.25);
+        assert_eq!(cdf[10], 0.5);
+        assert_eq!(cdf[30], 1.0);
+        assert_eq!(cdf[255], 1.0);
+    }
+}
@@ -0,0 +1,147 @@
+use thiserror::Error;
+
+use crate::{
+    component::{
+        kernel::Kernel,
+        primitive::{
+            Margin,
+            Offset,
+            Point,
+        },
+    },
+    error::IndexResult,
+    lens::Lens,
+    pixel::Pixel,
+};
+
+#[derive(Debug, Error)]
+pub enum CreationError {
+    #[error("invalid sigma")]
+    InvalidSigma,
+}
+
+pub type CreationResult = Result<BilateralKernel, CreationError>;
+
+/// Edge preserving smoothing kernel, averaging neighbours weighted both by their distance from
+/// the center and by how close their color is to the color of the center.
+///
+/// Range weights depend on the center pixel, so unlike
+/// [`GaussianKernel`](super::gaussian::GaussianKernel) only spatial weights are precomputed and the
+/// rest is evaluated for every lookup. Alpha of the center pixel is passed through unmodified.
+#[derive(Clone)]
+pub struct BilateralKernel {
+    margin: Margin,
+    spatial: Vec<(Offset, f32)>,
+    range_sigma: f32,
+}
+
+impl BilateralKernel {
+    /// Create a [`BilateralKernel`] covering `margin` around the center, with gaussian spatial
+    /// weights of `spatial_sigma` (in pixels) and range weights of `range_sigma` (in channel
+    /// values, applied to euclidean distance of RGB colors).
+    pub fn new(margin: Margin, spatial_sigma: f32, range_sigma: f32) -> CreationResult {
+        for sigma in [spatial_sigma, range_sigma] {
+            if !sigma.is_finite() || sigma <= 0f32 {
+                return Err(CreationError::InvalidSigma);
+            }
+        }
+
+        let (top, right) = (margin.top() as isize, margin.right() as isize);
+        let (bottom, left) = (margin.bottom() as isize, margin.left() as isize);
+        let spatial = (-top..=bottom)
+            .flat_map(|y| (-left..=right).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let distance_squared = (x * x + y * y) as f32;
+                let weight = (-distance_squared / (2f32 * spatial_sigma * spatial_sigma)).exp();
+
+                // SAFETY: coordinates are within margin, which fits in isize.
+                (Offset::new(x, y).expect("unexpected error in Offset::new"), weight)
+            })
+            .collect();
+
+        Ok(Self { margin, spatial, range_sigma })
+    }
+}
+
+impl<In> Kernel<In, Pixel> for BilateralKernel
+where
+    In: AsRef<Pixel>,
+{
+    fn apply<S>(&self, source: &S, point: Point) -> IndexResult<Pixel>
+    where
+        S: Lens<Item = In>,
+    {
+        let center = *source.look(point)?.as_ref();
+        let center_color = [center.r(), center.g(), center.b()].map(f32::from);
+        let range_factor = -1f32 / (2f32 * self.range_sigma * self.range_sigma);
+
+        let mut sum = [0f32; 3];
+        let mut total = 0f32;
+        for (offset, spatial_weight) in &self.spatial {
+            // SAFETY: kernel margin keeps every offset within source.
+            let px = *source
+                .look(point.translate(*offset).expect("unexpected error in translate"))?
+                .as_ref();
+            let color = [px.r(), px.g(), px.b()].map(f32::from);
+
+            let distance_squared =
+                (0..3).map(|channel| (color[channel] - center_color[channel]).powi(2)).sum::<f32>();
+            let weight = spatial_weight * (distance_squared * range_factor).exp();
+
+            (0..3).for_each(|channel| sum[channel] += weight * color[channel]);
+            total += weight;
+        }
+
+        // Center pixel always has weight 1, so total is never zero.
+        let [r, g, b] = sum.map(|value| (value / total).round().clamp(0f32, 255f32) as u8);
+
+        Ok(Pixel::new([r, g, b, center.a()]))
+    }
+
+    fn margin(&self) -> Margin {
+        self.margin
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        component::primitive::Size,
+        image::Image,
+        lens::FromLens,
+    };
+
+    #[test]
+    fn test_bilateral_kernel_preserves_edge_and_alpha() {
+        let size = Size::new(6, 3).unwrap();
+        let pixels = (0..size.area())
+            .map(|index| {
+                let value = if index % 6 < 3 { 20 } else { 220 };
+                Pixel::new([value, value, value, 100])
+            })
+            .collect();
+        let image = Image::new(size, pixels).unwrap();
+        let kernel = BilateralKernel::new(Margin::unified(1).unwrap(), 2f32, 10f32).unwrap();
+
+        let filtered = Image::from_lens(image.lens().kernel(kernel).unwrap());
+
+        assert_eq!(filtered.size(), Size::new(4, 1).unwrap());
+        let expected = [20, 20, 220, 220].map(|value| Pixel::new([value, value, value, 100]));
+        assert_eq!(filtered.pixels(), &expected);
+    }
+
+    #[test]
+    fn test_bilateral_kernel_invalid_sigma() {
+        let margin = Margin::unified(1).unwrap();
+
+        assert!(matches!(
+            BilateralKernel::new(margin, 0f32, 1f32),
+            Err(CreationError::InvalidSigma)
+        ));
+        assert!(matches!(
+            BilateralKernel::new(margin, 1f32, f32::NAN),
+            Err(CreationError::InvalidSigma)
+        ));
+    }
+}
@@ -52,11 +52,52 @@ impl ConvolutionKernel {
 
         Ok(Self { size, buffer, flags })
     }
+
+    fn window_margin(&self) -> Margin {
+        let (left, right) = if self.size.width().is_multiple_of(2) {
+            (self.size.width() / 2, self.size.width() / 2 - 1)
+        } else {
+            (self.size.width() / 2, self.size.width() / 2)
+        };
+
+        let (top, bottom) = if self.size.height().is_multiple_of(2) {
+            (self.size.height() / 2, self.size.height() / 2 - 1)
+        } else {
+            (self.size.height() / 2, self.size.height() / 2)
+        };
+
+        // SAFETY: all parameters are halves of some size which is guaranted to be
+        // less than or equal to DIMENSION_MAX.
+        Margin::new(top, right, bottom, left).expect("unexpected error in Margin::new")
+    }
 }
 
+/// Channel values normalized to 0-1 range, same as [`PixelRgbaf32`] getters return, so the 3x3
+/// path looks them up instead of dividing for every tap.
+const NORMALIZED: [f32; 256] = {
+    let mut table = [0f32; 256];
+    let mut value = 0;
+    while value < 256 {
+        table[value] = value as f32 / 255.0;
+        value += 1;
+    }
+    table
+};
+
 #[derive(Default)]
 struct IntermediatePixel(f32, f32, f32, f32);
 
+impl IntermediatePixel {
+    fn weighted(pixel: &Pixel, weight: f32) -> Self {
+        IntermediatePixel(
+            weight * pixel.r_f32(),
+            weight * pixel.g_f32(),
+            weight * pixel.b_f32(),
+            weight * pixel.a_f32(),
+        )
+    }
+}
+
 impl Add for IntermediatePixel {
     type Output = IntermediatePixel;
 
@@ -73,16 +114,32 @@ where
     where
         S: Lens<Item = In>,
     {
-        let working_area = Area::from_cropped_size(
-            lens.size(),
-            <ConvolutionKernel as Kernel<In, Pixel>>::margin(self),
-        )
-        .expect("failed to create working area, this is either lens or kernel bug");
+        let working_area = Area::from_cropped_size(lens.size(), self.window_margin())
+            .expect("failed to create working area, this is either lens or kernel bug");
 
         if !working_area.contains(&point) {
             return Err(IndexError::OutOfBounds);
         }
 
+        if self.size.width() == 3 && self.size.height() == 3 {
+            return Ok(self.apply_3x3(lens, point));
+        }
+
+        Ok(self.apply_generic(lens, point))
+    }
+
+    fn margin(&self) -> Margin {
+        self.window_margin()
+    }
+}
+
+impl ConvolutionKernel {
+    /// Evaluate kernel of any size at `point`, which must be within working area.
+    fn apply_generic<S, In>(&self, lens: &S, point: Point) -> Pixel
+    where
+        S: Lens<Item = In>,
+        In: AsRef<Pixel>,
+    {
         let center = self.size.middle();
 
         // SAFETY: `Lens::look` always returns a value when in bounds.
@@ -106,38 +163,127 @@ where
                     point.translate(offset).expect("unexpected error in Point::translate");
                 // SAFETY: `Lens::look` always returns a value when in bounds.
                 let current = lens.look(translated).expect("unexpected error in Lens::look");
-                let pixel = current.as_ref();
 
-                IntermediatePixel(
-                    value * pixel.r_f32(),
-                    value * pixel.g_f32(),
-                    value * pixel.b_f32(),
-                    value * pixel.a_f32(),
-                )
+                IntermediatePixel::weighted(current.as_ref(), *value)
             })
             .fold(IntermediatePixel(0f32, 0f32, 0f32, 0f32), |acc, item| acc + item);
 
         let mut px = *original.as_ref();
         px.set_with_flags_f32(sum.0, sum.1, sum.2, sum.3, self.flags);
 
-        Ok(px)
+        px
+    }
+
+    /// Evaluate 3x3 kernel at `point`, which must be within working area.
+    ///
+    /// The window is read once, row by row, into a fixed-size array, which also provides the
+    /// source pixel from its center. Taps pair with the window at constant offsets instead of
+    /// going through [`Point::from_index`] and [`Point::translate`], and channels are normalized
+    /// with a lookup table instead of a division. Taps are summed in the same order as in
+    /// [`ConvolutionKernel::apply_generic`], so results are identical.
+    fn apply_3x3<S, In>(&self, lens: &S, point: Point) -> Pixel
+    where
+        S: Lens<Item = In>,
+        In: AsRef<Pixel>,
+    {
+        // SAFETY: point is within working area, so it has at least one neighbor in each
+        // direction.
+        let (left, top) = (point.x() - 1, point.y() - 1);
+        let window: [Pixel; 9] = std::array::from_fn(|index| {
+            // SAFETY: every point of the window is within lens, see above.
+            let neighbor = Point::new(left + index % 3, top + index / 3)
+                .expect("unexpected error in Point::new");
+            // SAFETY: `Lens::look` always returns a value when in bounds.
+            *lens.look(neighbor).expect("unexpected error in Lens::look").as_ref()
+        });
+
+        // Tap at (kx, ky) reads neighbor mirrored around the center, which for a row-major
+        // window is the one at the mirrored index.
+        let sum =
+            self.buffer.iter().zip(window.iter().rev()).fold([0f32; 4], |mut sum, (weight, px)| {
+                sum.iter_mut()
+                    .zip(px.buffer())
+                    .for_each(|(sum, value)| *sum += weight * NORMALIZED[*value as usize]);
+                sum
+            });
+
+        let mut px = window[4];
+        px.set_with_flags_f32(sum[0], sum[1], sum[2], sum[3], self.flags);
+
+        px
+    }
+}
+
+impl Kernel<u8, u8> for ConvolutionKernel {
+    fn apply<S>(&self, lens: &S, point: Point) -> IndexResult<u8>
+    where
+        S: Lens<Item = u8>,
+    {
+        let working_area = Area::from_cropped_size(lens.size(), self.window_margin())
+            .expect("failed to create working area, this is either lens or kernel bug");
+
+        if !working_area.contains(&point) {
+            return Err(IndexError::OutOfBounds);
+        }
+
+        let center = self.size.middle();
+
+        let sum: f32 = self
+            .buffer
+            .iter()
+            .enumerate()
+            .map(|(index, value)| {
+                // SAFETY: index comes from the buffer of size used, so it is always in bounds.
+                let kernel_point = Point::from_index(index, self.size)
+                    .expect("unexpected error in Point::from_index");
+                // SAFETY: translated point always in bounds after previous checks.
+                let translated = point
+                    .translate(center - kernel_point)
+                    .expect("unexpected error in Point::translate");
+                // SAFETY: `Lens::look` always returns a value when in bounds.
+                value * lens.look(translated).expect("unexpected error in Lens::look") as f32
+            })
+            .sum();
+
+        Ok(sum.round().clamp(0f32, 255f32) as u8)
     }
 
     fn margin(&self) -> Margin {
-        let (left, right) = if self.size.width().is_multiple_of(2) {
-            (self.size.width() / 2, self.size.width() / 2 - 1)
-        } else {
-            (self.size.width() / 2, self.size.width() / 2)
-        };
+        self.window_margin()
+    }
+}
 
-        let (top, bottom) = if self.size.height().is_multiple_of(2) {
-            (self.size.height() / 2, self.size.height() / 2 - 1)
-        } else {
-            (self.size.height() / 2, self.size.height() / 2)
-        };
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
 
-        // SAFETY: all parameters are halves of some size which is guaranted to be
-        // less than or equal to DIMENSION_MAX.
-        Margin::new(top, right, bottom, left).expect("unexpected error in Margin::new")
+    use crate::image::Image;
+
+    use super::*;
+
+    fn random_kernel(rng: &mut SmallRng) -> ConvolutionKernel {
+        use rand::Rng;
+
+        let buffer: Vec<f32> = (0..9).map(|_| rng.random_range(-1f32..1f32)).collect();
+        ConvolutionKernel::new(Size::new(3, 3).unwrap(), buffer, ChannelFlags::RGBA).unwrap()
+    }
+
+    #[test]
+    fn test_apply_3x3_matches_generic() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let image = Image::random(Size::new(16, 12).unwrap(), &mut rng);
+        let lens = image.lens();
+
+        for _ in 0..4 {
+            let kernel = random_kernel(&mut rng);
+            for (x, y) in (1..15).cartesian_product(1..11) {
+                let point = Point::new(x, y).unwrap();
+                assert_eq!(kernel.apply_3x3(&lens, point), kernel.apply_generic(&lens, point));
+            }
+        }
     }
 }
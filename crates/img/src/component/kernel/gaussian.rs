@@ -94,3 +94,16 @@ where
         <ConvolutionKernel as Kernel<In, Pixel>>::margin(&self.inner)
     }
 }
+
+impl Kernel<u8, u8> for GaussianKernel {
+    fn apply<S>(&self, lens: &S, point: Point) -> IndexResult<u8>
+    where
+        S: Lens<Item = u8>,
+    {
+        self.inner.apply(lens, point)
+    }
+
+    fn margin(&self) -> Margin {
+        <ConvolutionKernel as Kernel<u8, u8>>::margin(&self.inner)
+    }
+}
@@ -0,0 +1,76 @@
+use crate::component::primitive::{
+    Point,
+    Size,
+    SizeCreationResult,
+};
+
+/// Create a mask of a square window with given `radius`, with every element enabled.
+///
+/// Mask is stored row-first, index of an element can be calculated with [`Point::index`] using
+/// [`Size::from_radius`] of the same `radius`.
+pub fn square_mask(radius: usize) -> SizeCreationResult<Vec<bool>> {
+    let size = Size::from_radius(radius)?;
+    Ok(vec![true; size.area()])
+}
+
+/// Create a mask of a square window with given `radius`, with only elements within a disk of
+/// `radius` enabled.
+///
+/// Mask is stored row-first, index of an element can be calculated with [`Point::index`] using
+/// [`Size::from_radius`] of the same `radius`.
+///
+/// # Examples
+///
+/// ```
+/// use img::component::kernel::mask::disk_mask;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let mask = disk_mask(1)?;
+///
+/// assert_eq!(mask, vec![false, true, false, true, true, true, false, true, false]);
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn disk_mask(radius: usize) -> SizeCreationResult<Vec<bool>> {
+    let size = Size::from_radius(radius)?;
+    let center = size.middle();
+
+    Ok((0..size.area())
+        .map(|index| {
+            // SAFETY: index comes from the area of size used, so it is always in bounds.
+            let point =
+                Point::from_index(index, size).expect("unexpected error in Point::from_index");
+            (point - center).length_squared() <= radius * radius
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_square_mask() {
+        assert_eq!(square_mask(0).unwrap(), vec![true]);
+        assert_eq!(square_mask(2).unwrap(), vec![true; 25]);
+    }
+
+    #[test]
+    fn test_disk_mask() {
+        assert_eq!(disk_mask(0).unwrap(), vec![true]);
+
+        let mask = disk_mask(2).unwrap();
+        let size = Size::from_radius(2).unwrap();
+        let at = |x, y| mask[Point::new(x, y).unwrap().index(size).unwrap()];
+
+        assert!(at(2, 2));
+        assert!(at(0, 2));
+        assert!(at(2, 4));
+        assert!(at(1, 1));
+        assert!(!at(0, 0));
+        assert!(!at(4, 4));
+        assert!(!at(0, 1));
+        assert_eq!(mask.iter().filter(|enabled| **enabled).count(), 13);
+    }
+}
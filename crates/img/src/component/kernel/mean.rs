@@ -6,11 +6,13 @@ use crate::{
             self,
             Kernel,
             convolution::ConvolutionKernel,
+            mask::disk_mask,
         },
         primitive::{
             Margin,
             Point,
             Size,
+            SizeCreationError,
         },
     },
     error::IndexResult,
@@ -25,6 +27,8 @@ use crate::{
 pub enum CreationError {
     #[error("invalid convolution kernel params: {0}")]
     ConvolutionKernelError(#[from] kernel::convolution::CreationError),
+    #[error("invalid size: {0}")]
+    Size(#[from] SizeCreationError),
 }
 
 pub type CreationResult = Result<MeanKernel, CreationError>;
@@ -44,6 +48,21 @@ impl MeanKernel {
             )?,
         })
     }
+
+    /// Create a [`MeanKernel`] averaging only values within a disk of given `radius`.
+    pub fn disk(radius: usize, flags: ChannelFlags) -> CreationResult {
+        let size = Size::from_radius(radius)?;
+        let mask = disk_mask(radius)?;
+        let count = mask.iter().filter(|enabled| **enabled).count();
+
+        Ok(Self {
+            inner: ConvolutionKernel::new(
+                size,
+                mask.into_iter().map(|enabled| if enabled { 1f32 / count as f32 } else { 0f32 }),
+                flags,
+            )?,
+        })
+    }
 }
 
 impl<In> Kernel<In, Pixel> for MeanKernel
@@ -61,3 +80,16 @@ where
         <ConvolutionKernel as Kernel<In, Pixel>>::margin(&self.inner)
     }
 }
+
+impl Kernel<u8, u8> for MeanKernel {
+    fn apply<S>(&self, lens: &S, point: Point) -> IndexResult<u8>
+    where
+        S: Lens<Item = u8>,
+    {
+        self.inner.apply(lens, point)
+    }
+
+    fn margin(&self) -> Margin {
+        <ConvolutionKernel as Kernel<u8, u8>>::margin(&self.inner)
+    }
+}
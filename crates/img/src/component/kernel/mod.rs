@@ -7,11 +7,16 @@ use crate::{
     lens::Lens,
 };
 
+pub mod bilateral;
 pub mod convolution;
 pub mod gaussian;
 pub mod identity;
+pub mod mask;
 pub mod mean;
+pub mod morphology;
+pub mod per_channel;
 pub mod sobel;
+pub mod stat;
 
 pub trait Kernel<In, Out> {
     fn apply<S>(&self, source: &S, point: Point) -> IndexResult<Out>
@@ -0,0 +1,159 @@
+use crate::{
+    component::{
+        kernel::Kernel,
+        primitive::{
+            Area,
+            Connectivity,
+            Margin,
+            Point,
+            Size,
+            SizeCreationResult,
+        },
+    },
+    error::{
+        IndexError,
+        IndexResult,
+    },
+    lens::Lens,
+    pixel::{
+        ChannelFlags,
+        Pixel,
+    },
+};
+
+use super::mask::{
+    disk_mask,
+    square_mask,
+};
+
+/// Shape of a window used by morphological operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructuringElement {
+    /// Square window with given radius.
+    Square(usize),
+    /// Disk with given radius.
+    Disk(usize),
+    /// Pixel with its neighbors of given [`Connectivity`], which is a cross for
+    /// [`Connectivity::Four`] and a 3x3 square for [`Connectivity::Eight`].
+    Neighborhood(Connectivity),
+}
+
+impl From<Connectivity> for StructuringElement {
+    fn from(value: Connectivity) -> Self {
+        StructuringElement::Neighborhood(value)
+    }
+}
+
+impl StructuringElement {
+    /// Get radius of [`StructuringElement`].
+    pub fn radius(&self) -> usize {
+        match self {
+            StructuringElement::Square(radius) => *radius,
+            StructuringElement::Disk(radius) => *radius,
+            StructuringElement::Neighborhood(_) => 1,
+        }
+    }
+
+    /// Get mask of [`StructuringElement`]. See [`disk_mask`] for layout.
+    pub fn mask(&self) -> SizeCreationResult<Vec<bool>> {
+        match self {
+            StructuringElement::Square(radius) => square_mask(*radius),
+            StructuringElement::Disk(radius) => disk_mask(*radius),
+            // Disk of radius 1 is exactly the cross of direct neighbors.
+            StructuringElement::Neighborhood(Connectivity::Four) => disk_mask(1),
+            StructuringElement::Neighborhood(Connectivity::Eight) => square_mask(1),
+        }
+    }
+}
+
+/// Operation applied over [`StructuringElement`] window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MorphologyOperation {
+    /// Take minimum of each channel.
+    Erode,
+    /// Take maximum of each channel.
+    Dilate,
+}
+
+/// A [`Kernel`] which takes per-channel minimum or maximum of all [`Pixel`]s within
+/// [`StructuringElement`].
+#[derive(Clone)]
+pub struct MorphologyKernel {
+    size: Size,
+    mask: Box<[bool]>,
+    operation: MorphologyOperation,
+    flags: ChannelFlags,
+}
+
+impl MorphologyKernel {
+    pub fn new(
+        element: StructuringElement,
+        operation: MorphologyOperation,
+        flags: ChannelFlags,
+    ) -> SizeCreationResult<Self> {
+        let size = Size::from_radius(element.radius())?;
+        let mask = element.mask()?.into_boxed_slice();
+
+        Ok(Self { size, mask, operation, flags })
+    }
+}
+
+impl<In> Kernel<In, Pixel> for MorphologyKernel
+where
+    In: AsRef<Pixel>,
+{
+    fn apply<S>(&self, lens: &S, point: Point) -> IndexResult<Pixel>
+    where
+        S: Lens<Item = In>,
+    {
+        let working_area = Area::from_cropped_size(
+            lens.size(),
+            <MorphologyKernel as Kernel<In, Pixel>>::margin(self),
+        )
+        .expect("failed to create working area, this is either lens or kernel bug");
+
+        if !working_area.contains(&point) {
+            return Err(IndexError::OutOfBounds);
+        }
+
+        let center = self.size.middle();
+        let initial = match self.operation {
+            MorphologyOperation::Erode => [u8::MAX; 4],
+            MorphologyOperation::Dilate => [u8::MIN; 4],
+        };
+
+        let result = self
+            .mask
+            .iter()
+            .enumerate()
+            .filter(|(_, enabled)| **enabled)
+            .map(|(index, _)| {
+                // SAFETY: index comes from the mask of size used, so it is always in bounds.
+                let kernel_point = Point::from_index(index, self.size)
+                    .expect("unexpected error in Point::from_index");
+                // SAFETY: translated point always in bounds after previous checks.
+                let translated = point
+                    .translate(kernel_point - center)
+                    .expect("unexpected error in Point::translate");
+                // SAFETY: `Lens::look` always returns a value when in bounds.
+                *lens.look(translated).expect("unexpected error in Lens::look").as_ref().buffer()
+            })
+            .fold(initial, |acc, item| {
+                std::array::from_fn(|i| match self.operation {
+                    MorphologyOperation::Erode => acc[i].min(item[i]),
+                    MorphologyOperation::Dilate => acc[i].max(item[i]),
+                })
+            });
+
+        // SAFETY: `Lens::look` always returns a value when in bounds.
+        let mut px = *lens.look(point).expect("unexpected error in Lens::look").as_ref();
+        px.set_with_flags(result[0], result[1], result[2], result[3], self.flags);
+
+        Ok(px)
+    }
+
+    fn margin(&self) -> Margin {
+        // SAFETY: radius is less than DIMENSION_MAX / 2 since size was created from it.
+        Margin::unified(self.size.width() / 2).expect("unexpected error in Margin::unified")
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{
+    component::{
+        kernel::Kernel,
+        primitive::{
+            Margin,
+            Point,
+        },
+    },
+    error::IndexResult,
+    lens::Lens,
+    pixel::Pixel,
+};
+
+/// A [`Kernel`] applying separate single channel [`Kernel`] to each channel of a [`Pixel`].
+///
+/// Each channel kernel can have a different [`Margin`], resulting margin is the largest one in
+/// each direction, so output size is the smallest of all channel kernels.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     component::kernel::{
+///         identity::IdentityKernel,
+///         mean::MeanKernel,
+///         per_channel::PerChannelKernel,
+///     },
+///     lens::Lens,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let image = Image::empty(Size::new(10, 10)?);
+/// let kernel = PerChannelKernel::new(
+///     IdentityKernel::new(),
+///     IdentityKernel::new(),
+///     MeanKernel::new(Size::from_radius(1)?, ChannelFlags::empty())?,
+///     IdentityKernel::new(),
+/// );
+///
+/// let lens = image.lens().kernel(kernel)?;
+/// assert_eq!(lens.size(), Size::new(8, 8)?);
+///
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct PerChannelKernel<R, G, B, A> {
+    red: R,
+    green: G,
+    blue: B,
+    alpha: A,
+}
+
+impl<R, G, B, A> PerChannelKernel<R, G, B, A> {
+    pub fn new(red: R, green: G, blue: B, alpha: A) -> Self {
+        Self { red, green, blue, alpha }
+    }
+}
+
+impl<In, R, G, B, A> Kernel<In, Pixel> for PerChannelKernel<R, G, B, A>
+where
+    In: AsRef<Pixel>,
+    R: Kernel<u8, u8>,
+    G: Kernel<u8, u8>,
+    B: Kernel<u8, u8>,
+    A: Kernel<u8, u8>,
+{
+    fn apply<S>(&self, source: &S, point: Point) -> IndexResult<Pixel>
+    where
+        S: Lens<Item = In>,
+    {
+        let red = self.red.apply(&source.map(|px| px.as_ref().r()), point)?;
+        let green = self.green.apply(&source.map(|px| px.as_ref().g()), point)?;
+        let blue = self.blue.apply(&source.map(|px| px.as_ref().b()), point)?;
+        let alpha = self.alpha.apply(&source.map(|px| px.as_ref().a()), point)?;
+
+        Ok(Pixel::new([red, green, blue, alpha]))
+    }
+
+    fn margin(&self) -> Margin {
+        [self.green.margin(), self.blue.margin(), self.alpha.margin()].into_iter().fold(
+            self.red.margin(),
+            |acc, margin| {
+                // SAFETY: maximum of valid margin components is a valid margin component.
+                Margin::new(
+                    acc.top().max(margin.top()),
+                    acc.right().max(margin.right()),
+                    acc.bottom().max(margin.bottom()),
+                    acc.left().max(margin.left()),
+                )
+                .expect("unexpected error in Margin::new")
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use crate::{
+        component::{
+            kernel::{
+                identity::IdentityKernel,
+                mean::MeanKernel,
+            },
+            primitive::{
+                Offset,
+                Size,
+            },
+        },
+        image::Image,
+        lens::FromLens,
+        pixel::ChannelFlags,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_blur_blue_only() {
+        let image = Image::random(Size::new(10, 10).unwrap(), &mut SmallRng::seed_from_u64(0));
+        let kernel = PerChannelKernel::new(
+            IdentityKernel::new(),
+            IdentityKernel::new(),
+            MeanKernel::new(Size::from_radius(1).unwrap(), ChannelFlags::empty()).unwrap(),
+            IdentityKernel::new(),
+        );
+
+        let blurred = Image::from_lens(image.lens().kernel(kernel).unwrap());
+        assert_eq!(blurred.size(), Size::new(8, 8).unwrap());
+
+        let mut blue_changed = false;
+        for (x, y) in (0..8).cartesian_product(0..8) {
+            let point = Point::new(x, y).unwrap();
+            let source_point = point.translate(Offset::new(1, 1).unwrap()).unwrap();
+            let original = image.pixel(source_point).unwrap();
+            let px = blurred.pixel(point).unwrap();
+
+            assert_eq!(px.r(), original.r());
+            assert_eq!(px.g(), original.g());
+            assert_eq!(px.a(), original.a());
+
+            let mean = (-1..=1)
+                .cartesian_product(-1..=1)
+                .map(|(dx, dy)| {
+                    let neighbor = source_point.translate(Offset::new(dx, dy).unwrap()).unwrap();
+                    image.pixel(neighbor).unwrap().b() as f32
+                })
+                .sum::<f32>()
+                / 9f32;
+            assert!((px.b() as f32 - mean).abs() <= 1f32);
+            blue_changed |= px.b() != original.b();
+        }
+        assert!(blue_changed);
+    }
+
+    #[test]
+    fn test_margin_is_largest_of_channels() {
+        let kernel = PerChannelKernel::new(
+            IdentityKernel::new(),
+            MeanKernel::new(Size::new(3, 1).unwrap(), ChannelFlags::empty()).unwrap(),
+            MeanKernel::new(Size::new(1, 5).unwrap(), ChannelFlags::empty()).unwrap(),
+            IdentityKernel::new(),
+        );
+
+        assert_eq!(
+            <PerChannelKernel<_, _, _, _> as Kernel<Pixel, Pixel>>::margin(&kernel),
+            Margin::new(2, 1, 2, 1).unwrap()
+        );
+    }
+}
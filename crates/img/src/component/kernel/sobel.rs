@@ -27,7 +27,7 @@ pub struct Gradient {
 
 impl Gradient {
     pub fn magnitude(&self) -> f32 {
-        ((self.x.pow(2) + self.y.pow(2)) as f32).sqrt()
+        (self.x as f32).hypot(self.y as f32)
     }
 
     pub fn direction(&self) -> f32 {
@@ -0,0 +1,151 @@
+use crate::{
+    component::{
+        kernel::Kernel,
+        primitive::{
+            Area,
+            Margin,
+            Point,
+            Size,
+            SizeCreationResult,
+        },
+    },
+    error::{
+        IndexError,
+        IndexResult,
+    },
+    lens::Lens,
+    pixel::Pixel,
+};
+
+/// A [`Kernel`] computing arbitrary statistic of luminance within a square window.
+///
+/// `f` is given luminance (ITU-R BT.601, in 0-255 range) of every pixel in the window in
+/// row-major order, and its result is rounded and clamped to `u8`. Useful for prototyping
+/// local operators like range or mode without implementing [`Kernel`].
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     component::kernel::stat::StatKernel,
+///     lens::Lens,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let image = Image::solid(Size::new(10, 10)?, Pixel::new([50, 50, 50, 255]));
+/// let kernel = StatKernel::new(1, |values| values.iter().sum::<f32>() / values.len() as f32)?;
+///
+/// let lens = image.lens().kernel(kernel)?;
+///
+/// assert_eq!(lens.size(), Size::new(8, 8)?);
+/// assert_eq!(lens.look(Point::zero())?, 50);
+///
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct StatKernel<F> {
+    radius: usize,
+    f: F,
+}
+
+impl<F> StatKernel<F>
+where
+    F: Fn(&[f32]) -> f32,
+{
+    /// Create a [`StatKernel`] with a square window of given `radius`.
+    ///
+    /// Returns [`SizeCreationError`] if window of `radius` is too large.
+    ///
+    /// [`SizeCreationError`]: crate::component::primitive::SizeCreationError
+    pub fn new(radius: usize, f: F) -> SizeCreationResult<Self> {
+        Size::from_radius(radius)?;
+        Ok(Self { radius, f })
+    }
+
+    fn window_margin(&self) -> Margin {
+        // SAFETY: radius was validated when creating the kernel.
+        Margin::unified(self.radius).expect("unexpected error in Margin::unified")
+    }
+}
+
+impl<In, F> Kernel<In, u8> for StatKernel<F>
+where
+    In: AsRef<Pixel>,
+    F: Fn(&[f32]) -> f32,
+{
+    fn apply<S>(&self, source: &S, point: Point) -> IndexResult<u8>
+    where
+        S: Lens<Item = In>,
+    {
+        let working_area = Area::from_cropped_size(source.size(), self.window_margin())
+            .expect("failed to create working area, this is either lens or kernel bug");
+        if !working_area.contains(&point) {
+            return Err(IndexError::OutOfBounds);
+        }
+
+        let (x, y) = (point.x() - self.radius, point.y() - self.radius);
+        let diameter = 2 * self.radius + 1;
+        let values = (y..y + diameter)
+            .flat_map(|y| (x..x + diameter).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                // SAFETY: window is within source thanks to the check above.
+                let point = Point::new(x, y).expect("unexpected error in Point::new");
+                let px = source.look(point).expect("unexpected error in Lens::look");
+                px.as_ref().luminance_f32() * 255f32
+            })
+            .collect::<Vec<_>>();
+
+        Ok((self.f)(&values).round().clamp(0f32, 255f32) as u8)
+    }
+
+    fn margin(&self) -> Margin {
+        self.window_margin()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image::Image;
+
+    fn local_range(values: &[f32]) -> f32 {
+        let max = values.iter().copied().fold(f32::MIN, f32::max);
+        let min = values.iter().copied().fold(f32::MAX, f32::min);
+        max - min
+    }
+
+    #[test]
+    fn test_local_range() {
+        // Gray values increasing by 10 along x and 1 along y.
+        let size = Size::new(5, 4).unwrap();
+        let pixels = (0..size.area())
+            .map(|index| (index % 5 * 10 + index / 5) as u8)
+            .map(|value| Pixel::new([value, value, value, 255]))
+            .collect();
+        let image = Image::new(size, pixels).unwrap();
+
+        let lens = image.lens().kernel(StatKernel::new(1, local_range).unwrap()).unwrap();
+
+        assert_eq!(lens.size(), Size::new(3, 2).unwrap());
+        // Window spans 3 columns and 3 rows, so range is 2 * 10 + 2 * 1.
+        assert!(lens.elements().all(|value| value == 22));
+        assert_eq!(lens.look(Point::new(3, 0).unwrap()), Err(IndexError::OutOfBounds));
+    }
+
+    #[test]
+    fn test_window_order() {
+        let size = Size::new(3, 3).unwrap();
+        let pixels = (0..9u8).map(|value| Pixel::new([value, value, value, 255])).collect();
+        let image = Image::new(size, pixels).unwrap();
+
+        // Returns position of the largest value, which is the last one in row-major order.
+        let kernel = StatKernel::new(1, |values: &[f32]| {
+            values.iter().enumerate().max_by(|a, b| a.1.total_cmp(b.1)).unwrap().0 as f32
+        })
+        .unwrap();
+
+        assert_eq!(image.lens().kernel(kernel).unwrap().look(Point::zero()).unwrap(), 8);
+    }
+}
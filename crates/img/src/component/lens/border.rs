@@ -2,7 +2,13 @@ use crate::{
     component::primitive::{
         Margin,
         Point,
+        Size,
         SizeCreationError,
+        SizeCreationResult,
+    },
+    error::{
+        IndexError,
+        IndexResult,
     },
     lens::{
         Lens,
@@ -35,3 +41,208 @@ where
         Point::new(margin.left(), margin.top()).expect("unexpected error in Point::new"),
     )
 }
+
+/// Describes how coordinates outside of a source are handled, both by [`border_lens`] filling
+/// the added border and by samplers, see [`EdgeMode`](crate::component::sampler::EdgeMode).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BorderMode<T> {
+    /// Use given value for every point outside of the source.
+    Constant(T),
+    /// Repeat the nearest edge value.
+    Clamp,
+    /// Mirror the source at its edges, edge values are repeated (`cba|abc|cba`).
+    Reflect,
+    /// Tile the source.
+    Wrap,
+}
+
+impl<T> BorderMode<T> {
+    /// Map `coordinate` into `0..length` range.
+    ///
+    /// Returns mapped coordinate, or `None` for [`BorderMode::Constant`] when `coordinate` is out
+    /// of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::{
+    ///     component::sampler::EdgeMode,
+    ///     prelude::*,
+    /// };
+    ///
+    /// assert_eq!(EdgeMode::Clamp.resolve(-2, 4), Some(0));
+    /// assert_eq!(EdgeMode::Reflect.resolve(-2, 4), Some(1));
+    /// assert_eq!(EdgeMode::Wrap.resolve(-2, 4), Some(2));
+    /// assert_eq!(EdgeMode::Constant(Pixel::zero()).resolve(-2, 4), None);
+    /// assert_eq!(EdgeMode::Constant(Pixel::zero()).resolve(3, 4), Some(3));
+    /// ```
+    pub fn resolve(&self, coordinate: isize, length: usize) -> Option<usize> {
+        self.map_coordinate(coordinate, length).ok()
+    }
+
+    /// Map `(x, y)` into a point of `size`, or get the constant value if this mode is
+    /// [`BorderMode::Constant`] and the point is outside of `size`.
+    pub(crate) fn map_point(&self, x: isize, y: isize, size: Size) -> Result<Point, &T> {
+        let x = self.map_coordinate(x, size.width())?;
+        let y = self.map_coordinate(y, size.height())?;
+
+        // SAFETY: mapped coordinates are within size.
+        Ok(Point::new(x, y).expect("unexpected error in Point::new"))
+    }
+
+    fn map_coordinate(&self, coordinate: isize, length: usize) -> Result<usize, &T> {
+        let length = length as isize;
+        let mapped = match self {
+            _ if (0..length).contains(&coordinate) => coordinate,
+            BorderMode::Constant(value) => return Err(value),
+            BorderMode::Clamp => coordinate.clamp(0, length - 1),
+            BorderMode::Reflect => {
+                let period = coordinate.rem_euclid(2 * length);
+                if period < length { period } else { 2 * length - 1 - period }
+            }
+            BorderMode::Wrap => coordinate.rem_euclid(length),
+        };
+
+        Ok(mapped as usize)
+    }
+}
+
+impl<T> BorderMode<T>
+where
+    T: Clone,
+{
+    /// Look at `source` at integer coordinates, which may be outside of it, handling them
+    /// according to this mode.
+    pub fn look<S>(&self, source: &S, x: isize, y: isize) -> IndexResult<T>
+    where
+        S: Lens<Item = T>,
+    {
+        match self.map_point(x, y, source.size()) {
+            Ok(point) => source.look(point),
+            Err(value) => Ok(value.clone()),
+        }
+    }
+}
+
+/// A [`Lens`] extending `source` by `margin`, filling the added border according to
+/// [`BorderMode`]. Created with [`border_lens`].
+#[derive(Clone)]
+pub struct BorderLens<S, T> {
+    source: S,
+    margin: Margin,
+    mode: BorderMode<T>,
+    size: Size,
+}
+
+impl<S, T> Lens for BorderLens<S, T>
+where
+    S: Lens<Item = T>,
+    T: Clone,
+{
+    type Item = T;
+
+    fn look(&self, point: Point) -> IndexResult<Self::Item> {
+        if !self.size.contains(&point) {
+            return Err(IndexError::OutOfBounds);
+        }
+
+        let x = point.x() as isize - self.margin.left() as isize;
+        let y = point.y() as isize - self.margin.top() as isize;
+
+        self.mode.look(&self.source, x, y)
+    }
+
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+/// Extend `source` by `margin`, filling the border according to `mode`.
+///
+/// Point `(margin.left(), margin.top())` of the result is the top left corner of `source`.
+/// Unlike [`value_border`], [`BorderMode::Clamp`], [`BorderMode::Reflect`] and
+/// [`BorderMode::Wrap`] map border points back into `source`, so padding before a kernel does
+/// not darken edges of the result, see [`Lens::clamp_border`].
+///
+/// Returns [`SizeCreationError`] if extended size exceeds maximum dimension.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     component::lens::border::{
+///         BorderMode,
+///         border_lens,
+///     },
+///     lens::{
+///         Lens,
+///         value::ValueLens,
+///     },
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let source = ValueLens::new(7u8, Size::new(2, 2)?);
+/// let lens = border_lens(source, Margin::unified(3)?, BorderMode::Wrap)?;
+///
+/// assert_eq!(lens.size(), Size::new(8, 8)?);
+/// assert_eq!(lens.look(Point::zero())?, 7);
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn border_lens<S, T>(
+    source: S,
+    margin: Margin,
+    mode: BorderMode<T>,
+) -> SizeCreationResult<BorderLens<S, T>>
+where
+    S: Lens<Item = T>,
+    T: Clone,
+{
+    let size = source.size().extend_by_margin(margin)?;
+    Ok(BorderLens { source, margin, mode, size })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Row of `0, 1, 2` extended by 4 on both sides.
+    fn row(mode: BorderMode<u8>) -> Vec<u8> {
+        let size = Size::new(3, 1).unwrap();
+        let source = ValueLens::new(0u8, size).remap(|_, point| Ok(point.x() as u8), size);
+        let margin = Margin::new(0, 4, 0, 4).unwrap();
+
+        border_lens(source, margin, mode).unwrap().elements().collect()
+    }
+
+    #[test]
+    fn test_border_modes() {
+        assert_eq!(row(BorderMode::Constant(9)), [9, 9, 9, 9, 0, 1, 2, 9, 9, 9, 9]);
+        assert_eq!(row(BorderMode::Clamp), [0, 0, 0, 0, 0, 1, 2, 2, 2, 2, 2]);
+        assert_eq!(row(BorderMode::Reflect), [2, 2, 1, 0, 0, 1, 2, 2, 1, 0, 0]);
+        assert_eq!(row(BorderMode::Wrap), [2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0]);
+    }
+
+    #[test]
+    fn test_clamp_border() {
+        let size = Size::new(3, 2).unwrap();
+        let source = ValueLens::new(0u8, size).remap(|_, p| Ok((p.y() * 3 + p.x()) as u8), size);
+
+        let padded = source.clamp_border(Margin::new(1, 2, 0, 1).unwrap()).unwrap();
+        let look = |x, y| padded.look(Point::new(x, y).unwrap()).unwrap();
+
+        assert_eq!(padded.size(), Size::new(6, 3).unwrap());
+        assert_eq!((look(0, 0), look(1, 1), look(3, 2), look(5, 0), look(0, 2)), (0, 0, 5, 2, 3));
+    }
+
+    #[test]
+    fn test_border_lens_out_of_bounds() {
+        let source = ValueLens::new(1u8, Size::new(2, 2).unwrap());
+        let lens = border_lens(source, Margin::unified(1).unwrap(), BorderMode::Clamp).unwrap();
+
+        assert_eq!(lens.size(), Size::new(4, 4).unwrap());
+        assert_eq!(lens.look(Point::new(4, 0).unwrap()), Err(IndexError::OutOfBounds));
+    }
+}
@@ -1,3 +1,6 @@
+pub mod histogram;
 pub mod kernel;
 pub mod lens;
 pub mod primitive;
+pub mod sampler;
+pub mod summed_area;
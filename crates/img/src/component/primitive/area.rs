@@ -17,7 +17,7 @@ pub enum AreaCreationError {
 pub type AreaCreationResult<T> = Result<T, AreaCreationError>;
 
 /// Represents a 2D area defined by size and top left point.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Area {
     size: Size,
     top_left: Point,
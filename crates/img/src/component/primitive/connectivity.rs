@@ -0,0 +1,55 @@
+use super::{
+    Offset,
+    Point,
+    Size,
+};
+
+const FOUR_OFFSETS: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+const EIGHT_OFFSETS: [(isize, isize); 8] =
+    [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+
+/// Neighbors considered adjacent to a pixel by region based operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Connectivity {
+    /// Horizontal and vertical neighbors.
+    #[default]
+    Four,
+    /// Horizontal, vertical and diagonal neighbors.
+    Eight,
+}
+
+impl Connectivity {
+    /// Get [`Offset`]s of all neighbors, in row-major order.
+    pub fn offsets(&self) -> impl Iterator<Item = Offset> {
+        let offsets: &[(isize, isize)] = match self {
+            Connectivity::Four => &FOUR_OFFSETS,
+            Connectivity::Eight => &EIGHT_OFFSETS,
+        };
+
+        // SAFETY: offsets of neighbors are always in valid range.
+        offsets.iter().map(|(x, y)| Offset::new(*x, *y).expect("unexpected error in Offset::new"))
+    }
+
+    /// Get neighbors of `point` which lie within `size`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::prelude::*;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let size = Size::new(3, 3)?;
+    ///
+    /// assert_eq!(Connectivity::Four.neighbors(Point::zero(), size).count(), 2);
+    /// assert_eq!(Connectivity::Eight.neighbors(Point::zero(), size).count(), 3);
+    /// assert_eq!(Connectivity::Eight.neighbors(Point::new(1, 1)?, size).count(), 8);
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn neighbors(&self, point: Point, size: Size) -> impl Iterator<Item = Point> {
+        self.offsets()
+            .filter_map(move |offset| point.translate(offset).ok())
+            .filter(move |neighbor| size.contains(neighbor))
+    }
+}
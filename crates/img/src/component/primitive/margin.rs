@@ -1,3 +1,5 @@
+use std::ops::Add;
+
 use thiserror::Error;
 
 use crate::image::DIMENSION_MAX;
@@ -77,6 +79,26 @@ impl Margin {
         Margin::new(value, value, value, value)
     }
 
+    /// Create a new [`Margin`] with top and bottom components equal to `vertical` and left and
+    /// right components equal to `horizontal`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::prelude::*;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let margin = Margin::symmetric(2, 3)?;
+    ///
+    /// assert_eq!(margin, Margin::new(2, 3, 2, 3)?);
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn symmetric(vertical: usize, horizontal: usize) -> MarginCreationResult<Self> {
+        Margin::new(vertical, horizontal, vertical, horizontal)
+    }
+
     /// Returns [`Margin`]'s top component.
     pub fn top(&self) -> usize {
         self.top
@@ -96,6 +118,47 @@ impl Margin {
     pub fn left(&self) -> usize {
         self.left
     }
+
+    /// Returns sum of [`Margin`]'s left and right components.
+    pub fn horizontal(&self) -> usize {
+        self.left + self.right
+    }
+
+    /// Returns sum of [`Margin`]'s top and bottom components.
+    pub fn vertical(&self) -> usize {
+        self.top + self.bottom
+    }
+}
+
+impl Add for Margin {
+    type Output = MarginCreationResult<Margin>;
+
+    /// Add two [`Margin`]s componentwise.
+    ///
+    /// Returns [`Margin`] if all resulting components are valid, [`MarginCreationError`]
+    /// otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::prelude::*;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let margin = (Margin::new(1, 2, 3, 4)? + Margin::unified(1)?)?;
+    ///
+    /// assert_eq!(margin, Margin::new(2, 3, 4, 5)?);
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn add(self, rhs: Self) -> Self::Output {
+        Margin::new(
+            self.top + rhs.top,
+            self.right + rhs.right,
+            self.bottom + rhs.bottom,
+            self.left + rhs.left,
+        )
+    }
 }
 
 #[cfg(test)]
@@ -150,4 +213,37 @@ mod tests {
     fn test_left() {
         assert_eq!(Margin::new(1, 2, 3, 4).unwrap().left(), 4);
     }
+
+    #[test]
+    fn test_symmetric() {
+        let margin = Margin::symmetric(2, 3).unwrap();
+        assert_eq!(margin.top(), 2);
+        assert_eq!(margin.bottom(), 2);
+        assert_eq!(margin.left(), 3);
+        assert_eq!(margin.right(), 3);
+        assert_eq!(
+            Margin::symmetric(DIMENSION_MAX, 0).unwrap_err(),
+            MarginCreationError::TopTooBig
+        );
+    }
+
+    #[test]
+    fn test_horizontal_vertical() {
+        let margin = Margin::new(1, 2, 3, 4).unwrap();
+        assert_eq!(margin.horizontal(), 6);
+        assert_eq!(margin.vertical(), 4);
+    }
+
+    #[test]
+    fn test_add() {
+        assert_eq!(
+            (Margin::new(1, 2, 3, 4).unwrap() + Margin::new(10, 20, 30, 40).unwrap()).unwrap(),
+            Margin::new(11, 22, 33, 44).unwrap()
+        );
+        assert_eq!(
+            (Margin::new(0, DIMENSION_MAX - 1, 0, 0).unwrap() + Margin::unified(1).unwrap())
+                .unwrap_err(),
+            MarginCreationError::RightTooBig
+        );
+    }
 }
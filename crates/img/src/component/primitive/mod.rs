@@ -1,4 +1,5 @@
 mod area;
+mod connectivity;
 mod limits;
 mod margin;
 mod offset;
@@ -11,6 +12,7 @@ pub use area::{
     AreaCreationError,
     AreaCreationResult,
 };
+pub use connectivity::Connectivity;
 pub use limits::DIMENSION_MAX;
 pub use margin::{
     Margin,
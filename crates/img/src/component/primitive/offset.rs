@@ -67,6 +67,40 @@ impl Offset {
     pub fn y(&self) -> isize {
         self.y
     }
+
+    /// Returns squared euclidean length of [`Offset`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::prelude::*;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// assert_eq!(Offset::new(3, -4)?.length_squared(), 25);
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn length_squared(&self) -> usize {
+        self.x.unsigned_abs().pow(2) + self.y.unsigned_abs().pow(2)
+    }
+
+    /// Returns chebyshev length of [`Offset`] - the larger of absolute components.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::prelude::*;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// assert_eq!(Offset::new(3, -4)?.chebyshev(), 4);
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn chebyshev(&self) -> usize {
+        self.x.unsigned_abs().max(self.y.unsigned_abs())
+    }
 }
 
 impl From<Point> for Offset {
@@ -119,6 +153,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_length_squared() {
+        assert_eq!(Offset::new(0, 0).unwrap().length_squared(), 0);
+        assert_eq!(Offset::new(3, 4).unwrap().length_squared(), 25);
+        assert_eq!(Offset::new(-3, -4).unwrap().length_squared(), 25);
+        assert_eq!(Offset::new(-2, 0).unwrap().length_squared(), 4);
+    }
+
+    #[test]
+    fn test_chebyshev() {
+        assert_eq!(Offset::new(0, 0).unwrap().chebyshev(), 0);
+        assert_eq!(Offset::new(3, -4).unwrap().chebyshev(), 4);
+        assert_eq!(Offset::new(-5, 2).unwrap().chebyshev(), 5);
+        assert_eq!(
+            Offset::new(-(DIMENSION_MAX as isize) + 1, 0).unwrap().chebyshev(),
+            DIMENSION_MAX - 1
+        );
+    }
+
     #[test]
     fn test_from_point() {
         assert_eq!(Offset::from(Point::new(0, 0).unwrap()), Offset::new(0, 0).unwrap());
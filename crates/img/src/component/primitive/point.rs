@@ -164,6 +164,23 @@ impl Point {
         Ok(self.y * size.width() + self.x)
     }
 
+    /// Calculate manhattan (taxicab) distance between two [`Point`]s.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::prelude::*;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// assert_eq!(Point::new(1, 2)?.manhattan_distance(&Point::new(4, 0)?), 5);
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn manhattan_distance(&self, other: &Point) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
     /// Translate [`Point`] by given [`Offset`].
     ///
     /// Returns [`Point`] if point components are non-negative, [`PointCreationError`] otherwise.
@@ -437,6 +454,19 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_manhattan_distance() {
+        let zero = Point::zero();
+        assert_eq!(zero.manhattan_distance(&zero), 0);
+        assert_eq!(zero.manhattan_distance(&Point::new(3, 4).unwrap()), 7);
+        assert_eq!(Point::new(3, 4).unwrap().manhattan_distance(&zero), 7);
+        assert_eq!(Point::new(5, 1).unwrap().manhattan_distance(&Point::new(2, 6).unwrap()), 8);
+        assert_eq!(
+            zero.manhattan_distance(&Point::new(DIMENSION_MAX - 1, DIMENSION_MAX - 1).unwrap()),
+            2 * (DIMENSION_MAX - 1)
+        );
+    }
+
     #[test]
     fn test_translate_ok() {
         assert!(Point::new(0, 0).unwrap().translate(Offset::new(0, 0).unwrap()).is_ok());
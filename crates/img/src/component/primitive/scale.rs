@@ -189,6 +189,37 @@ impl Scale {
 
         Point::new(new_x.floor() as usize, new_y.floor() as usize)
     }
+
+    /// Transform the point to scaled coordinate space without rounding.
+    ///
+    /// This is the fractional counterpart of [`Scale::translate`] and the basis for interpolating
+    /// resize, e.g. bilinear or Lanczos: calling it on the inverse of resize scale gives position
+    /// of output pixel's top left corner in source coordinates. Samplers working with pixel
+    /// centers should shift the result by `0.5 * scale - 0.5`.
+    ///
+    /// Returns scaled `(x, y)` coordinates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::prelude::*;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let downscale = Scale::new(0.5, 0.5)?;
+    /// let uneven = Scale::new(2.0, 1.5)?;
+    ///
+    /// assert_eq!(downscale.inverse().sample_coords(Point::new(1, 3)?), (2.0, 6.0));
+    /// assert_eq!(uneven.sample_coords(Point::new(3, 3)?), (6.0, 4.5));
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sample_coords(&self, point: Point) -> (f32, f32) {
+        let x = point.x() as f64 * self.0 as f64;
+        let y = point.y() as f64 * self.1 as f64;
+
+        (x as f32, y as f32)
+    }
 }
 
 /// [`Eq`] can be safely implemented since we guarantee that [`Scale`] has floats within range
@@ -255,6 +286,15 @@ mod test {
 
     use super::*;
 
+    #[test]
+    fn test_sample_coords() {
+        let half = Scale::new(0.5, 0.5).unwrap();
+
+        assert_eq!(half.inverse().sample_coords(Point::new(1, 1).unwrap()), (2.0, 2.0));
+        assert_eq!(half.sample_coords(Point::new(3, 1).unwrap()), (1.5, 0.5));
+        assert_eq!(Scale::new(1.5, 0.25).unwrap().sample_coords(Point::new(3, 6).unwrap()), (4.5, 1.5));
+    }
+
     #[test]
     fn test_new_ok() {
         assert!(Scale::new(1f32, 1f32).is_ok());
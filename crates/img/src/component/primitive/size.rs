@@ -109,6 +109,63 @@ impl Size {
         self.height
     }
 
+    /// Create a [`Size`] with width and height swapped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::prelude::*;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// assert_eq!(Size::new(10, 20)?.transpose(), Size::new(20, 10)?);
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn transpose(&self) -> Size {
+        Size { width: self.height, height: self.width }
+    }
+
+    /// Create a [`Size`] with the same height and given `width`.
+    ///
+    /// Returns [`Size`] if `width` is valid, [`SizeCreationError`] otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::prelude::*;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// assert_eq!(Size::new(10, 20)?.with_width(30)?, Size::new(30, 20)?);
+    /// assert!(Size::new(10, 20)?.with_width(0).is_err());
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_width(&self, width: usize) -> SizeCreationResult<Size> {
+        Size::new(width, self.height)
+    }
+
+    /// Create a [`Size`] with the same width and given `height`.
+    ///
+    /// Returns [`Size`] if `height` is valid, [`SizeCreationError`] otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::prelude::*;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// assert_eq!(Size::new(10, 20)?.with_height(30)?, Size::new(10, 30)?);
+    /// assert!(Size::new(10, 20)?.with_height(0).is_err());
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_height(&self, height: usize) -> SizeCreationResult<Size> {
+        Size::new(self.width, height)
+    }
+
     /// Calculate [`Size`]'s area (width * height).
     ///
     /// Returns [`Size`]'s area as `usize`. Is guaranteed to not be 0.
@@ -116,6 +173,34 @@ impl Size {
         self.width * self.height
     }
 
+    /// Calculate [`Size`]'s area (width * height), guarding against overflow.
+    ///
+    /// Returns [`Size`]'s area as `usize`, or `None` if it doesn't fit. Area itself can't
+    /// overflow for dimensions up to [`DIMENSION_MAX`], but byte length of a buffer can, so
+    /// allocation paths should chain it with [`usize::checked_mul`] by element size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::{
+    ///     image::DIMENSION_MAX,
+    ///     pixel::PIXEL_SIZE,
+    ///     prelude::*,
+    /// };
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// assert_eq!(Size::new(10, 20)?.area_checked(), Some(200));
+    ///
+    /// let largest = Size::new(DIMENSION_MAX, DIMENSION_MAX)?;
+    /// assert_eq!(largest.area_checked().and_then(|area| area.checked_mul(PIXEL_SIZE)), None);
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn area_checked(&self) -> Option<usize> {
+        self.width.checked_mul(self.height)
+    }
+
     /// Get rounded up middle point.
     ///
     /// Returns [`Size`]'s middle point. Rounds point up in case dimension is even.
@@ -209,16 +294,16 @@ impl Size {
     /// # }
     /// ```
     pub fn shrink_by_margin(&self, margin: Margin) -> SizeCreationResult<Self> {
-        if margin.left() + margin.right() >= self.width {
+        if margin.horizontal() >= self.width {
             return Err(SizeCreationError::WidthZero);
         }
 
-        if margin.top() + margin.bottom() >= self.height {
+        if margin.vertical() >= self.height {
             return Err(SizeCreationError::HeightZero);
         }
 
-        let width = self.width - margin.left() - margin.right();
-        let height = self.height - margin.top() - margin.bottom();
+        let width = self.width - margin.horizontal();
+        let height = self.height - margin.vertical();
 
         Size::new(width, height)
     }
@@ -258,16 +343,16 @@ impl Size {
     /// # }
     /// ```
     pub fn extend_by_margin(&self, margin: Margin) -> SizeCreationResult<Self> {
-        if margin.left() + margin.right() + self.width > DIMENSION_MAX {
+        if margin.horizontal() + self.width > DIMENSION_MAX {
             return Err(SizeCreationError::WidthTooBig);
         }
 
-        if margin.top() + margin.bottom() + self.height > DIMENSION_MAX {
+        if margin.vertical() + self.height > DIMENSION_MAX {
             return Err(SizeCreationError::HeightTooBig);
         }
 
-        let width = self.width + margin.left() + margin.right();
-        let height = self.height + margin.top() + margin.bottom();
+        let width = self.width + margin.horizontal();
+        let height = self.height + margin.vertical();
 
         Size::new(width, height)
     }
@@ -349,6 +434,35 @@ mod tests {
         assert_eq!(Size::from_radius(usize::MAX).unwrap_err(), SizeCreationError::WidthTooBig);
     }
 
+    #[test]
+    fn test_transpose() {
+        assert_eq!(Size::new(10, 20).unwrap().transpose(), Size::new(20, 10).unwrap());
+        assert_eq!(
+            Size::new(1, DIMENSION_MAX).unwrap().transpose(),
+            Size::new(DIMENSION_MAX, 1).unwrap()
+        );
+        assert_eq!(Size::new(5, 5).unwrap().transpose(), Size::new(5, 5).unwrap());
+    }
+
+    #[test]
+    fn test_with_width() {
+        let size = Size::new(10, 20).unwrap();
+        assert_eq!(size.with_width(DIMENSION_MAX).unwrap(), Size::new(DIMENSION_MAX, 20).unwrap());
+        assert_eq!(size.with_width(0).unwrap_err(), SizeCreationError::WidthZero);
+        assert_eq!(size.with_width(DIMENSION_MAX + 1).unwrap_err(), SizeCreationError::WidthTooBig);
+    }
+
+    #[test]
+    fn test_with_height() {
+        let size = Size::new(10, 20).unwrap();
+        assert_eq!(size.with_height(DIMENSION_MAX).unwrap(), Size::new(10, DIMENSION_MAX).unwrap());
+        assert_eq!(size.with_height(0).unwrap_err(), SizeCreationError::HeightZero);
+        assert_eq!(
+            size.with_height(DIMENSION_MAX + 1).unwrap_err(),
+            SizeCreationError::HeightTooBig
+        );
+    }
+
     #[test]
     fn test_area() {
         assert_eq!(
@@ -358,6 +472,15 @@ mod tests {
         assert_eq!(Size::new(1, 1).unwrap().area(), 1);
     }
 
+    #[test]
+    fn test_area_checked() {
+        let largest = Size::new(DIMENSION_MAX, DIMENSION_MAX).unwrap();
+
+        assert_eq!(largest.area_checked(), Some(largest.area()));
+        assert_eq!(largest.area_checked().and_then(|area| area.checked_mul(4)), None);
+        assert_eq!(Size::new(3, 5).unwrap().area_checked(), Some(15));
+    }
+
     #[test]
     fn test_middle() {
         assert_eq!(Size::new(1, 1).unwrap().middle(), Point::new(0, 0).unwrap());
@@ -0,0 +1,162 @@
+use crate::{
+    component::lens::border::BorderMode,
+    lens::Lens,
+    pixel::{
+        ColorSpace,
+        Pixel,
+        srgb::{
+            from_linear,
+            to_linear,
+        },
+    },
+};
+
+/// Describes how samplers treat coordinates outside of the source, see [`BorderMode`].
+pub type EdgeMode = BorderMode<Pixel>;
+
+/// Look at `source` at integer coordinates, which may be outside of it, handling edges
+/// according to `edge_mode`.
+pub fn look_with_edge<S>(source: &S, x: isize, y: isize, edge_mode: EdgeMode) -> Pixel
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    match edge_mode.map_point(x, y, source.size()) {
+        // SAFETY: mapped point is within source size and `Lens::look` always returns a value
+        // when in bounds.
+        Ok(point) => *source.look(point).expect("unexpected error in Lens::look").as_ref(),
+        Err(value) => *value,
+    }
+}
+
+/// Sample `source` at fractional coordinates using nearest neighbor. Pixel centers are at
+/// integer coordinates.
+pub fn sample_nearest<S>(source: &S, x: f32, y: f32, edge_mode: EdgeMode) -> Pixel
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    look_with_edge(source, x.round() as isize, y.round() as isize, edge_mode)
+}
+
+/// Sample `source` at fractional coordinates using bilinear interpolation of 4 nearest pixels.
+/// Pixel centers are at integer coordinates, so sampling at integer coordinates returns exact
+/// source pixel.
+pub fn sample_bilinear<S>(source: &S, x: f32, y: f32, edge_mode: EdgeMode) -> Pixel
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    interpolate_bilinear(source, x, y, edge_mode, ColorSpace::Srgb)
+}
+
+/// Sample `source` like [`sample_bilinear`], but interpolate color channels in linear light
+/// instead of sRGB, which keeps the brightness of mixed colors correct. Alpha is interpolated
+/// directly.
+pub fn sample_bilinear_linear<S>(source: &S, x: f32, y: f32, edge_mode: EdgeMode) -> Pixel
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    interpolate_bilinear(source, x, y, edge_mode, ColorSpace::Linear)
+}
+
+fn interpolate_bilinear<S>(
+    source: &S,
+    x: f32,
+    y: f32,
+    edge_mode: EdgeMode,
+    color_space: ColorSpace,
+) -> Pixel
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+    let (x0, y0) = (x0 as isize, y0 as isize);
+
+    let top_left = look_with_edge(source, x0, y0, edge_mode);
+    let top_right = look_with_edge(source, x0.saturating_add(1), y0, edge_mode);
+    let bottom_left = look_with_edge(source, x0, y0.saturating_add(1), edge_mode);
+    let bottom_right =
+        look_with_edge(source, x0.saturating_add(1), y0.saturating_add(1), edge_mode);
+
+    Pixel::new(std::array::from_fn(|i| {
+        // Alpha is stored last and is not gamma encoded.
+        let linear = color_space == ColorSpace::Linear && i < 3;
+        let decode = |px: Pixel| {
+            let value = px.buffer()[i] as f32 / 255f32;
+            if linear { to_linear(value) } else { value }
+        };
+
+        let top = decode(top_left) * (1f32 - fx) + decode(top_right) * fx;
+        let bottom = decode(bottom_left) * (1f32 - fx) + decode(bottom_right) * fx;
+        let value = top * (1f32 - fy) + bottom * fy;
+        let value = if linear { from_linear(value) } else { value };
+
+        (value * 255f32).round().clamp(0f32, 255f32) as u8
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        component::primitive::Size,
+        image::Image,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        let resolved = |mode: EdgeMode| (-5..9).map(|c| mode.resolve(c, 4)).collect::<Vec<_>>();
+
+        assert_eq!(
+            resolved(EdgeMode::Clamp),
+            [0, 0, 0, 0, 0, 0, 1, 2, 3, 3, 3, 3, 3, 3].map(Some).to_vec()
+        );
+        assert_eq!(
+            resolved(EdgeMode::Reflect),
+            [3, 3, 2, 1, 0, 0, 1, 2, 3, 3, 2, 1, 0, 0].map(Some).to_vec()
+        );
+        assert_eq!(
+            resolved(EdgeMode::Wrap),
+            [3, 0, 1, 2, 3, 0, 1, 2, 3, 0, 1, 2, 3, 0].map(Some).to_vec()
+        );
+        assert!(resolved(EdgeMode::Constant(Pixel::zero()))[..5].iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn test_sample_bilinear() {
+        let pixels = vec![Pixel::new([0, 0, 0, 255]), Pixel::new([200, 100, 50, 255])];
+        let image = Image::new(Size::new(2, 1).unwrap(), pixels.into_boxed_slice()).unwrap();
+        let lens = image.lens();
+
+        assert_eq!(sample_bilinear(&lens, 0.0, 0.0, EdgeMode::Clamp), Pixel::new([0, 0, 0, 255]));
+        assert_eq!(
+            sample_bilinear(&lens, 0.5, 0.0, EdgeMode::Clamp),
+            Pixel::new([100, 50, 25, 255])
+        );
+        assert_eq!(
+            sample_bilinear(&lens, 1.0, 0.5, EdgeMode::Constant(Pixel::zero())),
+            Pixel::new([100, 50, 25, 128])
+        );
+    }
+    #[test]
+    fn test_sample_bilinear_huge_coordinates() {
+        let pixels = vec![Pixel::new([0, 0, 0, 255]), Pixel::new([200, 100, 50, 255])];
+        let image = Image::new(Size::new(2, 1).unwrap(), pixels.into_boxed_slice()).unwrap();
+        let lens = image.lens();
+
+        assert_eq!(
+            sample_bilinear(&lens, 1e30, 1e30, EdgeMode::Clamp),
+            Pixel::new([200, 100, 50, 255])
+        );
+        assert_eq!(
+            sample_bilinear(&lens, -1e30, -1e30, EdgeMode::Clamp),
+            Pixel::new([0, 0, 0, 255])
+        );
+        assert_eq!(sample_bilinear_linear(&lens, 1e30, 0.0, EdgeMode::Wrap).a(), 255);
+    }
+}
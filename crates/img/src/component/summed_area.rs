@@ -0,0 +1,76 @@
+use crate::component::primitive::{
+    Area,
+    Size,
+};
+
+/// Summed-area table (integral image) of `N` statistics, allowing sums of each statistic over
+/// any rectangle in constant time.
+///
+/// Typical statistics are values and their squares, which give windowed mean and variance.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     component::summed_area::SummedArea,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let size = Size::new(3, 2)?;
+/// let values = [1f64, 2f64, 3f64, 4f64, 5f64, 6f64];
+/// let table = SummedArea::new(values.iter().map(|value| [*value, value * value]), size);
+///
+/// let area = Area::new(Size::new(2, 2)?, Point::new(1, 0)?);
+/// assert_eq!(table.sum(area), [2f64 + 3f64 + 5f64 + 6f64, 4f64 + 9f64 + 25f64 + 36f64]);
+///
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SummedArea<const N: usize> {
+    sums: Vec<[f64; N]>,
+    stride: usize,
+    size: Size,
+}
+
+impl<const N: usize> SummedArea<N> {
+    /// Build [`SummedArea`] from `values` of each point of `size`, in row-major order. Missing
+    /// values are treated as zeros, extra values are ignored.
+    pub fn new(values: impl Iterator<Item = [f64; N]>, size: Size) -> Self {
+        let (width, height) = (size.width(), size.height());
+        let stride = width + 1;
+        let mut sums = vec![[0f64; N]; stride * (height + 1)];
+
+        let mut values = values.fuse();
+        for index in 0..size.area() {
+            let value = values.next().unwrap_or([0f64; N]);
+            let (x, y) = (index % width + 1, index / width + 1);
+            sums[y * stride + x] = std::array::from_fn(|i| {
+                value[i] + sums[(y - 1) * stride + x][i] + sums[y * stride + x - 1][i]
+                    - sums[(y - 1) * stride + x - 1][i]
+            });
+        }
+
+        Self { sums, stride, size }
+    }
+
+    /// Get size of the table.
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// Get sums of statistics over `area`. Parts of `area` outside of the table are ignored.
+    pub fn sum(&self, area: Area) -> [f64; N] {
+        let left = area.top_left().x().min(self.size.width());
+        let top = area.top_left().y().min(self.size.height());
+        let right = (area.top_left().x() + area.size().width()).min(self.size.width());
+        let bottom = (area.top_left().y() + area.size().height()).min(self.size.height());
+
+        let at = |x: usize, y: usize| self.sums[y * self.stride + x];
+        let (top_left, top_right) = (at(left, top), at(right, top));
+        let (bottom_left, bottom_right) = (at(left, bottom), at(right, bottom));
+
+        std::array::from_fn(|i| bottom_right[i] - top_right[i] - bottom_left[i] + top_left[i])
+    }
+}
@@ -0,0 +1,400 @@
+use std::num::NonZeroUsize;
+
+use rayon::{
+    ThreadPool,
+    ThreadPoolBuildError,
+    ThreadPoolBuilder,
+};
+use thiserror::Error;
+
+use crate::{
+    component::primitive::{
+        Area,
+        Margin,
+        Size,
+    },
+    image::Image,
+    lens::{
+        FromLens,
+        Lens,
+        look_chunk,
+        par_chunks,
+        tile::{
+            TileLens,
+            Tiles,
+        },
+    },
+    operation::{
+        blur::{
+            GaussianBlurCreationResult,
+            MeanCreationResult,
+            gaussian_blur_lens,
+            mean_blur_lens,
+        },
+        color::{
+            brightness_lens,
+            gamma_correction_lens,
+            grayscale_lens,
+            negative_lens,
+            sepia_lens,
+        },
+    },
+    pixel::{
+        AlphaMode,
+        ChannelFlags,
+        Pixel,
+    },
+};
+
+#[derive(Debug, Error)]
+pub enum ContextCreationError {
+    #[error("failed to build thread pool: {0}")]
+    ThreadPool(#[from] ThreadPoolBuildError),
+}
+
+pub type ContextCreationResult<T> = std::result::Result<T, ContextCreationError>;
+
+/// Reusable thread pool for running parallel operations.
+///
+/// Standalone `*_par` functions spawn fresh scoped threads on every call, which adds up when
+/// processing many small images. [`Context`] keeps its worker threads alive between calls.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     context::Context,
+///     prelude::*,
+/// };
+/// use std::num::NonZero;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let context = Context::new(NonZero::new(2).unwrap())?;
+/// let image = Image::empty(Size::new(4, 4)?);
+///
+/// let negated = context.negative_par(&image, ChannelFlags::RGB);
+///
+/// assert_eq!(*negated.pixel(Point::zero())?, Pixel::new([255, 255, 255, 0]));
+///
+/// # Ok(())
+/// # }
+/// ```
+pub struct Context {
+    pool: ThreadPool,
+}
+
+impl Context {
+    /// Create a [`Context`] backed by a pool of `threads` worker threads.
+    ///
+    /// Returns [`ContextCreationError::ThreadPool`] if the threads could not be spawned.
+    pub fn new(threads: NonZeroUsize) -> ContextCreationResult<Self> {
+        let pool = ThreadPoolBuilder::new().num_threads(threads.get()).build()?;
+
+        Ok(Self { pool })
+    }
+
+    /// Number of worker threads in the pool.
+    pub fn threads(&self) -> NonZeroUsize {
+        // SAFETY: pool is always created with a non zero number of threads.
+        NonZeroUsize::new(self.pool.current_num_threads())
+            .expect("unexpected error in ThreadPool::current_num_threads")
+    }
+
+    /// Collect [`Lens`] into an [`Image`] using the pool.
+    ///
+    /// Pixels are split into equal contiguous chunks, one per worker thread, same as
+    /// [`FromLensPar`](crate::lens::FromLensPar) does.
+    pub fn image_from_lens<S>(&self, lens: S) -> Image
+    where
+        S: Lens + Send + Sync,
+        S::Item: Into<Pixel> + Send,
+    {
+        let size = lens.size();
+        let mut pixels = vec![Pixel::zero(); size.area()].into_boxed_slice();
+
+        self.pool.scope(|scope| {
+            par_chunks(&mut pixels, self.threads()).for_each(|(starting_index, chunk)| {
+                let lens = &lens;
+                scope.spawn(move |_| look_chunk(lens, starting_index, chunk, Into::into));
+            });
+        });
+
+        // SAFETY: pixels are allocated with exactly size.area() elements.
+        Image::new(size, pixels).expect("unexpected error in Image::new")
+    }
+
+    /// Pooled counterpart of [`grayscale_par`](crate::operation::color::grayscale_par).
+    pub fn grayscale_par(&self, image: &Image, flags: ChannelFlags) -> Image {
+        self.image_from_lens(grayscale_lens(image.lens(), flags))
+    }
+
+    /// Pooled counterpart of [`sepia_par`](crate::operation::color::sepia_par).
+    pub fn sepia_par(&self, image: &Image, flags: ChannelFlags) -> Image {
+        self.image_from_lens(sepia_lens(image.lens(), flags))
+    }
+
+    /// Pooled counterpart of [`negative_par`](crate::operation::color::negative_par).
+    pub fn negative_par(&self, image: &Image, flags: ChannelFlags) -> Image {
+        self.image_from_lens(negative_lens(image.lens(), flags))
+    }
+
+    /// Pooled counterpart of [`brightness_par`](crate::operation::color::brightness_par).
+    pub fn brightness_par(&self, image: &Image, amount: i16, flags: ChannelFlags) -> Image {
+        self.image_from_lens(brightness_lens(image.lens(), amount, flags))
+    }
+
+    /// Pooled counterpart of
+    /// [`gamma_correction_par`](crate::operation::color::gamma_correction_par).
+    pub fn gamma_correction_par(&self, image: &Image, gamma: f32, flags: ChannelFlags) -> Image {
+        self.image_from_lens(gamma_correction_lens(image.lens(), gamma, flags))
+    }
+
+    /// Pooled counterpart of [`mean_blur_par`](crate::operation::blur::mean_blur_par).
+    pub fn mean_blur_par(
+        &self,
+        image: &Image,
+        radius: usize,
+        flags: ChannelFlags,
+    ) -> MeanCreationResult<Image> {
+        Ok(self.image_from_lens(mean_blur_lens(image.lens(), radius, flags)?))
+    }
+
+    /// Pooled counterpart of [`gaussian_blur_par`](crate::operation::blur::gaussian_blur_par).
+    pub fn gaussian_blur_par(
+        &self,
+        image: &Image,
+        radius: usize,
+        sigma: f32,
+        flags: ChannelFlags,
+        alpha_mode: AlphaMode,
+    ) -> GaussianBlurCreationResult<Image> {
+        let lens = gaussian_blur_lens(image.lens(), radius, sigma, flags, alpha_mode)?;
+        Ok(self.image_from_lens(lens))
+    }
+
+    /// Process `image` in `tile` sized tiles on the pool and stitch the results back together.
+    ///
+    /// `f` receives [`Area`] of the tile within the image and an [`Image`] with that tile
+    /// extended by `halo` on every side, which gives kernels the neighborhood they need. Halo
+    /// outside of the image repeats its nearest edge pixel. `f` can return either an image of the
+    /// tile size, e.g. result of a kernel shrinking its source by `halo`, or an image of the same
+    /// size as its input, from which halo is cropped. Tiles on the right and bottom edges can be
+    /// smaller than `tile`. Every tile is a separate job, so the pool balances uneven tiles.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` returns an image of any other size, or if image extended by `halo` exceeds
+    /// [`DIMENSION_MAX`](crate::image::DIMENSION_MAX).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::{
+    ///     context::Context,
+    ///     prelude::*,
+    /// };
+    /// use std::num::NonZero;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let context = Context::new(NonZero::new(2).unwrap())?;
+    /// let image = Image::solid(Size::new(10, 10)?, Pixel::new([10, 20, 30, 255]));
+    ///
+    /// let negative =
+    ///     context.process_tiles(&image, Size::new(4, 4)?, Margin::unified(0)?, |_, tile| {
+    ///         negative(tile, ChannelFlags::RGB)
+    ///     });
+    ///
+    /// assert_eq!(*negative.pixel(Point::new(9, 9)?)?, Pixel::new([245, 235, 225, 255]));
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn process_tiles<F>(&self, image: &Image, tile: Size, halo: Margin, f: F) -> Image
+    where
+        F: Fn(Area, &Image) -> Image + Sync,
+    {
+        let padded =
+            image.lens().cloned().clamp_border(halo).expect("image extended by halo is too big");
+        let lens = image.lens();
+        let areas = Tiles::new(&lens, tile).map(|(area, _)| area).collect::<Vec<_>>();
+        let mut results = vec![None; areas.len()];
+
+        self.pool.scope(|scope| {
+            results.iter_mut().zip(&areas).for_each(|(result, area)| {
+                let (padded, f) = (&padded, &f);
+                scope.spawn(move |_| {
+                    // SAFETY: tile is within the image, so extended by halo it is within padded
+                    // image.
+                    let input_size = area
+                        .size()
+                        .extend_by_margin(halo)
+                        .expect("unexpected error in extend_by_margin");
+                    let input = Image::from_lens(TileLens::new(
+                        padded,
+                        Area::new(input_size, area.top_left()),
+                    ));
+
+                    *result = Some(crop_halo(f(*area, &input), area.size(), halo));
+                });
+            });
+        });
+
+        let mut stitched = Image::empty(image.size());
+        for (area, result) in areas.into_iter().zip(results) {
+            // SAFETY: pool scope returns only after every job stored its result.
+            stitched.paste_tile(area, &result.expect("unexpected missing tile result"));
+        }
+
+        stitched
+    }
+}
+
+/// Get part of `result` of tile processing without halo, see [`Context::process_tiles`].
+fn crop_halo(result: Image, tile: Size, halo: Margin) -> Image {
+    if result.size() == tile {
+        return result;
+    }
+
+    assert_eq!(
+        Some(result.size()),
+        tile.extend_by_margin(halo).ok(),
+        "tile result must have size of the tile or of the tile with halo"
+    );
+    // SAFETY: area of the tile lies within result extended by halo.
+    let area = Area::from_cropped_size(result.size(), halo)
+        .expect("unexpected error in Area::from_cropped_size");
+
+    let lens = result.lens().cloned();
+    Image::from_lens(TileLens::new(&lens, area))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        SeedableRng,
+        rngs::StdRng,
+    };
+
+    use super::*;
+    use crate::{
+        component::{
+            kernel::mean::MeanKernel,
+            primitive::Point,
+        },
+        lens::kernel::pad_for_kernel,
+        operation::{
+            blur::{
+                gaussian_blur_par,
+                mean_blur_par,
+            },
+            color::{
+                brightness_par,
+                gamma_correction_par,
+                grayscale_par,
+                negative_par,
+                sepia_par,
+            },
+        },
+    };
+
+    #[test]
+    fn test_context_matches_standalone() {
+        let threads = NonZeroUsize::new(3).unwrap();
+        let context = Context::new(threads).unwrap();
+        assert_eq!(context.threads(), threads);
+
+        let mut rng = StdRng::seed_from_u64(1202);
+        let image = Image::random(Size::new(17, 9).unwrap(), &mut rng);
+        let flags = ChannelFlags::RGB;
+        let (rgba, premultiplied) = (ChannelFlags::RGBA, AlphaMode::Premultiplied);
+
+        for _ in 0..2 {
+            let pairs = [
+                (context.grayscale_par(&image, flags), grayscale_par(&image, threads, flags)),
+                (context.sepia_par(&image, flags), sepia_par(&image, threads, flags)),
+                (context.negative_par(&image, flags), negative_par(&image, threads, flags)),
+                (
+                    context.brightness_par(&image, -20, flags),
+                    brightness_par(&image, threads, -20, flags),
+                ),
+                (
+                    context.gamma_correction_par(&image, 2.2, flags),
+                    gamma_correction_par(&image, threads, 2.2, flags),
+                ),
+                (
+                    context.mean_blur_par(&image, 2, flags).unwrap(),
+                    mean_blur_par(&image, threads, 2, flags).unwrap(),
+                ),
+                (
+                    context.gaussian_blur_par(&image, 2, 1.5, rgba, premultiplied).unwrap(),
+                    gaussian_blur_par(&image, threads, 2, 1.5, rgba, premultiplied).unwrap(),
+                ),
+            ];
+
+            for (pooled, standalone) in pairs {
+                assert_eq!(pooled.pixels(), standalone.pixels());
+            }
+        }
+    }
+
+    #[test]
+    fn test_context_more_threads_than_pixels() {
+        let context = Context::new(NonZeroUsize::new(8).unwrap()).unwrap();
+        let image = Image::empty(Size::new(3, 1).unwrap());
+
+        assert_eq!(context.negative_par(&image, ChannelFlags::RGB).size(), image.size());
+    }
+
+    #[test]
+    fn test_process_tiles_blur_with_halo() {
+        let image = Image::random(Size::new(23, 17).unwrap(), &mut StdRng::seed_from_u64(1249));
+        let kernel = MeanKernel::new(Size::from_radius(2).unwrap(), ChannelFlags::RGBA).unwrap();
+        let whole = Image::from_lens(pad_for_kernel(image.lens(), kernel.clone()).unwrap());
+
+        for threads in [1, 3, 64] {
+            let context = Context::new(NonZeroUsize::new(threads).unwrap()).unwrap();
+            let tiled = context.process_tiles(
+                &image,
+                Size::new(5, 4).unwrap(),
+                Margin::unified(2).unwrap(),
+                |_, input| Image::from_lens(input.lens().kernel(kernel.clone()).unwrap()),
+            );
+
+            assert_eq!(tiled.size(), image.size());
+            assert_eq!(tiled.pixels(), whole.pixels());
+        }
+    }
+
+    #[test]
+    fn test_process_tiles_crops_halo() {
+        let context = Context::new(NonZeroUsize::new(2).unwrap()).unwrap();
+        let image = Image::random(Size::new(9, 7).unwrap(), &mut StdRng::seed_from_u64(1249));
+        let halo = Margin::new(1, 2, 3, 4).unwrap();
+
+        let tiled = context.process_tiles(&image, Size::new(4, 4).unwrap(), halo, |area, input| {
+            assert_eq!(input.size(), area.size().extend_by_margin(halo).unwrap());
+            // Top left pixel of the tile is offset by halo within input.
+            assert_eq!(
+                input.pixel(Point::new(4, 1).unwrap()).unwrap(),
+                image.pixel(area.top_left()).unwrap()
+            );
+            input.clone()
+        });
+
+        assert_eq!(tiled.pixels(), image.pixels());
+    }
+
+    #[test]
+    #[should_panic(expected = "tile result must have size of the tile or of the tile with halo")]
+    fn test_process_tiles_wrong_size() {
+        let context = Context::new(NonZeroUsize::new(1).unwrap()).unwrap();
+        let image = Image::empty(Size::new(4, 4).unwrap());
+
+        context.process_tiles(
+            &image,
+            Size::new(2, 2).unwrap(),
+            Margin::unified(1).unwrap(),
+            |_, _| Image::empty(Size::new(3, 3).unwrap()),
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use crate::{
+    component::primitive::{
+        Connectivity,
+        Point,
+    },
+    error::IndexResult,
+    image::Image,
+    pixel::Pixel,
+};
+
+impl Image {
+    /// Replace region of pixels equal to the one at `seed` and connected to it with `color`.
+    ///
+    /// Pixels are part of the region if they are reachable from `seed` through neighbors of
+    /// given `connectivity` with the same value. Returns [`IndexError`] if `seed` is outside of
+    /// the image.
+    ///
+    /// [`IndexError`]: crate::error::IndexError
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::prelude::*;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let mut image = Image::empty(Size::new(4, 4)?);
+    /// let red = Pixel::new([255, 0, 0, 255]);
+    ///
+    /// image.flood_fill(Point::zero(), red, Connectivity::Four)?;
+    ///
+    /// assert!(image.pixels().iter().all(|px| *px == red));
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn flood_fill(
+        &mut self,
+        seed: Point,
+        color: Pixel,
+        connectivity: Connectivity,
+    ) -> IndexResult<()> {
+        let target = *self.pixel(seed)?;
+        if target == color {
+            return Ok(());
+        }
+
+        let size = self.size();
+        let mut stack = vec![seed];
+        while let Some(point) = stack.pop() {
+            // SAFETY: only points within the image are pushed onto the stack.
+            let px = self.pixel_mut(point).expect("unexpected error in Image::pixel_mut");
+            if *px != target {
+                continue;
+            }
+            *px = color;
+
+            stack.extend(connectivity.neighbors(point, size));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        component::primitive::Size,
+        error::IndexError,
+    };
+
+    use super::*;
+
+    const WHITE: Pixel = Pixel::new([255, 255, 255, 255]);
+    const BLACK: Pixel = Pixel::new([0, 0, 0, 255]);
+    const RED: Pixel = Pixel::new([255, 0, 0, 255]);
+
+    /// White 6x6 image with two black 3x3 squares touching only at their corners.
+    fn diagonal_bridge() -> Image {
+        let mut image = Image::solid(Size::new(6, 6).unwrap(), WHITE);
+        for (x, y) in (0..3).flat_map(|y| (0..3).map(move |x| (x, y))) {
+            *image.pixel_mut(Point::new(x, y).unwrap()).unwrap() = BLACK;
+            *image.pixel_mut(Point::new(x + 3, y + 3).unwrap()).unwrap() = BLACK;
+        }
+
+        image
+    }
+
+    #[test]
+    fn test_flood_fill_four_stops_at_diagonal() {
+        let mut image = diagonal_bridge();
+
+        image.flood_fill(Point::zero(), RED, Connectivity::Four).unwrap();
+
+        assert_eq!(image.pixel(Point::new(2, 2).unwrap()).unwrap(), &RED);
+        assert_eq!(image.pixel(Point::new(3, 3).unwrap()).unwrap(), &BLACK);
+        assert_eq!(image.pixel(Point::new(5, 0).unwrap()).unwrap(), &WHITE);
+    }
+
+    #[test]
+    fn test_flood_fill_eight_crosses_diagonal() {
+        let mut image = diagonal_bridge();
+
+        image.flood_fill(Point::zero(), RED, Connectivity::Eight).unwrap();
+
+        assert_eq!(image.pixel(Point::new(2, 2).unwrap()).unwrap(), &RED);
+        assert_eq!(image.pixel(Point::new(5, 5).unwrap()).unwrap(), &RED);
+        assert_eq!(image.pixels().iter().filter(|px| **px == RED).count(), 18);
+    }
+
+    #[test]
+    fn test_flood_fill_out_of_bounds() {
+        let mut image = diagonal_bridge();
+
+        assert_eq!(
+            image.flood_fill(Point::new(6, 0).unwrap(), RED, Connectivity::Four),
+            Err(IndexError::OutOfBounds)
+        );
+    }
+}
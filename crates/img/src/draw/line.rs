@@ -0,0 +1,129 @@
+use crate::{
+    component::primitive::Point,
+    image::Image,
+    pixel::Pixel,
+};
+
+impl Image {
+    /// Draw an anti-aliased line from `from` to `to` using Wu's algorithm.
+    ///
+    /// Each pixel along the line is blended with `color` using [`Pixel::lerp`], proportionally
+    /// to how much of it the line covers. Parts of the line outside of the image are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::prelude::*;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let mut image = Image::empty(Size::new(10, 10)?);
+    /// let white = Pixel::new([255, 255, 255, 255]);
+    ///
+    /// image.draw_line_aa(Point::new(0, 0)?, Point::new(9, 9)?, white);
+    ///
+    /// assert_eq!(image.pixel(Point::new(5, 5)?)?, &white);
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn draw_line_aa(&mut self, from: Point, to: Point, color: Pixel) {
+        let (mut x0, mut y0) = (from.x() as f32, from.y() as f32);
+        let (mut x1, mut y1) = (to.x() as f32, to.y() as f32);
+
+        let steep = (y1 - y0).abs() > (x1 - x0).abs();
+        if steep {
+            std::mem::swap(&mut x0, &mut y0);
+            std::mem::swap(&mut x1, &mut y1);
+        }
+        if x0 > x1 {
+            std::mem::swap(&mut x0, &mut x1);
+            std::mem::swap(&mut y0, &mut y1);
+        }
+
+        let dx = x1 - x0;
+        let gradient = if dx == 0f32 { 0f32 } else { (y1 - y0) / dx };
+
+        for x in x0 as usize..=x1 as usize {
+            let y = y0 + gradient * (x as f32 - x0);
+            let y_floor = y.floor();
+            let coverage = y - y_floor;
+            let y_floor = y_floor as usize;
+
+            let (near, far) = if steep {
+                ((y_floor, x), (y_floor + 1, x))
+            } else {
+                ((x, y_floor), (x, y_floor + 1))
+            };
+
+            self.plot(near, color, 1f32 - coverage);
+            self.plot(far, color, coverage);
+        }
+    }
+
+    /// Blend `color` into pixel at `(x, y)` with given `coverage`, skipping points outside of
+    /// the image.
+    fn plot(&mut self, (x, y): (usize, usize), color: Pixel, coverage: f32) {
+        if coverage <= 0f32 {
+            return;
+        }
+
+        let Ok(point) = Point::new(x, y) else {
+            return;
+        };
+
+        if let Ok(px) = self.pixel_mut(point) {
+            *px = px.lerp(color, coverage);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::component::primitive::Size;
+
+    use super::*;
+
+    const WHITE: Pixel = Pixel::new([255, 255, 255, 255]);
+
+    fn red_at(image: &Image, x: usize, y: usize) -> u8 {
+        image.pixel(Point::new(x, y).unwrap()).unwrap().r()
+    }
+
+    #[test]
+    fn test_draw_line_aa_near_horizontal() {
+        let mut image = Image::empty(Size::new(11, 6).unwrap());
+        image.draw_line_aa(Point::new(0, 2).unwrap(), Point::new(10, 3).unwrap(), WHITE);
+
+        // endpoints are fully covered
+        assert_eq!(red_at(&image, 0, 2), 255);
+        assert_eq!(red_at(&image, 10, 3), 255);
+
+        // y = 2.2 at x = 2, so 80% lands on row 2 and 20% on row 3
+        assert_eq!(red_at(&image, 2, 2), 204);
+        assert_eq!(red_at(&image, 2, 3), 51);
+
+        // y = 2.5 at x = 5, so coverage is split evenly
+        assert_eq!(red_at(&image, 5, 2), red_at(&image, 5, 3));
+        assert_eq!(red_at(&image, 5, 2), 128);
+
+        // rows further away stay untouched
+        assert!((0..11).all(|x| red_at(&image, x, 0) == 0 && red_at(&image, x, 5) == 0));
+    }
+
+    #[test]
+    fn test_draw_line_aa_steep_and_reversed() {
+        let mut image = Image::empty(Size::new(4, 8).unwrap());
+        image.draw_line_aa(Point::new(2, 7).unwrap(), Point::new(2, 0).unwrap(), WHITE);
+
+        assert!((0..8).all(|y| red_at(&image, 2, y) == 255));
+        assert!((0..8).all(|y| red_at(&image, 1, y) == 0 && red_at(&image, 3, y) == 0));
+    }
+
+    #[test]
+    fn test_draw_line_aa_clips() {
+        let mut image = Image::empty(Size::new(4, 4).unwrap());
+        image.draw_line_aa(Point::new(0, 0).unwrap(), Point::new(20, 20).unwrap(), WHITE);
+
+        assert_eq!(red_at(&image, 3, 3), 255);
+    }
+}
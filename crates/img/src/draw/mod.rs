@@ -0,0 +1,8 @@
+//! Drawing primitives operating in place on an [`Image`].
+//!
+//! [`Image`]: crate::image::Image
+
+mod flood_fill;
+pub mod font;
+mod line;
+mod text;
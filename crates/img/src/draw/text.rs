@@ -0,0 +1,186 @@
+use crate::{
+    component::primitive::Point,
+    draw::font::{
+        GLYPH_SIZE,
+        glyph,
+    },
+    image::Image,
+    pixel::Pixel,
+};
+
+impl Image {
+    /// Draw `text` with top left corner at `at` using embedded 8x8 bitmap font.
+    ///
+    /// Pixels covered by glyphs are set to `color`, `\n` starts a new line below. Characters
+    /// outside of printable ASCII range are drawn as `?`, parts of text outside of the image
+    /// are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::prelude::*;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let mut image = Image::empty(Size::new(40, 10)?);
+    ///
+    /// image.draw_text("img", Point::new(1, 1)?, Pixel::new([255, 255, 255, 255]));
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn draw_text(&mut self, text: &str, at: Point, color: Pixel) {
+        for (line_index, line) in text.lines().enumerate() {
+            let y = at.y() + line_index * GLYPH_SIZE;
+            for (char_index, c) in line.chars().enumerate() {
+                let x = at.x() + char_index * GLYPH_SIZE;
+                self.draw_glyph(c, x, y, color);
+            }
+        }
+    }
+
+    /// Draw `text` like [`Image::draw_text`] in `fg` color, on top of a box filled with `bg`.
+    ///
+    /// The box has top left corner at `at` and covers exactly the glyphs of `text`, 8 pixels per
+    /// character of the longest line wide and 8 pixels per line high. Parts outside of the image
+    /// are skipped and nothing is drawn for empty `text`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::prelude::*;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let mut image = Image::empty(Size::new(40, 10)?);
+    /// let black = Pixel::new([0, 0, 0, 255]);
+    ///
+    /// image.caption("img", Point::new(1, 1)?, Pixel::new([255, 255, 255, 255]), black);
+    ///
+    /// assert_eq!(*image.pixel(Point::new(24, 8)?)?, black);
+    /// assert_eq!(*image.pixel(Point::new(25, 8)?)?, Pixel::zero());
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn caption(&mut self, text: &str, at: Point, fg: Pixel, bg: Pixel) {
+        let columns = text.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+        let rows = text.lines().count();
+
+        let width = self.size().width();
+        let height = self.size().height();
+        let xs = at.x().min(width)..(at.x() + columns * GLYPH_SIZE).min(width);
+        for y in at.y().min(height)..(at.y() + rows * GLYPH_SIZE).min(height) {
+            for x in xs.clone() {
+                // SAFETY: coordinates are clipped to the image.
+                *self
+                    .pixel_mut(Point::new(x, y).expect("unexpected error in Point::new"))
+                    .expect("unexpected error in pixel_mut") = bg;
+            }
+        }
+
+        self.draw_text(text, at, fg);
+    }
+
+    fn draw_glyph(&mut self, c: char, x: usize, y: usize, color: Pixel) {
+        for (dy, row) in glyph(c).iter().enumerate() {
+            for dx in 0..GLYPH_SIZE {
+                if row & (1 << dx) == 0 {
+                    continue;
+                }
+
+                let Ok(point) = Point::new(x + dx, y + dy) else {
+                    continue;
+                };
+
+                if let Ok(px) = self.pixel_mut(point) {
+                    *px = color;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::component::primitive::Size;
+
+    use super::*;
+
+    const WHITE: Pixel = Pixel::new([255, 255, 255, 255]);
+
+    fn is_on(image: &Image, x: usize, y: usize) -> bool {
+        image.pixel(Point::new(x, y).unwrap()).unwrap() == &WHITE
+    }
+
+    #[test]
+    fn test_draw_text_a() {
+        let mut image = Image::empty(Size::new(12, 12).unwrap());
+        image.draw_text("A", Point::new(2, 3).unwrap(), WHITE);
+
+        // apex of the letter
+        assert!(is_on(&image, 4, 3));
+        assert!(is_on(&image, 5, 3));
+        assert!(!is_on(&image, 2, 3));
+        // legs
+        assert!(is_on(&image, 2, 6));
+        assert!(is_on(&image, 7, 6));
+        assert!(!is_on(&image, 4, 6));
+        // crossbar
+        assert!((2..8).all(|x| is_on(&image, x, 7)));
+        // empty last row of the glyph
+        assert!((0..12).all(|x| !is_on(&image, x, 10)));
+    }
+
+    #[test]
+    fn test_caption_box_and_glyphs() {
+        let black = Pixel::new([0, 0, 0, 255]);
+        let mut image = Image::empty(Size::new(40, 30).unwrap());
+
+        image.caption("Hi!\nx", Point::new(3, 2).unwrap(), WHITE, black);
+
+        // Box covers 3 characters of the longest line and 2 lines.
+        let covered = |x: usize, y: usize| (3..27).contains(&x) && (2..18).contains(&y);
+        for y in 0..30 {
+            for x in 0..40 {
+                let px = *image.pixel(Point::new(x, y).unwrap()).unwrap();
+                match covered(x, y) {
+                    true => assert!(px == WHITE || px == black, "({x}, {y}) {px:?}"),
+                    false => assert_eq!(px, Pixel::zero(), "({x}, {y})"),
+                }
+            }
+        }
+
+        // Glyph pixels match plain text drawn over the same box.
+        let mut expected = Image::solid(image.size(), black);
+        expected.draw_text("Hi!\nx", Point::new(3, 2).unwrap(), WHITE);
+        let glyphs =
+            |image: &Image| image.pixels().iter().map(|px| *px == WHITE).collect::<Vec<_>>();
+        assert_eq!(glyphs(&image), glyphs(&expected));
+        assert!(glyphs(&image).contains(&true));
+    }
+
+    #[test]
+    fn test_caption_clips() {
+        let black = Pixel::new([0, 0, 0, 255]);
+        let mut image = Image::empty(Size::new(10, 10).unwrap());
+
+        image.caption("long", Point::new(4, 6).unwrap(), WHITE, black);
+        image.caption("", Point::zero(), WHITE, black);
+
+        // Empty caption draws nothing, the other one is clipped at the bottom right corner.
+        assert_eq!(image.pixel(Point::zero()).unwrap(), &Pixel::zero());
+        assert_ne!(image.pixel(Point::new(9, 9).unwrap()).unwrap(), &Pixel::zero());
+        assert_eq!(image.pixel(Point::new(3, 9).unwrap()).unwrap(), &Pixel::zero());
+    }
+
+    #[test]
+    fn test_draw_text_advances_and_clips() {
+        let mut image = Image::empty(Size::new(12, 20).unwrap());
+        image.draw_text("||\n|", Point::new(0, 0).unwrap(), WHITE);
+
+        // '|' is drawn at columns 3-4 of each glyph
+        assert!(is_on(&image, 3, 0));
+        assert!(is_on(&image, 11, 0));
+        assert!(is_on(&image, 3, 8));
+        assert!(!is_on(&image, 11, 8));
+    }
+}
@@ -19,6 +19,12 @@ pub enum IoError {
     #[cfg(feature = "png")]
     #[error("png decoding error: {0}")]
     PngDecoding(#[from] png::DecodingError),
+    #[cfg(feature = "gif")]
+    #[error("gif decoding error: {0}")]
+    GifDecoding(#[from] gif::DecodingError),
+    #[cfg(feature = "gif")]
+    #[error("gif encoding error: {0}")]
+    GifEncoding(#[from] gif::EncodingError),
     #[cfg(feature = "jpeg")]
     #[error("jpeg decoding error: {0}")]
     JpegDecoding(turbojpeg::Error),
@@ -0,0 +1,249 @@
+#[cfg(feature = "parallel")]
+use std::num::NonZeroUsize;
+
+use thiserror::Error;
+
+#[cfg(feature = "parallel")]
+use crate::lens::FromLensPar;
+use crate::{
+    component::primitive::{
+        Point,
+        Size,
+    },
+    error::IndexResult,
+    lens::{
+        FromLens,
+        Lens,
+        LensMut,
+        gray_image::GrayImageLens,
+    },
+};
+
+/// Luma weights of red, green and blue channels from ITU-R BT.601.
+pub const REC601_WEIGHTS: [f32; 3] = [0.299, 0.587, 0.114];
+
+/// Luma weights of red, green and blue channels from ITU-R BT.709.
+pub const REC709_WEIGHTS: [f32; 3] = [0.2126, 0.7152, 0.0722];
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CreationError {
+    #[error("size does not match values size")]
+    SizeValuesMismatch,
+}
+
+pub type CreationResult<T> = Result<T, CreationError>;
+
+/// A `struct` representing in-memory single channel image.
+///
+/// Uses one `u8` per pixel, which makes it a quarter of the size of an [`Image`] with the same
+/// dimensions.
+///
+/// [`Image`]: crate::image::Image
+#[derive(Debug, Clone)]
+pub struct GrayImage {
+    size: Size,
+    values: Box<[u8]>,
+}
+
+impl GrayImage {
+    /// Create a [`GrayImage`] with the given size and buffer.
+    ///
+    /// Returns [`GrayImage`] if `values` length is equal to `size.area()`,
+    /// [`SizeValuesMismatch`] otherwise.
+    ///
+    /// [`SizeValuesMismatch`]: CreationError::SizeValuesMismatch
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::{
+    ///     gray_image::CreationError,
+    ///     prelude::*,
+    /// };
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let size = Size::new(2, 2)?;
+    /// let image = GrayImage::new(size, vec![0; 4].into_boxed_slice())?;
+    ///
+    /// let mismatch = GrayImage::new(size, vec![0; 3].into_boxed_slice());
+    /// assert_eq!(mismatch.unwrap_err(), CreationError::SizeValuesMismatch);
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(size: Size, values: Box<[u8]>) -> CreationResult<Self> {
+        if values.len() != size.area() {
+            return Err(CreationError::SizeValuesMismatch);
+        }
+
+        Ok(GrayImage { size, values })
+    }
+
+    /// Create an empty [`GrayImage`] with the given size filled with zeros.
+    pub fn empty(size: Size) -> Self {
+        // SAFETY: values guaranted to be correct size
+        Self::new(size, vec![0; size.area()].into_boxed_slice())
+            .expect("unexpected error in GrayImage::new")
+    }
+
+    /// Get [`GrayImage`]'s [`Size`].
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// Get value at given `point`.
+    ///
+    /// Returns value if point is within image bounds, [`OutOfBoundsError`] otherwise.
+    ///
+    /// [`OutOfBoundsError`]: crate::error::OutOfBoundsError
+    pub fn value(&self, point: Point) -> IndexResult<u8> {
+        let index = point.index(self.size())?;
+
+        // SAFETY: index from point.to_index is always valid
+        Ok(self.values[index])
+    }
+
+    /// Get mutable value at given `point`.
+    ///
+    /// Returns value if point is within image bounds, [`OutOfBoundsError`] otherwise.
+    ///
+    /// [`OutOfBoundsError`]: crate::error::OutOfBoundsError
+    pub fn value_mut(&mut self, point: Point) -> IndexResult<&mut u8> {
+        let index = point.index(self.size())?;
+
+        // SAFETY: index from point.to_index is always valid
+        Ok(&mut self.values[index])
+    }
+
+    /// Get raw `u8` buffer of underlying image data, one value per pixel in row-major order.
+    pub fn buffer(&self) -> &[u8] {
+        &self.values
+    }
+
+    /// Get [`GrayImageLens`] which borrows the [`GrayImage`] to use with [`Lens`] API.
+    pub fn lens(&self) -> GrayImageLens<'_> {
+        GrayImageLens::new(self)
+    }
+}
+
+impl FromLens<u8> for GrayImage {
+    /// Collect [`Lens`] into a [`GrayImage`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::{
+    ///     lens::{
+    ///         FromLens,
+    ///         value::ValueLens,
+    ///     },
+    ///     prelude::*,
+    /// };
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let lens = ValueLens::new(7u8, Size::new(2, 2)?);
+    /// let image = GrayImage::from_lens(lens);
+    ///
+    /// assert_eq!(image.buffer(), &[7, 7, 7, 7]);
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn from_lens<S>(lens: S) -> Self
+    where
+        S: Lens<Item = u8>,
+    {
+        let size = lens.size();
+        let values = Box::from_iter(lens.elements());
+
+        // SAFETY: both size and values come from one Lens, which is guaranted
+        // to return correct values.
+        Self::new(size, values).expect("bug in lens implementation")
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl FromLensPar<u8> for GrayImage {
+    /// Collect [`Lens`] into a [`GrayImage`] using up to `threads` threads.
+    ///
+    /// Values are split into equal contiguous chunks, one per thread, like
+    /// [`Image`](crate::image::Image) does.
+    fn from_lens_par<S>(lens: S, threads: NonZeroUsize) -> Self
+    where
+        S: Lens<Item = u8> + Send + Sync,
+        S::Item: Send,
+    {
+        use std::thread;
+
+        use crate::lens::{
+            look_chunk,
+            par_chunks,
+        };
+
+        let size = lens.size();
+        let mut values = vec![0u8; size.area()].into_boxed_slice();
+
+        thread::scope(|scope| {
+            par_chunks(&mut values, threads).for_each(|(starting_index, chunk)| {
+                let lens = &lens;
+                scope.spawn(move || look_chunk(lens, starting_index, chunk, |value| value));
+            });
+        });
+
+        // SAFETY: values are allocated with exactly size.area() elements.
+        Self::new(size, values).expect("unexpected error in GrayImage::new")
+    }
+}
+
+impl LensMut for GrayImage {
+    type Item = u8;
+
+    fn look_mut(&mut self, point: Point) -> IndexResult<&mut u8> {
+        self.value_mut(point)
+    }
+
+    fn size(&self) -> Size {
+        self.size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use crate::error::IndexError;
+
+    use super::*;
+
+    #[test]
+    fn test_new_err() {
+        let size = Size::new(2, 2).unwrap();
+        let image = GrayImage::new(size, vec![0; 5].into_boxed_slice());
+        assert_eq!(image.unwrap_err(), CreationError::SizeValuesMismatch);
+    }
+
+    #[test]
+    fn test_value() {
+        let size = Size::new(2, 2).unwrap();
+        let mut image = GrayImage::empty(size);
+        let point = Point::new(1, 0).unwrap();
+        *image.value_mut(point).unwrap() = 42;
+
+        assert_eq!(image.value(point).unwrap(), 42);
+        assert_eq!(image.buffer(), &[0, 42, 0, 0]);
+        assert_eq!(image.value(Point::new(2, 0).unwrap()).unwrap_err(), IndexError::OutOfBounds);
+    }
+
+    #[test]
+    fn test_lens() {
+        let size = Size::new(3, 2).unwrap();
+        let image = GrayImage::new(size, (0..6).collect()).unwrap();
+        let lens = image.lens();
+
+        assert_eq!(lens.size(), size);
+        for (x, y) in (0..3).cartesian_product(0..2) {
+            let point = Point::new(x, y).unwrap();
+            assert_eq!(lens.look(point).unwrap(), image.value(point).unwrap());
+        }
+    }
+}
@@ -5,17 +5,33 @@ use std::num::NonZeroUsize;
 use rand::Rng;
 use thiserror::Error;
 
+#[cfg(feature = "parallel")]
+use crate::component::primitive::Area;
+#[cfg(feature = "image-compat")]
+use crate::pixel::PIXEL_SIZE;
 use crate::{
     component::primitive::{
         Point,
         Size,
+        SizeCreationError,
     },
     error::IndexResult,
+    gray_image::GrayImage,
     lens::{
         FromLens,
         FromLensPar,
         Lens,
+        LensMut,
+        checkerboard::{
+            CheckerboardCreationResult,
+            CheckerboardLens,
+        },
         image::ImageLens,
+        test_pattern::{
+            TestPattern,
+            TestPatternLens,
+        },
+        value::ValueLens,
     },
     pixel::Pixel,
 };
@@ -24,6 +40,12 @@ use crate::{
 pub enum CreationError {
     #[error("size does not match pixels size")]
     SizePixelsMismatch,
+    #[error("images have different sizes")]
+    SizeMismatch,
+    #[error("at least one image is required")]
+    Empty,
+    #[error("invalid size: {0}")]
+    InvalidSize(#[from] SizeCreationError),
 }
 
 pub type ResultError<T> = Result<T, CreationError>;
@@ -36,6 +58,9 @@ pub const DIMENSION_MAX: usize = (1u64 << 32) as usize - 1;
 #[cfg(target_pointer_width = "32")]
 pub const DIMENSION_MAX: usize = (1u32 << 16) as usize - 1;
 
+/// Ratio of height to width of a terminal character cell, used by [`Image::to_ascii`].
+pub const ASCII_CELL_ASPECT: f32 = 2f32;
+
 /// A `struct` representing in-memory image.
 #[derive(Debug, Clone)]
 pub struct Image {
@@ -123,6 +148,116 @@ impl Image {
             .expect("unexpected error in Image::new")
     }
 
+    /// Create a random fully opaque [`Image`] with the given size. Color channels are uniformly
+    /// distributed, alpha is always 255.
+    ///
+    /// Output depends only on the state of `rng`, so a seeded rng gives reproducible images.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::prelude::*;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let mut rng = rand::rng();
+    /// let image = Image::random_opaque(Size::new(2, 2)?, &mut rng);
+    ///
+    /// assert!(image.pixels().iter().all(|px| px.a() == 255));
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn random_opaque<R>(size: Size, rng: &mut R) -> Self
+    where
+        R: Rng,
+    {
+        let pixels = from_fn(|| Some(Pixel::new([rng.random(), rng.random(), rng.random(), 255])))
+            .take(size.area())
+            .collect();
+
+        // SAFETY: pixels guaranted to be correct size
+        Self::new(size, pixels).expect("unexpected error in Image::new")
+    }
+
+    /// Create a random fully opaque gray [`Image`] with the given size. Every pixel has equal,
+    /// uniformly distributed color channels and alpha of 255.
+    ///
+    /// Output depends only on the state of `rng`, so a seeded rng gives reproducible images.
+    pub fn random_gray<R>(size: Size, rng: &mut R) -> Self
+    where
+        R: Rng,
+    {
+        let pixels = from_fn(|| {
+            let value = rng.random();
+            Some(Pixel::new([value, value, value, 255]))
+        })
+        .take(size.area())
+        .collect();
+
+        // SAFETY: pixels guaranted to be correct size
+        Self::new(size, pixels).expect("unexpected error in Image::new")
+    }
+
+    /// Create an [`Image`] with the given size, filled with `pixel`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::prelude::*;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let red = Pixel::new([255, 0, 0, 255]);
+    /// let image = Image::solid(Size::new(2, 2)?, red);
+    ///
+    /// assert_eq!(*image.pixel(Point::new(1, 1)?)?, red);
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn solid(size: Size, pixel: Pixel) -> Self {
+        Self::from_lens(ValueLens::new(pixel, size))
+    }
+
+    /// Create an [`Image`] with the given size, filled with a checkerboard of `cell` sized
+    /// squares alternating between `a` and `b`, starting with `a` in the top left corner.
+    ///
+    /// Returns [`CheckerboardCreationError::CellZero`] if `cell` is zero.
+    ///
+    /// [`CheckerboardCreationError::CellZero`]: crate::lens::checkerboard::CheckerboardCreationError::CellZero
+    pub fn checkerboard(
+        size: Size,
+        cell: usize,
+        a: Pixel,
+        b: Pixel,
+    ) -> CheckerboardCreationResult<Self> {
+        Ok(Self::from_lens(CheckerboardLens::new(size, cell, a, b)?))
+    }
+
+    /// Create an [`Image`] with the given size, filled with a standard [`TestPattern`].
+    ///
+    /// Patterns are deterministic, which makes them suitable for benchmarks and for visual
+    /// inspection of filters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::{
+    ///     lens::test_pattern::TestPattern,
+    ///     prelude::*,
+    /// };
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let image = Image::test_pattern(Size::new(80, 10)?, TestPattern::ColorBars);
+    ///
+    /// assert_eq!(*image.pixel(Point::new(15, 0)?)?, Pixel::new([255, 255, 0, 255]));
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn test_pattern(size: Size, pattern: TestPattern) -> Self {
+        Self::from_lens(TestPatternLens::new(size, pattern))
+    }
+
     /// Get [`Image`]'s [`Size`].
     pub fn size(&self) -> Size {
         self.size
@@ -157,10 +292,161 @@ impl Image {
         self.pixels.iter().flat_map(|px| px.buffer()).cloned().collect()
     }
 
+    /// Get raw `u8` buffer of underlying image data without copying. It uses RGBA layout.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::prelude::*;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let image = Image::empty(Size::new(2, 2)?);
+    ///
+    /// assert_eq!(image.pixels_as_bytes(), &*image.buffer());
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "bytemuck")]
+    pub fn pixels_as_bytes(&self) -> &[u8] {
+        bytemuck::cast_slice(&self.pixels)
+    }
+
+    /// Get underlying pixels in row-major order.
+    pub fn pixels(&self) -> &[Pixel] {
+        &self.pixels
+    }
+
+    /// Create an [`Image`] by applying `f` to every pair of pixels at the same position in `a`
+    /// and `b`.
+    ///
+    /// Returns [`Image`] if sizes of `a` and `b` are equal, [`SizeMismatch`] otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::prelude::*;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let a = Image::solid(Size::new(2, 2)?, Pixel::new([10, 200, 30, 255]));
+    /// let b = Image::solid(Size::new(2, 2)?, Pixel::new([100, 20, 30, 255]));
+    ///
+    /// let lighten = Image::zip_map(&a, &b, |a, b| {
+    ///     Pixel::new(std::array::from_fn(|i| a.buffer()[i].max(b.buffer()[i])))
+    /// })?;
+    ///
+    /// assert_eq!(*lighten.pixel(Point::zero())?, Pixel::new([100, 200, 30, 255]));
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`SizeMismatch`]: CreationError::SizeMismatch
+    pub fn zip_map<F>(a: &Image, b: &Image, f: F) -> ResultError<Image>
+    where
+        F: Fn(Pixel, Pixel) -> Pixel,
+    {
+        if a.size() != b.size() {
+            return Err(CreationError::SizeMismatch);
+        }
+
+        let pixels = a.pixels().iter().zip(b.pixels()).map(|(a, b)| f(*a, *b)).collect();
+
+        Self::new(a.size(), pixels)
+    }
+
     /// Get [`ImageLens`] which borrows the [`Image`] to use with [`Lens`] API.
     pub fn lens(&self) -> ImageLens<'_> {
         ImageLens::new(self)
     }
+
+    /// Convert [`Image`] into single channel [`GrayImage`].
+    ///
+    /// Each value is a weighted sum of red, green and blue channels using `weights`, rounded and
+    /// clamped to `u8` range. Alpha channel is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::{
+    ///     gray_image::REC601_WEIGHTS,
+    ///     prelude::*,
+    /// };
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let image = Image::empty(Size::new(2, 2)?);
+    /// let gray = image.to_gray(REC601_WEIGHTS);
+    ///
+    /// assert_eq!(gray.size(), image.size());
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_gray(&self, weights: [f32; 3]) -> GrayImage {
+        GrayImage::from_lens(self.lens().map(move |px| {
+            let value = weights[0] * px.r() as f32
+                + weights[1] * px.g() as f32
+                + weights[2] * px.b() as f32;
+            value.round().clamp(0f32, 255f32) as u8
+        }))
+    }
+
+    /// Render [`Image`] as text for terminal preview.
+    ///
+    /// Image is downscaled to `cols` characters per line by averaging luma of pixels covered by
+    /// each character, with the number of lines reduced further by [`ASCII_CELL_ASPECT`] to
+    /// account for character cells being taller than wide. Luma is mapped to characters of
+    /// `charset`, which should be ordered from the darkest to the lightest. Every line ends with
+    /// `'\n'`. Images are never upscaled, so there are at most `width` columns and `height` lines.
+    ///
+    /// Returns empty [`String`] if `cols` is zero or `charset` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::prelude::*;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let image = Image::solid(Size::new(8, 8)?, Pixel::new([255, 255, 255, 255]));
+    /// let text = image.to_ascii(4, " .:#");
+    ///
+    /// assert_eq!(text, "####\n####\n");
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_ascii(&self, cols: usize, charset: &str) -> String {
+        let charset = charset.chars().collect::<Vec<_>>();
+        if cols == 0 || charset.is_empty() {
+            return String::new();
+        }
+
+        let (width, height) = (self.size.width(), self.size.height());
+        let cols = cols.min(width);
+        let rows =
+            (height as f32 * cols as f32 / width as f32 / ASCII_CELL_ASPECT).round() as usize;
+        let rows = rows.clamp(1, height);
+
+        let mut text = String::with_capacity(rows * (cols + 1));
+        for row in 0..rows {
+            let ys = row * height / rows..(row + 1) * height / rows;
+            for col in 0..cols {
+                let xs = col * width / cols..(col + 1) * width / cols;
+                let sum = ys
+                    .clone()
+                    .flat_map(|y| xs.clone().map(move |x| y * width + x))
+                    .map(|index| self.pixels[index].luminance_f32())
+                    .sum::<f32>();
+                let luma = sum / (xs.len() * ys.len()) as f32;
+
+                let index = (luma * (charset.len() - 1) as f32).round() as usize;
+                text.push(charset[index.min(charset.len() - 1)]);
+            }
+            text.push('\n');
+        }
+
+        text
+    }
 }
 
 impl<T: Into<Pixel>> FromLens<T> for Image {
@@ -199,9 +485,24 @@ impl<T: Into<Pixel>> FromLens<T> for Image {
     }
 }
 
+impl LensMut for Image {
+    type Item = Pixel;
+
+    fn look_mut(&mut self, point: Point) -> IndexResult<&mut Pixel> {
+        self.pixel_mut(point)
+    }
+
+    fn size(&self) -> Size {
+        self.size()
+    }
+}
+
 #[cfg(feature = "parallel")]
 impl<T: Into<Pixel> + Send> FromLensPar<T> for Image {
-    /// Collect [`Lens`] into an [`Image`].
+    /// Collect [`Lens`] into an [`Image`] using up to `threads` threads.
+    ///
+    /// Pixels are split into equal contiguous chunks, one per thread. Number of threads is
+    /// capped at the number of pixels, so no thread is spawned without work.
     ///
     /// # Examples
     ///
@@ -231,29 +532,17 @@ impl<T: Into<Pixel> + Send> FromLensPar<T> for Image {
     {
         use std::thread;
 
-        let size = lens.size();
-        let threads = threads.get();
-        let chunk_size = (size.area() as f32 / threads as f32).ceil() as usize;
-
-        let mut image = Image::empty(size);
+        use crate::lens::{
+            look_chunk,
+            par_chunks,
+        };
 
-        let image_chunks = image.pixels.chunks_mut(chunk_size);
+        let mut image = Image::empty(lens.size());
 
         thread::scope(|scope| {
-            image_chunks.enumerate().for_each(|(index, chunk)| {
+            par_chunks(&mut image.pixels, threads).for_each(|(starting_index, chunk)| {
                 let lens = &lens;
-                scope.spawn(move || {
-                    let starting_index = index * chunk_size;
-                    chunk.iter_mut().enumerate().for_each(|(index, pixel)| {
-                        // SAFETY: all starting_index + index will be in bounds since it enumerates
-                        // over the image that it is indexing.
-                        let point = Point::from_index(starting_index + index, size)
-                            .expect("unexpected error calculating index");
-                        // SAFETY: Lens::look is guaranted to return Ok if point is in bounds,
-                        // and point is guaranted to be in bounds because of the check above.
-                        *pixel = lens.look(point).expect("unexpected error in Lens::look").into();
-                    });
-                });
+                scope.spawn(move || look_chunk(lens, starting_index, chunk, Into::into));
             });
         });
 
@@ -261,6 +550,77 @@ impl<T: Into<Pixel> + Send> FromLensPar<T> for Image {
     }
 }
 
+#[cfg(feature = "parallel")]
+impl Image {
+    /// Copy pixels of `tile` into `area` of the image, see
+    /// [`Context::process_tiles`](crate::context::Context::process_tiles).
+    pub(crate) fn paste_tile(&mut self, area: Area, tile: &Image) {
+        let (x, y) = (area.top_left().x(), area.top_left().y());
+        let width = area.size().width();
+        for (row, pixels) in tile.pixels.chunks(width).enumerate() {
+            let start = (y + row) * self.size.width() + x;
+            self.pixels[start..start + width].copy_from_slice(pixels);
+        }
+    }
+}
+
+#[cfg(feature = "image-compat")]
+impl Image {
+    /// Convert `image` crate's [`RgbaImage`] into an [`Image`].
+    ///
+    /// Returns [`InvalidSize`] if [`RgbaImage`] has zero width or height, or dimensions
+    /// exceeding [`DIMENSION_MAX`].
+    ///
+    /// [`RgbaImage`]: ::image::RgbaImage
+    /// [`InvalidSize`]: CreationError::InvalidSize
+    pub fn from_rgba_image(value: ::image::RgbaImage) -> ResultError<Self> {
+        let size = Size::new(value.width() as usize, value.height() as usize)?;
+        let pixels = value
+            .as_raw()
+            .chunks_exact(PIXEL_SIZE)
+            .map(|chunk| {
+                // SAFETY: chunks_exact always yields slices of PIXEL_SIZE length.
+                Pixel::new(chunk.try_into().expect("unexpected error in slice conversion"))
+            })
+            .collect();
+
+        Self::new(size, pixels)
+    }
+}
+
+#[cfg(feature = "image-compat")]
+impl From<::image::RgbaImage> for Image {
+    /// Convert `image` crate's [`RgbaImage`] into an [`Image`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`RgbaImage`] has zero width or height, or dimensions exceeding
+    /// [`DIMENSION_MAX`], use [`Image::from_rgba_image`] to handle those.
+    ///
+    /// [`RgbaImage`]: ::image::RgbaImage
+    fn from(value: ::image::RgbaImage) -> Self {
+        Self::from_rgba_image(value).expect("RgbaImage size is not a valid Image size")
+    }
+}
+
+#[cfg(feature = "image-compat")]
+impl From<Image> for ::image::RgbaImage {
+    /// Convert an [`Image`] into `image` crate's [`RgbaImage`].
+    ///
+    /// [`RgbaImage`]: ::image::RgbaImage
+    fn from(value: Image) -> Self {
+        let size = value.size();
+
+        // SAFETY: DIMENSION_MAX fits in u32 and buffer length always matches size.
+        ::image::RgbaImage::from_raw(
+            size.width() as u32,
+            size.height() as u32,
+            value.buffer().into_vec(),
+        )
+        .expect("unexpected error in RgbaImage::from_raw")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use itertools::Itertools;
@@ -288,6 +648,91 @@ mod tests {
         assert_eq!(image.unwrap_err(), CreationError::SizePixelsMismatch);
     }
 
+    #[test]
+    fn test_zip_map_lighten() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let a = Image::random(Size::new(5, 4).unwrap(), &mut rng);
+        let b = Image::random(Size::new(5, 4).unwrap(), &mut rng);
+
+        let lighten = Image::zip_map(&a, &b, |a, b| {
+            Pixel::new(std::array::from_fn(|i| a.buffer()[i].max(b.buffer()[i])))
+        })
+        .unwrap();
+
+        for index in 0..a.size().area() {
+            let point = Point::from_index(index, a.size()).unwrap();
+            let (px_a, px_b) = (a.pixel(point).unwrap(), b.pixel(point).unwrap());
+            let expected = Pixel::new([
+                px_a.r().max(px_b.r()),
+                px_a.g().max(px_b.g()),
+                px_a.b().max(px_b.b()),
+                px_a.a().max(px_b.a()),
+            ]);
+            assert_eq!(*lighten.pixel(point).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_zip_map_size_mismatch() {
+        let a = Image::empty(Size::new(2, 2).unwrap());
+        let b = Image::empty(Size::new(3, 2).unwrap());
+
+        assert_eq!(Image::zip_map(&a, &b, |a, _| a).unwrap_err(), CreationError::SizeMismatch);
+    }
+
+    #[test]
+    fn test_to_ascii() {
+        let charset = " .:-=+*#%@";
+        let black = Image::solid(Size::new(40, 20).unwrap(), Pixel::new([0, 0, 0, 255]));
+        let white = Image::solid(Size::new(40, 20).unwrap(), Pixel::new([255, 255, 255, 255]));
+
+        let black_text = black.to_ascii(20, charset);
+        let white_text = white.to_ascii(20, charset);
+
+        assert_eq!(black_text.lines().count(), 5);
+        assert!(black_text.lines().all(|line| line == " ".repeat(20)));
+        assert!(white_text.lines().all(|line| line == "@".repeat(20)));
+        assert_eq!(black.to_ascii(100, charset).lines().next().unwrap().len(), 40);
+        assert!(black.to_ascii(0, charset).is_empty());
+        assert!(black.to_ascii(20, "").is_empty());
+    }
+
+    #[test]
+    fn test_to_ascii_horizontal_gradient() {
+        let image = Image::from_lens(Image::empty(Size::new(3, 2).unwrap()).lens().map_with_point(
+            |point, _| {
+                let value = (point.x() * 127) as u8 + point.x() as u8 / 2;
+                Pixel::new([value, value, value, 255])
+            },
+        ));
+
+        assert_eq!(image.to_ascii(3, ".o#"), ".o#\n");
+    }
+
+    #[test]
+    fn test_solid() {
+        let pixel = Pixel::new([10, 20, 30, 40]);
+        let image = Image::solid(Size::new(3, 2).unwrap(), pixel);
+
+        assert_eq!(image.size(), Size::new(3, 2).unwrap());
+        assert!(image.pixels().iter().all(|px| *px == pixel));
+    }
+
+    #[test]
+    fn test_checkerboard() {
+        let a = Pixel::new([255, 255, 255, 255]);
+        let b = Pixel::new([0, 0, 0, 255]);
+        let image = Image::checkerboard(Size::new(6, 4).unwrap(), 2, a, b).unwrap();
+
+        let at = |x, y| *image.pixel(Point::new(x, y).unwrap()).unwrap();
+        assert_eq!(at(0, 0), a);
+        assert_eq!(at(1, 1), a);
+        assert_eq!(at(2, 0), b);
+        assert_eq!(at(0, 2), b);
+        assert_eq!(at(2, 2), a);
+        assert_eq!(at(5, 3), b);
+    }
+
     #[test]
     fn test_empty() {
         let size = Size::new(2, 2).unwrap();
@@ -349,6 +794,17 @@ mod tests {
         assert_eq!(buffer, vec![1, 2, 3, 4, 5, 6, 7, 8].into_boxed_slice());
     }
 
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn test_pixels_as_bytes() {
+        let size = Size::new(3, 2).unwrap();
+        let image = Image::random(size, &mut SmallRng::seed_from_u64(0));
+        let bytes = image.pixels_as_bytes();
+
+        assert_eq!(bytes.len(), size.area() * crate::pixel::PIXEL_SIZE);
+        assert_eq!(bytes, &*image.buffer());
+    }
+
     #[test]
     fn test_lens() {
         let size = Size::new(2, 2).unwrap();
@@ -375,6 +831,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_to_gray() {
+        use crate::gray_image::REC601_WEIGHTS;
+
+        let size = Size::new(3, 2).unwrap();
+        let pixels = vec![Pixel::new([200, 100, 50, 255]); size.area()].into_boxed_slice();
+        let image = Image::new(size, pixels).unwrap();
+        let gray = image.to_gray(REC601_WEIGHTS);
+
+        assert_eq!(gray.size(), size);
+        // 0.299 * 200 + 0.587 * 100 + 0.114 * 50 = 124.2
+        assert!(gray.buffer().iter().all(|&value| value == 124));
+    }
+
+    #[test]
+    fn test_lens_mut() {
+        let size = Size::new(3, 2).unwrap();
+        let mut image = Image::empty(size);
+
+        *image.look_mut(Point::new(2, 1).unwrap()).unwrap() = Pixel::new([9, 9, 9, 9]);
+        assert_eq!(
+            image.lens().look(Point::new(2, 1).unwrap()).unwrap(),
+            &Pixel::new([9, 9, 9, 9])
+        );
+        assert_eq!(image.look_mut(Point::new(3, 0).unwrap()).unwrap_err(), IndexError::OutOfBounds);
+
+        image.for_each_mut(|point, px| px.set_r(point.x() as u8 + 10 * point.y() as u8));
+        for (x, y) in (0..3).cartesian_product(0..2) {
+            let point = Point::new(x, y).unwrap();
+            assert_eq!(image.lens().look(point).unwrap().r(), x as u8 + 10 * y as u8);
+        }
+    }
+
+    #[test]
+    fn test_random_opaque() {
+        let size = Size::new(16, 8).unwrap();
+        let image1 = Image::random_opaque(size, &mut SmallRng::seed_from_u64(7));
+        let image2 = Image::random_opaque(size, &mut SmallRng::seed_from_u64(7));
+
+        assert!(image1.pixels().iter().all(|px| px.a() == 255));
+        assert!(image1.pixels().iter().any(|px| px.r() != image1.pixels()[0].r()));
+        assert_eq!(image1.buffer(), image2.buffer());
+    }
+
+    #[test]
+    fn test_random_gray() {
+        let size = Size::new(16, 8).unwrap();
+        let image1 = Image::random_gray(size, &mut SmallRng::seed_from_u64(7));
+        let image2 = Image::random_gray(size, &mut SmallRng::seed_from_u64(7));
+
+        assert!(image1.pixels().iter().all(|px| px.r() == px.g() && px.g() == px.b()));
+        assert!(image1.pixels().iter().all(|px| px.a() == 255));
+        assert_eq!(image1.buffer(), image2.buffer());
+    }
+
     #[cfg(feature = "parallel")]
     #[test]
     fn test_from_lens_par() {
@@ -389,4 +900,69 @@ mod tests {
             assert_eq!(image1.pixel(point).unwrap(), image2.pixel(point).unwrap());
         }
     }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_from_lens_par_more_threads_than_pixels() {
+        use std::{
+            collections::HashSet,
+            num::NonZero,
+            sync::Mutex,
+            thread,
+        };
+
+        use crate::lens::value::ValueLens;
+
+        let threads_used = Mutex::new(HashSet::new());
+        let pixel = Pixel::new([1, 2, 3, 4]);
+        for width in [1, 3] {
+            let size = Size::new(width, 1).unwrap();
+            let lens = ValueLens::new(pixel, size).remap(
+                |s, point| {
+                    threads_used.lock().unwrap().insert(thread::current().id());
+                    s.look(point)
+                },
+                size,
+            );
+
+            let image = Image::from_lens_par(lens, NonZero::new(8).unwrap());
+
+            assert_eq!(image.size(), size);
+            assert!(image.pixels().iter().all(|px| *px == pixel));
+            assert!(threads_used.lock().unwrap().len() <= width);
+            threads_used.lock().unwrap().clear();
+        }
+    }
+
+    #[cfg(feature = "image-compat")]
+    #[test]
+    fn test_rgba_image_round_trip() {
+        let size = Size::new(3, 2).unwrap();
+        let image1 = Image::random(size, &mut SmallRng::seed_from_u64(0));
+
+        let rgba: ::image::RgbaImage = image1.clone().into();
+        assert_eq!((rgba.width(), rgba.height()), (3, 2));
+        assert_eq!(
+            rgba.get_pixel(2, 1).0,
+            *image1.pixel(Point::new(2, 1).unwrap()).unwrap().buffer()
+        );
+
+        let image2 = Image::from(rgba);
+        assert_eq!(image1.size(), image2.size());
+        assert_eq!(image1.buffer(), image2.buffer());
+    }
+
+    #[cfg(feature = "image-compat")]
+    #[test]
+    fn test_rgba_image_empty_err() {
+        let rgba = ::image::RgbaImage::new(0, 2);
+        assert!(matches!(Image::from_rgba_image(rgba).unwrap_err(), CreationError::InvalidSize(_)));
+    }
+
+    #[cfg(feature = "image-compat")]
+    #[test]
+    #[should_panic]
+    fn test_rgba_image_empty_from_panics() {
+        let _ = Image::from(::image::RgbaImage::new(2, 0));
+    }
 }
@@ -0,0 +1,152 @@
+use thiserror::Error;
+
+use crate::{
+    component::primitive::{
+        Point,
+        Size,
+    },
+    error::IndexResult,
+    lens::Lens,
+    pixel::{
+        PIXEL_SIZE,
+        Pixel,
+    },
+};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CreationError {
+    #[error("size does not match buffer length")]
+    SizeBufferMismatch,
+    #[error("size is too large to be addressed in memory")]
+    TooLarge,
+}
+
+pub type CreationResult<T> = Result<T, CreationError>;
+
+/// A zero-copy view over borrowed RGBA buffer.
+///
+/// Useful for processing frames owned by other libraries, [`Pixel`]s are read from the buffer
+/// on the fly when looked at.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     image_view::ImageView,
+///     lens::Lens,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let buffer = [1, 2, 3, 4, 5, 6, 7, 8];
+/// let view = ImageView::new(&buffer, Size::new(2, 1)?)?;
+///
+/// assert_eq!(view.look(Point::new(1, 0)?)?, Pixel::new([5, 6, 7, 8]));
+///
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ImageView<'a> {
+    buffer: &'a [u8],
+    size: Size,
+}
+
+impl<'a> ImageView<'a> {
+    /// Create an [`ImageView`] over `buffer` in RGBA layout.
+    ///
+    /// Returns [`ImageView`] if `buffer` length is equal to `size.area() * PIXEL_SIZE`,
+    /// [`TooLarge`] if that length overflows `usize` and [`SizeBufferMismatch`] otherwise.
+    ///
+    /// [`TooLarge`]: CreationError::TooLarge
+    /// [`SizeBufferMismatch`]: CreationError::SizeBufferMismatch
+    pub fn new(buffer: &'a [u8], size: Size) -> CreationResult<Self> {
+        let length = size
+            .area_checked()
+            .and_then(|area| area.checked_mul(PIXEL_SIZE))
+            .ok_or(CreationError::TooLarge)?;
+        if buffer.len() != length {
+            return Err(CreationError::SizeBufferMismatch);
+        }
+
+        Ok(Self { buffer, size })
+    }
+
+    /// Get underlying buffer.
+    pub fn buffer(&self) -> &'a [u8] {
+        self.buffer
+    }
+}
+
+impl Lens for ImageView<'_> {
+    type Item = Pixel;
+
+    fn look(&self, point: Point) -> IndexResult<Pixel> {
+        let index = point.index(self.size)? * PIXEL_SIZE;
+
+        // SAFETY: index from point.index is always valid and buffer length is checked in new.
+        Ok(Pixel::new(
+            self.buffer[index..index + PIXEL_SIZE]
+                .try_into()
+                .expect("unexpected error in slice conversion"),
+        ))
+    }
+
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use crate::{
+        error::IndexError,
+        image::{
+            DIMENSION_MAX,
+            Image,
+        },
+        lens::FromLens,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_new_err() {
+        let size = Size::new(2, 2).unwrap();
+        let buffer = [0; 15];
+
+        assert_eq!(ImageView::new(&buffer, size).unwrap_err(), CreationError::SizeBufferMismatch);
+    }
+
+    #[test]
+    fn test_new_too_large() {
+        let size = Size::new(DIMENSION_MAX, DIMENSION_MAX).unwrap();
+
+        assert_eq!(ImageView::new(&[], size).unwrap_err(), CreationError::TooLarge);
+    }
+
+    #[test]
+    fn test_look_matches_buffer() {
+        let size = Size::new(3, 2).unwrap();
+        let buffer: Vec<u8> = (0..24).collect();
+        let view = ImageView::new(&buffer, size).unwrap();
+
+        for (x, y) in (0..3).cartesian_product(0..2) {
+            let point = Point::new(x, y).unwrap();
+            let index = (y * 3 + x) * PIXEL_SIZE;
+            assert_eq!(view.look(point).unwrap().buffer(), &buffer[index..index + PIXEL_SIZE]);
+        }
+        assert_eq!(view.look(Point::new(3, 0).unwrap()).unwrap_err(), IndexError::OutOfBounds);
+    }
+
+    #[test]
+    fn test_from_lens() {
+        let size = Size::new(3, 2).unwrap();
+        let buffer: Vec<u8> = (0..24).collect();
+        let image = Image::from_lens(ImageView::new(&buffer, size).unwrap());
+
+        assert_eq!(&*image.buffer(), buffer.as_slice());
+    }
+}
@@ -0,0 +1,278 @@
+use std::{
+    io,
+    time::Duration,
+};
+
+use gif::{
+    ColorOutput,
+    DecodeOptions,
+    DisposalMethod,
+    Encoder,
+    Repeat,
+};
+
+use crate::{
+    component::primitive::Size,
+    error::{
+        IoError,
+        IoResult,
+    },
+    image::Image,
+    pixel::{
+        PIXEL_SIZE,
+        Pixel,
+    },
+};
+
+/// Speed of color quantization used when writing frames, 1 is the slowest and best, 30 the
+/// fastest.
+const QUANTIZATION_SPEED: i32 = 10;
+
+/// Single frame of an animation with its delay before the next frame is shown.
+#[derive(Debug, Clone)]
+pub struct Frame {
+    image: Image,
+    delay: Duration,
+}
+
+impl Frame {
+    pub fn new(image: Image, delay: Duration) -> Self {
+        Self { image, delay }
+    }
+
+    pub fn image(&self) -> &Image {
+        &self.image
+    }
+
+    pub fn delay(&self) -> Duration {
+        self.delay
+    }
+
+    pub fn into_image(self) -> Image {
+        self.image
+    }
+}
+
+/// Trait for reading gif animation as a sequence of frames
+pub trait ReadGif
+where
+    Self: Sized,
+{
+    fn read_gif(read: impl io::Read) -> IoResult<Self>;
+}
+
+impl ReadGif for Vec<Frame> {
+    /// Decode every frame of a gif, compositing it onto the canvas of the whole animation.
+    ///
+    /// Every returned frame has size of the canvas, with offsets, transparency and disposal of
+    /// previous frames already applied. Pixels not covered by any frame are transparent.
+    fn read_gif(read: impl io::Read) -> IoResult<Self> {
+        let mut options = DecodeOptions::new();
+        options.set_color_output(ColorOutput::RGBA);
+        let mut decoder = options.read_info(read)?;
+
+        let size = Size::new(decoder.width() as usize, decoder.height() as usize)
+            .map_err(|e| IoError::Unsupported(format!("unsupported: {e}")))?;
+        let mut canvas = vec![Pixel::zero(); size.area()];
+        let mut frames = Vec::new();
+
+        while let Some(gif_frame) = decoder.read_next_frame()? {
+            let previous = (gif_frame.dispose == DisposalMethod::Previous).then(|| canvas.clone());
+            let rows = frame_rows(size, gif_frame.left, gif_frame.top, gif_frame.width)
+                .take(gif_frame.height as usize);
+
+            for (row, source) in
+                rows.zip(gif_frame.buffer.chunks(gif_frame.width as usize * PIXEL_SIZE))
+            {
+                for (target, source) in canvas[row].iter_mut().zip(source.chunks(PIXEL_SIZE)) {
+                    // Transparent pixels of a frame leave the canvas unchanged.
+                    if source[3] != 0 {
+                        // SAFETY: chunks are of size PIXEL_SIZE.
+                        *target = Pixel::new(
+                            source.try_into().expect("unexpected chunk returned from chunks"),
+                        );
+                    }
+                }
+            }
+
+            // SAFETY: canvas always has one pixel for every pixel of its size.
+            let image = Image::new(size, canvas.clone().into_boxed_slice())
+                .expect("unexpected error in Image::new");
+            frames.push(Frame::new(image, Duration::from_millis(gif_frame.delay as u64 * 10)));
+
+            match gif_frame.dispose {
+                DisposalMethod::Background => {
+                    for row in frame_rows(size, gif_frame.left, gif_frame.top, gif_frame.width)
+                        .take(gif_frame.height as usize)
+                    {
+                        canvas[row].fill(Pixel::zero());
+                    }
+                }
+                DisposalMethod::Previous => {
+                    // SAFETY: previous canvas is always saved for this disposal method.
+                    canvas = previous.expect("unexpected missing previous canvas");
+                }
+                DisposalMethod::Any | DisposalMethod::Keep => {}
+            }
+        }
+
+        Ok(frames)
+    }
+}
+
+/// Ranges of canvas pixels covered by consecutive rows of a frame, clipped to the canvas.
+fn frame_rows(
+    size: Size,
+    left: u16,
+    top: u16,
+    width: u16,
+) -> impl Iterator<Item = std::ops::Range<usize>> {
+    let start = (left as usize).min(size.width());
+    let end = (left as usize + width as usize).min(size.width());
+
+    (top as usize..size.height()).map(move |y| y * size.width() + start..y * size.width() + end)
+}
+
+/// Trait for writing sequence of frames as gif animation
+pub trait WriteGif {
+    fn write_gif(&self, write: impl io::Write) -> IoResult<()>;
+}
+
+impl WriteGif for [Frame] {
+    /// Encode frames as a looping gif animation.
+    ///
+    /// Every frame is quantized to its own palette of at most 256 colors, pixels with alpha below
+    /// half are written as transparent. Delays are rounded down to 10ms. All frames must have the
+    /// same size, which can't exceed 65535 in any dimension.
+    fn write_gif(&self, write: impl io::Write) -> IoResult<()> {
+        let size = match self.first() {
+            Some(frame) => frame.image.size(),
+            None => return Err(IoError::Unsupported("gif without frames".to_string())),
+        };
+        if self.iter().any(|frame| frame.image.size() != size) {
+            return Err(IoError::Unsupported("frames of different sizes".to_string()));
+        }
+        let (width, height) = match (u16::try_from(size.width()), u16::try_from(size.height())) {
+            (Ok(width), Ok(height)) => (width, height),
+            _ => return Err(IoError::Unsupported("gif dimensions above 65535".to_string())),
+        };
+
+        let mut encoder = Encoder::new(write, width, height, &[])?;
+        encoder.set_repeat(Repeat::Infinite)?;
+
+        for frame in self {
+            let mut buffer = frame.image.buffer();
+            let mut gif_frame =
+                gif::Frame::from_rgba_speed(width, height, &mut buffer, QUANTIZATION_SPEED);
+            gif_frame.delay = (frame.delay.as_millis() / 10).min(u16::MAX as u128) as u16;
+            // Frames cover the whole canvas, so the previous one must not show through
+            // transparent pixels.
+            gif_frame.dispose = DisposalMethod::Background;
+
+            encoder.write_frame(&gif_frame)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::primitive::Point;
+
+    const RED: Pixel = Pixel::new([255, 0, 0, 255]);
+    const GREEN: Pixel = Pixel::new([0, 255, 0, 255]);
+
+    /// 4x4 gif with full red frame followed by 2x2 green frame at offset (1, 1).
+    fn two_frame_gif(first_dispose: DisposalMethod) -> Vec<u8> {
+        let palette = [255, 0, 0, 0, 255, 0];
+        let mut data = Vec::new();
+        let mut encoder = Encoder::new(&mut data, 4, 4, &palette).unwrap();
+
+        let mut first = gif::Frame::from_indexed_pixels(4, 4, vec![0; 16], None);
+        first.dispose = first_dispose;
+        first.delay = 5;
+        encoder.write_frame(&first).unwrap();
+
+        let mut second = gif::Frame::from_indexed_pixels(2, 2, vec![1; 4], None);
+        second.left = 1;
+        second.top = 1;
+        second.delay = 12;
+        encoder.write_frame(&second).unwrap();
+
+        drop(encoder);
+        data
+    }
+
+    fn pixel(frame: &Frame, x: usize, y: usize) -> Pixel {
+        *frame.image().pixel(Point::new(x, y).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_read_two_frames() {
+        let frames = Vec::<Frame>::read_gif(&two_frame_gif(DisposalMethod::Keep)[..]).unwrap();
+
+        assert_eq!(frames.len(), 2);
+        for frame in &frames {
+            assert_eq!(frame.image().size(), Size::new(4, 4).unwrap());
+        }
+        assert_eq!(frames[0].delay(), Duration::from_millis(50));
+        assert_eq!(frames[1].delay(), Duration::from_millis(120));
+        assert!(frames[0].image().pixels().iter().all(|px| *px == RED));
+        // Kept first frame shows around the second one.
+        assert_eq!(pixel(&frames[1], 0, 0), RED);
+        assert_eq!(pixel(&frames[1], 1, 1), GREEN);
+        assert_eq!(pixel(&frames[1], 2, 2), GREEN);
+        assert_eq!(pixel(&frames[1], 3, 3), RED);
+    }
+
+    #[test]
+    fn test_read_background_disposal() {
+        let frames =
+            Vec::<Frame>::read_gif(&two_frame_gif(DisposalMethod::Background)[..]).unwrap();
+
+        assert_eq!(pixel(&frames[1], 0, 0), Pixel::zero());
+        assert_eq!(pixel(&frames[1], 1, 2), GREEN);
+        assert_eq!(pixel(&frames[1], 3, 0), Pixel::zero());
+    }
+
+    #[test]
+    fn test_read_previous_disposal() {
+        let frames = Vec::<Frame>::read_gif(&two_frame_gif(DisposalMethod::Previous)[..]).unwrap();
+
+        // First frame is replaced with the empty canvas it was drawn on.
+        assert_eq!(pixel(&frames[1], 0, 0), Pixel::zero());
+        assert_eq!(pixel(&frames[1], 2, 1), GREEN);
+    }
+
+    #[test]
+    fn test_write_read_same_frames() {
+        let size = Size::new(3, 2).unwrap();
+        let frames = [
+            Frame::new(Image::solid(size, RED), Duration::from_millis(100)),
+            Frame::new(Image::solid(size, GREEN), Duration::from_millis(40)),
+        ];
+
+        let mut data = Vec::new();
+        frames.write_gif(&mut data).unwrap();
+        let decoded = Vec::<Frame>::read_gif(&data[..]).unwrap();
+
+        assert_eq!(decoded.len(), 2);
+        for (frame, decoded) in frames.iter().zip(&decoded) {
+            assert_eq!(decoded.delay(), frame.delay());
+            assert_eq!(decoded.image().pixels(), frame.image().pixels());
+        }
+    }
+
+    #[test]
+    fn test_write_mismatched_sizes() {
+        let frames = [
+            Frame::new(Image::solid(Size::new(3, 2).unwrap(), RED), Duration::ZERO),
+            Frame::new(Image::solid(Size::new(2, 3).unwrap(), RED), Duration::ZERO),
+        ];
+
+        assert!(matches!(frames.write_gif(Vec::new()), Err(IoError::Unsupported(_))));
+        assert!(matches!(Vec::<Frame>::new().write_gif(Vec::new()), Err(IoError::Unsupported(_))));
+    }
+}
@@ -7,6 +7,7 @@ use crate::{
         IoError,
         IoResult,
     },
+    gray_image::GrayImage,
     image::Image,
     pixel::{
         PIXEL_SIZE,
@@ -23,18 +24,11 @@ where
 }
 
 impl ReadJpeg for Image {
-    fn read_jpeg(mut read: impl io::Read) -> IoResult<Self> {
-        let mut jpeg_data = Vec::new();
-        read.read_to_end(&mut jpeg_data)?;
-        let turbojpeg_image = turbojpeg::decompress(&jpeg_data, turbojpeg::PixelFormat::RGBA)
-            .map_err(IoError::JpegDecoding)?;
-
-        let size = Size::new(turbojpeg_image.width, turbojpeg_image.height)
-            .map_err(|e| IoError::Unsupported(format!("unsupported: {e}")))?;
+    fn read_jpeg(read: impl io::Read) -> IoResult<Self> {
+        let (size, pixels) = decompress(read, turbojpeg::PixelFormat::RGBA)?;
         let image = Image::new(
             size,
-            turbojpeg_image
-                .pixels
+            pixels
                 .chunks(PIXEL_SIZE)
                 // SAFETY: chunks are of size PIXEL_SIZE, which is the same
                 // as Pixel::new expects.
@@ -47,6 +41,35 @@ impl ReadJpeg for Image {
     }
 }
 
+impl ReadJpeg for GrayImage {
+    /// Decode only luminance of a jpeg, which skips color conversion and is faster than decoding
+    /// an [`Image`] for pipelines working on luminance alone.
+    fn read_jpeg(read: impl io::Read) -> IoResult<Self> {
+        let (size, values) = decompress(read, turbojpeg::PixelFormat::GRAY)?;
+        let image = GrayImage::new(size, values.into_boxed_slice()).map_err(|_| {
+            IoError::Unexpected("Unexpected value received from turbojpeg".to_string())
+        })?;
+
+        Ok(image)
+    }
+}
+
+/// Decompress whole jpeg from `read` into buffer of given pixel format.
+fn decompress(
+    mut read: impl io::Read,
+    format: turbojpeg::PixelFormat,
+) -> IoResult<(Size, Vec<u8>)> {
+    let mut jpeg_data = Vec::new();
+    read.read_to_end(&mut jpeg_data)?;
+    let turbojpeg_image =
+        turbojpeg::decompress(&jpeg_data, format).map_err(IoError::JpegDecoding)?;
+
+    let size = Size::new(turbojpeg_image.width, turbojpeg_image.height)
+        .map_err(|e| IoError::Unsupported(format!("unsupported: {e}")))?;
+
+    Ok((size, turbojpeg_image.pixels))
+}
+
 #[derive(Debug)]
 pub struct JpegQuality(i32);
 
@@ -129,3 +152,32 @@ impl WriteJpeg for Image {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gray_image::REC601_WEIGHTS;
+
+    #[test]
+    fn test_read_gray_matches_luminance() {
+        let size = Size::new(32, 24).unwrap();
+        let pixels = (0..size.area())
+            .map(|index| {
+                let (x, y) = ((index % 32) as u8, (index / 32) as u8);
+                Pixel::new([x * 8, y * 10, 255 - x * 4 - y * 4, 255])
+            })
+            .collect();
+        let image = Image::new(size, pixels).unwrap();
+        let mut data = Vec::new();
+        image.write_jpeg(&mut data, JpegQuality::new(95).unwrap(), JpegSubsampling::None).unwrap();
+
+        let color = Image::read_jpeg(&data[..]).unwrap();
+        let gray = GrayImage::read_jpeg(&data[..]).unwrap();
+
+        assert_eq!(gray.size(), size);
+        let luminance = color.to_gray(REC601_WEIGHTS);
+        for (value, expected) in gray.buffer().iter().zip(luminance.buffer()) {
+            assert!(value.abs_diff(*expected) <= 3, "{value} != {expected}");
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use crate::error::{
+    IoError,
+    IoResult,
+};
+
+const MARKER_PREFIX: u8 = 0xFF;
+const SOI: u8 = 0xD8;
+const SOS: u8 = 0xDA;
+const APP0: u8 = 0xE0;
+const APP1: u8 = 0xE1;
+const APP2: u8 = 0xE2;
+
+const EXIF_SIGNATURE: &[u8] = b"Exif\0\0";
+const ICC_SIGNATURE: &[u8] = b"ICC_PROFILE\0";
+
+/// Exif tag holding image orientation.
+const ORIENTATION_TAG: u16 = 0x0112;
+
+/// Metadata segments (EXIF and ICC profile) of a JPEG file.
+///
+/// Segments are kept as raw bytes, so they can be copied from a decoded file into a freshly
+/// encoded one without interpreting their content.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JpegMetadata {
+    segments: Vec<Vec<u8>>,
+}
+
+impl JpegMetadata {
+    /// Collect EXIF (APP1) and ICC profile (APP2) segments from JPEG data.
+    ///
+    /// Only headers are scanned, reading stops at the start of scan. Returns
+    /// [`IoError::Unsupported`] if `data` is not a JPEG file or [`IoError::Unexpected`] if a
+    /// segment is truncated.
+    pub fn read(data: &[u8]) -> IoResult<Self> {
+        let (headers, _) = header_segments(data)?;
+        let segments = headers
+            .into_iter()
+            .filter(|(_, segment)| is_metadata(segment))
+            .map(|(_, segment)| segment.to_vec())
+            .collect();
+
+        Ok(Self { segments })
+    }
+
+    /// Insert metadata segments into JPEG data, after SOI and JFIF (APP0) segments.
+    ///
+    /// EXIF and ICC profile segments already present in `jpeg` are replaced.
+    pub fn embed(&self, jpeg: &[u8]) -> IoResult<Vec<u8>> {
+        let (headers, scan_offset) = header_segments(jpeg)?;
+        let leading = headers.iter().take_while(|(_, segment)| segment[1] == APP0).count();
+        let leading_length =
+            headers[..leading].last().map_or(2, |(offset, segment)| offset + segment.len());
+
+        let mut output =
+            Vec::with_capacity(jpeg.len() + self.segments.iter().map(Vec::len).sum::<usize>());
+        output.extend_from_slice(&jpeg[..leading_length]);
+        self.segments.iter().for_each(|segment| output.extend_from_slice(segment));
+        headers[leading..]
+            .iter()
+            .filter(|(_, segment)| !is_metadata(segment))
+            .for_each(|(_, segment)| output.extend_from_slice(segment));
+        output.extend_from_slice(&jpeg[scan_offset..]);
+
+        Ok(output)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// TIFF structure of the EXIF segment, if present.
+    pub fn exif(&self) -> Option<&[u8]> {
+        self.segments.iter().find_map(|segment| segment[4..].strip_prefix(EXIF_SIGNATURE))
+    }
+
+    /// Value of the EXIF orientation tag, if present.
+    pub fn orientation(&self) -> Option<u16> {
+        let tiff = self.exif()?;
+        let big_endian = match tiff.get(..2)? {
+            b"II" => false,
+            b"MM" => true,
+            _ => return None,
+        };
+        let read = |offset: usize, length: usize| -> Option<usize> {
+            let bytes = tiff.get(offset..offset + length)?;
+            let append = |value: usize, byte: &u8| value << 8 | *byte as usize;
+            match big_endian {
+                true => Some(bytes.iter().fold(0, append)),
+                false => Some(bytes.iter().rev().fold(0, append)),
+            }
+        };
+
+        let ifd = read(4, 4)?;
+        (0..read(ifd, 2)?)
+            .map(|entry| ifd + 2 + entry * 12)
+            .find(|entry| read(*entry, 2) == Some(ORIENTATION_TAG as usize))
+            .and_then(|entry| read(entry + 8, 2))
+            .map(|orientation| orientation as u16)
+    }
+}
+
+/// Header segment with its offset in JPEG data.
+type Segment<'a> = (usize, &'a [u8]);
+
+/// Split JPEG headers (everything between SOI and SOS) into segments, each with its marker and
+/// length, paired with its offset in `data`. Also returns offset of the SOS marker.
+///
+/// Fill bytes (`0xFF`) preceding markers are skipped and not included in any segment.
+fn header_segments(data: &[u8]) -> IoResult<(Vec<Segment<'_>>, usize)> {
+    if !data.starts_with(&[MARKER_PREFIX, SOI]) {
+        return Err(IoError::Unsupported("not a jpeg file".to_string()));
+    }
+
+    let mut segments = Vec::new();
+    let mut offset = 2;
+    loop {
+        while data.get(offset..offset + 2) == Some(&[MARKER_PREFIX, MARKER_PREFIX]) {
+            offset += 1;
+        }
+
+        match data.get(offset..offset + 4) {
+            Some([MARKER_PREFIX, SOS, ..]) => break,
+            Some([MARKER_PREFIX, _, high, low]) => {
+                // Length includes its own 2 bytes.
+                let length = u16::from_be_bytes([*high, *low]) as usize;
+                let segment = data
+                    .get(offset..offset + 2 + length)
+                    .filter(|_| length >= 2)
+                    .ok_or_else(|| IoError::Unexpected("truncated jpeg segment".to_string()))?;
+                segments.push((offset, segment));
+                offset += segment.len();
+            }
+            _ => return Err(IoError::Unexpected("invalid jpeg segment".to_string())),
+        }
+    }
+
+    Ok((segments, offset))
+}
+
+fn is_metadata(segment: &[u8]) -> bool {
+    match segment[1] {
+        APP1 => segment[4..].starts_with(EXIF_SIGNATURE),
+        APP2 => segment[4..].starts_with(ICC_SIGNATURE),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(marker: u8, payload: &[u8]) -> Vec<u8> {
+        let length = (payload.len() as u16 + 2).to_be_bytes();
+        [&[MARKER_PREFIX, marker], &length[..], payload].concat()
+    }
+
+    /// Exif segment with IFD0 containing only orientation tag.
+    fn exif_segment(orientation: u16) -> Vec<u8> {
+        let mut tiff = b"MM\0\x2A\0\0\0\x08".to_vec();
+        tiff.extend_from_slice(&1u16.to_be_bytes());
+        tiff.extend_from_slice(&ORIENTATION_TAG.to_be_bytes());
+        tiff.extend_from_slice(&3u16.to_be_bytes());
+        tiff.extend_from_slice(&1u32.to_be_bytes());
+        tiff.extend_from_slice(&orientation.to_be_bytes());
+        tiff.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+
+        segment(APP1, &[EXIF_SIGNATURE, &tiff].concat())
+    }
+
+    /// JPEG-like data with given header segments, scan is not valid entropy coded data.
+    fn jpeg(headers: &[Vec<u8>]) -> Vec<u8> {
+        let scan = [&segment(SOS, &[1, 2, 3])[..], &[0x12, 0x34, MARKER_PREFIX, 0xD9]].concat();
+        [&[MARKER_PREFIX, SOI][..], &headers.concat(), &scan].concat()
+    }
+
+    #[test]
+    fn test_round_trip_keeps_orientation() {
+        let jfif = segment(APP0, b"JFIF\0\x01\x01");
+        let icc = segment(APP2, &[ICC_SIGNATURE, &[1, 1, 9, 9]].concat());
+        let quantization = segment(0xDB, &[0; 65]);
+        let source = jpeg(&[jfif.clone(), exif_segment(6), icc.clone(), quantization.clone()]);
+        // Re-encoded file only has encoder's own headers.
+        let encoded = jpeg(&[jfif.clone(), quantization.clone()]);
+
+        let metadata = JpegMetadata::read(&source).unwrap();
+        let output = JpegMetadata::read(&metadata.embed(&encoded).unwrap()).unwrap();
+
+        assert_eq!(metadata.orientation(), Some(6));
+        assert_eq!(output, metadata);
+        assert_eq!(metadata.embed(&encoded).unwrap(), source);
+    }
+
+    #[test]
+    fn test_stripped_metadata() {
+        let source = jpeg(&[exif_segment(3)]);
+
+        let stripped = JpegMetadata::default().embed(&source).unwrap();
+
+        assert_eq!(JpegMetadata::read(&source).unwrap().orientation(), Some(3));
+        assert!(JpegMetadata::read(&stripped).unwrap().is_empty());
+        assert_eq!(JpegMetadata::read(&stripped).unwrap().orientation(), None);
+    }
+
+    #[test]
+    fn test_fill_bytes() {
+        let jfif = segment(APP0, b"JFIF\0\x01\x01");
+        let quantization = segment(0xDB, &[0; 65]);
+        let filled = |segment: &[u8]| [&[MARKER_PREFIX; 3][..], segment].concat();
+        let source = jpeg(&[filled(&jfif), filled(&exif_segment(8)), filled(&quantization)]);
+        let encoded = jpeg(&[jfif.clone(), quantization.clone()]);
+
+        let metadata = JpegMetadata::read(&source).unwrap();
+        let output = metadata.embed(&encoded).unwrap();
+
+        assert_eq!(metadata.orientation(), Some(8));
+        assert_eq!(output, jpeg(&[jfif, exif_segment(8), quantization]));
+    }
+
+    #[test]
+    fn test_invalid_data() {
+        assert!(matches!(JpegMetadata::read(b"\x89PNG"), Err(IoError::Unsupported(_))));
+        assert!(matches!(
+            JpegMetadata::read(&[MARKER_PREFIX, SOI, MARKER_PREFIX, APP1, 0, 40]),
+            Err(IoError::Unexpected(_))
+        ));
+    }
+}
@@ -1,5 +1,10 @@
 #[cfg(feature = "jpeg")]
 pub mod jpeg;
 
+#[cfg(feature = "gif")]
+pub mod gif;
+
+pub mod metadata;
+
 #[cfg(feature = "png")]
 pub mod png;
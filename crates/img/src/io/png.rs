@@ -140,6 +140,47 @@ impl WritePng for Image {
     }
 }
 
+/// Write frames as an animated png (APNG), looping forever.
+///
+/// Every frame is a pair of an [`Image`] and its delay in milliseconds. All frames must share
+/// the same size, otherwise [`IoError::Unsupported`] is returned. The first frame is also the
+/// default image shown by decoders without animation support.
+pub fn write_apng(frames: &[(Image, u16)], write: impl std::io::Write) -> IoResult<()> {
+    let size = match frames.first() {
+        Some((image, _)) => image.size(),
+        None => return Err(IoError::Unsupported("apng without frames".to_string())),
+    };
+    if frames.iter().any(|(image, _)| image.size() != size) {
+        return Err(IoError::Unsupported("frames of different sizes".to_string()));
+    }
+
+    // SAFETY: Platforms with ptr width other than 32 and 64 are unsupported.
+    let mut encoder = png::Encoder::new(
+        write,
+        size.width().try_into().expect("unsupported"),
+        size.height().try_into().expect("unsupported"),
+    );
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .set_animated(frames.len().try_into().expect("unsupported"), 0)
+        .map_err(|_| IoError::Unexpected("failed to set png animation".to_string()))?;
+    let mut writer = encoder
+        .write_header()
+        .map_err(|_| IoError::Unexpected("failed to write png header".to_string()))?;
+
+    for (image, delay) in frames {
+        writer
+            .set_frame_delay(*delay, 1000)
+            .map_err(|_| IoError::Unexpected("failed to set png frame delay".to_string()))?;
+        writer
+            .write_image_data(image.buffer().as_ref())
+            .map_err(|_| IoError::Unexpected("failed to write png frame data".to_string()))?;
+    }
+
+    writer.finish().map_err(|_| IoError::Unexpected("failed to finish png animation".to_string()))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -190,4 +231,40 @@ mod test {
             image2.pixel(Point::new(1, 1).unwrap()).unwrap()
         );
     }
+
+    #[test]
+    fn test_write_apng_two_frames() {
+        let size = Size::new(3, 2).unwrap();
+        let frames = [
+            (Image::solid(size, Pixel::new([255, 0, 0, 255])), 100),
+            (Image::solid(size, Pixel::new([0, 0, 255, 128])), 250),
+        ];
+
+        let mut data = Vec::new();
+        write_apng(&frames, &mut data).unwrap();
+
+        let mut reader = png::Decoder::new(&data[..]).read_info().unwrap();
+        let info = reader.info();
+        assert_eq!((info.width, info.height), (3, 2));
+        assert_eq!(info.animation_control.unwrap().num_frames, 2);
+
+        let mut buf = vec![0; reader.output_buffer_size()];
+        for (image, delay) in &frames {
+            let output = reader.next_frame(&mut buf).unwrap();
+            let control = reader.info().frame_control.unwrap();
+            assert_eq!((output.width, output.height), (3, 2));
+            assert_eq!((control.delay_num, control.delay_den), (*delay, 1000));
+            assert_eq!(&buf[..output.buffer_size()], image.buffer().as_ref());
+        }
+    }
+
+    #[test]
+    fn test_write_apng_mismatched_sizes() {
+        let frames = [
+            (Image::empty(Size::new(3, 2).unwrap()), 0),
+            (Image::empty(Size::new(2, 3).unwrap()), 0),
+        ];
+
+        assert!(matches!(write_apng(&frames, Vec::new()), Err(IoError::Unsupported(_))));
+    }
 }
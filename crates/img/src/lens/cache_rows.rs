@@ -0,0 +1,154 @@
+use std::cell::RefCell;
+
+use crate::{
+    component::primitive::{
+        Point,
+        Size,
+    },
+    error::{
+        IndexError,
+        IndexResult,
+    },
+    lens::Lens,
+};
+
+/// A [`Lens`] which memoizes whole rows of `source`, keeping up to `capacity` rows.
+///
+/// When a point from a row which is not cached is looked up, every [`Lens::Item`] of that row
+/// is evaluated and saved, evicting the cached row farthest from it if needed. This avoids
+/// re-evaluating expensive upstream lenses when windows of a [`Kernel`] overlap, without the
+/// memory cost of [`Lens::materialize`]. Cache size should be at least the height of such
+/// windows.
+///
+/// It assumes row-first access, as done by [`Lens::elements`] and [`FromLens`]. Other access
+/// patterns evaluate more of `source` than needed. Cache uses interior mutability, so this
+/// [`Lens`] is not [`Sync`] and can't be evaluated in parallel.
+///
+/// [`Kernel`]: crate::component::kernel::Kernel
+/// [`FromLens`]: crate::lens::FromLens
+pub struct CacheRowsLens<S>
+where
+    S: Lens,
+{
+    source: S,
+    capacity: usize,
+    rows: RefCell<Vec<(usize, Vec<S::Item>)>>,
+}
+
+impl<S> CacheRowsLens<S>
+where
+    S: Lens,
+{
+    /// Create a [`CacheRowsLens`] keeping up to `capacity` rows, at least one row is always kept.
+    pub fn new(source: S, capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self { source, capacity, rows: RefCell::new(Vec::with_capacity(capacity)) }
+    }
+}
+
+impl<S> Lens for CacheRowsLens<S>
+where
+    S: Lens,
+    S::Item: Clone,
+{
+    type Item = S::Item;
+
+    fn look(&self, point: Point) -> IndexResult<Self::Item> {
+        if !self.size().contains(&point) {
+            return Err(IndexError::OutOfBounds);
+        }
+
+        let mut rows = self.rows.borrow_mut();
+        if let Some((_, row)) = rows.iter().find(|(y, _)| *y == point.y()) {
+            return Ok(row[point.x()].clone());
+        }
+
+        let row = (0..self.size().width())
+            .map(|x| {
+                // SAFETY: x and y are within size of source.
+                self.source.look(Point::new(x, point.y()).expect("unexpected error in Point::new"))
+            })
+            .collect::<IndexResult<Vec<_>>>()?;
+        let value = row[point.x()].clone();
+
+        if rows.len() == self.capacity {
+            // SAFETY: capacity is at least 1, so there is always a row to evict.
+            let farthest = (0..rows.len())
+                .max_by_key(|index| rows[*index].0.abs_diff(point.y()))
+                .expect("unexpected empty cache");
+            rows.swap_remove(farthest);
+        }
+        rows.push((point.y(), row));
+
+        Ok(value)
+    }
+
+    fn size(&self) -> Size {
+        self.source.size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use crate::{
+        component::kernel::mean::MeanKernel,
+        image::Image,
+        lens::FromLens,
+        pixel::{
+            ChannelFlags,
+            Pixel,
+        },
+    };
+
+    use super::*;
+
+    fn gradient() -> Image {
+        let size = Size::new(12, 9).unwrap();
+        let pixels =
+            (0..size.area()).map(|index| Pixel::new([index as u8, 0, 255 - index as u8, 255]));
+
+        Image::new(size, pixels.collect()).unwrap()
+    }
+
+    #[test]
+    fn test_cache_rows_reduces_looks() {
+        let image = gradient();
+        let kernel = || MeanKernel::new(Size::from_radius(1).unwrap(), ChannelFlags::RGBA).unwrap();
+        let (cached_looks, uncached_looks) = (Cell::new(0), Cell::new(0));
+
+        let uncached = Image::from_lens(
+            image
+                .lens()
+                .inspect(|_, _| uncached_looks.set(uncached_looks.get() + 1))
+                .kernel(kernel())
+                .unwrap(),
+        );
+        let cached = Image::from_lens(
+            image
+                .lens()
+                .inspect(|_, _| cached_looks.set(cached_looks.get() + 1))
+                .cache_rows(3)
+                .kernel(kernel())
+                .unwrap(),
+        );
+
+        assert_eq!(cached.pixels(), uncached.pixels());
+        assert!(uncached_looks.get() >= 9 * cached.size().area());
+        // Every row of source is evaluated exactly once.
+        assert_eq!(cached_looks.get(), image.size().area());
+    }
+
+    #[test]
+    fn test_cache_rows_out_of_order() {
+        let image = gradient();
+        let lens = image.lens().cache_rows(2);
+
+        for (x, y) in [(0, 0), (3, 5), (1, 0), (11, 8), (4, 5), (2, 0)] {
+            let point = Point::new(x, y).unwrap();
+            assert_eq!(lens.look(point).unwrap(), image.pixel(point).unwrap());
+        }
+        assert_eq!(lens.look(Point::new(12, 0).unwrap()).unwrap_err(), IndexError::OutOfBounds);
+    }
+}
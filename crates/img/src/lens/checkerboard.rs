@@ -0,0 +1,88 @@
+use thiserror::Error;
+
+use crate::{
+    component::primitive::{
+        Point,
+        Size,
+    },
+    error::{
+        IndexError,
+        IndexResult,
+    },
+    lens::Lens,
+};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CheckerboardCreationError {
+    #[error("cell size must be greater than zero")]
+    CellZero,
+}
+
+pub type CheckerboardCreationResult<T> = std::result::Result<T, CheckerboardCreationError>;
+
+/// [`Lens`] generating a checkerboard of square `cell` sized cells, alternating between `light`
+/// and `dark` values, with `light` in the top left corner.
+pub struct CheckerboardLens<T> {
+    size: Size,
+    cell: usize,
+    light: T,
+    dark: T,
+}
+
+impl<T> CheckerboardLens<T> {
+    /// Create a [`CheckerboardLens`] of given `size`.
+    ///
+    /// Returns [`CheckerboardCreationError::CellZero`] if `cell` is zero.
+    pub fn new(size: Size, cell: usize, light: T, dark: T) -> CheckerboardCreationResult<Self> {
+        if cell == 0 {
+            return Err(CheckerboardCreationError::CellZero);
+        }
+
+        Ok(Self { size, cell, light, dark })
+    }
+}
+
+impl<T> Lens for CheckerboardLens<T>
+where
+    T: Clone,
+{
+    type Item = T;
+
+    fn look(&self, point: Point) -> IndexResult<Self::Item> {
+        if !self.size.contains(&point) {
+            return Err(IndexError::OutOfBounds);
+        }
+
+        if (point.x() / self.cell + point.y() / self.cell).is_multiple_of(2) {
+            Ok(self.light.clone())
+        } else {
+            Ok(self.dark.clone())
+        }
+    }
+
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkerboard_cells() {
+        let lens = CheckerboardLens::new(Size::new(4, 4).unwrap(), 2, 1u8, 0u8).unwrap();
+
+        let values = lens.elements().collect::<Vec<_>>();
+
+        assert_eq!(values, vec![1, 1, 0, 0, 1, 1, 0, 0, 0, 0, 1, 1, 0, 0, 1, 1]);
+    }
+
+    #[test]
+    fn test_checkerboard_cell_zero() {
+        assert!(matches!(
+            CheckerboardLens::new(Size::new(4, 4).unwrap(), 0, 1u8, 0u8),
+            Err(CheckerboardCreationError::CellZero)
+        ));
+    }
+}
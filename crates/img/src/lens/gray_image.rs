@@ -0,0 +1,30 @@
+use crate::{
+    component::primitive::{
+        Point,
+        Size,
+    },
+    error::IndexResult,
+    gray_image::GrayImage,
+    lens::Lens,
+};
+
+#[derive(Clone)]
+pub struct GrayImageLens<'a>(&'a GrayImage);
+
+impl<'a> GrayImageLens<'a> {
+    pub fn new(image: &'a GrayImage) -> Self {
+        Self(image)
+    }
+}
+
+impl Lens for GrayImageLens<'_> {
+    type Item = u8;
+
+    fn look(&self, point: Point) -> IndexResult<u8> {
+        self.0.value(point)
+    }
+
+    fn size(&self) -> Size {
+        self.0.size()
+    }
+}
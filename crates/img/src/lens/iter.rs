@@ -83,6 +83,48 @@ where
     }
 }
 
+/// Iterator for going over elements of a single column from top to bottom.
+#[derive(Clone)]
+pub struct ColumnElements<'a, S> {
+    source: &'a S,
+    current: Option<Point>,
+}
+
+impl<'a, S> ColumnElements<'a, S> {
+    pub(crate) fn new(source: &'a S, column: usize) -> IndexResult<Self> {
+        let point = Point::new(column, 0).map_err(|_| IndexError::OutOfBounds)?;
+        Ok(Self { source, current: Some(point) })
+    }
+}
+
+impl<'a, S> Iterator for ColumnElements<'a, S>
+where
+    S: Lens,
+{
+    type Item = S::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.current {
+            Some(current) => {
+                let value = self.source.look(current).expect("bug in lens implementation");
+                if current.y() + 1 == self.source.size().height() {
+                    self.current = None
+                } else {
+                    // SAFETY: y parameter in point::new is always less than height, which is
+                    // less than or equal to DIMENSION_MAX
+                    self.current = Some(
+                        Point::new(current.x(), current.y() + 1)
+                            .expect("unexpected error in Point::new"),
+                    );
+                }
+
+                Some(value)
+            }
+            None => None,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Elements<'a, S> {
     lens: &'a S,
@@ -112,3 +154,36 @@ where
         Some(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        component::primitive::Size,
+        lens::value::ValueLens,
+    };
+
+    use super::*;
+
+    fn gradient() -> impl Lens<Item = u32> {
+        let size = Size::new(4, 5).unwrap();
+        ValueLens::new(0u32, size).remap(|_, point| Ok((point.x() + 10 * point.y()) as u32), size)
+    }
+
+    #[test]
+    fn test_reduce_rows_gradient_profile() {
+        let sums = gradient().reduce_rows(|row| row.sum::<u32>());
+
+        assert_eq!(sums.len(), 5);
+        assert!(sums.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(sums[0], 6);
+        assert_eq!(sums[4], 166);
+    }
+
+    #[test]
+    fn test_reduce_columns_gradient_profile() {
+        let sums = gradient().reduce_columns(|column| column.sum::<u32>());
+
+        assert_eq!(sums, vec![100, 105, 110, 115]);
+        assert_eq!(gradient().reduce_columns(|column| column.count()), vec![5; 4]);
+    }
+}
@@ -5,10 +5,13 @@ use thiserror::Error;
 use crate::{
     component::{
         kernel::Kernel,
+        lens::border::BorderLens,
         primitive::{
+            Margin,
             Point,
             Size,
             SizeCreationError,
+            SizeCreationResult,
         },
     },
     error::{
@@ -45,12 +48,7 @@ where
 {
     pub fn new(source: S, kernel: K) -> Result<Self, CreationError> {
         let margin = kernel.margin();
-
-        let size = source.size().shrink_by_margin(kernel.margin()).map_err(|e| match e {
-            SizeCreationError::WidthZero => CreationError::KernelTooBigX,
-            SizeCreationError::HeightZero => CreationError::KernelTooBigY,
-            _ => unreachable!("unexpected error returned from shrink_by_margin"),
-        })?;
+        let size = working_size(source.size(), margin)?;
 
         let top_left =
             Point::new(margin.left(), margin.top()).expect("unexpected error in Point::new");
@@ -61,6 +59,58 @@ where
     }
 }
 
+/// Get size of the area of a lens of given `size` which a kernel with `margin` can be applied to.
+pub(crate) fn working_size(size: Size, margin: Margin) -> Result<Size, CreationError> {
+    size.shrink_by_margin(margin).map_err(|e| match e {
+        SizeCreationError::WidthZero => CreationError::KernelTooBigX,
+        SizeCreationError::HeightZero => CreationError::KernelTooBigY,
+        _ => unreachable!("unexpected error returned from shrink_by_margin"),
+    })
+}
+
+/// Apply `kernel` to `source` padded by [`Kernel::margin`] with [`Lens::clamp_border`], so the
+/// resulting [`KernelLens`] has the same size as `source`.
+///
+/// Returns [`SizeCreationError`] if padded size exceeds maximum dimension.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     component::kernel::mean::MeanKernel,
+///     lens::{
+///         Lens,
+///         kernel::pad_for_kernel,
+///     },
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let image = Image::empty(Size::new(4, 3)?);
+/// let kernel = MeanKernel::new(Size::from_radius(1)?, ChannelFlags::RGBA)?;
+///
+/// let lens = pad_for_kernel(image.lens(), kernel)?;
+///
+/// assert_eq!(lens.size(), image.size());
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn pad_for_kernel<S, K, T>(
+    source: S,
+    kernel: K,
+) -> SizeCreationResult<KernelLens<BorderLens<S, S::Item>, K, T>>
+where
+    S: Lens,
+    S::Item: Clone,
+    K: Kernel<S::Item, T>,
+{
+    let padded = source.clamp_border(kernel.margin())?;
+
+    // SAFETY: padded source is larger than kernel margin on every side.
+    Ok(KernelLens::new(padded, kernel).expect("unexpected error in KernelLens::new"))
+}
+
 impl<S, K, T> Lens for KernelLens<S, K, T>
 where
     S: Lens,
@@ -69,7 +119,7 @@ where
     type Item = T;
 
     fn look(&self, point: Point) -> IndexResult<Self::Item> {
-        if !self.working_area.contains(&point) {
+        if !self.working_area.size().contains(&point) {
             return Err(IndexError::OutOfBounds);
         }
         let offset: Offset = self.working_area.top_left().into();
@@ -82,3 +132,45 @@ where
         self.working_area.size()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        component::kernel::mean::MeanKernel,
+        image::Image,
+        lens::FromLens,
+        pixel::{
+            ChannelFlags,
+            Pixel,
+        },
+    };
+
+    #[test]
+    fn test_pad_for_kernel_preserves_size() {
+        let kernel = MeanKernel::new(Size::from_radius(1).unwrap(), ChannelFlags::RGBA).unwrap();
+        for size in [Size::new(1, 1).unwrap(), Size::new(2, 5).unwrap(), Size::new(7, 4).unwrap()] {
+            let image = Image::solid(size, Pixel::new([10, 20, 30, 255]));
+
+            let blurred = Image::from_lens(pad_for_kernel(image.lens(), kernel.clone()).unwrap());
+
+            assert_eq!(blurred.size(), size);
+            // Clamped border keeps flat image flat up to the edges.
+            assert_eq!(blurred.pixels(), image.pixels());
+        }
+    }
+
+    #[test]
+    fn test_look_bounds_are_relative_to_output() {
+        let kernel = MeanKernel::new(Size::from_radius(1).unwrap(), ChannelFlags::RGBA).unwrap();
+        let image = Image::solid(Size::new(5, 4).unwrap(), Pixel::new([10, 20, 30, 255]));
+        let lens = KernelLens::new(image.lens(), kernel).unwrap();
+        assert_eq!(lens.size(), Size::new(3, 2).unwrap());
+
+        // Working area starts at (1, 1) of source, but output points start at (0, 0).
+        assert!(lens.look(Point::zero()).is_ok());
+        assert!(lens.look(Point::new(2, 1).unwrap()).is_ok());
+        assert!(matches!(lens.look(Point::new(3, 0).unwrap()), Err(IndexError::OutOfBounds)));
+        assert!(matches!(lens.look(Point::new(0, 2).unwrap()), Err(IndexError::OutOfBounds)));
+    }
+}
@@ -3,7 +3,10 @@ use crate::{
         Point,
         Size,
     },
-    error::IndexResult,
+    error::{
+        IndexError,
+        IndexResult,
+    },
     lens::Lens,
 };
 
@@ -39,3 +42,212 @@ where
         self.source.size()
     }
 }
+
+/// A [`Lens`] that maps values of two lenses at the same [`Point`] with `f`.
+///
+/// Its size is the intersection of sizes of both lenses, anchored at their top left corners.
+///
+/// This `struct` is created by the [`map2`] mathod on [`Lens`]. See its documentation for more.
+///
+/// [`map2`]: Lens::map2
+#[derive(Clone)]
+pub struct Map2Lens<S, O, F> {
+    source: S,
+    other: O,
+    f: F,
+    size: Size,
+}
+
+impl<S, O, F> Map2Lens<S, O, F>
+where
+    S: Lens,
+    O: Lens,
+{
+    pub(super) fn new(source: S, other: O, f: F) -> Self {
+        // SAFETY: taking minimum of each dimension of valid sizes produces a valid size.
+        let size = Size::new(
+            source.size().width().min(other.size().width()),
+            source.size().height().min(other.size().height()),
+        )
+        .expect("unexpected error in Size::new");
+
+        Self { source, other, f, size }
+    }
+}
+
+impl<T, S, O, F> Lens for Map2Lens<S, O, F>
+where
+    S: Lens,
+    O: Lens,
+    F: Fn(S::Item, O::Item) -> T,
+{
+    type Item = T;
+
+    fn look(&self, point: Point) -> IndexResult<Self::Item> {
+        if !self.size.contains(&point) {
+            return Err(IndexError::OutOfBounds);
+        }
+
+        Ok((self.f)(self.source.look(point)?, self.other.look(point)?))
+    }
+
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+/// A [`Lens`] that maps values of `source` with `f`, which also receives their [`Point`].
+///
+/// This `struct` is created by the [`map_with_point`] mathod on [`Lens`]. See its documentation
+/// for more.
+///
+/// [`map_with_point`]: Lens::map_with_point
+#[derive(Clone)]
+pub struct MapWithPointLens<S, F> {
+    source: S,
+    f: F,
+}
+
+impl<S, F> MapWithPointLens<S, F> {
+    pub(super) fn new(source: S, f: F) -> Self {
+        Self { source, f }
+    }
+}
+
+impl<T, S, F> Lens for MapWithPointLens<S, F>
+where
+    S: Lens,
+    F: Fn(Point, S::Item) -> T,
+{
+    type Item = T;
+
+    fn look(&self, point: Point) -> IndexResult<Self::Item> {
+        self.source.look(point).map(|value| (self.f)(point, value))
+    }
+
+    fn size(&self) -> Size {
+        self.source.size()
+    }
+}
+
+/// A [`Lens`] that calls `f` with every value looked up in `source` and its [`Point`], passing
+/// the value on unchanged.
+///
+/// This `struct` is created by the [`inspect`] mathod on [`Lens`]. See its documentation for more.
+///
+/// [`inspect`]: Lens::inspect
+#[derive(Clone)]
+pub struct InspectLens<S, F> {
+    source: S,
+    f: F,
+}
+
+impl<S, F> InspectLens<S, F> {
+    pub(super) fn new(source: S, f: F) -> Self {
+        Self { source, f }
+    }
+}
+
+impl<S, F> Lens for InspectLens<S, F>
+where
+    S: Lens,
+    F: Fn(Point, &S::Item),
+{
+    type Item = S::Item;
+
+    fn look(&self, point: Point) -> IndexResult<Self::Item> {
+        self.source.look(point).inspect(|value| (self.f)(point, value))
+    }
+
+    fn size(&self) -> Size {
+        self.source.size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use crate::{
+        image::Image,
+        lens::{
+            FromLens,
+            value::ValueLens,
+        },
+        pixel::Pixel,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_map_with_point_horizontal_tint() {
+        let size = Size::new(5, 2).unwrap();
+        let gray = Image::from_lens(ValueLens::new(Pixel::new([100, 100, 100, 255]), size));
+
+        let tinted = Image::from_lens(gray.lens().map_with_point(|point, px| {
+            let mut px = *px;
+            px.set_r(px.r() + 25 * point.x() as u8);
+            px
+        }));
+
+        for y in 0..2 {
+            for x in 0..5 {
+                let px = tinted.pixel(Point::new(x, y).unwrap()).unwrap();
+                assert_eq!(*px, Pixel::new([100 + 25 * x as u8, 100, 100, 255]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_map2_matches_split_and_map() {
+        let mut rng = SmallRng::seed_from_u64(1245);
+        let size = Size::new(6, 5).unwrap();
+        let a = Image::random(size, &mut rng);
+        let b = Image::random(size, &mut rng);
+        let average = |a: &Pixel, b: &Pixel| a.lerp(*b, 0.5);
+
+        let fused = Image::from_lens(a.lens().map2(b.lens(), average));
+        let split =
+            Image::from_lens(a.lens().split2(|s| s, |_| b.lens()).map(|(a, b)| average(a, b)));
+
+        assert_eq!(fused.size(), size);
+        assert_eq!(fused.pixels(), split.pixels());
+    }
+
+    #[test]
+    fn test_map2_intersects_sizes() {
+        let wide = ValueLens::new(1u8, Size::new(5, 2).unwrap());
+        let tall = ValueLens::new(2u8, Size::new(3, 4).unwrap());
+
+        let sum = wide.map2(tall, |a, b| a + b);
+
+        assert_eq!(sum.size(), Size::new(3, 2).unwrap());
+        assert_eq!(sum.look(Point::new(2, 1).unwrap()).unwrap(), 3);
+        assert!(sum.look(Point::new(3, 0).unwrap()).is_err());
+        assert!(sum.look(Point::new(0, 2).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_inspect_observes_every_look() {
+        let size = Size::new(4, 3).unwrap();
+        let source = || {
+            ValueLens::new(Pixel::new([1, 2, 3, 4]), size).map_with_point(|point, px| {
+                Pixel::new([point.x() as u8, point.y() as u8, px.b(), 255])
+            })
+        };
+        let seen = RefCell::new(vec![0; size.area()]);
+
+        let inspected = Image::from_lens(source().inspect(|point, px| {
+            assert_eq!((px.r(), px.g()), (point.x() as u8, point.y() as u8));
+            seen.borrow_mut()[point.index(size).unwrap()] += 1;
+        }));
+
+        assert!(seen.borrow().iter().all(|count| *count == 1));
+        assert_eq!(inspected.pixels(), Image::from_lens(source()).pixels());
+    }
+}
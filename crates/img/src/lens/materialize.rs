@@ -34,6 +34,7 @@ impl<T> MaterializeLens<T> {
         Self { size, values }
     }
 
+    /// Evaluate `source` using up to `threads` threads, capped at the number of values.
     #[cfg(feature = "parallel")]
     pub fn new_par<S>(source: S, threads: NonZeroUsize) -> Self
     where
@@ -42,34 +43,32 @@ impl<T> MaterializeLens<T> {
     {
         use std::thread;
 
-        let size = source.size();
-        let chunk_size = (size.area() as f32 / threads.get() as f32).ceil() as usize;
+        use crate::lens::{
+            look_chunk,
+            par_chunks,
+        };
 
+        let size = source.size();
         let mut values = Box::from_iter(from_fn(|| Some(None)).take(size.area()));
 
-        let value_chunks = values.chunks_mut(chunk_size);
-
         thread::scope(|scope| {
-            value_chunks.enumerate().for_each(|(index, chunk)| {
+            par_chunks(&mut values, threads).for_each(|(starting_index, chunk)| {
                 let source = &source;
-                scope.spawn(move || {
-                    let starting_index = index * chunk_size;
-                    chunk.iter_mut().enumerate().for_each(|(index, value)| {
-                        // SAFETY: all starting_index + index will be in bounds since it enumerates
-                        // over the lens that it is indexing.
-                        let point = Point::from_index(starting_index + index, size)
-                            .expect("Point::from_index");
-                        // SAFETY: `Lens::look` is guaranteed to return Ok if point is in bounds,
-                        // and point is guaranted to be in bounds because of the check above.
-                        *value =
-                            Some(source.look(point).expect("unexpected error from Lens::look"));
-                    });
-                });
+                scope.spawn(move || look_chunk(source, starting_index, chunk, Some));
             });
         });
 
         Self { size, values: values.into() }
     }
+
+    /// Create [`MaterializeLens`] from already computed `values` in row-major order.
+    ///
+    /// `values` has to contain exactly `size.area()` elements.
+    pub(crate) fn from_values(values: Vec<T>, size: Size) -> Self {
+        debug_assert_eq!(values.len(), size.area());
+
+        Self { size, values: Arc::from_iter(values.into_iter().map(Some)) }
+    }
 }
 
 impl<T> Clone for MaterializeLens<T> {
@@ -4,20 +4,35 @@ use std::num::NonZeroUsize;
 use crate::{
     component::{
         kernel::Kernel,
+        lens::border::{
+            BorderLens,
+            BorderMode,
+            border_lens,
+        },
         primitive::{
+            Margin,
             Point,
             Size,
+            SizeCreationResult,
         },
     },
     error::IndexResult,
     lens::{
+        cache_rows::CacheRowsLens,
         cloned::ClonedLens,
         iter::{
+            ColumnElements,
             Elements,
+            RowElements,
             Rows,
         },
         kernel::KernelLens,
-        map::MapLens,
+        map::{
+            InspectLens,
+            Map2Lens,
+            MapLens,
+            MapWithPointLens,
+        },
         materialize::MaterializeLens,
         overlay::OverlayLens,
         remap::RemapLens,
@@ -26,10 +41,16 @@ use crate::{
             SplitLens3,
             SplitLens4,
         },
+        step::StepLens,
+        tile::Tiles,
     },
+    pixel::Pixel,
 };
 
+pub mod cache_rows;
+pub mod checkerboard;
 pub mod cloned;
+pub mod gray_image;
 pub mod image;
 pub mod iter;
 pub mod kernel;
@@ -37,9 +58,16 @@ pub mod map;
 pub mod materialize;
 pub mod overlay;
 pub mod remap;
+pub mod run_length;
 pub mod split;
+pub mod step;
+pub mod test_pattern;
+pub mod tile;
 pub mod value;
 
+/// Maximum number of interior points sampled in each dimension by [`Lens::assert_valid`].
+pub const ASSERT_VALID_SAMPLES: usize = 16;
+
 /// A trait for chaining operations for a 2D structures.
 ///
 /// This is main way for applying transformations to [`Image`].
@@ -145,6 +173,67 @@ pub trait Lens {
         Elements::new(self)
     }
 
+    /// Get [`Tiles`] iterator for going through tiles of `tile` size in Morton order.
+    ///
+    /// See [`Tiles`] for more details.
+    fn tiles(&self, tile: Size) -> Tiles<'_, Self>
+    where
+        Self: Sized,
+    {
+        Tiles::new(self, tile)
+    }
+
+    /// Reduce every row to a single value with `f`, which receives an iterator over the row's
+    /// elements from left to right. Returns one value per row, from top to bottom.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::{
+    ///     lens::{
+    ///         Lens,
+    ///         value::ValueLens,
+    ///     },
+    ///     prelude::*,
+    /// };
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let lens = ValueLens::new(2u32, Size::new(3, 2)?);
+    ///
+    /// assert_eq!(lens.reduce_rows(|row| row.sum::<u32>()), vec![6, 6]);
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn reduce_rows<B, F>(&self, f: F) -> Vec<B>
+    where
+        Self: Sized,
+        F: FnMut(RowElements<'_, Self>) -> B,
+    {
+        self.rows().map(f).collect()
+    }
+
+    /// Reduce every column to a single value with `f`, which receives an iterator over the
+    /// column's elements from top to bottom. Returns one value per column, from left to right.
+    ///
+    /// Every element is looked at exactly once, same as with [`Lens::reduce_rows`], but in
+    /// column order. For lenses backed by row-major buffers (e.g. [`Image`](crate::image::Image))
+    /// this strides through memory and is slower than reducing rows.
+    fn reduce_columns<B, F>(&self, f: F) -> Vec<B>
+    where
+        Self: Sized,
+        F: FnMut(ColumnElements<'_, Self>) -> B,
+    {
+        (0..self.size().width())
+            .map(|column| {
+                // SAFETY: column is less than width, which is less than or equal to
+                // DIMENSION_MAX.
+                ColumnElements::new(self, column).expect("unexpected error in ColumnElements::new")
+            })
+            .map(f)
+            .collect()
+    }
+
     /// Get [`MapLens`] which applies `f` to every [`Lens::Item`].
     ///
     /// See [`MapLens`] for more details.
@@ -156,6 +245,141 @@ pub trait Lens {
         MapLens::new(self, f)
     }
 
+    /// Get [`Map2Lens`] which applies `f` to [`Lens::Item`]s of `self` and `other` at the same
+    /// [`Point`].
+    ///
+    /// Unlike [`Lens::split2`] followed by [`Lens::map`], `other` can be any [`Lens`] and no
+    /// intermediate tuple is built. Lenses are expected to be aligned, size of the result is the
+    /// intersection of their sizes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::{
+    ///     lens::Lens,
+    ///     prelude::*,
+    /// };
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let a = Image::solid(Size::new(2, 2)?, Pixel::new([10, 20, 30, 255]));
+    /// let b = Image::solid(Size::new(2, 2)?, Pixel::new([5, 5, 5, 255]));
+    ///
+    /// let red = a.lens().map2(b.lens(), |a, b| a.r() - b.r());
+    ///
+    /// assert_eq!(red.look(Point::zero())?, 5);
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn map2<O, T, F>(self, other: O, f: F) -> Map2Lens<Self, O, F>
+    where
+        Self: Sized,
+        O: Lens,
+        F: Fn(Self::Item, O::Item) -> T,
+    {
+        Map2Lens::new(self, other, f)
+    }
+
+    /// Get [`MapLens`] with luminance of every [`Pixel`], rounded to `u8`.
+    ///
+    /// Luminance is computed with [`Pixel::luminance_f32`], alpha is ignored. Useful for feeding
+    /// single channel kernels, e.g. Sobel or thresholds, without splitting channels manually.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::{
+    ///     lens::Lens,
+    ///     prelude::*,
+    /// };
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let image = Image::solid(Size::new(2, 2)?, Pixel::new([255, 0, 0, 255]));
+    ///
+    /// assert_eq!(image.lens().to_luma().look(Point::zero())?, 76);
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn to_luma(self) -> MapLens<Self, fn(Self::Item) -> u8>
+    where
+        Self: Sized,
+        Self::Item: AsRef<Pixel>,
+    {
+        self.map(|px| px.as_ref().luminance())
+    }
+
+    /// Get [`MapWithPointLens`] which applies `f` to every [`Lens::Item`] together with its
+    /// [`Point`]. Unlike [`Lens::remap`], it keeps the size and looks at the same point of the
+    /// source, which makes it a lighter choice for position dependent effects.
+    ///
+    /// See [`MapWithPointLens`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::{
+    ///     lens::{
+    ///         Lens,
+    ///         value::ValueLens,
+    ///     },
+    ///     prelude::*,
+    /// };
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let lens =
+    ///     ValueLens::new(1usize, Size::new(3, 3)?).map_with_point(|point, value| value + point.x());
+    ///
+    /// assert_eq!(lens.look(Point::new(2, 0)?)?, 3);
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn map_with_point<T, F>(self, f: F) -> MapWithPointLens<Self, F>
+    where
+        Self: Sized,
+        F: Fn(Point, Self::Item) -> T,
+    {
+        MapWithPointLens::new(self, f)
+    }
+
+    /// Get [`InspectLens`] which calls `f` with every looked up [`Lens::Item`] and its [`Point`]
+    /// without changing it, analogous to [`Iterator::inspect`]. Useful for finding where in a
+    /// pipeline unexpected values appear.
+    ///
+    /// See [`InspectLens`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::{
+    ///     lens::{
+    ///         Lens,
+    ///         value::ValueLens,
+    ///     },
+    ///     prelude::*,
+    /// };
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let lens = ValueLens::new(f32::NAN, Size::new(2, 2)?).inspect(|point, value| {
+    ///     if value.is_nan() {
+    ///         eprintln!("NaN at {point:?}");
+    ///     }
+    /// });
+    ///
+    /// assert!(lens.look(Point::zero())?.is_nan());
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn inspect<F>(self, f: F) -> InspectLens<Self, F>
+    where
+        Self: Sized,
+        F: Fn(Point, &Self::Item),
+    {
+        InspectLens::new(self, f)
+    }
+
     /// Get [`RemapLens`] which resizes [`Lens`] and remaps each [`Lens::Item`] using `f`.
     ///
     /// See [`RemapLens`] for more details.
@@ -189,6 +413,43 @@ pub trait Lens {
         KernelLens::new(self, kernel)
     }
 
+    /// Get [`BorderLens`] which extends [`Lens`] by `margin`, repeating the nearest edge
+    /// [`Lens::Item`] in the border. Mostly useful for padding before [`Lens::kernel`], so that
+    /// the result keeps size of `self`, see [`pad_for_kernel`](kernel::pad_for_kernel).
+    ///
+    /// See [`border_lens`] with [`BorderMode::Clamp`] for more details.
+    fn clamp_border(self, margin: Margin) -> SizeCreationResult<BorderLens<Self, Self::Item>>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        border_lens(self, margin, BorderMode::Clamp)
+    }
+
+    /// Get [`StepLens`] which samples every `step.width()`-th column and `step.height()`-th row,
+    /// a fast nearest neighbor downsample.
+    ///
+    /// See [`StepLens`] for more details.
+    fn step_by(self, step: Size) -> StepLens<Self>
+    where
+        Self: Sized,
+    {
+        StepLens::new(self, step)
+    }
+
+    /// Get [`CacheRowsLens`] which memoizes up to `rows` recently looked up rows, so
+    /// overlapping row-first lookups, like those of [`Lens::kernel`], don't evaluate preceding
+    /// [`Lens`] again.
+    ///
+    /// See [`CacheRowsLens`] for more details.
+    fn cache_rows(self, rows: usize) -> CacheRowsLens<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        CacheRowsLens::new(self, rows)
+    }
+
     /// Get [`MaterializeLens`] which evaluates [`Lens::look`] for every [`Lens::Item`], saves
     /// results and provides those values using [`Lens`] interface.
     ///
@@ -297,6 +558,184 @@ pub trait Lens {
     {
         OverlayLens::new(self, overlay, overlay_start)
     }
+
+    /// Get [`Lens`] as a boxed trait object, erasing its type.
+    ///
+    /// Useful for storing differently built lenses together or for building pipelines
+    /// dynamically, at the cost of dynamic dispatch on every [`Lens::look`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::{
+    ///     lens::{
+    ///         Lens,
+    ///         value::ValueLens,
+    ///     },
+    ///     prelude::*,
+    /// };
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let size = Size::new(2, 2)?;
+    /// let lenses: Vec<Box<dyn Lens<Item = u8>>> = vec![
+    ///     ValueLens::new(1u8, size).boxed(),
+    ///     ValueLens::new(1u8, size).map(|value| value + 1).boxed(),
+    /// ];
+    ///
+    /// assert_eq!(lenses[1].look(Point::zero())?, 2);
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn boxed<'a>(self) -> Box<dyn Lens<Item = Self::Item> + 'a>
+    where
+        Self: Sized + 'a,
+    {
+        Box::new(self)
+    }
+
+    /// Check that [`Lens::look`] agrees with [`Lens::size`], panicking otherwise.
+    ///
+    /// Every point on the border of the [`Lens`] and just outside of it is looked up, together with
+    /// a grid of up to [`ASSERT_VALID_SAMPLES`] points in each dimension of the interior. Looking
+    /// up a point has to succeed exactly when it is contained in [`Lens::size`]. Meant for
+    /// testing custom implementations, as a mismatch otherwise causes panics deep within
+    /// collectors.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the first point for which [`Lens::look`] and [`Lens::size`] disagree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::{
+    ///     lens::Lens,
+    ///     prelude::*,
+    /// };
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let image = Image::empty(Size::new(10, 20)?);
+    ///
+    /// image.lens().map(|px| px.r()).assert_valid();
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn assert_valid(&self) {
+        let size = self.size();
+        let (width, height) = (size.width(), size.height());
+
+        let border = (0..=width)
+            .flat_map(|x| [(x, 0), (x, height - 1), (x, height)])
+            .chain((0..=height).flat_map(|y| [(0, y), (width - 1, y), (width, y)]));
+        let step = |length: usize| (length / ASSERT_VALID_SAMPLES).max(1);
+        let interior = (0..height)
+            .step_by(step(height))
+            .flat_map(|y| (0..width).step_by(step(width)).map(move |x| (x, y)));
+
+        // Points beyond maximum dimension can't be created, so they are skipped.
+        for point in border.chain(interior).filter_map(|(x, y)| Point::new(x, y).ok()) {
+            let contained = size.contains(&point);
+            let found = self.look(point).is_ok();
+            assert_eq!(
+                found,
+                contained,
+                "look at {point:?} returned {}, but size {size:?} {} it",
+                if found { "a value" } else { "an error" },
+                if contained { "contains" } else { "does not contain" },
+            );
+        }
+    }
+}
+
+impl<L> Lens for &L
+where
+    L: Lens + ?Sized,
+{
+    type Item = L::Item;
+
+    fn look(&self, point: Point) -> IndexResult<Self::Item> {
+        (**self).look(point)
+    }
+
+    fn size(&self) -> Size {
+        (**self).size()
+    }
+}
+
+impl<L> Lens for Box<L>
+where
+    L: Lens + ?Sized,
+{
+    type Item = L::Item;
+
+    fn look(&self, point: Point) -> IndexResult<Self::Item> {
+        (**self).look(point)
+    }
+
+    fn size(&self) -> Size {
+        (**self).size()
+    }
+}
+
+/// A trait for mutable 2D structures, enabling generic in-place algorithms.
+///
+/// Unlike [`Lens`], which is lazy and read-only, [`LensMut`] gives mutable access to items
+/// stored in the underlying structure.
+pub trait LensMut {
+    /// Type of individual items within underlying 2D structure.
+    type Item;
+
+    /// Look at mutable value for given [`Point`].
+    ///
+    /// Returns `&mut Self::Item` if point is within bounds, [`OutOfBoundsError`] otherwise.
+    ///
+    /// [`OutOfBoundsError`]: crate::error::OutOfBoundsError
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::{
+    ///     lens::{
+    ///         Lens,
+    ///         LensMut,
+    ///     },
+    ///     prelude::*,
+    /// };
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let mut image = Image::empty(Size::new(10, 20)?);
+    /// let point = Point::new(1, 2)?;
+    ///
+    /// *image.look_mut(point)? = Pixel::new([1, 2, 3, 4]);
+    ///
+    /// assert_eq!(image.lens().look(point)?, &Pixel::new([1, 2, 3, 4]));
+    /// assert!(image.look_mut(Point::new(10, 0)?).is_err());
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn look_mut(&mut self, point: Point) -> IndexResult<&mut Self::Item>;
+
+    /// Get [`Size`] of underlying structure. This should be aligned with the behaviour of
+    /// [`LensMut::look_mut()`].
+    fn size(&self) -> Size;
+
+    /// Call `f` for every item with its [`Point`], going row-first.
+    fn for_each_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(Point, &mut Self::Item),
+    {
+        let size = self.size();
+        for y in 0..size.height() {
+            for x in 0..size.width() {
+                // SAFETY: x and y are within size, so point is valid and in bounds.
+                let point = Point::new(x, y).expect("unexpected error in Point::new");
+                f(point, self.look_mut(point).expect("unexpected error in LensMut::look_mut"));
+            }
+        }
+    }
 }
 
 /// Trait for collecting [`Lens`].
@@ -314,3 +753,132 @@ pub trait FromLensPar<T>: Sized {
         S: Lens<Item = T> + Send + Sync,
         S::Item: Send;
 }
+
+/// Split `values` into equal contiguous chunks, one per job, each with index of its first value.
+///
+/// Number of jobs is capped at the number of values, so that every job gets work.
+#[cfg(feature = "parallel")]
+pub(crate) fn par_chunks<T>(
+    values: &mut [T],
+    jobs: NonZeroUsize,
+) -> impl Iterator<Item = (usize, &mut [T])> {
+    let chunk_size = values.len().div_ceil(jobs.get().min(values.len()).max(1)).max(1);
+
+    values.chunks_mut(chunk_size).enumerate().map(move |(index, chunk)| (index * chunk_size, chunk))
+}
+
+/// Fill `chunk` of values of `lens` in row-major order, starting at `starting_index`, converted
+/// with `f`.
+#[cfg(feature = "parallel")]
+pub(crate) fn look_chunk<S, T>(
+    lens: &S,
+    starting_index: usize,
+    chunk: &mut [T],
+    f: impl Fn(S::Item) -> T,
+) where
+    S: Lens,
+{
+    let size = lens.size();
+    chunk.iter_mut().enumerate().for_each(|(index, value)| {
+        // SAFETY: all starting_index + index will be in bounds since chunks cover values of the
+        // lens size.
+        let point = Point::from_index(starting_index + index, size)
+            .expect("unexpected error calculating index");
+        // SAFETY: Lens::look is guaranted to return Ok if point is in bounds, and point is
+        // guaranted to be in bounds because of the check above.
+        *value = f(lens.look(point).expect("unexpected error in Lens::look"));
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        Rng,
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use super::*;
+    use crate::{
+        error::IndexError,
+        image::Image,
+        operation::blur::mean_blur_lens,
+        pixel::ChannelFlags,
+    };
+
+    #[test]
+    fn test_boxed_lenses_in_vec() {
+        let size = Size::new(3, 2).unwrap();
+        let image = Image::solid(size, Pixel::new([10, 20, 30, 255]));
+
+        let lenses: Vec<Box<dyn Lens<Item = Pixel>>> = vec![
+            image.lens().cloned().boxed(),
+            image.lens().map(|px| Pixel::new([px.b(), px.g(), px.r(), px.a()])).boxed(),
+        ];
+
+        assert_eq!(lenses[0].look(Point::zero()).unwrap(), Pixel::new([10, 20, 30, 255]));
+        assert_eq!(lenses[1].look(Point::zero()).unwrap(), Pixel::new([30, 20, 10, 255]));
+        for lens in lenses {
+            assert_eq!(lens.size(), size);
+            // Boxed lens still supports combinators and collecting.
+            let collected = Image::from_lens(lens.map(|px| px));
+            assert_eq!(collected.size(), size);
+        }
+    }
+
+    #[test]
+    fn test_to_luma_matches_luminance() {
+        let mut rng = SmallRng::seed_from_u64(1243);
+        let image = Image::random(Size::new(17, 9).unwrap(), &mut rng);
+
+        let luma = image.lens().to_luma();
+
+        assert_eq!(luma.size(), image.size());
+        for _ in 0..50 {
+            let point = Point::new(rng.random_range(0..17), rng.random_range(0..9)).unwrap();
+            let expected = (image.pixel(point).unwrap().luminance_f32() * 255f32).round() as u8;
+            assert_eq!(luma.look(point).unwrap(), expected);
+        }
+        assert_eq!(
+            Image::solid(Size::new(1, 1).unwrap(), Pixel::new([255, 255, 255, 0]))
+                .lens()
+                .to_luma()
+                .look(Point::zero())
+                .unwrap(),
+            255
+        );
+    }
+
+    /// Lens which reports a bigger size than it can look up.
+    struct OverreportingLens(Size);
+
+    impl Lens for OverreportingLens {
+        type Item = u8;
+
+        fn look(&self, point: Point) -> IndexResult<u8> {
+            match point.x() < self.0.width() - 1 && point.y() < self.0.height() {
+                true => Ok(0),
+                false => Err(IndexError::OutOfBounds),
+            }
+        }
+
+        fn size(&self) -> Size {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_assert_valid_accepts_correct_lenses() {
+        let image = Image::empty(Size::new(40, 3).unwrap());
+
+        image.lens().assert_valid();
+        mean_blur_lens(image.lens(), 1, ChannelFlags::RGBA).unwrap().assert_valid();
+        Image::empty(Size::new(1, 1).unwrap()).lens().assert_valid();
+    }
+
+    #[test]
+    #[should_panic(expected = "returned an error, but size")]
+    fn test_assert_valid_catches_overreported_size() {
+        OverreportingLens(Size::new(5, 4).unwrap()).assert_valid();
+    }
+}
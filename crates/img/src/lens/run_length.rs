@@ -0,0 +1,179 @@
+use std::ops::Range;
+
+use crate::{
+    component::primitive::{
+        Point,
+        Size,
+    },
+    error::{
+        IndexError,
+        IndexResult,
+    },
+    lens::{
+        FromLens,
+        Lens,
+    },
+};
+
+/// Value of a set pixel in a [`RunLengthMask`].
+pub const MASK_SET: u8 = 255;
+
+/// Binary mask stored as runs of set pixels for every row.
+///
+/// Any non zero value is treated as set when building the mask, and looking at a set pixel always
+/// returns [`MASK_SET`], while unset pixels return 0. Memory used is proportional to the number of
+/// runs instead of the area, which makes it well suited for large, sparse masks.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     lens::{
+///         FromLens,
+///         Lens,
+///         run_length::RunLengthMask,
+///         value::ValueLens,
+///     },
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let mask = RunLengthMask::from_lens(ValueLens::new(255u8, Size::new(100, 100)?));
+///
+/// assert_eq!(mask.runs(0)?, &[0..100]);
+/// assert_eq!(mask.look(Point::new(50, 50)?)?, 255);
+///
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunLengthMask {
+    size: Size,
+    rows: Box<[Box<[Range<usize>]>]>,
+}
+
+impl RunLengthMask {
+    /// Get sorted, non overlapping runs of set pixels in given `row`, as ranges of x coordinates.
+    ///
+    /// Returns [`IndexError::OutOfBounds`] if `row` is not less than height of the mask.
+    pub fn runs(&self, row: usize) -> IndexResult<&[Range<usize>]> {
+        self.rows.get(row).map(|runs| &runs[..]).ok_or(IndexError::OutOfBounds)
+    }
+
+    /// Get total number of runs in the mask.
+    pub fn run_count(&self) -> usize {
+        self.rows.iter().map(|runs| runs.len()).sum()
+    }
+
+    /// Get number of set pixels in the mask.
+    pub fn set_count(&self) -> usize {
+        self.rows.iter().flat_map(|runs| runs.iter()).map(|run| run.len()).sum()
+    }
+}
+
+impl Lens for RunLengthMask {
+    type Item = u8;
+
+    fn look(&self, point: Point) -> IndexResult<Self::Item> {
+        if !self.size.contains(&point) {
+            return Err(IndexError::OutOfBounds);
+        }
+
+        let runs = &self.rows[point.y()];
+        let next = runs.partition_point(|run| run.end <= point.x());
+
+        match runs.get(next) {
+            Some(run) if run.contains(&point.x()) => Ok(MASK_SET),
+            _ => Ok(0),
+        }
+    }
+
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+impl FromLens<u8> for RunLengthMask {
+    /// Collect [`Lens`] into a [`RunLengthMask`], treating non zero values as set.
+    fn from_lens<S>(source: S) -> Self
+    where
+        S: Lens<Item = u8>,
+    {
+        let size = source.size();
+        let rows = source
+            .rows()
+            .map(|row| {
+                let mut runs = Vec::new();
+                let mut start = None;
+
+                for (x, value) in row.enumerate() {
+                    match (start, value != 0) {
+                        (None, true) => start = Some(x),
+                        (Some(run_start), false) => {
+                            runs.push(run_start..x);
+                            start = None;
+                        }
+                        _ => {}
+                    }
+                }
+
+                if let Some(run_start) = start {
+                    runs.push(run_start..size.width());
+                }
+
+                runs.into_boxed_slice()
+            })
+            .collect();
+
+        Self { size, rows }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        Rng,
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use super::*;
+    use crate::lens::materialize::MaterializeLens;
+
+    fn random_mask(size: Size, rng: &mut SmallRng) -> MaterializeLens<u8> {
+        let values = (0..size.area())
+            .map(|_| if rng.random_bool(0.2) { MASK_SET } else { 0 })
+            .collect::<Vec<_>>();
+
+        MaterializeLens::from_values(values, size)
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut rng = SmallRng::seed_from_u64(1208);
+        let size = Size::new(37, 23).unwrap();
+        let mask = random_mask(size, &mut rng);
+
+        let rle = RunLengthMask::from_lens(mask.clone());
+
+        assert_eq!(rle.size(), size);
+        assert_eq!(rle.elements().collect::<Vec<_>>(), mask.elements().collect::<Vec<_>>());
+        assert_eq!(rle.set_count(), mask.elements().filter(|value| *value != 0).count());
+        assert_eq!(RunLengthMask::from_lens(rle.clone()), rle);
+    }
+
+    #[test]
+    fn test_runs() {
+        let size = Size::new(6, 2).unwrap();
+        let values = vec![0, 255, 255, 0, 1, 255, 0, 0, 0, 0, 0, 0];
+        let rle = RunLengthMask::from_lens(MaterializeLens::from_values(values, size));
+
+        assert_eq!(rle.runs(0).unwrap(), &[1..3, 4..6]);
+        assert!(rle.runs(1).unwrap().is_empty());
+        assert_eq!(rle.runs(2), Err(IndexError::OutOfBounds));
+        assert_eq!(rle.run_count(), 2);
+        assert_eq!(rle.look(Point::new(4, 0).unwrap()), Ok(MASK_SET));
+        assert_eq!(rle.look(Point::new(3, 0).unwrap()), Ok(0));
+        assert_eq!(rle.look(Point::new(6, 0).unwrap()), Err(IndexError::OutOfBounds));
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{
+    component::primitive::{
+        Point,
+        Size,
+    },
+    error::{
+        IndexError,
+        IndexResult,
+    },
+    lens::Lens,
+};
+
+/// A [`Lens`] sampling every `step.width()`-th column and `step.height()`-th row of `source`,
+/// starting with the top left [`Lens::Item`].
+///
+/// This is a nearest neighbor downsample, size is `source` size divided by `step`, rounded up,
+/// so the last partial block is still sampled.
+#[derive(Clone)]
+pub struct StepLens<S> {
+    source: S,
+    step: Size,
+    size: Size,
+}
+
+impl<S> StepLens<S>
+where
+    S: Lens,
+{
+    /// Create a [`StepLens`].
+    pub fn new(source: S, step: Size) -> Self {
+        let source_size = source.size();
+        // SAFETY: dividing a valid size by a non zero step, rounding up, gives a valid size.
+        let size = Size::new(
+            source_size.width().div_ceil(step.width()),
+            source_size.height().div_ceil(step.height()),
+        )
+        .expect("unexpected error in Size::new");
+
+        Self { source, step, size }
+    }
+}
+
+impl<S> Lens for StepLens<S>
+where
+    S: Lens,
+{
+    type Item = S::Item;
+
+    fn look(&self, point: Point) -> IndexResult<Self::Item> {
+        if !self.size.contains(&point) {
+            return Err(IndexError::OutOfBounds);
+        }
+
+        // SAFETY: point is within size, so scaled point is within source size.
+        let point = Point::new(point.x() * self.step.width(), point.y() * self.step.height())
+            .expect("unexpected error in Point::new");
+
+        self.source.look(point)
+    }
+
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lens::value::ValueLens;
+
+    /// Lens with `x + 10 * y` at every point.
+    fn indexed(size: Size) -> impl Lens<Item = usize> {
+        ValueLens::new(0usize, size).remap(|_, point| Ok(point.x() + 10 * point.y()), size)
+    }
+
+    #[test]
+    fn test_step_by_samples_top_left_of_blocks() {
+        let lens = indexed(Size::new(4, 4).unwrap()).step_by(Size::new(2, 2).unwrap());
+
+        assert_eq!(lens.size(), Size::new(2, 2).unwrap());
+        assert_eq!(lens.elements().collect::<Vec<_>>(), [0, 2, 20, 22]);
+    }
+
+    #[test]
+    fn test_step_by_rounds_size_up() {
+        let lens = indexed(Size::new(5, 3).unwrap()).step_by(Size::new(2, 3).unwrap());
+
+        assert_eq!(lens.size(), Size::new(3, 1).unwrap());
+        assert_eq!(lens.elements().collect::<Vec<_>>(), [0, 2, 4]);
+        assert_eq!(lens.look(Point::new(0, 1).unwrap()), Err(IndexError::OutOfBounds));
+        lens.assert_valid();
+    }
+}
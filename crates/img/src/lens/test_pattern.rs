@@ -0,0 +1,172 @@
+use crate::{
+    component::primitive::{
+        Point,
+        Size,
+    },
+    error::{
+        IndexError,
+        IndexResult,
+    },
+    lens::{
+        Lens,
+        checkerboard::CheckerboardLens,
+    },
+    pixel::Pixel,
+};
+
+const WHITE: Pixel = Pixel::new([255, 255, 255, 255]);
+const BLACK: Pixel = Pixel::new([0, 0, 0, 255]);
+
+/// Colors of [`TestPattern::ColorBars`] from left to right, 100% bars in the usual order of
+/// decreasing luminance.
+pub const COLOR_BARS: [Pixel; 8] = [
+    WHITE,
+    Pixel::new([255, 255, 0, 255]),
+    Pixel::new([0, 255, 255, 255]),
+    Pixel::new([0, 255, 0, 255]),
+    Pixel::new([255, 0, 255, 255]),
+    Pixel::new([255, 0, 0, 255]),
+    Pixel::new([0, 0, 255, 255]),
+    BLACK,
+];
+
+/// Number of checkerboard cells and rings of circles along the shorter side of an image.
+const DIVISIONS: usize = 8;
+
+/// Standard, fully opaque image generated by [`TestPatternLens`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestPattern {
+    /// Horizontal gray ramp from black on the left to white on the right.
+    Gradient,
+    /// Black and white checkerboard with 8 cells along the shorter side, white in the top left
+    /// corner.
+    Checkerboard,
+    /// 8 vertical bars of equal width, colored with [`COLOR_BARS`]. Sharp transitions between
+    /// saturated colors reveal chroma subsampling and color bleeding.
+    ColorBars,
+    /// Concentric black and white rings around the center, 8 rings along half of the shorter
+    /// side, white in the middle. Reveals aliasing of resampling filters.
+    Circles,
+}
+
+/// [`Lens`] generating a [`TestPattern`] of given size.
+///
+/// Every [`Pixel`] depends only on its [`Point`], size and pattern, so results are deterministic,
+/// which makes patterns suitable as benchmark inputs.
+pub struct TestPatternLens {
+    size: Size,
+    pattern: TestPattern,
+    checkerboard: CheckerboardLens<Pixel>,
+}
+
+impl TestPatternLens {
+    pub fn new(size: Size, pattern: TestPattern) -> Self {
+        let cell = (size.width().min(size.height()) / DIVISIONS).max(1);
+        // SAFETY: cell is at least 1.
+        let checkerboard = CheckerboardLens::new(size, cell, WHITE, BLACK)
+            .expect("unexpected error in CheckerboardLens::new");
+
+        Self { size, pattern, checkerboard }
+    }
+}
+
+impl Lens for TestPatternLens {
+    type Item = Pixel;
+
+    fn look(&self, point: Point) -> IndexResult<Self::Item> {
+        if !self.size.contains(&point) {
+            return Err(IndexError::OutOfBounds);
+        }
+
+        let (width, height) = (self.size.width(), self.size.height());
+        let px = match self.pattern {
+            TestPattern::Gradient => {
+                let value = (point.x() * 255 / (width - 1).max(1)) as u8;
+                Pixel::new([value, value, value, 255])
+            }
+            TestPattern::Checkerboard => self.checkerboard.look(point)?,
+            TestPattern::ColorBars => COLOR_BARS[point.x() * COLOR_BARS.len() / width],
+            TestPattern::Circles => {
+                let ring = (width.min(height) / (2 * DIVISIONS)).max(1) as f32;
+                let dx = point.x() as f32 + 0.5 - width as f32 / 2f32;
+                let dy = point.y() as f32 + 0.5 - height as f32 / 2f32;
+                match ((dx * dx + dy * dy).sqrt() / ring) as usize % 2 {
+                    0 => WHITE,
+                    _ => BLACK,
+                }
+            }
+        };
+
+        Ok(px)
+    }
+
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn look(lens: &TestPatternLens, x: usize, y: usize) -> Pixel {
+        lens.look(Point::new(x, y).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_color_bars_segments() {
+        let lens = TestPatternLens::new(Size::new(80, 6).unwrap(), TestPattern::ColorBars);
+
+        for (index, color) in COLOR_BARS.iter().enumerate() {
+            for x in index * 10..(index + 1) * 10 {
+                assert_eq!(look(&lens, x, 0), *color, "x = {x}");
+                assert_eq!(look(&lens, x, 5), *color, "x = {x}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_gradient_and_checkerboard() {
+        let gradient = TestPatternLens::new(Size::new(5, 2).unwrap(), TestPattern::Gradient);
+        let checkerboard =
+            TestPatternLens::new(Size::new(16, 32).unwrap(), TestPattern::Checkerboard);
+
+        assert_eq!(look(&gradient, 0, 1), BLACK);
+        assert_eq!(look(&gradient, 2, 0), Pixel::new([127, 127, 127, 255]));
+        assert_eq!(look(&gradient, 4, 1), WHITE);
+        assert_eq!(look(&checkerboard, 1, 1), WHITE);
+        assert_eq!(look(&checkerboard, 2, 1), BLACK);
+        assert_eq!(look(&checkerboard, 2, 2), WHITE);
+    }
+
+    #[test]
+    fn test_circles_symmetric() {
+        let lens = TestPatternLens::new(Size::new(32, 32).unwrap(), TestPattern::Circles);
+
+        assert_eq!(look(&lens, 16, 16), WHITE);
+        assert_eq!(look(&lens, 16, 18), BLACK);
+        for (x, y) in [(3, 7), (10, 1), (0, 0)] {
+            assert_eq!(look(&lens, x, y), look(&lens, 31 - x, y));
+            assert_eq!(look(&lens, x, y), look(&lens, y, x));
+        }
+    }
+
+    #[test]
+    fn test_sizes_respected() {
+        let patterns = [
+            TestPattern::Gradient,
+            TestPattern::Checkerboard,
+            TestPattern::ColorBars,
+            TestPattern::Circles,
+        ];
+        for pattern in patterns {
+            for size in [Size::new(1, 1).unwrap(), Size::new(3, 17).unwrap()] {
+                let lens = TestPatternLens::new(size, pattern);
+
+                assert_eq!(lens.size(), size);
+                assert_eq!(lens.elements().count(), size.area());
+                lens.assert_valid();
+            }
+        }
+    }
+}
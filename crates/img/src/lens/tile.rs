@@ -0,0 +1,184 @@
+use std::vec;
+
+use itertools::Itertools;
+
+use crate::{
+    component::primitive::{
+        Area,
+        Point,
+        Size,
+    },
+    error::{
+        IndexError,
+        IndexResult,
+    },
+    lens::Lens,
+};
+
+/// Iterator over tiles of a [`Lens`], yielding [`Area`] of each tile with [`TileLens`] looking
+/// at it.
+///
+/// Tiles are visited in Morton (Z-order) order of the tile grid: the grid is split into 2x2
+/// blocks of tiles visited top left, top right, bottom left, bottom right, recursively. This
+/// keeps consecutive tiles close to each other in both dimensions. Tiles on the right and bottom
+/// edges are clipped to the source size, so they can be smaller than requested.
+#[derive(Clone)]
+pub struct Tiles<'a, S> {
+    source: &'a S,
+    tile: Size,
+    order: vec::IntoIter<(usize, usize)>,
+}
+
+impl<'a, S> Tiles<'a, S>
+where
+    S: Lens,
+{
+    pub fn new(source: &'a S, tile: Size) -> Self {
+        let size = source.size();
+        let columns = size.width().div_ceil(tile.width());
+        let rows = size.height().div_ceil(tile.height());
+        let order = (0..rows)
+            .cartesian_product(0..columns)
+            .map(|(row, column)| (column, row))
+            .sorted_unstable_by_key(|&(column, row)| morton_encode(column, row))
+            .collect_vec();
+
+        Self { source, tile, order: order.into_iter() }
+    }
+}
+
+impl<'a, S> Iterator for Tiles<'a, S>
+where
+    S: Lens,
+{
+    type Item = (Area, TileLens<'a, S>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (column, row) = self.order.next()?;
+
+        let size = self.source.size();
+        let x = column * self.tile.width();
+        let y = row * self.tile.height();
+        // SAFETY: x and y are within source size, so tile is at least 1x1 and at most
+        // source size.
+        let tile_size = Size::new(
+            self.tile.width().min(size.width() - x),
+            self.tile.height().min(size.height() - y),
+        )
+        .expect("unexpected error in Size::new");
+        let top_left = Point::new(x, y).expect("unexpected error in Point::new");
+        let area = Area::new(tile_size, top_left);
+
+        Some((area, TileLens::new(self.source, area)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.order.size_hint()
+    }
+}
+
+/// A [`Lens`] looking at an [`Area`] of the source.
+#[derive(Clone)]
+pub struct TileLens<'a, S> {
+    source: &'a S,
+    area: Area,
+}
+
+impl<'a, S> TileLens<'a, S> {
+    pub fn new(source: &'a S, area: Area) -> Self {
+        Self { source, area }
+    }
+}
+
+impl<S> Lens for TileLens<'_, S>
+where
+    S: Lens,
+{
+    type Item = S::Item;
+
+    fn look(&self, point: Point) -> IndexResult<Self::Item> {
+        if !self.area.size().contains(&point) {
+            return Err(IndexError::OutOfBounds);
+        }
+
+        let top_left = self.area.top_left();
+        let point = Point::new(point.x() + top_left.x(), point.y() + top_left.y())
+            .map_err(|_| IndexError::OutOfBounds)?;
+
+        self.source.look(point)
+    }
+
+    fn size(&self) -> Size {
+        self.area.size()
+    }
+}
+
+/// Interleave bits of column (even bits) and row (odd bits) into Morton code.
+fn morton_encode(column: usize, row: usize) -> u128 {
+    let spread = |value: usize| {
+        (0..usize::BITS).fold(0u128, |code, bit| code | (((value >> bit) & 1) as u128) << (2 * bit))
+    };
+
+    spread(column) | spread(row) << 1
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        image::Image,
+        lens::value::ValueLens,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_morton_encode() {
+        let points = [(0, 0), (1, 0), (0, 1), (1, 1), (2, 0), (3, 0), (2, 1), (3, 1)];
+        let encoded: Vec<_> = points.map(|(column, row)| morton_encode(column, row)).to_vec();
+        assert_eq!(encoded, (0..8).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_tiles_visit_every_pixel_once() {
+        let size = Size::new(10, 7).unwrap();
+        let lens = ValueLens::new(0u8, size).remap(|_, point| Ok(point), size);
+        let mut visited = vec![0; size.area()];
+        let mut covered = 0;
+
+        for (area, tile) in lens.tiles(Size::new(4, 3).unwrap()) {
+            assert_eq!(area.size(), tile.size());
+            covered += area.size().area();
+            for point in tile.elements() {
+                assert!(area.contains(&point));
+                visited[point.index(size).unwrap()] += 1;
+            }
+        }
+
+        assert_eq!(covered, size.area());
+        assert!(visited.iter().all(|&count| count == 1));
+    }
+
+    #[test]
+    fn test_tiles_morton_order() {
+        let image = Image::empty(Size::new(4, 4).unwrap());
+        let top_lefts: Vec<_> = image
+            .lens()
+            .tiles(Size::new(2, 2).unwrap())
+            .map(|(area, _)| (area.top_left().x(), area.top_left().y()))
+            .collect();
+
+        assert_eq!(top_lefts, vec![(0, 0), (2, 0), (0, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn test_tiles_morton_order_skips_missing_tiles() {
+        let image = Image::empty(Size::new(8, 2).unwrap());
+        let lens = image.lens();
+        let tiles = lens.tiles(Size::new(2, 1).unwrap());
+
+        assert_eq!(tiles.size_hint(), (8, Some(8)));
+        let top_lefts: Vec<_> =
+            tiles.map(|(area, _)| (area.top_left().x(), area.top_left().y())).collect();
+        assert_eq!(top_lefts, vec![(0, 0), (2, 0), (0, 1), (2, 1), (4, 0), (6, 0), (4, 1), (6, 1)]);
+    }
+}
@@ -1,6 +1,11 @@
 pub mod component;
+#[cfg(feature = "parallel")]
+pub mod context;
+pub mod draw;
 pub mod error;
+pub mod gray_image;
 pub mod image;
+pub mod image_view;
 pub mod io;
 pub mod lens;
 pub mod operation;
@@ -0,0 +1,111 @@
+use crate::{
+    image::{
+        Image,
+        ResultError,
+    },
+    pixel::Pixel,
+};
+
+/// Compose a red/cyan anaglyph from a stereo pair, to be viewed with red filter over the left eye
+/// and cyan over the right one.
+///
+/// Red channel is taken from `left` and green and blue from `right`. Alpha is the higher of both
+/// images, so that a pixel is visible if either eye sees it. Full color anaglyphs keep hues, but
+/// saturated colors may cause retinal rivalry, see [`anaglyph_gray`] for a variant without it.
+///
+/// Returns [`SizeMismatch`] if sizes of `left` and `right` differ.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     operation::blend::anaglyph,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let left = Image::solid(Size::new(2, 2)?, Pixel::new([200, 10, 20, 255]));
+/// let right = Image::solid(Size::new(2, 2)?, Pixel::new([30, 120, 140, 255]));
+///
+/// let composed = anaglyph(&left, &right)?;
+///
+/// assert_eq!(*composed.pixel(Point::zero())?, Pixel::new([200, 120, 140, 255]));
+///
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`SizeMismatch`]: crate::image::CreationError::SizeMismatch
+pub fn anaglyph(left: &Image, right: &Image) -> ResultError<Image> {
+    Image::zip_map(left, right, |left, right| {
+        Pixel::new([left.r(), right.g(), right.b(), left.a().max(right.a())])
+    })
+}
+
+/// Compose a gray red/cyan anaglyph from a stereo pair, see [`anaglyph`].
+///
+/// Both images are converted to luminance first, so the red channel holds luminance of `left` and
+/// green and blue of `right`. Colors are lost, but both eyes see the same brightness.
+///
+/// Returns [`SizeMismatch`] if sizes of `left` and `right` differ.
+///
+/// [`SizeMismatch`]: crate::image::CreationError::SizeMismatch
+pub fn anaglyph_gray(left: &Image, right: &Image) -> ResultError<Image> {
+    Image::zip_map(left, right, |left, right| {
+        let right_luma = right.luminance();
+        Pixel::new([left.luminance(), right_luma, right_luma, left.a().max(right.a())])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use super::*;
+    use crate::{
+        component::primitive::Size,
+        image::CreationError,
+    };
+
+    fn stereo_pair() -> (Image, Image) {
+        let mut rng = SmallRng::seed_from_u64(1239);
+        let size = Size::new(9, 7).unwrap();
+
+        (Image::random(size, &mut rng), Image::random(size, &mut rng))
+    }
+
+    #[test]
+    fn test_channels_from_each_eye() {
+        let (left, right) = stereo_pair();
+
+        let composed = anaglyph(&left, &right).unwrap();
+
+        for ((px, left), right) in composed.pixels().iter().zip(left.pixels()).zip(right.pixels()) {
+            assert_eq!(px.r(), left.r());
+            assert_eq!(px.g(), right.g());
+            assert_eq!(px.b(), right.b());
+        }
+    }
+
+    #[test]
+    fn test_gray_uses_luminance() {
+        let left = Image::solid(Size::new(2, 2).unwrap(), Pixel::new([0, 255, 0, 255]));
+        let right = Image::solid(Size::new(2, 2).unwrap(), Pixel::new([255, 0, 0, 255]));
+
+        let composed = anaglyph_gray(&left, &right).unwrap();
+
+        assert!(composed.pixels().iter().all(|px| *px == Pixel::new([150, 76, 76, 255])));
+    }
+
+    #[test]
+    fn test_size_mismatch() {
+        let left = Image::empty(Size::new(2, 2).unwrap());
+        let right = Image::empty(Size::new(3, 2).unwrap());
+
+        assert_eq!(anaglyph(&left, &right).unwrap_err(), CreationError::SizeMismatch);
+        assert_eq!(anaglyph_gray(&left, &right).unwrap_err(), CreationError::SizeMismatch);
+    }
+}
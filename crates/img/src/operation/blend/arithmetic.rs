@@ -0,0 +1,169 @@
+#[cfg(feature = "parallel")]
+use std::num::NonZeroUsize;
+
+use crate::{
+    image::{
+        CreationError,
+        Image,
+        ResultError,
+    },
+    lens::{
+        FromLens,
+        Lens,
+    },
+    pixel::Pixel,
+};
+
+/// Per channel operation applied by [`image_arithmetic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArithmeticOp {
+    /// `a + b`, saturating at 255.
+    Add,
+    /// `a - b`, saturating at 0.
+    Subtract,
+    /// `|a - b|`.
+    Difference,
+    /// `a * b / 255`, rounded to nearest.
+    Multiply,
+}
+
+impl ArithmeticOp {
+    fn apply(self, a: u8, b: u8) -> u8 {
+        match self {
+            ArithmeticOp::Add => a.saturating_add(b),
+            ArithmeticOp::Subtract => a.saturating_sub(b),
+            ArithmeticOp::Difference => a.abs_diff(b),
+            ArithmeticOp::Multiply => ((a as u32 * b as u32 + 127) / 255) as u8,
+        }
+    }
+}
+
+/// Create a [`Lens`] combining two lenses of the same size with simple pixel math, applying `op`
+/// to every channel, alpha included.
+///
+/// Unlike blend modes, no compositing is done, which makes it suitable for image analysis, e.g.
+/// finding changes between frames.
+///
+/// Returns [`SizeMismatch`] if sizes of `a` and `b` differ.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     operation::blend::{
+///         ArithmeticOp,
+///         image_arithmetic,
+///     },
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let a = Image::new(Size::new(1, 1)?, Box::new([Pixel::new([200, 100, 50, 255])]))?;
+/// let b = Image::new(Size::new(1, 1)?, Box::new([Pixel::new([100, 150, 50, 255])]))?;
+///
+/// let sum = image_arithmetic(&a, &b, ArithmeticOp::Add)?;
+///
+/// assert_eq!(*sum.pixel(Point::zero())?, Pixel::new([255, 250, 100, 255]));
+///
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`SizeMismatch`]: CreationError::SizeMismatch
+pub fn image_arithmetic_lens<S1, S2>(
+    a: S1,
+    b: S2,
+    op: ArithmeticOp,
+) -> ResultError<impl Lens<Item = Pixel>>
+where
+    S1: Lens,
+    S1::Item: AsRef<Pixel>,
+    S2: Lens,
+    S2::Item: AsRef<Pixel>,
+{
+    if a.size() != b.size() {
+        return Err(CreationError::SizeMismatch);
+    }
+
+    Ok(a.map2(b, move |px_a, px_b| {
+        let (px_a, px_b) = (px_a.as_ref(), px_b.as_ref());
+        Pixel::new(std::array::from_fn(|channel| {
+            op.apply(px_a.buffer()[channel], px_b.buffer()[channel])
+        }))
+    }))
+}
+
+/// Combine two images of the same size with simple pixel math, see [`image_arithmetic_lens`].
+pub fn image_arithmetic(a: &Image, b: &Image, op: ArithmeticOp) -> ResultError<Image> {
+    let lens = image_arithmetic_lens(a.lens(), b.lens(), op)?;
+    Ok(Image::from_lens(lens))
+}
+
+/// Combine two images of the same size with simple pixel math in parallel, see
+/// [`image_arithmetic_lens`].
+#[cfg(feature = "parallel")]
+pub fn image_arithmetic_par(
+    a: &Image,
+    b: &Image,
+    threads: NonZeroUsize,
+    op: ArithmeticOp,
+) -> ResultError<Image> {
+    use crate::lens::FromLensPar;
+
+    let lens = image_arithmetic_lens(a.lens(), b.lens(), op)?;
+    Ok(Image::from_lens_par(lens, threads))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use crate::component::primitive::Size;
+
+    use super::*;
+
+    #[test]
+    fn test_difference_with_itself_is_zero() {
+        let image = Image::random(Size::new(8, 6).unwrap(), &mut SmallRng::seed_from_u64(0));
+
+        let difference = image_arithmetic(&image, &image, ArithmeticOp::Difference).unwrap();
+
+        assert!(difference.pixels().iter().all(|px| *px == Pixel::zero()));
+    }
+
+    #[test]
+    fn test_ops_saturate() {
+        assert_eq!(ArithmeticOp::Add.apply(200, 100), 255);
+        assert_eq!(ArithmeticOp::Subtract.apply(100, 200), 0);
+        assert_eq!(ArithmeticOp::Difference.apply(100, 200), 100);
+        assert_eq!(ArithmeticOp::Multiply.apply(255, 128), 128);
+        assert_eq!(ArithmeticOp::Multiply.apply(128, 128), 64);
+    }
+
+    #[test]
+    fn test_size_mismatch() {
+        let a = Image::empty(Size::new(2, 2).unwrap());
+        let b = Image::empty(Size::new(2, 3).unwrap());
+
+        assert_eq!(
+            image_arithmetic(&a, &b, ArithmeticOp::Add).unwrap_err(),
+            CreationError::SizeMismatch
+        );
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_image_arithmetic_par() {
+        let a = Image::random(Size::new(13, 7).unwrap(), &mut SmallRng::seed_from_u64(1));
+        let b = Image::random(Size::new(13, 7).unwrap(), &mut SmallRng::seed_from_u64(2));
+        let threads = NonZeroUsize::new(3).unwrap();
+
+        let expected = image_arithmetic(&a, &b, ArithmeticOp::Multiply).unwrap();
+        let actual = image_arithmetic_par(&a, &b, threads, ArithmeticOp::Multiply).unwrap();
+
+        assert_eq!(actual.pixels(), expected.pixels());
+    }
+}
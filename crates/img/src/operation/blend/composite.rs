@@ -0,0 +1,275 @@
+use thiserror::Error;
+
+use crate::{
+    component::primitive::{
+        Offset,
+        Point,
+    },
+    image::Image,
+    lens::{
+        FromLens,
+        Lens,
+        checkerboard::{
+            CheckerboardCreationResult,
+            CheckerboardLens,
+        },
+    },
+    pixel::Pixel,
+};
+
+/// Error returned by composite_premultiplied function
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CompositeCreationError {
+    #[error("composite position is out of base image bounds")]
+    PositionOutOfBounds,
+}
+
+pub type CompositeCreationResult<T> = std::result::Result<T, CompositeCreationError>;
+
+/// Composite `top` over `base` with its top left corner placed at `at`, treating both images as
+/// premultiplied by alpha and producing a premultiplied result.
+///
+/// Every channel is calculated with `top + base * (1 - top_alpha)`, so layers can be stacked
+/// by repeated calls without converting to straight alpha in between. Parts of `top` reaching
+/// past `base` are clipped, size of the result is always the size of `base`.
+///
+/// Returns [`CompositeCreationError::PositionOutOfBounds`] if `at` is outside of `base`.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     operation::blend::{
+///         composite_premultiplied,
+///         premultiply,
+///         unpremultiply,
+///     },
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let base = Image::new(Size::new(1, 1)?, Box::new([Pixel::new([0, 0, 255, 255])]))?;
+/// let top = Image::new(Size::new(1, 1)?, Box::new([Pixel::new([255, 0, 0, 128])]))?;
+///
+/// let result = composite_premultiplied(&premultiply(&base), &premultiply(&top), Point::zero())?;
+///
+/// assert_eq!(*unpremultiply(&result).pixel(Point::zero())?, Pixel::new([128, 0, 127, 255]));
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn composite_premultiplied(
+    base: &Image,
+    top: &Image,
+    at: Point,
+) -> CompositeCreationResult<Image> {
+    if !base.size().contains(&at) {
+        return Err(CompositeCreationError::PositionOutOfBounds);
+    }
+
+    let offset = Offset::from(at);
+    let lens = base.lens().remap(
+        move |s, point| {
+            let dst = *s.look(point)?;
+            let src = match point.translate(-offset).ok().map(|point| top.pixel(point)) {
+                Some(Ok(src)) => *src,
+                _ => return Ok(dst),
+            };
+
+            Ok(over_premultiplied(src, dst))
+        },
+        base.size(),
+    );
+
+    Ok(Image::from_lens(lens))
+}
+
+/// Convert straight alpha image into premultiplied one, multiplying color channels by alpha.
+pub fn premultiply(image: &Image) -> Image {
+    Image::from_lens(image.lens().map(|px| premultiply_px(*px)))
+}
+
+/// Convert premultiplied image into straight alpha one, dividing color channels by alpha.
+/// Fully transparent pixels become transparent black.
+pub fn unpremultiply(image: &Image) -> Image {
+    Image::from_lens(image.lens().map(|px| unpremultiply_px(*px)))
+}
+
+/// Composite `source` over a checkerboard of `cell` sized squares alternating between `light` and
+/// `dark`, so transparent parts of an image are visible in previews.
+///
+/// Checkerboard colors are treated as opaque, so the result is always opaque.
+///
+/// Returns [`CheckerboardCreationError::CellZero`] if `cell` is zero.
+///
+/// [`CheckerboardCreationError::CellZero`]: crate::lens::checkerboard::CheckerboardCreationError::CellZero
+pub fn over_checkerboard_lens<S>(
+    source: S,
+    cell: usize,
+    light: Pixel,
+    dark: Pixel,
+) -> CheckerboardCreationResult<impl Lens<Item = Pixel>>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    let [light, dark] = [light, dark].map(|mut px| {
+        px.set_a(255);
+        px
+    });
+    let size = source.size();
+    let checkerboard = CheckerboardLens::new(size, cell, light, dark)?;
+
+    Ok(source.remap(
+        move |s, point| {
+            let src = premultiply_px(*s.look(point)?.as_ref());
+            let dst = checkerboard.look(point)?;
+
+            Ok(over_premultiplied(src, dst))
+        },
+        size,
+    ))
+}
+
+pub(crate) fn premultiply_px(px: Pixel) -> Pixel {
+    let alpha = px.a() as u32;
+    let [r, g, b, a] = *px.buffer();
+
+    Pixel::new([
+        mul_div_255(r as u32, alpha),
+        mul_div_255(g as u32, alpha),
+        mul_div_255(b as u32, alpha),
+        a,
+    ])
+}
+
+pub(crate) fn unpremultiply_px(px: Pixel) -> Pixel {
+    let alpha = px.a() as u32;
+    if alpha == 0 {
+        return Pixel::zero();
+    }
+
+    let [r, g, b, a] = *px.buffer();
+    let divide = |value: u8| ((value as u32 * 255 + alpha / 2) / alpha).min(255) as u8;
+
+    Pixel::new([divide(r), divide(g), divide(b), a])
+}
+
+fn over_premultiplied(src: Pixel, dst: Pixel) -> Pixel {
+    let inverse_alpha = 255 - src.a() as u32;
+
+    Pixel::new(std::array::from_fn(|i| {
+        (src.buffer()[i] as u32 + mul_div_255(dst.buffer()[i] as u32, inverse_alpha) as u32)
+            .min(255) as u8
+    }))
+}
+
+/// Calculate `value * factor / 255` rounded to nearest.
+fn mul_div_255(value: u32, factor: u32) -> u8 {
+    ((value * factor + 127) / 255) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        component::primitive::Size,
+        lens::value::ValueLens,
+    };
+
+    use super::*;
+
+    /// Straight alpha "over" operator calculated in floating point.
+    fn over_straight(src: [f32; 4], dst: [f32; 4]) -> [f32; 4] {
+        let alpha = src[3] + dst[3] * (1f32 - src[3]);
+        if alpha == 0f32 {
+            return [0f32; 4];
+        }
+
+        let mut result = [0f32; 4];
+        for i in 0..3 {
+            result[i] = (src[i] * src[3] + dst[i] * dst[3] * (1f32 - src[3])) / alpha;
+        }
+        result[3] = alpha;
+
+        result
+    }
+
+    fn normalized(px: Pixel) -> [f32; 4] {
+        px.buffer().map(|value| value as f32 / 255f32)
+    }
+
+    fn solid(size: Size, pixel: Pixel) -> Image {
+        Image::from_lens(ValueLens::new(pixel, size))
+    }
+
+    #[test]
+    fn test_composite_three_layers_matches_straight_reference() {
+        let base_px = Pixel::new([20, 200, 40, 255]);
+        let middle_px = Pixel::new([240, 30, 90, 160]);
+        let top_px = Pixel::new([60, 120, 250, 200]);
+        let base = solid(Size::new(6, 6).unwrap(), base_px);
+        let middle = solid(Size::new(4, 4).unwrap(), middle_px);
+        let top = solid(Size::new(4, 4).unwrap(), top_px);
+
+        let stacked = composite_premultiplied(
+            &composite_premultiplied(
+                &premultiply(&base),
+                &premultiply(&middle),
+                Point::new(0, 0).unwrap(),
+            )
+            .unwrap(),
+            &premultiply(&top),
+            Point::new(2, 2).unwrap(),
+        )
+        .unwrap();
+        let result = unpremultiply(&stacked);
+
+        let expected = |x: usize, y: usize| {
+            let mut acc = normalized(base_px);
+            if x < 4 && y < 4 {
+                acc = over_straight(normalized(middle_px), acc);
+            }
+            if x >= 2 && y >= 2 {
+                acc = over_straight(normalized(top_px), acc);
+            }
+            acc
+        };
+
+        for y in 0..6 {
+            for x in 0..6 {
+                let actual = normalized(*result.pixel(Point::new(x, y).unwrap()).unwrap());
+                for (a, e) in actual.iter().zip(expected(x, y)) {
+                    assert!((a - e).abs() <= 2f32 / 255f32, "({x}, {y}): {actual:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_over_checkerboard() {
+        let light = Pixel::new([200, 200, 200, 255]);
+        let dark = Pixel::new([100, 100, 100, 255]);
+        let mut image = Image::empty(Size::new(4, 4).unwrap());
+        let opaque = Pixel::new([10, 20, 30, 255]);
+        *image.pixel_mut(Point::new(3, 3).unwrap()).unwrap() = opaque;
+
+        let preview =
+            Image::from_lens(over_checkerboard_lens(image.lens(), 2, light, dark).unwrap());
+
+        assert_eq!(*preview.pixel(Point::new(0, 0).unwrap()).unwrap(), light);
+        assert_eq!(*preview.pixel(Point::new(2, 1).unwrap()).unwrap(), dark);
+        assert_eq!(*preview.pixel(Point::new(1, 2).unwrap()).unwrap(), dark);
+        assert_eq!(*preview.pixel(Point::new(2, 2).unwrap()).unwrap(), light);
+        assert_eq!(*preview.pixel(Point::new(3, 3).unwrap()).unwrap(), opaque);
+    }
+
+    #[test]
+    fn test_composite_position_out_of_bounds() {
+        let base = Image::empty(Size::new(4, 4).unwrap());
+
+        assert_eq!(
+            composite_premultiplied(&base, &base, Point::new(4, 0).unwrap()).unwrap_err(),
+            CompositeCreationError::PositionOutOfBounds
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{
+    component::primitive::Point,
+    image::{
+        Image,
+        ResultError,
+    },
+    lens::Lens,
+    operation::{
+        blend::stack::common_size,
+        detection::edge::gradient_magnitude_lens,
+    },
+};
+
+/// Radius of the window in which gradient energy is summed to measure local sharpness.
+const SHARPNESS_RADIUS: usize = 2;
+
+/// Combine a focus bracket of same sized `images` into a single all-in-focus image.
+///
+/// Every pixel is taken from the image which is locally the sharpest, measured as the sum of
+/// squared Sobel gradient magnitudes of luma in a small window around it. Ties are resolved in
+/// favour of the earlier image.
+///
+/// Returns [`Empty`] if `images` is empty and [`SizeMismatch`] if their sizes differ.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     operation::blend::focus_stack,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let flat = Image::solid(Size::new(8, 8)?, Pixel::new([128, 128, 128, 255]));
+/// let mut detailed = flat.clone();
+/// *detailed.pixel_mut(Point::new(4, 4)?)? = Pixel::new([255, 255, 255, 255]);
+///
+/// let stacked = focus_stack(&[flat, detailed.clone()])?;
+///
+/// assert_eq!(stacked.pixel(Point::new(4, 4)?)?, detailed.pixel(Point::new(4, 4)?)?);
+///
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Empty`]: crate::image::CreationError::Empty
+/// [`SizeMismatch`]: crate::image::CreationError::SizeMismatch
+pub fn focus_stack(images: &[Image]) -> ResultError<Image> {
+    let size = common_size(images)?;
+
+    let sharpness = images.iter().map(sharpness).collect::<Vec<_>>();
+    let pixels = (0..size.area())
+        .map(|index| {
+            let sharpest = (1..images.len()).fold(0, |best, image| {
+                if sharpness[image][index] > sharpness[best][index] { image } else { best }
+            });
+            images[sharpest].pixels()[index]
+        })
+        .collect();
+
+    Image::new(size, pixels)
+}
+
+/// Sum of squared gradient magnitudes within [`SHARPNESS_RADIUS`] of every pixel, windows are
+/// cropped to the image.
+fn sharpness(image: &Image) -> Vec<f32> {
+    let size = image.size();
+    let energy = gradient_magnitude_lens(image.lens())
+        .map(|magnitude| magnitude * magnitude)
+        .elements()
+        .collect::<Vec<_>>();
+
+    let window = |center: usize, length: usize| {
+        center.saturating_sub(SHARPNESS_RADIUS)..=(center + SHARPNESS_RADIUS).min(length - 1)
+    };
+    (0..size.area())
+        .map(|index| {
+            // SAFETY: index is within area of the image size.
+            let point = Point::from_index(index, size).expect("unexpected error in from_index");
+            window(point.y(), size.height())
+                .flat_map(|y| window(point.x(), size.width()).map(move |x| (x, y)))
+                .map(|(x, y)| energy[y * size.width() + x])
+                .sum()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use super::*;
+    use crate::{
+        component::primitive::Size,
+        image::CreationError,
+        operation::blur::gaussian_approx,
+        pixel::ChannelFlags,
+    };
+
+    /// Take left half (`x < split`) from `left` and the rest from `right`.
+    fn halves(left: &Image, right: &Image, split: usize) -> Image {
+        let pixels = (0..left.size().area())
+            .map(|index| {
+                let source = if index % left.size().width() < split { left } else { right };
+                source.pixels()[index]
+            })
+            .collect();
+
+        Image::new(left.size(), pixels).unwrap()
+    }
+
+    #[test]
+    fn test_focus_stack_takes_sharp_halves() {
+        let size = Size::new(32, 16).unwrap();
+        let sharp = Image::random(size, &mut SmallRng::seed_from_u64(1254));
+        let blurred = gaussian_approx(&sharp, 2f32, ChannelFlags::RGB).unwrap();
+        let near = halves(&sharp, &blurred, 16);
+        let far = halves(&blurred, &sharp, 16);
+
+        let stacked = focus_stack(&[near, far]).unwrap();
+
+        // Columns near the split see both halves in their windows.
+        let margin = SHARPNESS_RADIUS + 1;
+        for y in 0..size.height() {
+            for x in (0..size.width()).filter(|x| x.abs_diff(16) > margin) {
+                let point = Point::new(x, y).unwrap();
+                assert_eq!(stacked.pixel(point).unwrap(), sharp.pixel(point).unwrap(), "{point:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_focus_stack_invalid_input() {
+        let small = Image::empty(Size::new(2, 2).unwrap());
+        let large = Image::empty(Size::new(3, 2).unwrap());
+
+        assert!(matches!(focus_stack(&[]), Err(CreationError::Empty)));
+        assert!(matches!(focus_stack(&[small, large]), Err(CreationError::SizeMismatch)));
+    }
+}
@@ -0,0 +1,347 @@
+use thiserror::Error;
+
+use crate::{
+    component::primitive::{
+        Point,
+        Size,
+    },
+    error::{
+        IndexError,
+        IndexResult,
+    },
+    image::Image,
+    lens::{
+        FromLens,
+        Lens,
+        materialize::MaterializeLens,
+    },
+    pixel::{
+        Pixel,
+        PixelRgbaf32,
+    },
+};
+
+/// Error returned by laplacian_blend function
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum LaplacianBlendCreationError {
+    #[error("images have different sizes")]
+    SizeMismatch,
+    #[error("mask size does not match images size")]
+    MaskSizeMismatch,
+    #[error("levels must be greater than zero")]
+    LevelsZero,
+}
+
+pub type LaplacianBlendCreationResult<T> = std::result::Result<T, LaplacianBlendCreationError>;
+
+/// Normalized RGBA channels used for intermediate pyramid levels.
+type Channels = [f32; 4];
+
+/// 5-tap binomial filter used for building and expanding pyramid levels.
+const BINOMIAL: [f32; 5] = [1f32 / 16f32, 4f32 / 16f32, 6f32 / 16f32, 4f32 / 16f32, 1f32 / 16f32];
+
+/// Blend two images across a `mask` using Laplacian pyramids.
+///
+/// Where `mask` is 255 the result is taken from `a`, where it is 0 from `b`, values in between
+/// mix both images. Each of `levels` pyramid levels is blended separately, with coarse levels
+/// using a blurred mask, which hides the seam between images. The pyramid stops early once a
+/// level reaches 1x1 size.
+///
+/// Returns blended [`Image`] or [`LaplacianBlendCreationError`] if sizes of `a`, `b` and `mask`
+/// differ or `levels` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     lens::{
+///         Lens,
+///         value::ValueLens,
+///     },
+///     operation::blend::laplacian_blend,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let size = Size::new(4, 4)?;
+/// let a = Image::empty(size);
+/// let b = Image::empty(size);
+/// let mask = ValueLens::new(255u8, size);
+///
+/// let blended = laplacian_blend(&a, &b, &mask, 2)?;
+/// assert_eq!(blended.size(), size);
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn laplacian_blend<M>(
+    a: &Image,
+    b: &Image,
+    mask: &M,
+    levels: usize,
+) -> LaplacianBlendCreationResult<Image>
+where
+    M: Lens<Item = u8>,
+{
+    if a.size() != b.size() {
+        return Err(LaplacianBlendCreationError::SizeMismatch);
+    }
+
+    if mask.size() != a.size() {
+        return Err(LaplacianBlendCreationError::MaskSizeMismatch);
+    }
+
+    if levels == 0 {
+        return Err(LaplacianBlendCreationError::LevelsZero);
+    }
+
+    let laplacian_a = laplacian_pyramid(gaussian_pyramid(a.lens().map(to_channels), levels));
+    let laplacian_b = laplacian_pyramid(gaussian_pyramid(b.lens().map(to_channels), levels));
+    let gaussian_mask = gaussian_pyramid(
+        mask.remap(|s, point| s.look(point).map(|value| [value as f32 / 255f32; 4]), mask.size()),
+        levels,
+    );
+
+    let blended: Vec<_> = laplacian_a
+        .into_iter()
+        .zip(laplacian_b)
+        .zip(gaussian_mask)
+        .map(|((level_a, level_b), level_mask)| {
+            level_a
+                .split3(|s| s, |_| level_b.clone(), |_| level_mask.clone())
+                .map(|(a, b, mask)| {
+                    std::array::from_fn(|i| a[i] * mask[i] + b[i] * (1f32 - mask[i]))
+                })
+                .materialize()
+        })
+        .collect();
+
+    let result = collapse_pyramid(blended);
+
+    Ok(Image::from_lens(result.map(to_pixel)))
+}
+
+fn to_channels(px: &Pixel) -> Channels {
+    [px.r_f32(), px.g_f32(), px.b_f32(), px.a_f32()]
+}
+
+fn to_pixel(channels: Channels) -> Pixel {
+    let mut px = Pixel::zero();
+    px.set_r_f32(channels[0]);
+    px.set_g_f32(channels[1]);
+    px.set_b_f32(channels[2]);
+    px.set_a_f32(channels[3]);
+
+    px
+}
+
+/// Build gaussian pyramid with at most `levels` levels, the first one being `source` itself.
+fn gaussian_pyramid<S>(source: S, levels: usize) -> Vec<MaterializeLens<Channels>>
+where
+    S: Lens<Item = Channels>,
+{
+    let mut pyramid = vec![source.materialize()];
+
+    while pyramid.len() < levels {
+        // SAFETY: pyramid always contains at least one level.
+        let last = pyramid.last().expect("unexpected empty pyramid");
+        if last.size() == Size::new(1, 1).expect("unexpected error in Size::new") {
+            break;
+        }
+
+        pyramid.push(downsample(last.clone()));
+    }
+
+    pyramid
+}
+
+/// Turn gaussian pyramid into laplacian pyramid. Each level but the last one stores the
+/// difference between the gaussian level and expanded next (coarser) level.
+fn laplacian_pyramid(gaussian: Vec<MaterializeLens<Channels>>) -> Vec<MaterializeLens<Channels>> {
+    let mut laplacian: Vec<_> = gaussian
+        .windows(2)
+        .map(|levels| {
+            let fine = levels[0].clone();
+            let expanded = upsample(levels[1].clone(), fine.size());
+
+            fine.split2(|s| s, |_| expanded.clone())
+                .map(|(fine, expanded)| std::array::from_fn(|i| fine[i] - expanded[i]))
+                .materialize()
+        })
+        .collect();
+
+    // SAFETY: gaussian pyramid always contains at least one level.
+    laplacian.push(gaussian.last().expect("unexpected empty pyramid").clone());
+
+    laplacian
+}
+
+/// Reconstruct the finest level of a laplacian pyramid.
+fn collapse_pyramid(laplacian: Vec<MaterializeLens<Channels>>) -> MaterializeLens<Channels> {
+    let mut levels = laplacian.into_iter().rev();
+    // SAFETY: laplacian pyramid always contains at least one level.
+    let coarsest = levels.next().expect("unexpected empty pyramid");
+
+    levels.fold(coarsest, |coarse, fine| {
+        let expanded = upsample(coarse, fine.size());
+
+        fine.split2(|s| s, |_| expanded.clone())
+            .map(|(fine, expanded)| std::array::from_fn(|i| fine[i] + expanded[i]))
+            .materialize()
+    })
+}
+
+/// Blur `source` with binomial filter and drop every other row and column.
+fn downsample<S>(source: S) -> MaterializeLens<Channels>
+where
+    S: Lens<Item = Channels>,
+{
+    let source_size = source.size();
+    // SAFETY: halving a valid size rounding up always produces a valid size.
+    let size = Size::new(source_size.width().div_ceil(2), source_size.height().div_ceil(2))
+        .expect("unexpected error in Size::new");
+
+    source
+        .remap(
+            move |s, point| {
+                if !size.contains(&point) {
+                    return Err(IndexError::OutOfBounds);
+                }
+
+                let mut sum = [0f32; 4];
+                for (dy, weight_y) in BINOMIAL.iter().enumerate() {
+                    let y = clamp_coordinate(
+                        2 * point.y() as isize + dy as isize - 2,
+                        source_size.height(),
+                    );
+                    for (dx, weight_x) in BINOMIAL.iter().enumerate() {
+                        let x = clamp_coordinate(
+                            2 * point.x() as isize + dx as isize - 2,
+                            source_size.width(),
+                        );
+                        let value = look_at(s, x, y)?;
+                        for (acc, channel) in sum.iter_mut().zip(value) {
+                            *acc += weight_x * weight_y * channel;
+                        }
+                    }
+                }
+
+                Ok(sum)
+            },
+            size,
+        )
+        .materialize()
+}
+
+/// Expand `source` to `size` by interpolating missing rows and columns with binomial filter.
+fn upsample<S>(source: S, size: Size) -> MaterializeLens<Channels>
+where
+    S: Lens<Item = Channels>,
+{
+    let source_size = source.size();
+
+    source
+        .remap(
+            move |s, point| {
+                if !size.contains(&point) {
+                    return Err(IndexError::OutOfBounds);
+                }
+
+                let mut sum = [0f32; 4];
+                for (dy, weight_y) in expand_taps(point.y(), source_size.height()) {
+                    for (dx, weight_x) in expand_taps(point.x(), source_size.width()) {
+                        let value = look_at(s, dx, dy)?;
+                        for (acc, channel) in sum.iter_mut().zip(value) {
+                            *acc += weight_x * weight_y * channel;
+                        }
+                    }
+                }
+
+                Ok(sum)
+            },
+            size,
+        )
+        .materialize()
+}
+
+/// Get coarse coordinates and weights contributing to `coordinate` of an expanded level.
+fn expand_taps(coordinate: usize, coarse_length: usize) -> impl Iterator<Item = (usize, f32)> {
+    BINOMIAL.iter().enumerate().filter_map(move |(tap, weight)| {
+        let fine = coordinate as isize + tap as isize - 2;
+        if fine % 2 != 0 {
+            return None;
+        }
+
+        Some((clamp_coordinate(fine / 2, coarse_length), 2f32 * weight))
+    })
+}
+
+fn clamp_coordinate(value: isize, length: usize) -> usize {
+    value.clamp(0, length as isize - 1) as usize
+}
+
+fn look_at<S>(source: &S, x: usize, y: usize) -> IndexResult<Channels>
+where
+    S: Lens<Item = Channels>,
+{
+    let point = Point::new(x, y).map_err(|_| IndexError::OutOfBounds)?;
+    source.look(point)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lens::value::ValueLens;
+
+    use super::*;
+
+    fn solid(size: Size, pixel: Pixel) -> Image {
+        Image::from_lens(ValueLens::new(pixel, size))
+    }
+
+    #[test]
+    fn test_laplacian_blend_smooth_seam() {
+        let size = Size::new(32, 8).unwrap();
+        let red = solid(size, Pixel::new([255, 0, 0, 255]));
+        let blue = solid(size, Pixel::new([0, 0, 255, 255]));
+        let mask = ValueLens::new(0u8, size)
+            .remap(|s, point| s.look(point).map(|_| if point.x() < 16 { 255 } else { 0 }), size);
+
+        let blended = laplacian_blend(&red, &blue, &mask, 4).unwrap();
+
+        let row: Vec<_> =
+            (0..32).map(|x| *blended.pixel(Point::new(x, 4).unwrap()).unwrap()).collect();
+
+        assert!(row[0].r() > 245 && row[0].b() < 10);
+        assert!(row[31].r() < 10 && row[31].b() > 245);
+        for pair in row.windows(2) {
+            assert!(pair[0].r() >= pair[1].r());
+            assert!(pair[0].b() <= pair[1].b());
+        }
+        for px in &row[14..18] {
+            assert!(px.r() > 0 && px.r() < 255);
+            assert!(px.b() > 0 && px.b() < 255);
+            assert_eq!(px.a(), 255);
+        }
+    }
+
+    #[test]
+    fn test_laplacian_blend_invalid_params() {
+        let a = Image::empty(Size::new(4, 4).unwrap());
+        let b = Image::empty(Size::new(4, 5).unwrap());
+        let mask = ValueLens::new(0u8, Size::new(4, 4).unwrap());
+        let bad_mask = ValueLens::new(0u8, Size::new(5, 4).unwrap());
+
+        assert_eq!(
+            laplacian_blend(&a, &b, &mask, 2).unwrap_err(),
+            LaplacianBlendCreationError::SizeMismatch
+        );
+        assert_eq!(
+            laplacian_blend(&a, &a, &bad_mask, 2).unwrap_err(),
+            LaplacianBlendCreationError::MaskSizeMismatch
+        );
+        assert_eq!(
+            laplacian_blend(&a, &a, &mask, 0).unwrap_err(),
+            LaplacianBlendCreationError::LevelsZero
+        );
+    }
+}
@@ -0,0 +1,38 @@
+mod anaglyph;
+mod arithmetic;
+pub(crate) mod composite;
+mod focus_stack;
+mod laplacian;
+mod stack;
+
+pub use anaglyph::{
+    anaglyph,
+    anaglyph_gray,
+};
+pub use arithmetic::{
+    ArithmeticOp,
+    image_arithmetic,
+    image_arithmetic_lens,
+};
+pub use composite::{
+    CompositeCreationError,
+    CompositeCreationResult,
+    composite_premultiplied,
+    over_checkerboard_lens,
+    premultiply,
+    unpremultiply,
+};
+pub use focus_stack::focus_stack;
+pub use laplacian::{
+    LaplacianBlendCreationError,
+    LaplacianBlendCreationResult,
+    laplacian_blend,
+};
+pub use stack::{
+    StackCreationError,
+    StackCreationResult,
+    stack_average,
+};
+
+#[cfg(feature = "parallel")]
+pub use arithmetic::image_arithmetic_par;
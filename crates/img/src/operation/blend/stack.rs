@@ -0,0 +1,164 @@
+use thiserror::Error;
+
+use crate::{
+    component::primitive::Size,
+    image::{
+        CreationError,
+        Image,
+        ResultError,
+    },
+    pixel::{
+        PIXEL_SIZE,
+        Pixel,
+    },
+};
+
+/// Error returned by stack_average function
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum StackCreationError {
+    #[error("invalid images: {0}")]
+    Image(#[from] CreationError),
+    #[error("number of weights does not match number of images")]
+    WeightsLength,
+    #[error("weights must be finite, non-negative and sum to a positive value")]
+    InvalidWeights,
+}
+
+pub type StackCreationResult<T> = std::result::Result<T, StackCreationError>;
+
+/// Average same sized `images` per channel, alpha included, e.g. to reduce noise by stacking
+/// multiple frames of the same scene.
+///
+/// Without `weights` every image contributes equally. Otherwise every image is weighted by its
+/// weight divided by sum of all weights, so weights don't have to be normalized. Sums are
+/// accumulated in `f32` and rounded once.
+///
+/// Returns [`Empty`] if `images` is empty, [`SizeMismatch`] if their sizes differ,
+/// [`StackCreationError::WeightsLength`] if there is not exactly one weight per image and
+/// [`StackCreationError::InvalidWeights`] if any weight is negative or not finite, or all
+/// weights are zero.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     operation::blend::stack_average,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let black = Image::solid(Size::new(2, 2)?, Pixel::new([0, 0, 0, 255]));
+/// let white = Image::solid(Size::new(2, 2)?, Pixel::new([255, 255, 255, 255]));
+///
+/// let stacked = stack_average(&[black, white], Some(&[3.0, 1.0]))?;
+///
+/// assert_eq!(*stacked.pixel(Point::zero())?, Pixel::new([64, 64, 64, 255]));
+///
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Empty`]: CreationError::Empty
+/// [`SizeMismatch`]: CreationError::SizeMismatch
+pub fn stack_average(images: &[Image], weights: Option<&[f32]>) -> StackCreationResult<Image> {
+    let size = common_size(images)?;
+
+    let weights = match weights {
+        Some(weights) if weights.len() != images.len() => {
+            return Err(StackCreationError::WeightsLength);
+        }
+        Some(weights) => weights.to_vec(),
+        None => vec![1f32; images.len()],
+    };
+    let total = weights.iter().sum::<f32>();
+    if weights.iter().any(|weight| !weight.is_finite() || *weight < 0f32) || total <= 0f32 {
+        return Err(StackCreationError::InvalidWeights);
+    }
+
+    let mut sums = vec![[0f32; PIXEL_SIZE]; size.area()];
+    for (image, weight) in images.iter().zip(&weights) {
+        for (sum, px) in sums.iter_mut().zip(image.pixels()) {
+            for (channel, value) in sum.iter_mut().zip(px.buffer()) {
+                *channel += weight * *value as f32;
+            }
+        }
+    }
+
+    let pixels = sums
+        .into_iter()
+        .map(|sum| Pixel::new(sum.map(|value| (value / total).round().clamp(0f32, 255f32) as u8)))
+        .collect();
+
+    // SAFETY: there is one pixel for every pixel of the first image.
+    Ok(Image::new(size, pixels).expect("unexpected error in Image::new"))
+}
+
+/// Get size shared by all `images`.
+///
+/// Returns [`CreationError::Empty`] if `images` is empty and [`CreationError::SizeMismatch`] if
+/// their sizes differ.
+pub(crate) fn common_size(images: &[Image]) -> ResultError<Size> {
+    let size = images.first().ok_or(CreationError::Empty)?.size();
+    if images.iter().any(|image| image.size() != size) {
+        return Err(CreationError::SizeMismatch);
+    }
+
+    Ok(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_stack_identical() {
+        let image = Image::random(Size::new(7, 5).unwrap(), &mut SmallRng::seed_from_u64(1251));
+        let images = vec![image.clone(); 4];
+
+        assert_eq!(stack_average(&images, None).unwrap().pixels(), image.pixels());
+        assert_eq!(
+            stack_average(&images, Some(&[0.1, 2.0, 0.0, 7.5])).unwrap().pixels(),
+            image.pixels()
+        );
+    }
+
+    #[test]
+    fn test_stack_black_and_white() {
+        let size = Size::new(3, 3).unwrap();
+        let black = Image::solid(size, Pixel::new([0, 0, 0, 255]));
+        let white = Image::solid(size, Pixel::new([255, 255, 255, 255]));
+
+        let stacked = stack_average(&[black.clone(), white.clone()], None).unwrap();
+        let weighted = stack_average(&[black, white], Some(&[0.0, 1.0])).unwrap();
+
+        assert!(stacked.pixels().iter().all(|px| *px == Pixel::new([128, 128, 128, 255])));
+        assert!(weighted.pixels().iter().all(|px| *px == Pixel::new([255, 255, 255, 255])));
+    }
+
+    #[test]
+    fn test_stack_invalid() {
+        let a = Image::empty(Size::new(2, 2).unwrap());
+        let b = Image::empty(Size::new(3, 2).unwrap());
+
+        assert_eq!(stack_average(&[], None).unwrap_err(), CreationError::Empty.into());
+        assert_eq!(
+            stack_average(&[a.clone(), b], None).unwrap_err(),
+            CreationError::SizeMismatch.into()
+        );
+        assert_eq!(
+            stack_average(&[a.clone(), a.clone()], Some(&[1.0])).unwrap_err(),
+            StackCreationError::WeightsLength
+        );
+        for weights in [[0.0, 0.0], [-1.0, 2.0], [f32::NAN, 1.0]] {
+            assert_eq!(
+                stack_average(&[a.clone(), a.clone()], Some(&weights)).unwrap_err(),
+                StackCreationError::InvalidWeights
+            );
+        }
+    }
+}
@@ -0,0 +1,180 @@
+#[cfg(feature = "parallel")]
+use std::num::NonZeroUsize;
+
+use thiserror::Error;
+
+use crate::{
+    component::{
+        kernel::{
+            self,
+            bilateral::BilateralKernel,
+        },
+        primitive::{
+            Margin,
+            MarginCreationError,
+        },
+    },
+    image::Image,
+    lens::{
+        self,
+        FromLens,
+        Lens,
+    },
+    pixel::Pixel,
+};
+
+/// Error returned by bilateral functions
+#[derive(Debug, Error)]
+pub enum BilateralCreationError {
+    #[error("failed to create bilateral kernel: {0}")]
+    Kernel(#[from] kernel::bilateral::CreationError),
+    #[error("failed to create kernel lens: {0}")]
+    KernelLens(#[from] lens::kernel::CreationError),
+    #[error("failed to create margin: {0}")]
+    Margin(#[from] MarginCreationError),
+}
+
+pub type BilateralCreationResult<T> = std::result::Result<T, BilateralCreationError>;
+
+/// Smooth `source` with a bilateral filter of given `radius`, preserving edges.
+///
+/// Neighbours are weighted by a gaussian of their distance with `spatial_sigma` and by a gaussian
+/// of their color difference from the center with `range_sigma`, so pixels across an edge barely
+/// contribute. Alpha is preserved.
+///
+/// Like other kernel based lenses, result is smaller than `source` by `radius` on every side.
+///
+/// Returns [`BilateralCreationError::Kernel`] if any sigma is not positive and finite and
+/// [`BilateralCreationError::KernelLens`] if `source` is too small for `radius`.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     lens::{
+///         Lens,
+///         value::ValueLens,
+///     },
+///     operation::blur::bilateral_lens,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let px = Pixel::new([10, 20, 30, 40]);
+/// let filtered = bilateral_lens(ValueLens::new(px, Size::new(7, 7)?), 2, 2f32, 25f32)?;
+///
+/// assert_eq!(filtered.size(), Size::new(3, 3)?);
+/// assert_eq!(filtered.look(Point::zero())?, px);
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn bilateral_lens<S>(
+    source: S,
+    radius: usize,
+    spatial_sigma: f32,
+    range_sigma: f32,
+) -> BilateralCreationResult<impl Lens<Item = Pixel>>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    let kernel = BilateralKernel::new(Margin::unified(radius)?, spatial_sigma, range_sigma)?;
+    let lens = source.kernel(kernel)?;
+
+    Ok(lens)
+}
+
+pub fn bilateral(
+    image: &Image,
+    radius: usize,
+    spatial_sigma: f32,
+    range_sigma: f32,
+) -> BilateralCreationResult<Image> {
+    let lens = bilateral_lens(image.lens(), radius, spatial_sigma, range_sigma)?;
+    Ok(Image::from_lens(lens))
+}
+
+#[cfg(feature = "parallel")]
+pub fn bilateral_par(
+    image: &Image,
+    threads: NonZeroUsize,
+    radius: usize,
+    spatial_sigma: f32,
+    range_sigma: f32,
+) -> BilateralCreationResult<Image> {
+    use crate::lens::FromLensPar;
+
+    let lens = bilateral_lens(image.lens(), radius, spatial_sigma, range_sigma)?;
+    Ok(Image::from_lens_par(lens, threads))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        Rng,
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use super::*;
+    use crate::{
+        component::primitive::{
+            Point,
+            Size,
+        },
+        operation::blur::gaussian_blur,
+        pixel::{
+            AlphaMode,
+            ChannelFlags,
+        },
+    };
+
+    /// Dark and bright halves split at `x = 8`, with small noise.
+    fn noisy_step() -> Image {
+        let mut rng = SmallRng::seed_from_u64(1252);
+        let size = Size::new(16, 8).unwrap();
+        let pixels = (0..size.area())
+            .map(|index| {
+                let base: u8 = if index % 16 < 8 { 30 } else { 200 };
+                let value = base + rng.random_range(0..8);
+                Pixel::new([value, value, value, 128])
+            })
+            .collect();
+
+        Image::new(size, pixels).unwrap()
+    }
+
+    #[test]
+    fn test_bilateral_keeps_edge_sharper_than_gaussian() {
+        let image = noisy_step();
+
+        let bilateral = bilateral(&image, 2, 2f32, 20f32).unwrap();
+        let gaussian =
+            gaussian_blur(&image, 2, 2f32, ChannelFlags::RGB, AlphaMode::Straight).unwrap();
+
+        // Output is offset by radius, so the edge lies between x = 5 and x = 6.
+        let step = |image: &Image| {
+            let left = image.pixel(Point::new(5, 2).unwrap()).unwrap().r();
+            let right = image.pixel(Point::new(6, 2).unwrap()).unwrap().r();
+            right - left
+        };
+        assert!(step(&bilateral) > 150, "bilateral step {}", step(&bilateral));
+        assert!(step(&bilateral) > step(&gaussian));
+        assert!(bilateral.pixels().iter().all(|px| px.a() == 128));
+    }
+
+    #[test]
+    fn test_bilateral_invalid_parameters() {
+        let image = noisy_step();
+
+        assert!(matches!(
+            bilateral(&image, 2, -1f32, 20f32),
+            Err(BilateralCreationError::Kernel(_))
+        ));
+        assert!(matches!(
+            bilateral(&image, 4, 2f32, 20f32),
+            Err(BilateralCreationError::KernelLens(_))
+        ));
+    }
+}
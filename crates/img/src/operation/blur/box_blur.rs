@@ -0,0 +1,289 @@
+#[cfg(feature = "parallel")]
+use std::num::NonZeroUsize;
+
+use crate::{
+    component::primitive::{
+        Point,
+        Size,
+    },
+    error::{
+        IndexError,
+        IndexResult,
+    },
+    image::Image,
+    lens::{
+        FromLens,
+        Lens,
+        materialize::MaterializeLens,
+    },
+    pixel::{
+        ChannelFlags,
+        Pixel,
+        PixelRgbaf32,
+    },
+};
+
+/// Normalized RGBA channels used for intermediate passes.
+type Channels = [f32; 4];
+
+/// Create a lens averaging each pixel with its neighbors in a square of `2 * radius + 1` side,
+/// using running sums, so the cost per pixel does not depend on `radius`. Pixels outside of
+/// `source` are clamped to the nearest edge, so the size of `source` is preserved.
+///
+/// Channels not present in `flags` are copied from `source`.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     lens::{
+///         Lens,
+///         value::ValueLens,
+///     },
+///     operation::blur::box_blur_lens,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let size = Size::new(5, 5)?;
+/// let source = ValueLens::new(Pixel::new([10, 20, 30, 255]), size);
+/// let blurred = box_blur_lens(source, 2, ChannelFlags::RGBA);
+///
+/// assert_eq!(blurred.size(), size);
+/// assert_eq!(blurred.look(Point::new(0, 0)?)?, Pixel::new([10, 20, 30, 255]));
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn box_blur_lens<S>(source: S, radius: usize, flags: ChannelFlags) -> impl Lens<Item = Pixel>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    box_passes_lens(source, [radius], flags)
+}
+
+pub fn box_blur(image: &Image, radius: usize, flags: ChannelFlags) -> Image {
+    Image::from_lens(box_blur_lens(image.lens(), radius, flags))
+}
+
+#[cfg(feature = "parallel")]
+pub fn box_blur_par(
+    image: &Image,
+    threads: NonZeroUsize,
+    radius: usize,
+    flags: ChannelFlags,
+) -> Image {
+    use crate::lens::FromLensPar;
+
+    Image::from_lens_par(box_passes_lens_par(image.lens(), [radius], flags, threads), threads)
+}
+
+/// Apply box blur of each of `radii` in turn, rounding to [`Pixel`] only once at the end.
+pub(crate) fn box_passes_lens<S>(
+    source: S,
+    radii: impl IntoIterator<Item = usize>,
+    flags: ChannelFlags,
+) -> impl Lens<Item = Pixel>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    box_passes_with(source, radii, flags, |lines| lines.materialize())
+}
+
+/// Apply box blur passes like [`box_passes_lens`], evaluating lines of every pass using up to
+/// `threads` threads.
+#[cfg(feature = "parallel")]
+pub(crate) fn box_passes_lens_par<S>(
+    source: S,
+    radii: impl IntoIterator<Item = usize>,
+    flags: ChannelFlags,
+    threads: NonZeroUsize,
+) -> impl Lens<Item = Pixel>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    box_passes_with(source, radii, flags, move |lines| lines.materialize_par(threads))
+}
+
+/// Apply box blur passes, evaluating every [`PassLines`] with `materialize`.
+fn box_passes_with<S, M>(
+    source: S,
+    radii: impl IntoIterator<Item = usize>,
+    flags: ChannelFlags,
+    materialize: M,
+) -> impl Lens<Item = Pixel>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+    M: Fn(PassLines) -> MaterializeLens<Vec<Channels>>,
+{
+    let pass = |source: MaterializeLens<Channels>, radius: usize, horizontal: bool| {
+        let size = source.size();
+        from_lines(materialize(PassLines { source, radius, horizontal }), size, horizontal)
+    };
+
+    let original = source.map(|px| *px.as_ref()).materialize();
+    let blurred = radii
+        .into_iter()
+        .fold(original.clone().map(to_channels).materialize(), |current, radius| {
+            pass(pass(current, radius, true), radius, false)
+        });
+
+    original.split2(|s| s, |_| blurred.clone()).map(move |(mut px, channels)| {
+        px.set_with_flags_f32(channels[0], channels[1], channels[2], channels[3], flags);
+        px
+    })
+}
+
+/// [`Lens`] over lines of a single horizontal or vertical running sum pass over `source`.
+///
+/// Every item is a whole blurred line, as running sums have to go along it in order, but lines
+/// are independent of each other, so they can be evaluated in parallel.
+struct PassLines {
+    source: MaterializeLens<Channels>,
+    radius: usize,
+    horizontal: bool,
+}
+
+impl PassLines {
+    fn length(&self) -> usize {
+        let size = self.source.size();
+        if self.horizontal { size.width() } else { size.height() }
+    }
+
+    fn line(&self, line: usize) -> Vec<Channels> {
+        let (length, radius) = (self.length() as isize, self.radius as isize);
+        let value = |position: isize| {
+            let position = position.clamp(0, length - 1) as usize;
+            let point = if self.horizontal {
+                Point::new(position, line)
+            } else {
+                Point::new(line, position)
+            };
+            // SAFETY: position is clamped to the line and line is within source.
+            let point = point.expect("unexpected error in Point::new");
+            self.source.look(point).expect("unexpected error in Lens::look")
+        };
+        let window = (2 * self.radius + 1) as f32;
+
+        let mut sum = [0f32; 4];
+        for position in -radius..=radius {
+            sum.iter_mut().zip(value(position)).for_each(|(acc, channel)| *acc += channel);
+        }
+
+        (0..length)
+            .map(|position| {
+                let result = sum.map(|acc| acc / window);
+
+                let (removed, added) = (value(position - radius), value(position + radius + 1));
+                for (i, acc) in sum.iter_mut().enumerate() {
+                    *acc += added[i] - removed[i];
+                }
+
+                result
+            })
+            .collect()
+    }
+}
+
+impl Lens for PassLines {
+    type Item = Vec<Channels>;
+
+    fn look(&self, point: Point) -> IndexResult<Self::Item> {
+        if !self.size().contains(&point) {
+            return Err(IndexError::OutOfBounds);
+        }
+
+        Ok(self.line(point.x()))
+    }
+
+    fn size(&self) -> Size {
+        let size = self.source.size();
+        let lines = if self.horizontal { size.height() } else { size.width() };
+
+        // SAFETY: number of lines is a dimension of a valid size.
+        Size::new(lines, 1).expect("unexpected error in Size::new")
+    }
+}
+
+/// Assemble blurred `lines` of a pass back into a lens of `size`.
+fn from_lines(
+    lines: MaterializeLens<Vec<Channels>>,
+    size: Size,
+    horizontal: bool,
+) -> MaterializeLens<Channels> {
+    let mut result = vec![[0f32; 4]; size.area()];
+    for (line, values) in lines.elements().enumerate() {
+        for (position, value) in values.into_iter().enumerate() {
+            let index = if horizontal {
+                line * size.width() + position
+            } else {
+                position * size.width() + line
+            };
+            result[index] = value;
+        }
+    }
+
+    MaterializeLens::from_values(result, size)
+}
+
+fn to_channels(px: Pixel) -> Channels {
+    [px.r_f32(), px.g_f32(), px.b_f32(), px.a_f32()]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lens::value::ValueLens;
+
+    use super::*;
+
+    #[test]
+    fn test_box_blur_matches_naive_mean() {
+        let size = Size::new(6, 4).unwrap();
+        let image = Image::from_lens(ValueLens::new(Pixel::zero(), size).remap(
+            |_, point| Ok(Pixel::new([(point.x() * 40) as u8, (point.y() * 60) as u8, 0, 255])),
+            size,
+        ));
+
+        let blurred = box_blur(&image, 1, ChannelFlags::RGB);
+
+        let at = |x: isize, y: isize| {
+            let x = x.clamp(0, 5) as usize;
+            let y = y.clamp(0, 3) as usize;
+            *image.pixel(Point::new(x, y).unwrap()).unwrap()
+        };
+        for y in 0..4isize {
+            for x in 0..6isize {
+                let (mut r, mut g) = (0u32, 0u32);
+                for dy in -1..=1 {
+                    for dx in -1..=1 {
+                        r += at(x + dx, y + dy).r() as u32;
+                        g += at(x + dx, y + dy).g() as u32;
+                    }
+                }
+                let px = blurred.pixel(Point::new(x as usize, y as usize).unwrap()).unwrap();
+                assert!((px.r() as f32 - r as f32 / 9f32).abs() <= 1f32);
+                assert!((px.g() as f32 - g as f32 / 9f32).abs() <= 1f32);
+                assert_eq!(px.a(), 255);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn test_box_blur_par_matches_serial() {
+        let size = Size::new(7, 5).unwrap();
+        let image = Image::from_lens(ValueLens::new(Pixel::zero(), size).remap(
+            |_, point| Ok(Pixel::new([(point.x() * 30) as u8, (point.y() * 50) as u8, 7, 200])),
+            size,
+        ));
+
+        let serial = box_blur(&image, 2, ChannelFlags::RGBA);
+        let parallel = box_blur_par(&image, NonZeroUsize::new(3).unwrap(), 2, ChannelFlags::RGBA);
+
+        assert_eq!(serial.pixels(), parallel.pixels());
+    }
+}
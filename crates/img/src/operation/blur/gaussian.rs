@@ -14,13 +14,24 @@ use crate::{
             SizeCreationError,
         },
     },
-    image::Image,
+    image::{
+        DIMENSION_MAX,
+        Image,
+    },
     lens::{
         self,
         FromLens,
         Lens,
     },
+    operation::{
+        blend::composite::{
+            premultiply_px,
+            unpremultiply_px,
+        },
+        blur::box_blur::box_passes_lens,
+    },
     pixel::{
+        AlphaMode,
         ChannelFlags,
         Pixel,
     },
@@ -35,22 +46,44 @@ pub enum GaussianBlurCreationError {
     KernelLens(#[from] lens::kernel::CreationError),
     #[error("failed to create size: {0}")]
     Size(#[from] SizeCreationError),
+    #[error("invalid sigma")]
+    InvalidSigma,
+    #[error("premultiplied alpha mode requires alpha channel to be blurred")]
+    AlphaNotBlurred,
 }
 
 pub type GaussianBlurCreationResult<T> = std::result::Result<T, GaussianBlurCreationError>;
 
+/// Blur `source` with a gaussian kernel of given `radius` and `sigma`.
+///
+/// With [`AlphaMode::Premultiplied`], color channels are multiplied by alpha before blurring and
+/// divided by the blurred alpha afterwards, so color of fully transparent pixels, which is
+/// arbitrary, does not bleed into their neighbours.
+///
+/// Returns [`GaussianBlurCreationError::AlphaNotBlurred`] for [`AlphaMode::Premultiplied`] if
+/// `flags` do not contain [`ChannelFlags::ALPHA`], as colors would be divided by alpha they were
+/// not blurred with.
 pub fn gaussian_blur_lens<S>(
     source: S,
     radius: usize,
     sigma: f32,
     flags: ChannelFlags,
+    alpha_mode: AlphaMode,
 ) -> GaussianBlurCreationResult<impl Lens<Item = Pixel>>
 where
     S: Lens,
     S::Item: AsRef<Pixel>,
 {
+    let premultiplied = alpha_mode == AlphaMode::Premultiplied;
+    if premultiplied && !flags.contains(ChannelFlags::ALPHA) {
+        return Err(GaussianBlurCreationError::AlphaNotBlurred);
+    }
+
     let kernel = GaussianKernel::new(Size::from_radius(radius)?, sigma, flags)?;
-    let lens = source.kernel(kernel)?;
+    let lens = source
+        .map(move |px| if premultiplied { premultiply_px(*px.as_ref()) } else { *px.as_ref() })
+        .kernel(kernel)?
+        .map(move |px| if premultiplied { unpremultiply_px(px) } else { px });
 
     Ok(lens)
 }
@@ -60,8 +93,9 @@ pub fn gaussian_blur(
     radius: usize,
     sigma: f32,
     flags: ChannelFlags,
+    alpha_mode: AlphaMode,
 ) -> GaussianBlurCreationResult<Image> {
-    let lens = gaussian_blur_lens(image.lens(), radius, sigma, flags)?;
+    let lens = gaussian_blur_lens(image.lens(), radius, sigma, flags, alpha_mode)?;
     Ok(Image::from_lens(lens))
 }
 
@@ -72,9 +106,323 @@ pub fn gaussian_blur_par(
     radius: usize,
     sigma: f32,
     flags: ChannelFlags,
+    alpha_mode: AlphaMode,
 ) -> GaussianBlurCreationResult<Image> {
     use crate::lens::FromLensPar;
 
-    let lens = gaussian_blur_lens(image.lens(), radius, sigma, flags)?;
+    let lens = gaussian_blur_lens(image.lens(), radius, sigma, flags, alpha_mode)?;
+    Ok(Image::from_lens_par(lens, threads))
+}
+
+/// Blur `source` with a gaussian kernel of given `radius` and `sigma`, applied as a horizontal
+/// and a vertical pass.
+///
+/// Gaussian is separable, so this matches [`gaussian_blur_lens`] up to rounding of the
+/// intermediate result (at most 1 per channel), but costs `O(radius)` per pixel instead of
+/// `O(radius²)`. Horizontal pass is materialized before the vertical one.
+///
+/// Like other kernel based lenses, result is smaller than `source` by `radius` on every side.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     lens::{
+///         Lens,
+///         value::ValueLens,
+///     },
+///     operation::blur::separable_gaussian_blur_lens,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let px = Pixel::new([10, 20, 30, 40]);
+/// let lens = ValueLens::new(px, Size::new(9, 9)?);
+/// let blurred = separable_gaussian_blur_lens(lens, 3, 1.5f32, ChannelFlags::RGBA)?;
+///
+/// assert_eq!(blurred.size(), Size::new(3, 3)?);
+/// assert_eq!(blurred.look(Point::zero())?, px);
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn separable_gaussian_blur_lens<S>(
+    source: S,
+    radius: usize,
+    sigma: f32,
+    flags: ChannelFlags,
+) -> GaussianBlurCreationResult<impl Lens<Item = Pixel>>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    let (horizontal, vertical) = separable_kernels(radius, sigma, flags)?;
+    let lens = source.map(|px| *px.as_ref()).kernel(horizontal)?.materialize().kernel(vertical)?;
+
+    Ok(lens)
+}
+
+pub fn separable_gaussian_blur(
+    image: &Image,
+    radius: usize,
+    sigma: f32,
+    flags: ChannelFlags,
+) -> GaussianBlurCreationResult<Image> {
+    let lens = separable_gaussian_blur_lens(image.lens(), radius, sigma, flags)?;
+    Ok(Image::from_lens(lens))
+}
+
+/// Blur an image like [`separable_gaussian_blur`], evaluating both passes in parallel.
+#[cfg(feature = "parallel")]
+pub fn separable_gaussian_blur_par(
+    image: &Image,
+    threads: NonZeroUsize,
+    radius: usize,
+    sigma: f32,
+    flags: ChannelFlags,
+) -> GaussianBlurCreationResult<Image> {
+    use crate::lens::FromLensPar;
+
+    let (horizontal, vertical) = separable_kernels(radius, sigma, flags)?;
+    let lens = image.lens().kernel(horizontal)?.materialize_par(threads).kernel(vertical)?;
+    Ok(Image::from_lens_par(lens, threads))
+}
+
+/// Create horizontal and vertical 1D gaussian kernels of given `radius` and `sigma`.
+fn separable_kernels(
+    radius: usize,
+    sigma: f32,
+    flags: ChannelFlags,
+) -> GaussianBlurCreationResult<(GaussianKernel, GaussianKernel)> {
+    let diameter = Size::from_radius(radius)?.width();
+    let horizontal = GaussianKernel::new(Size::new(diameter, 1)?, sigma, flags)?;
+    let vertical = GaussianKernel::new(Size::new(1, diameter)?, sigma, flags)?;
+
+    Ok((horizontal, vertical))
+}
+
+/// Approximate gaussian blur of given `sigma` with three successive box blurs, with box sizes
+/// chosen so that the variance of their combination matches `sigma` as closely as possible.
+///
+/// Unlike [`gaussian_blur_lens`], cost per pixel does not depend on `sigma` and the size of
+/// `source` is preserved, with pixels outside of it clamped to the nearest edge.
+///
+/// Returns [`GaussianBlurCreationError::InvalidSigma`] if `sigma` is not positive and finite, or
+/// exceeds [`DIMENSION_MAX`], as boxes would then be wider than any image.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     lens::{
+///         Lens,
+///         value::ValueLens,
+///     },
+///     operation::blur::gaussian_approx_lens,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let size = Size::new(5, 5)?;
+/// let blurred =
+///     gaussian_approx_lens(ValueLens::new(Pixel::zero(), size), 2f32, ChannelFlags::RGBA)?;
+///
+/// assert_eq!(blurred.size(), size);
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn gaussian_approx_lens<S>(
+    source: S,
+    sigma: f32,
+    flags: ChannelFlags,
+) -> GaussianBlurCreationResult<impl Lens<Item = Pixel>>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    Ok(box_passes_lens(source, approx_radii(sigma)?, flags))
+}
+
+pub fn gaussian_approx(
+    image: &Image,
+    sigma: f32,
+    flags: ChannelFlags,
+) -> GaussianBlurCreationResult<Image> {
+    let lens = gaussian_approx_lens(image.lens(), sigma, flags)?;
+    Ok(Image::from_lens(lens))
+}
+
+#[cfg(feature = "parallel")]
+pub fn gaussian_approx_par(
+    image: &Image,
+    threads: NonZeroUsize,
+    sigma: f32,
+    flags: ChannelFlags,
+) -> GaussianBlurCreationResult<Image> {
+    use crate::{
+        lens::FromLensPar,
+        operation::blur::box_blur::box_passes_lens_par,
+    };
+
+    let lens = box_passes_lens_par(image.lens(), approx_radii(sigma)?, flags, threads);
     Ok(Image::from_lens_par(lens, threads))
 }
+
+/// Validate `sigma` for [`gaussian_approx_lens`] and compute its box radii.
+fn approx_radii(sigma: f32) -> GaussianBlurCreationResult<[usize; BOX_PASSES]> {
+    if !sigma.is_finite() || sigma <= 0f32 || sigma > DIMENSION_MAX as f32 {
+        return Err(GaussianBlurCreationError::InvalidSigma);
+    }
+
+    Ok(box_radii(sigma))
+}
+
+/// Number of box blur passes used by [`gaussian_approx_lens`].
+const BOX_PASSES: usize = 3;
+
+/// Radii of box blurs approximating gaussian of given `sigma` (Kovesi, "Fast Almost-Gaussian
+/// Filtering"). Boxes have odd widths `wl` or `wl + 2`, the first `m` of them being narrower.
+fn box_radii(sigma: f32) -> [usize; BOX_PASSES] {
+    let n = BOX_PASSES as f32;
+    let ideal_width = (12f32 * sigma * sigma / n + 1f32).sqrt();
+    let mut lower_width = ideal_width.floor() as usize;
+    if lower_width.is_multiple_of(2) {
+        lower_width = lower_width.saturating_sub(1).max(1);
+    }
+    let upper_width = lower_width + 2;
+
+    let wl = lower_width as f32;
+    let ideal_count =
+        (12f32 * sigma * sigma - n * wl * wl - 4f32 * n * wl - 3f32 * n) / (-4f32 * wl - 4f32);
+    let count = ideal_count.round().max(0f32) as usize;
+
+    std::array::from_fn(|pass| if pass < count { lower_width / 2 } else { upper_width / 2 })
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use crate::component::primitive::Point;
+
+    use super::*;
+
+    #[test]
+    fn test_gaussian_blur_premultiplied_no_bleeding() {
+        // Left half is opaque blue, right half is fully transparent with red color channels.
+        let size = Size::new(8, 5).unwrap();
+        let mut image = Image::solid(size, Pixel::new([0, 0, 255, 255]));
+        for y in 0..5 {
+            for x in 4..8 {
+                *image.pixel_mut(Point::new(x, y).unwrap()).unwrap() = Pixel::new([255, 0, 0, 0]);
+            }
+        }
+
+        let straight =
+            gaussian_blur(&image, 2, 1.5f32, ChannelFlags::RGBA, AlphaMode::Straight).unwrap();
+        let premultiplied =
+            gaussian_blur(&image, 2, 1.5f32, ChannelFlags::RGBA, AlphaMode::Premultiplied).unwrap();
+
+        // Pixel at the edge of the opaque half, next to the transparent one.
+        let point = Point::new(1, 0).unwrap();
+        let straight = straight.pixel(point).unwrap();
+        let premultiplied = premultiplied.pixel(point).unwrap();
+
+        assert!(straight.r() > 50, "{straight:?}");
+        assert!(premultiplied.a() < 255);
+        assert_eq!(premultiplied.r(), 0, "{premultiplied:?}");
+        assert_eq!(premultiplied.b(), 255, "{premultiplied:?}");
+    }
+
+    #[test]
+    fn test_gaussian_blur_premultiplied_requires_alpha() {
+        let image = Image::solid(Size::new(5, 5).unwrap(), Pixel::new([0, 0, 255, 128]));
+
+        let result = gaussian_blur(&image, 1, 1f32, ChannelFlags::RGB, AlphaMode::Premultiplied);
+        assert!(matches!(result, Err(GaussianBlurCreationError::AlphaNotBlurred)));
+
+        assert!(gaussian_blur(&image, 1, 1f32, ChannelFlags::RGB, AlphaMode::Straight).is_ok());
+    }
+
+    #[test]
+    fn test_box_radii() {
+        assert_eq!(box_radii(1f32), [0, 0, 1]);
+        assert_eq!(box_radii(3f32), [2, 2, 3]);
+    }
+
+    #[test]
+    fn test_gaussian_approx_close_to_exact() {
+        let image = Image::random(Size::new(40, 40).unwrap(), &mut SmallRng::seed_from_u64(0));
+        let image =
+            gaussian_blur(&image, 1, 1f32, ChannelFlags::RGBA, AlphaMode::Straight).unwrap();
+        let (radius, sigma) = (9, 3f32);
+
+        let exact =
+            gaussian_blur(&image, radius, sigma, ChannelFlags::RGB, AlphaMode::Straight).unwrap();
+        let approx = gaussian_approx(&image, sigma, ChannelFlags::RGB).unwrap();
+
+        let mut error = 0f32;
+        let mut count = 0;
+        for y in 0..exact.size().height() {
+            for x in 0..exact.size().width() {
+                let expected = exact.pixel(Point::new(x, y).unwrap()).unwrap();
+                let actual = approx.pixel(Point::new(x + radius, y + radius).unwrap()).unwrap();
+                for channel in 0..3 {
+                    error +=
+                        (expected.buffer()[channel] as f32 - actual.buffer()[channel] as f32).abs();
+                    count += 1;
+                }
+            }
+        }
+
+        assert!(error / (count as f32) < 1f32, "average error {}", error / count as f32);
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn test_gaussian_approx_par_matches_serial() {
+        let image = Image::random(Size::new(23, 17).unwrap(), &mut SmallRng::seed_from_u64(0));
+
+        let serial = gaussian_approx(&image, 2f32, ChannelFlags::RGBA).unwrap();
+        let parallel =
+            gaussian_approx_par(&image, NonZeroUsize::new(4).unwrap(), 2f32, ChannelFlags::RGBA)
+                .unwrap();
+
+        assert_eq!(serial.pixels(), parallel.pixels());
+    }
+
+    #[test]
+    fn test_separable_gaussian_blur_matches_exact() {
+        let image = Image::random(Size::new(64, 64).unwrap(), &mut SmallRng::seed_from_u64(1253));
+        let (radius, sigma) = (8, 3f32);
+
+        let exact =
+            gaussian_blur(&image, radius, sigma, ChannelFlags::RGB, AlphaMode::Straight).unwrap();
+        let separable = separable_gaussian_blur(&image, radius, sigma, ChannelFlags::RGB).unwrap();
+
+        assert_eq!(separable.size(), exact.size());
+        for (expected, actual) in exact.pixels().iter().zip(separable.pixels()) {
+            for channel in 0..3 {
+                let difference = expected.buffer()[channel].abs_diff(actual.buffer()[channel]);
+                assert!(difference <= 1, "{expected:?} {actual:?}");
+            }
+            assert_eq!(expected.a(), actual.a());
+        }
+    }
+
+    #[test]
+    fn test_gaussian_approx_invalid_sigma() {
+        let image = Image::empty(Size::new(4, 4).unwrap());
+
+        for sigma in [0f32, f32::NAN, 1e30] {
+            assert!(matches!(
+                gaussian_approx(&image, sigma, ChannelFlags::RGBA),
+                Err(GaussianBlurCreationError::InvalidSigma)
+            ));
+        }
+    }
+}
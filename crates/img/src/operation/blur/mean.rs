@@ -69,3 +69,66 @@ pub fn mean_blur_par(
     let lens = mean_blur_lens(image.lens(), radius, flags)?;
     Ok(Image::from_lens_par(lens, threads))
 }
+
+/// Blur `source` with a mean of pixels within a disk of given `radius`, see [`MeanKernel::disk`].
+///
+/// Unlike the square window of [`mean_blur_lens`], a disk does not produce blocky artifacts
+/// along diagonals. Like other kernel based lenses, result is smaller than `source` by `radius`
+/// on every side.
+pub fn mean_blur_disk_lens<S>(
+    source: S,
+    radius: usize,
+    flags: ChannelFlags,
+) -> MeanCreationResult<impl Lens<Item = Pixel>>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    let kernel = MeanKernel::disk(radius, flags)?;
+    let lens = source.kernel(kernel)?;
+    Ok(lens)
+}
+
+pub fn mean_blur_disk(
+    image: &Image,
+    radius: usize,
+    flags: ChannelFlags,
+) -> MeanCreationResult<Image> {
+    let lens = mean_blur_disk_lens(image.lens(), radius, flags)?;
+    Ok(Image::from_lens(lens))
+}
+
+#[cfg(feature = "parallel")]
+pub fn mean_blur_disk_par(
+    image: &Image,
+    threads: NonZeroUsize,
+    radius: usize,
+    flags: ChannelFlags,
+) -> MeanCreationResult<Image> {
+    use crate::lens::FromLensPar;
+
+    let lens = mean_blur_disk_lens(image.lens(), radius, flags)?;
+    Ok(Image::from_lens_par(lens, threads))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::primitive::Point;
+
+    #[test]
+    fn test_mean_blur_disk_ignores_corners() {
+        let black = Pixel::new([0, 0, 0, 255]);
+        let white = Pixel::new([255, 255, 255, 255]);
+        let mut image = Image::solid(Size::new(3, 3).unwrap(), black);
+        for (x, y) in [(0, 0), (2, 0), (0, 2), (2, 2)] {
+            *image.pixel_mut(Point::new(x, y).unwrap()).unwrap() = white;
+        }
+
+        let square = mean_blur(&image, 1, ChannelFlags::RGB).unwrap();
+        let disk = mean_blur_disk(&image, 1, ChannelFlags::RGB).unwrap();
+
+        assert_eq!(square.pixels(), [Pixel::new([113, 113, 113, 255])]);
+        assert_eq!(disk.pixels(), [black]);
+    }
+}
@@ -1,23 +1,72 @@
+mod bilateral;
+mod box_blur;
 mod gaussian;
 mod kuwahara;
+mod kuwahara_generalized;
 mod mean;
+mod radial;
 
+pub use bilateral::{
+    BilateralCreationError,
+    BilateralCreationResult,
+    bilateral,
+    bilateral_lens,
+};
+pub use box_blur::{
+    box_blur,
+    box_blur_lens,
+};
 pub use gaussian::{
+    GaussianBlurCreationError,
+    GaussianBlurCreationResult,
+    gaussian_approx,
+    gaussian_approx_lens,
     gaussian_blur,
     gaussian_blur_lens,
+    separable_gaussian_blur,
+    separable_gaussian_blur_lens,
 };
 pub use kuwahara::{
     kuwahara,
     kuwahara_lens,
 };
+pub use kuwahara_generalized::{
+    KuwaharaGeneralizedCreationError,
+    KuwaharaGeneralizedCreationResult,
+    MAX_SECTORS,
+    kuwahara_generalized,
+    kuwahara_generalized_lens,
+};
 pub use mean::{
+    MeanCreationError,
+    MeanCreationResult,
     mean_blur,
+    mean_blur_disk,
+    mean_blur_disk_lens,
     mean_blur_lens,
 };
+pub use radial::{
+    RadialBlurCreationError,
+    RadialBlurCreationResult,
+    RadialKind,
+    radial_blur,
+    radial_blur_lens,
+};
 
 #[cfg(feature = "parallel")]
 pub use self::{
-    gaussian::gaussian_blur_par,
+    bilateral::bilateral_par,
+    box_blur::box_blur_par,
+    gaussian::{
+        gaussian_approx_par,
+        gaussian_blur_par,
+        separable_gaussian_blur_par,
+    },
     kuwahara::kuwahara_par,
-    mean::mean_blur_par,
+    kuwahara_generalized::kuwahara_generalized_par,
+    mean::{
+        mean_blur_disk_par,
+        mean_blur_par,
+    },
+    radial::radial_blur_par,
 };
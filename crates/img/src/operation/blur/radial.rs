@@ -0,0 +1,219 @@
+#[cfg(feature = "parallel")]
+use std::num::NonZeroUsize;
+
+use thiserror::Error;
+
+use crate::{
+    component::{
+        primitive::Point,
+        sampler::{
+            EdgeMode,
+            sample_bilinear,
+        },
+    },
+    error::IndexError,
+    image::Image,
+    lens::{
+        FromLens,
+        Lens,
+    },
+    pixel::{
+        PIXEL_SIZE,
+        Pixel,
+    },
+};
+
+/// Maximum number of samples taken along the blur path of a pixel.
+const MAX_SAMPLES: usize = 64;
+
+/// Direction of [`radial_blur_lens`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RadialKind {
+    /// Blur along lines going through the center, like zooming during exposure. `amount` is
+    /// the fraction of distance to the center covered by the blur.
+    Zoom,
+    /// Blur along circles around the center, like spinning during exposure. `amount` is the
+    /// angle covered by the blur in radians.
+    Spin,
+}
+
+/// Error returned by radial_blur functions
+#[derive(Debug, Error)]
+pub enum RadialBlurCreationError {
+    #[error("amount must be finite and non-negative")]
+    InvalidAmount,
+}
+
+pub type RadialBlurCreationResult<T> = std::result::Result<T, RadialBlurCreationError>;
+
+/// Create a [`Lens`] blurring `source` along radial or tangential direction around `center`.
+///
+/// Every pixel is the average of bilinear samples along a path centered on it, see
+/// [`RadialKind`] for its shape. Path length grows with distance from `center`, so `center`
+/// stays sharp while periphery is blurred the most. Samples are spaced about one pixel apart,
+/// up to 64 samples. Points outside of `source` repeat its edges, so no vignette is
+/// introduced. Output keeps the size of `source`.
+///
+/// Returns [`RadialBlurCreationError::InvalidAmount`] if `amount` is negative or not finite.
+pub fn radial_blur_lens<S>(
+    source: S,
+    center: Point,
+    amount: f32,
+    kind: RadialKind,
+) -> RadialBlurCreationResult<impl Lens<Item = Pixel>>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    if !amount.is_finite() || amount < 0f32 {
+        return Err(RadialBlurCreationError::InvalidAmount);
+    }
+
+    let size = source.size();
+    let (center_x, center_y) = (center.x() as f32, center.y() as f32);
+
+    let lens = source.remap(
+        move |lens, point| {
+            if !size.contains(&point) {
+                return Err(IndexError::OutOfBounds);
+            }
+
+            let dx = point.x() as f32 - center_x;
+            let dy = point.y() as f32 - center_y;
+            let path_length = amount * dx.hypot(dy);
+            // Clamped before casting, as path of a huge amount does not fit in usize.
+            let samples = (path_length.ceil() + 1f32).clamp(1f32, MAX_SAMPLES as f32) as usize;
+
+            let mut sum = [0f32; PIXEL_SIZE];
+            for sample in 0..samples {
+                // Position along the path, from -0.5 to 0.5.
+                let t = match samples {
+                    1 => 0f32,
+                    _ => sample as f32 / (samples - 1) as f32 - 0.5,
+                };
+                let (x, y) = match kind {
+                    RadialKind::Zoom => {
+                        let scale = 1f32 + amount * t;
+                        (center_x + dx * scale, center_y + dy * scale)
+                    }
+                    RadialKind::Spin => {
+                        let (sin, cos) = (amount * t).sin_cos();
+                        (center_x + cos * dx - sin * dy, center_y + sin * dx + cos * dy)
+                    }
+                };
+
+                let px = sample_bilinear(lens, x, y, EdgeMode::Clamp);
+                sum.iter_mut().zip(px.buffer()).for_each(|(sum, value)| *sum += *value as f32);
+            }
+
+            Ok(Pixel::new(sum.map(|value| (value / samples as f32).round() as u8)))
+        },
+        size,
+    );
+
+    Ok(lens)
+}
+
+/// Apply radial blur to an image, see [`radial_blur_lens`].
+pub fn radial_blur(
+    image: &Image,
+    center: Point,
+    amount: f32,
+    kind: RadialKind,
+) -> RadialBlurCreationResult<Image> {
+    let lens = radial_blur_lens(image.lens(), center, amount, kind)?;
+    Ok(Image::from_lens(lens))
+}
+
+/// Apply radial blur to an image in parallel, see [`radial_blur_lens`].
+#[cfg(feature = "parallel")]
+pub fn radial_blur_par(
+    image: &Image,
+    threads: NonZeroUsize,
+    center: Point,
+    amount: f32,
+    kind: RadialKind,
+) -> RadialBlurCreationResult<Image> {
+    use crate::lens::FromLensPar;
+
+    let lens = radial_blur_lens(image.lens(), center, amount, kind)?;
+    Ok(Image::from_lens_par(lens, threads))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::primitive::Size;
+
+    /// Vertical black and white stripes, 2 pixels wide.
+    fn stripes() -> Image {
+        let size = Size::new(41, 41).unwrap();
+        let pixels = (0..size.area())
+            .map(|index| if index % 41 / 2 % 2 == 0 { 0 } else { 255 })
+            .map(|value| Pixel::new([value, value, value, 255]))
+            .collect();
+
+        Image::new(size, pixels).unwrap()
+    }
+
+    #[test]
+    fn test_zero_amount_is_identity() {
+        let image = stripes();
+        let center = Point::new(20, 20).unwrap();
+
+        for kind in [RadialKind::Zoom, RadialKind::Spin] {
+            let blurred = radial_blur(&image, center, 0f32, kind).unwrap();
+            assert_eq!(blurred.pixels(), image.pixels());
+        }
+    }
+
+    #[test]
+    fn test_zoom_keeps_center_sharp() {
+        let image = stripes();
+        let center = Point::new(20, 20).unwrap();
+
+        let blurred = radial_blur(&image, center, 0.4, RadialKind::Zoom).unwrap();
+
+        assert_eq!(blurred.pixel(center).unwrap(), image.pixel(center).unwrap());
+        // Far from the center, radial path crosses several stripes and averages them.
+        let periphery = blurred.pixel(Point::new(38, 20).unwrap()).unwrap();
+        assert!((60..200).contains(&periphery.r()), "{periphery:?}");
+    }
+
+    #[test]
+    fn test_spin_keeps_radial_lines() {
+        let image = stripes();
+        let center = Point::new(20, 20).unwrap();
+
+        let blurred = radial_blur(&image, center, 0.4, RadialKind::Spin).unwrap();
+
+        // Above the center, spin path runs across vertical stripes, mostly along them to the
+        // side.
+        let across = blurred.pixel(Point::new(20, 2).unwrap()).unwrap();
+        let along = blurred.pixel(Point::new(38, 20).unwrap()).unwrap();
+        assert!((60..200).contains(&across.r()), "{across:?}");
+        assert!(along.r() > 200, "{along:?}");
+    }
+
+    #[test]
+    fn test_huge_amount() {
+        let image = Image::solid(Size::new(8, 8).unwrap(), Pixel::new([10, 20, 30, 255]));
+
+        for kind in [RadialKind::Zoom, RadialKind::Spin] {
+            let blurred = radial_blur(&image, Point::zero(), f32::MAX, kind).unwrap();
+            assert_eq!(blurred.size(), image.size());
+        }
+    }
+
+    #[test]
+    fn test_invalid_amount() {
+        let image = stripes();
+
+        for amount in [-1f32, f32::NAN, f32::INFINITY] {
+            assert!(matches!(
+                radial_blur(&image, Point::zero(), amount, RadialKind::Zoom),
+                Err(RadialBlurCreationError::InvalidAmount)
+            ));
+        }
+    }
+}
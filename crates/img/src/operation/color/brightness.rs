@@ -0,0 +1,68 @@
+#[cfg(feature = "parallel")]
+use std::num::NonZeroUsize;
+
+use crate::{
+    image::Image,
+    lens::{
+        FromLens,
+        Lens,
+    },
+    pixel::{
+        ChannelFlags,
+        Pixel,
+    },
+};
+
+/// Create a [`Lens`] that adds `amount` to each channel specified in `flags`, clamping
+/// results to `u8` range.
+pub fn brightness_lens<S>(source: S, amount: i16, flags: ChannelFlags) -> impl Lens<Item = Pixel>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    source.map(move |px| map_px(px, amount, flags))
+}
+
+/// Adjust brightness of an image.
+pub fn brightness(image: &Image, amount: i16, flags: ChannelFlags) -> Image {
+    let lens = brightness_lens(image.lens(), amount, flags);
+    Image::from_lens(lens)
+}
+
+/// Adjust brightness of an image in parallel.
+#[cfg(feature = "parallel")]
+pub fn brightness_par(
+    image: &Image,
+    threads: NonZeroUsize,
+    amount: i16,
+    flags: ChannelFlags,
+) -> Image {
+    use crate::lens::FromLensPar;
+
+    let lens = brightness_lens(image.lens(), amount, flags);
+    Image::from_lens_par(lens, threads)
+}
+
+fn map_px(px: impl AsRef<Pixel>, amount: i16, flags: ChannelFlags) -> Pixel {
+    let px = px.as_ref();
+    let adjust = |value: u8| (value as i16 + amount).clamp(0, u8::MAX as i16) as u8;
+
+    let mut px = *px;
+    px.set_with_flags(adjust(px.r()), adjust(px.g()), adjust(px.b()), adjust(px.a()), flags);
+
+    px
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_px_clamps() {
+        let px = Pixel::new([10, 200, 250, 100]);
+
+        assert_eq!(map_px(px, 10, ChannelFlags::RGB), Pixel::new([20, 210, 255, 100]));
+        assert_eq!(map_px(px, -20, ChannelFlags::RGBA), Pixel::new([0, 180, 230, 80]));
+        assert_eq!(map_px(px, 300, ChannelFlags::RED), Pixel::new([255, 200, 250, 100]));
+    }
+}
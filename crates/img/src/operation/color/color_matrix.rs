@@ -0,0 +1,164 @@
+#[cfg(feature = "parallel")]
+use std::num::NonZeroUsize;
+
+use crate::{
+    image::Image,
+    lens::{
+        FromLens,
+        Lens,
+    },
+    pixel::{
+        ChannelFlags,
+        Pixel,
+    },
+};
+
+/// 3x3 matrix transforming RGB colors, every row produces one output channel.
+pub type ColorMatrix = [[f32; 3]; 3];
+
+/// Color matrix which leaves every color unchanged.
+pub const COLOR_MATRIX_IDENTITY: ColorMatrix =
+    [[1f32, 0f32, 0f32], [0f32, 1f32, 0f32], [0f32, 0f32, 1f32]];
+
+/// Create a [`Lens`] applying `matrix` and adding `bias` to RGB channels of every pixel.
+///
+/// Values are in `u8` range, results are clamped to it and truncated. Only channels specified in
+/// `flags` are modified, alpha is always preserved.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     lens::{
+///         Lens,
+///         value::ValueLens,
+///     },
+///     operation::color::color_matrix_lens,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// // Swap red and blue channels and brighten green.
+/// let matrix = [[0f32, 0f32, 1f32], [0f32, 1f32, 0f32], [1f32, 0f32, 0f32]];
+/// let lens = ValueLens::new(Pixel::new([10, 20, 30, 40]), Size::new(1, 1)?);
+/// let lens = color_matrix_lens(lens, matrix, [0f32, 5f32, 0f32], ChannelFlags::RGB);
+///
+/// assert_eq!(lens.look(Point::zero())?, Pixel::new([30, 25, 10, 40]));
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn color_matrix_lens<S>(
+    source: S,
+    matrix: ColorMatrix,
+    bias: [f32; 3],
+    flags: ChannelFlags,
+) -> impl Lens<Item = Pixel>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    source.map(move |px| map_px(px, &matrix, &bias, flags))
+}
+
+/// Apply color matrix to an image, see [`color_matrix_lens`].
+pub fn color_matrix(
+    image: &Image,
+    matrix: ColorMatrix,
+    bias: [f32; 3],
+    flags: ChannelFlags,
+) -> Image {
+    let lens = color_matrix_lens(image.lens(), matrix, bias, flags);
+    Image::from_lens(lens)
+}
+
+/// Apply color matrix to an image in parallel, see [`color_matrix_lens`].
+#[cfg(feature = "parallel")]
+pub fn color_matrix_par(
+    image: &Image,
+    threads: NonZeroUsize,
+    matrix: ColorMatrix,
+    bias: [f32; 3],
+    flags: ChannelFlags,
+) -> Image {
+    use crate::lens::FromLensPar;
+
+    let lens = color_matrix_lens(image.lens(), matrix, bias, flags);
+    Image::from_lens_par(lens, threads)
+}
+
+fn map_px(
+    px: impl AsRef<Pixel>,
+    matrix: &ColorMatrix,
+    bias: &[f32; 3],
+    flags: ChannelFlags,
+) -> Pixel {
+    let px = px.as_ref();
+    let color = [px.r(), px.g(), px.b()].map(f32::from);
+
+    let [r, g, b] = std::array::from_fn(|channel| {
+        let row = matrix[channel];
+        let value = row[0] * color[0] + row[1] * color[1] + row[2] * color[2] + bias[channel];
+        value.clamp(0f32, 255f32) as u8
+    });
+
+    let mut px = *px;
+    px.set_with_flags(r, g, b, px.a(), flags);
+
+    px
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use super::*;
+    use crate::{
+        component::primitive::Size,
+        operation::color::{
+            SEPIA_MATRIX,
+            sepia,
+        },
+    };
+
+    fn random_image() -> Image {
+        Image::random(Size::new(16, 16).unwrap(), &mut SmallRng::seed_from_u64(1253))
+    }
+
+    #[test]
+    fn test_color_matrix_identity() {
+        let image = random_image();
+
+        let result = color_matrix(&image, COLOR_MATRIX_IDENTITY, [0f32; 3], ChannelFlags::RGBA);
+
+        assert_eq!(result.pixels(), image.pixels());
+    }
+
+    #[test]
+    fn test_color_matrix_sepia() {
+        let image = random_image();
+
+        let result = color_matrix(&image, SEPIA_MATRIX, [0f32; 3], ChannelFlags::RGB);
+
+        assert_eq!(result.pixels(), sepia(&image, ChannelFlags::RGB).pixels());
+        assert_eq!(
+            map_px(Pixel::new([100, 150, 200, 255]), &SEPIA_MATRIX, &[0f32; 3], ChannelFlags::RGB),
+            Pixel::new([192, 171, 133, 255])
+        );
+    }
+
+    #[test]
+    fn test_color_matrix_bias_and_flags() {
+        let px = Pixel::new([100, 150, 200, 50]);
+
+        let result =
+            map_px(px, &COLOR_MATRIX_IDENTITY, &[-120f32, 10f32, 80f32], ChannelFlags::RGB);
+        let red_only = map_px(px, &COLOR_MATRIX_IDENTITY, &[20f32; 3], ChannelFlags::RED);
+
+        assert_eq!(result, Pixel::new([0, 160, 255, 50]));
+        assert_eq!(red_only, Pixel::new([120, 150, 200, 50]));
+    }
+}
@@ -0,0 +1,133 @@
+#[cfg(feature = "parallel")]
+use std::num::NonZeroUsize;
+
+use thiserror::Error;
+
+use crate::{
+    image::Image,
+    lens::{
+        FromLens,
+        Lens,
+    },
+    pixel::{
+        ChannelFlags,
+        Pixel,
+    },
+};
+
+/// Error returned by extract_channel functions
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ExtractChannelCreationError {
+    #[error("exactly one channel has to be selected")]
+    NotSingleChannel,
+}
+
+pub type ExtractChannelCreationResult<T> = std::result::Result<T, ExtractChannelCreationError>;
+
+/// Create a [`Lens`] copying value of a single `channel` into all RGB channels of an opaque
+/// pixel, to visualize that channel as a grayscale image.
+///
+/// Returns [`ExtractChannelCreationError::NotSingleChannel`] if `channel` does not contain
+/// exactly one channel.
+pub fn extract_channel_lens<S>(
+    source: S,
+    channel: ChannelFlags,
+) -> ExtractChannelCreationResult<impl Lens<Item = Pixel>>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    let getter = if channel == ChannelFlags::RED {
+        Pixel::r
+    } else if channel == ChannelFlags::GREEN {
+        Pixel::g
+    } else if channel == ChannelFlags::BLUE {
+        Pixel::b
+    } else if channel == ChannelFlags::ALPHA {
+        Pixel::a
+    } else {
+        return Err(ExtractChannelCreationError::NotSingleChannel);
+    };
+
+    Ok(source.map(move |px| {
+        let value = getter(px.as_ref());
+        Pixel::new([value, value, value, u8::MAX])
+    }))
+}
+
+/// Extract a single `channel` of an image as a grayscale image.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     operation::color::extract_channel,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let image = Image::new(Size::new(1, 1)?, Box::new([Pixel::new([10, 20, 30, 40])]))?;
+/// let blue = extract_channel(&image, ChannelFlags::BLUE)?;
+///
+/// assert_eq!(*blue.pixel(Point::zero())?, Pixel::new([30, 30, 30, 255]));
+/// assert!(extract_channel(&image, ChannelFlags::RGB).is_err());
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn extract_channel(
+    image: &Image,
+    channel: ChannelFlags,
+) -> ExtractChannelCreationResult<Image> {
+    let lens = extract_channel_lens(image.lens(), channel)?;
+    Ok(Image::from_lens(lens))
+}
+
+/// Extract a single `channel` of an image as a grayscale image in parallel.
+#[cfg(feature = "parallel")]
+pub fn extract_channel_par(
+    image: &Image,
+    threads: NonZeroUsize,
+    channel: ChannelFlags,
+) -> ExtractChannelCreationResult<Image> {
+    use crate::lens::FromLensPar;
+
+    let lens = extract_channel_lens(image.lens(), channel)?;
+    Ok(Image::from_lens_par(lens, threads))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use crate::component::primitive::Size;
+
+    use super::*;
+
+    #[test]
+    fn test_extract_red_channel() {
+        let image = Image::random(Size::new(7, 5).unwrap(), &mut SmallRng::seed_from_u64(0));
+
+        let red = extract_channel(&image, ChannelFlags::RED).unwrap();
+
+        assert_eq!(red.size(), image.size());
+        for (extracted, original) in red.pixels().iter().zip(image.pixels()) {
+            assert_eq!(*extracted, Pixel::new([original.r(), original.r(), original.r(), 255]));
+        }
+    }
+
+    #[test]
+    fn test_extract_channel_requires_single_channel() {
+        let image = Image::empty(Size::new(2, 2).unwrap());
+
+        for flags in [ChannelFlags::empty(), ChannelFlags::RED | ChannelFlags::ALPHA] {
+            assert_eq!(
+                extract_channel(&image, flags).unwrap_err(),
+                ExtractChannelCreationError::NotSingleChannel
+            );
+        }
+    }
+}
@@ -0,0 +1,147 @@
+#[cfg(feature = "parallel")]
+use std::num::NonZeroUsize;
+
+use crate::{
+    component::histogram::{
+        HISTOGRAM_BINS,
+        Histogram,
+    },
+    image::Image,
+    lens::{
+        FromLens,
+        Lens,
+    },
+    pixel::{
+        ChannelFlags,
+        Pixel,
+    },
+};
+
+/// Create a [`Lens`] that remaps `source` channels so that their distribution matches
+/// `reference`'s.
+///
+/// Both `source` and `reference` are fully evaluated to calculate histograms.
+pub fn histogram_match_lens<S, R>(
+    source: S,
+    reference: R,
+    flags: ChannelFlags,
+) -> impl Lens<Item = Pixel>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+    R: Lens,
+    R::Item: AsRef<Pixel>,
+{
+    let tables = [
+        lookup_table(&source, &reference, Pixel::r),
+        lookup_table(&source, &reference, Pixel::g),
+        lookup_table(&source, &reference, Pixel::b),
+        lookup_table(&source, &reference, Pixel::a),
+    ];
+
+    source.map(move |px| map_px(px, &tables, flags))
+}
+
+/// Remap channels of `source` so that their distribution matches `reference`'s.
+pub fn histogram_match(source: &Image, reference: &Image, flags: ChannelFlags) -> Image {
+    let lens = histogram_match_lens(source.lens(), reference.lens(), flags);
+    Image::from_lens(lens)
+}
+
+/// Remap channels of `source` so that their distribution matches `reference`'s in parallel.
+#[cfg(feature = "parallel")]
+pub fn histogram_match_par(
+    source: &Image,
+    threads: NonZeroUsize,
+    reference: &Image,
+    flags: ChannelFlags,
+) -> Image {
+    use crate::lens::FromLensPar;
+
+    let lens = histogram_match_lens(source.lens(), reference.lens(), flags);
+    Image::from_lens_par(lens, threads)
+}
+
+fn lookup_table<S, R>(source: &S, reference: &R, channel: fn(&Pixel) -> u8) -> [u8; HISTOGRAM_BINS]
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+    R: Lens,
+    R::Item: AsRef<Pixel>,
+{
+    let source_cdf = Histogram::from_lens(source.map(|px| channel(px.as_ref()))).cdf();
+    let reference_cdf = Histogram::from_lens(reference.map(|px| channel(px.as_ref()))).cdf();
+
+    let mut table = [0u8; HISTOGRAM_BINS];
+    let mut reference_value = 0;
+    for (value, source_fraction) in table.iter_mut().zip(source_cdf) {
+        // Both cdfs are non-decreasing, so search can continue from the last match.
+        while reference_value < u8::MAX as usize && reference_cdf[reference_value] < source_fraction
+        {
+            reference_value += 1;
+        }
+        *value = reference_value as u8;
+    }
+
+    table
+}
+
+fn map_px(px: impl AsRef<Pixel>, tables: &[[u8; HISTOGRAM_BINS]; 4], flags: ChannelFlags) -> Pixel {
+    let px = px.as_ref();
+
+    let new_red = tables[0][px.r() as usize];
+    let new_green = tables[1][px.g() as usize];
+    let new_blue = tables[2][px.b() as usize];
+    let new_alpha = tables[3][px.a() as usize];
+
+    let mut px = *px;
+    px.set_with_flags(new_red, new_green, new_blue, new_alpha, flags);
+
+    px
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use crate::{
+        component::primitive::Size,
+        lens::Lens,
+    };
+
+    use super::*;
+
+    fn mean_red(image: &Image) -> f32 {
+        image.lens().elements().map(|px| px.r() as f32).sum::<f32>() / image.size().area() as f32
+    }
+
+    #[test]
+    fn test_histogram_match_self_is_identity() {
+        let image = Image::random(Size::new(32, 32).unwrap(), &mut SmallRng::seed_from_u64(0));
+        let matched = histogram_match(&image, &image, ChannelFlags::RGBA);
+
+        for (original, matched) in image.lens().elements().zip(matched.lens().elements()) {
+            assert!(original.r().abs_diff(matched.r()) <= 1);
+            assert!(original.g().abs_diff(matched.g()) <= 1);
+            assert!(original.b().abs_diff(matched.b()) <= 1);
+            assert!(original.a().abs_diff(matched.a()) <= 1);
+        }
+    }
+
+    #[test]
+    fn test_histogram_match_dark_to_bright() {
+        let size = Size::new(32, 32).unwrap();
+        let random = Image::random(size, &mut SmallRng::seed_from_u64(0));
+        let dark = Image::from_lens(random.lens().map(|px| Pixel::new([px.r() / 4, 0, 0, 255])));
+        let bright =
+            Image::from_lens(random.lens().map(|px| Pixel::new([192 + px.r() / 4, 0, 0, 255])));
+
+        let matched = histogram_match(&dark, &bright, ChannelFlags::RGB);
+
+        assert!(mean_red(&matched) > mean_red(&dark));
+        assert!(mean_red(&matched) > 192f32);
+    }
+}
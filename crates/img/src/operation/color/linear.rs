@@ -0,0 +1,150 @@
+#[cfg(feature = "parallel")]
+use std::num::NonZeroUsize;
+
+use crate::{
+    image::Image,
+    lens::{
+        FromLens,
+        Lens,
+    },
+    pixel::{
+        Pixel,
+        PixelRgbaf32,
+    },
+};
+
+/// Create a [`Lens`] mapping every channel to `value * scale + offset`, in 0-1 normalized values
+/// and in RGBA order, clamping results.
+///
+/// Brightness and contrast can be combined into a single `scale` and `offset`, so the result is
+/// rounded only once instead of after every adjustment.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     lens::{
+///         Lens,
+///         value::ValueLens,
+///     },
+///     operation::color::linear_lens,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let lens = ValueLens::new(Pixel::new([100, 100, 100, 255]), Size::new(1, 1)?);
+/// let lens = linear_lens(lens, [2f32, 1f32, 0.5f32, 1f32], [0f32, 0.1f32, 0f32, 0f32]);
+///
+/// assert_eq!(lens.look(Point::zero())?, Pixel::new([200, 126, 50, 255]));
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn linear_lens<S>(source: S, scale: [f32; 4], offset: [f32; 4]) -> impl Lens<Item = Pixel>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    source.map(move |px| map_px(px, scale, offset))
+}
+
+/// Apply per channel scale and offset to an image, see [`linear_lens`].
+pub fn linear(image: &Image, scale: [f32; 4], offset: [f32; 4]) -> Image {
+    let lens = linear_lens(image.lens(), scale, offset);
+    Image::from_lens(lens)
+}
+
+/// Apply per channel scale and offset to an image in parallel, see [`linear_lens`].
+#[cfg(feature = "parallel")]
+pub fn linear_par(
+    image: &Image,
+    threads: NonZeroUsize,
+    scale: [f32; 4],
+    offset: [f32; 4],
+) -> Image {
+    use crate::lens::FromLensPar;
+
+    let lens = linear_lens(image.lens(), scale, offset);
+    Image::from_lens_par(lens, threads)
+}
+
+fn map_px(px: impl AsRef<Pixel>, scale: [f32; 4], offset: [f32; 4]) -> Pixel {
+    let px = px.as_ref();
+    let values = [px.r_f32(), px.g_f32(), px.b_f32(), px.a_f32()];
+
+    let mut result = *px;
+    result.set_r_f32(values[0] * scale[0] + offset[0]);
+    result.set_g_f32(values[1] * scale[1] + offset[1]);
+    result.set_b_f32(values[2] * scale[2] + offset[2]);
+    result.set_a_f32(values[3] * scale[3] + offset[3]);
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::primitive::Size;
+
+    /// Image with every channel value once in every channel.
+    fn ramp() -> Image {
+        let pixels = (0..=255u8).map(|value| Pixel::new([value; 4])).collect();
+        Image::new(Size::new(256, 1).unwrap(), pixels).unwrap()
+    }
+
+    #[test]
+    fn test_linear_identity() {
+        let image = ramp();
+
+        let result = linear(&image, [1f32; 4], [0f32; 4]);
+
+        assert_eq!(result.pixels(), image.pixels());
+    }
+
+    #[test]
+    fn test_linear_clamps() {
+        let px = Pixel::new([10, 200, 250, 100]);
+
+        let result = map_px(px, [1f32, 2f32, 1f32, 1f32], [-0.1f32, 0f32, 0.1f32, 0f32]);
+
+        assert_eq!(result, Pixel::new([0, 255, 255, 100]));
+    }
+
+    #[test]
+    fn test_linear_combined_brightness_contrast() {
+        let image = ramp();
+        let (brightness, contrast) = (0.0412f32, 1.37f32);
+
+        // Brightness followed by contrast around mid gray, rounded after each step.
+        let brightened = linear(&image, [1f32; 4], [brightness; 4]);
+        let sequential = linear(&brightened, [contrast; 4], [0.5f32 * (1f32 - contrast); 4]);
+        let combined =
+            linear(&image, [contrast; 4], [brightness * contrast + 0.5f32 * (1f32 - contrast); 4]);
+
+        let error = |result: &Image| {
+            result
+                .pixels()
+                .iter()
+                .zip(image.pixels())
+                .map(|(actual, px)| {
+                    let exact = ((px.r() as f32 / 255f32 + brightness - 0.5f32) * contrast
+                        + 0.5f32)
+                        .clamp(0f32, 1f32)
+                        * 255f32;
+                    (actual.r() as f32 - exact).abs()
+                })
+                .sum::<f32>()
+        };
+
+        for (sequential, combined) in sequential.pixels().iter().zip(combined.pixels()) {
+            assert!(sequential.r().abs_diff(combined.r()) <= 1);
+        }
+        assert!(
+            error(&combined) < error(&sequential),
+            "combined {} sequential {}",
+            error(&combined),
+            error(&sequential)
+        );
+        assert!(error(&combined) <= 0.5f32 * 256f32);
+    }
+}
@@ -1,8 +1,36 @@
+mod brightness;
+mod color_matrix;
+mod extract_channel;
 mod gamma_correction;
 mod grayscale;
+mod histogram_match;
+mod linear;
 mod negative;
+mod quantize;
+mod reduce_depth;
+mod selective_color;
 mod sepia;
+#[cfg(feature = "simd")]
+mod simd;
+mod tone_map;
+mod white_balance;
 
+pub use brightness::{
+    brightness,
+    brightness_lens,
+};
+pub use color_matrix::{
+    COLOR_MATRIX_IDENTITY,
+    ColorMatrix,
+    color_matrix,
+    color_matrix_lens,
+};
+pub use extract_channel::{
+    ExtractChannelCreationError,
+    ExtractChannelCreationResult,
+    extract_channel,
+    extract_channel_lens,
+};
 pub use gamma_correction::{
     gamma_correction,
     gamma_correction_lens,
@@ -11,19 +39,75 @@ pub use grayscale::{
     grayscale,
     grayscale_lens,
 };
+pub use histogram_match::{
+    histogram_match,
+    histogram_match_lens,
+};
+pub use linear::{
+    linear,
+    linear_lens,
+};
 pub use negative::{
     negative,
     negative_lens,
+    negative_value,
+    negative_value_lens,
+};
+pub use quantize::{
+    QuantizeCreationError,
+    QuantizeCreationResult,
+    quantize_kmeans,
+};
+pub use reduce_depth::{
+    ReduceDepthCreationError,
+    ReduceDepthCreationResult,
+    reduce_depth,
+    reduce_depth_lens,
+};
+pub use selective_color::{
+    selective_color,
+    selective_color_lens,
 };
 pub use sepia::{
+    SEPIA_MATRIX,
     sepia,
     sepia_lens,
 };
+pub use tone_map::{
+    ToneMapOperator,
+    tone_map,
+    tone_map_lens,
+};
+pub use white_balance::{
+    NEUTRAL_TEMPERATURE,
+    white_balance,
+    white_balance_lens,
+};
 
 #[cfg(feature = "parallel")]
 pub use self::{
+    brightness::brightness_par,
+    color_matrix::color_matrix_par,
+    extract_channel::extract_channel_par,
     gamma_correction::gamma_correction_par,
     grayscale::grayscale_par,
-    negative::negative_par,
+    histogram_match::histogram_match_par,
+    linear::linear_par,
+    negative::{
+        negative_par,
+        negative_value_par,
+    },
+    reduce_depth::reduce_depth_par,
+    selective_color::selective_color_par,
     sepia::sepia_par,
+    tone_map::tone_map_par,
+    white_balance::white_balance_par,
+};
+
+#[cfg(feature = "simd")]
+pub use simd::{
+    LANES,
+    brightness_simd,
+    grayscale_simd,
+    negative_simd,
 };
@@ -10,6 +10,7 @@ use crate::{
     pixel::{
         ChannelFlags,
         Pixel,
+        hsv::HsvPixel,
     },
 };
 
@@ -37,6 +38,32 @@ pub fn negative_par(image: &Image, threads: NonZeroUsize, flags: ChannelFlags) -
     Image::from_lens_par(lens, threads)
 }
 
+/// Create a [`Lens`] that inverts only the HSV value (brightness) of each pixel, preserving its
+/// hue, saturation and alpha. Bright colors become dark ones of the same hue and vice versa,
+/// unlike [`negative_lens`], which turns e.g. red into cyan.
+pub fn negative_value_lens<S>(source: S) -> impl Lens<Item = Pixel>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    source.map(map_px_value)
+}
+
+/// Apply HSV value negative filter to an image.
+pub fn negative_value(image: &Image) -> Image {
+    let lens = negative_value_lens(image.lens());
+    Image::from_lens(lens)
+}
+
+/// Apply HSV value negative filter to an image in parallel.
+#[cfg(feature = "parallel")]
+pub fn negative_value_par(image: &Image, threads: NonZeroUsize) -> Image {
+    use crate::lens::FromLensPar;
+
+    let lens = negative_value_lens(image.lens());
+    Image::from_lens_par(lens, threads)
+}
+
 fn map_px(px: impl AsRef<Pixel>, flags: ChannelFlags) -> Pixel {
     let px = px.as_ref();
 
@@ -50,3 +77,39 @@ fn map_px(px: impl AsRef<Pixel>, flags: ChannelFlags) -> Pixel {
 
     px
 }
+
+fn map_px_value(px: impl AsRef<Pixel>) -> Pixel {
+    let mut hsv = HsvPixel::from(*px.as_ref());
+    hsv.set_value(1f32 - hsv.value());
+
+    Pixel::from(hsv)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_negative_value_preserves_hue() {
+        let dark_red = Pixel::new([64, 0, 0, 200]);
+
+        let inverted = map_px_value(dark_red);
+
+        assert_eq!(inverted, Pixel::new([191, 0, 0, 200]));
+        assert_eq!(map_px(dark_red, ChannelFlags::RGB), Pixel::new([191, 255, 255, 200]));
+    }
+
+    #[test]
+    fn test_negative_value_bright_becomes_dark() {
+        let red = Pixel::new([255, 0, 0, 255]);
+        let orange = Pixel::new([200, 100, 0, 255]);
+
+        assert_eq!(map_px_value(red), Pixel::new([0, 0, 0, 255]));
+
+        let inverted = HsvPixel::from(map_px_value(orange));
+        let original = HsvPixel::from(orange);
+        assert!((inverted.hue() - original.hue()).abs() < 2f32);
+        assert!((inverted.saturation() - original.saturation()).abs() < 2e-2);
+        assert!((inverted.value() - (1f32 - original.value())).abs() < 1e-2);
+    }
+}
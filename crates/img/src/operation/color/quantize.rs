@@ -0,0 +1,215 @@
+use rand::{
+    Rng,
+    SeedableRng,
+    rngs::StdRng,
+};
+use thiserror::Error;
+
+use crate::{
+    image::Image,
+    pixel::Pixel,
+};
+
+/// Error returned by quantize_kmeans function
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum QuantizeCreationError {
+    #[error("number of colors must be greater than zero")]
+    ZeroColors,
+}
+
+pub type QuantizeCreationResult<T> = std::result::Result<T, QuantizeCreationError>;
+
+/// Reduce `image` to a palette of at most `k` colors found with k-means clustering.
+///
+/// Initial centroids are picked with k-means++ using a rng seeded with `seed`, so results are
+/// reproducible, and then refined with Lloyd's algorithm in RGB space for up to `max_iter`
+/// iterations or until no pixel changes its cluster. Every pixel is replaced with its nearest
+/// palette color, keeping its alpha. Palette is shorter than `k` if the image has fewer distinct
+/// colors.
+///
+/// Returns quantized [`Image`] with its palette or [`QuantizeCreationError::ZeroColors`] if `k`
+/// is zero.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     operation::color::quantize_kmeans,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let image = Image::solid(Size::new(4, 4)?, Pixel::new([10, 20, 30, 255]));
+/// let (quantized, palette) = quantize_kmeans(&image, 4, 10, 0)?;
+///
+/// assert_eq!(palette, vec![Pixel::new([10, 20, 30, 255])]);
+/// assert_eq!(quantized.pixels(), image.pixels());
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn quantize_kmeans(
+    image: &Image,
+    k: usize,
+    max_iter: usize,
+    seed: u64,
+) -> QuantizeCreationResult<(Image, Vec<Pixel>)> {
+    if k == 0 {
+        return Err(QuantizeCreationError::ZeroColors);
+    }
+
+    let colors = image.pixels().iter().map(|px| [px.r(), px.g(), px.b()].map(f32::from));
+    let colors = colors.collect::<Vec<_>>();
+
+    let mut centroids = initial_centroids(&colors, k, &mut StdRng::seed_from_u64(seed));
+    let mut labels = colors.iter().map(|color| nearest(&centroids, color)).collect::<Vec<_>>();
+
+    for _ in 0..max_iter {
+        let mut sums = vec![([0f32; 3], 0usize); centroids.len()];
+        for (color, label) in colors.iter().zip(&labels) {
+            let (sum, count) = &mut sums[*label];
+            (0..3).for_each(|channel| sum[channel] += color[channel]);
+            *count += 1;
+        }
+
+        // Empty clusters keep their previous centroid.
+        for (centroid, (sum, count)) in centroids.iter_mut().zip(sums) {
+            if count > 0 {
+                *centroid = sum.map(|value| value / count as f32);
+            }
+        }
+
+        let new_labels = colors.iter().map(|color| nearest(&centroids, color)).collect::<Vec<_>>();
+        if new_labels == labels {
+            break;
+        }
+        labels = new_labels;
+    }
+
+    let palette = centroids
+        .iter()
+        .map(|centroid| {
+            let [r, g, b] = centroid.map(|value| value.round().clamp(0f32, 255f32) as u8);
+            Pixel::new([r, g, b, 255])
+        })
+        .collect::<Vec<_>>();
+
+    let pixels = image
+        .pixels()
+        .iter()
+        .zip(labels)
+        .map(|(px, label)| {
+            let mut quantized = palette[label];
+            quantized.set_a(px.a());
+            quantized
+        })
+        .collect();
+
+    // SAFETY: one pixel is produced for every pixel of the image.
+    let image = Image::new(image.size(), pixels).expect("unexpected error in Image::new");
+
+    Ok((image, palette))
+}
+
+/// Pick up to `k` initial centroids with k-means++, preferring colors far from already picked
+/// ones.
+fn initial_centroids<R>(colors: &[[f32; 3]], k: usize, rng: &mut R) -> Vec<[f32; 3]>
+where
+    R: Rng,
+{
+    let mut centroids = vec![colors[rng.random_range(0..colors.len())]];
+
+    while centroids.len() < k {
+        let weights = colors
+            .iter()
+            .map(|color| distance_squared(&centroids[nearest(&centroids, color)], color))
+            .collect::<Vec<_>>();
+        let total = weights.iter().sum::<f32>();
+        if total <= 0f32 {
+            // Every color is already a centroid.
+            break;
+        }
+
+        let mut target = rng.random::<f32>() * total;
+        let index = weights
+            .iter()
+            .position(|weight| {
+                target -= weight;
+                target < 0f32 && *weight > 0f32
+            })
+            .unwrap_or_else(|| weights.iter().rposition(|weight| *weight > 0f32).unwrap_or(0));
+
+        centroids.push(colors[index]);
+    }
+
+    centroids
+}
+
+fn nearest(centroids: &[[f32; 3]], color: &[f32; 3]) -> usize {
+    centroids
+        .iter()
+        .map(|centroid| distance_squared(centroid, color))
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(index, _)| index)
+        .unwrap_or(0)
+}
+
+fn distance_squared(a: &[f32; 3], b: &[f32; 3]) -> f32 {
+    a.iter().zip(b).map(|(a, b)| (a - b) * (a - b)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::component::primitive::Size;
+
+    use super::*;
+
+    fn three_colors() -> (Image, [Pixel; 3]) {
+        let colors = [
+            Pixel::new([220, 30, 30, 255]),
+            Pixel::new([30, 200, 60, 255]),
+            Pixel::new([40, 50, 230, 255]),
+        ];
+        let size = Size::new(9, 7).unwrap();
+        let pixels = (0..size.area()).map(|index| colors[(index / 3 + index / 9) % 3]).collect();
+
+        (Image::new(size, pixels).unwrap(), colors)
+    }
+
+    #[test]
+    fn test_three_colors_recovered() {
+        let (image, colors) = three_colors();
+
+        for seed in 0..5 {
+            let (quantized, mut palette) = quantize_kmeans(&image, 3, 10, seed).unwrap();
+
+            palette.sort_by_key(|px| *px.buffer());
+            let mut expected = colors.to_vec();
+            expected.sort_by_key(|px| *px.buffer());
+            assert_eq!(palette, expected);
+            assert_eq!(quantized.pixels(), image.pixels());
+        }
+    }
+
+    #[test]
+    fn test_k_below_color_count_is_reproducible() {
+        let (image, _) = three_colors();
+
+        let (quantized, palette) = quantize_kmeans(&image, 2, 10, 7).unwrap();
+
+        assert_eq!(palette.len(), 2);
+        assert!(quantized.pixels().iter().all(|px| palette.contains(px)));
+        assert_eq!(quantize_kmeans(&image, 2, 10, 7).unwrap().1, palette);
+    }
+
+    #[test]
+    fn test_zero_colors() {
+        let (image, _) = three_colors();
+
+        assert_eq!(
+            quantize_kmeans(&image, 0, 10, 0).unwrap_err(),
+            QuantizeCreationError::ZeroColors
+        );
+    }
+}
@@ -0,0 +1,152 @@
+#[cfg(feature = "parallel")]
+use std::num::NonZeroUsize;
+
+use thiserror::Error;
+
+use crate::{
+    image::Image,
+    lens::{
+        FromLens,
+        Lens,
+    },
+    pixel::{
+        ChannelFlags,
+        Pixel,
+    },
+};
+
+/// Error returned by reduce_depth functions
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ReduceDepthCreationError {
+    #[error("bits per channel must be in range 1..=8")]
+    InvalidBits,
+}
+
+pub type ReduceDepthCreationResult<T> = std::result::Result<T, ReduceDepthCreationError>;
+
+/// Create a [`Lens`] keeping only the top `bits_per_channel` bits of channels selected by
+/// `flags`, simulating displays with lower color depth.
+///
+/// Low bits are truncated, not rounded, so e.g. 1 bit maps every channel to 0 or 128. Unlike
+/// posterization, levels are not spread evenly up to 255. Use different flags and bit counts in
+/// separate passes to simulate formats like RGB565.
+///
+/// Returns [`ReduceDepthCreationError::InvalidBits`] if `bits_per_channel` is not in `1..=8`.
+pub fn reduce_depth_lens<S>(
+    source: S,
+    bits_per_channel: u8,
+    flags: ChannelFlags,
+) -> ReduceDepthCreationResult<impl Lens<Item = Pixel>>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    if !(1..=8).contains(&bits_per_channel) {
+        return Err(ReduceDepthCreationError::InvalidBits);
+    }
+
+    let mask = u8::MAX << (8 - bits_per_channel);
+
+    Ok(source.map(move |px| {
+        let px = px.as_ref();
+        let mut reduced = *px;
+        reduced.set_with_flags(px.r() & mask, px.g() & mask, px.b() & mask, px.a() & mask, flags);
+        reduced
+    }))
+}
+
+/// Reduce color depth of an image to `bits_per_channel` bits in channels selected by `flags`.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     operation::color::reduce_depth,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let image = Image::new(Size::new(1, 1)?, Box::new([Pixel::new([255, 100, 7, 200])]))?;
+/// let reduced = reduce_depth(&image, 3, ChannelFlags::RGB)?;
+///
+/// assert_eq!(*reduced.pixel(Point::zero())?, Pixel::new([224, 96, 0, 200]));
+/// assert!(reduce_depth(&image, 0, ChannelFlags::RGB).is_err());
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn reduce_depth(
+    image: &Image,
+    bits_per_channel: u8,
+    flags: ChannelFlags,
+) -> ReduceDepthCreationResult<Image> {
+    let lens = reduce_depth_lens(image.lens(), bits_per_channel, flags)?;
+    Ok(Image::from_lens(lens))
+}
+
+/// Reduce color depth of an image to `bits_per_channel` bits in channels selected by `flags` in
+/// parallel.
+#[cfg(feature = "parallel")]
+pub fn reduce_depth_par(
+    image: &Image,
+    threads: NonZeroUsize,
+    bits_per_channel: u8,
+    flags: ChannelFlags,
+) -> ReduceDepthCreationResult<Image> {
+    use crate::lens::FromLensPar;
+
+    let lens = reduce_depth_lens(image.lens(), bits_per_channel, flags)?;
+    Ok(Image::from_lens_par(lens, threads))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use crate::component::primitive::Size;
+
+    use super::*;
+
+    fn random_image() -> Image {
+        Image::random(Size::new(9, 6).unwrap(), &mut SmallRng::seed_from_u64(1220))
+    }
+
+    #[test]
+    fn test_eight_bits_is_identity() {
+        let image = random_image();
+
+        let reduced = reduce_depth(&image, 8, ChannelFlags::all()).unwrap();
+
+        assert_eq!(reduced.pixels(), image.pixels());
+    }
+
+    #[test]
+    fn test_one_bit() {
+        let image = random_image();
+
+        let reduced = reduce_depth(&image, 1, ChannelFlags::RGB).unwrap();
+
+        for (reduced, original) in reduced.pixels().iter().zip(image.pixels()) {
+            for value in [reduced.r(), reduced.g(), reduced.b()] {
+                assert!(value == 0 || value == 128);
+            }
+            assert_eq!(reduced.r(), original.r() & 128);
+            assert_eq!(reduced.a(), original.a());
+        }
+    }
+
+    #[test]
+    fn test_invalid_bits() {
+        let image = random_image();
+
+        for bits in [0, 9] {
+            assert_eq!(
+                reduce_depth(&image, bits, ChannelFlags::RGB).unwrap_err(),
+                ReduceDepthCreationError::InvalidBits
+            );
+        }
+    }
+}
@@ -0,0 +1,180 @@
+#[cfg(feature = "parallel")]
+use std::num::NonZeroUsize;
+
+use crate::{
+    image::Image,
+    lens::{
+        FromLens,
+        Lens,
+    },
+    pixel::{
+        Pixel,
+        hsv::HsvPixel,
+    },
+};
+
+/// Create a [`Lens`] that adjusts saturation and value (lightness) of pixels with hue within a
+/// band `hue_width` degrees wide centered at `hue_center` degrees.
+///
+/// Pixels within the band get full `saturation_delta` and `lightness_delta` added (both in
+/// 0-1 range, results are clamped). Adjustment fades out linearly over further
+/// `hue_width / 2` degrees on both sides of the band, so there are no hard edges. Hue distance
+/// wraps around, so a band centered at 0 covers both reds near 0 and near 360. Pixels outside
+/// of the band and its falloff, as well as gray pixels, which have no hue, are left unchanged.
+pub fn selective_color_lens<S>(
+    source: S,
+    hue_center: f32,
+    hue_width: f32,
+    saturation_delta: f32,
+    lightness_delta: f32,
+) -> impl Lens<Item = Pixel>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    let hue_center = hue_center.rem_euclid(360f32);
+    let half_width = hue_width.max(0f32) / 2f32;
+
+    source.map(move |px| map_px(px, hue_center, half_width, saturation_delta, lightness_delta))
+}
+
+/// Adjust saturation and value of pixels within a hue band, see [`selective_color_lens`].
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     operation::color::selective_color,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let red = Pixel::new([200, 100, 100, 255]);
+/// let blue = Pixel::new([100, 100, 200, 255]);
+/// let image = Image::new(Size::new(2, 1)?, Box::new([red, blue]))?;
+///
+/// // Make reds more saturated.
+/// let result = selective_color(&image, 0.0, 30.0, 0.3, 0.0);
+///
+/// assert!(result.pixel(Point::new(0, 0)?)?.g() < 100);
+/// assert_eq!(*result.pixel(Point::new(1, 0)?)?, blue);
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn selective_color(
+    image: &Image,
+    hue_center: f32,
+    hue_width: f32,
+    saturation_delta: f32,
+    lightness_delta: f32,
+) -> Image {
+    let lens = selective_color_lens(
+        image.lens(),
+        hue_center,
+        hue_width,
+        saturation_delta,
+        lightness_delta,
+    );
+    Image::from_lens(lens)
+}
+
+/// Adjust saturation and value of pixels within a hue band in parallel, see
+/// [`selective_color_lens`].
+#[cfg(feature = "parallel")]
+pub fn selective_color_par(
+    image: &Image,
+    threads: NonZeroUsize,
+    hue_center: f32,
+    hue_width: f32,
+    saturation_delta: f32,
+    lightness_delta: f32,
+) -> Image {
+    use crate::lens::FromLensPar;
+
+    let lens = selective_color_lens(
+        image.lens(),
+        hue_center,
+        hue_width,
+        saturation_delta,
+        lightness_delta,
+    );
+    Image::from_lens_par(lens, threads)
+}
+
+fn map_px(
+    px: impl AsRef<Pixel>,
+    hue_center: f32,
+    half_width: f32,
+    saturation_delta: f32,
+    lightness_delta: f32,
+) -> Pixel {
+    let px = *px.as_ref();
+    let mut hsv = HsvPixel::from(px);
+    if hsv.saturation() == 0f32 {
+        return px;
+    }
+
+    let weight = band_weight(hsv.hue(), hue_center, half_width);
+    if weight == 0f32 {
+        return px;
+    }
+
+    hsv.set_saturation((hsv.saturation() + saturation_delta * weight).clamp(0f32, 1f32));
+    hsv.set_value((hsv.value() + lightness_delta * weight).clamp(0f32, 1f32));
+
+    Pixel::from(hsv)
+}
+
+/// Get 0-1 weight of adjustment for `hue`, 1 within the band, falling linearly to 0 over
+/// `half_width` degrees past its edges.
+fn band_weight(hue: f32, hue_center: f32, half_width: f32) -> f32 {
+    let distance = (hue - hue_center).abs() % 360f32;
+    let distance = distance.min(360f32 - distance);
+
+    if distance <= half_width {
+        1f32
+    } else if half_width == 0f32 {
+        0f32
+    } else {
+        (1f32 - (distance - half_width) / half_width).max(0f32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_band_weight() {
+        assert_eq!(band_weight(10f32, 0f32, 20f32), 1f32);
+        assert_eq!(band_weight(350f32, 0f32, 20f32), 1f32);
+        assert_eq!(band_weight(30f32, 0f32, 20f32), 0.5f32);
+        assert_eq!(band_weight(40f32, 0f32, 20f32), 0f32);
+        assert_eq!(band_weight(180f32, 0f32, 20f32), 0f32);
+    }
+
+    #[test]
+    fn test_selective_color_out_of_band_unchanged() {
+        let green = Pixel::new([40, 180, 60, 255]);
+        let blue = Pixel::new([30, 40, 200, 128]);
+        let gray = Pixel::new([90, 90, 90, 255]);
+
+        for px in [green, blue, gray] {
+            assert_eq!(map_px(px, 0f32, 15f32, 0.4, -0.3), px);
+        }
+    }
+
+    #[test]
+    fn test_selective_color_in_band_deltas() {
+        let red = Pixel::new([200, 100, 100, 255]);
+        let original = HsvPixel::from(red);
+
+        let adjusted = HsvPixel::from(map_px(red, 0f32, 15f32, 0.2, -0.1));
+
+        assert!((adjusted.hue() - original.hue()).abs() < 1f32);
+        assert!((adjusted.saturation() - (original.saturation() + 0.2)).abs() < 1e-2);
+        assert!((adjusted.value() - (original.value() - 0.1)).abs() < 1e-2);
+        assert_eq!(adjusted.alpha(), 255);
+    }
+}
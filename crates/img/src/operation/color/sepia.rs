@@ -7,18 +7,29 @@ use crate::{
         FromLens,
         Lens,
     },
+    operation::color::color_matrix::{
+        ColorMatrix,
+        color_matrix_lens,
+    },
     pixel::{
         ChannelFlags,
         Pixel,
     },
 };
 
+/// Color matrix producing sepia tone, see [`color_matrix_lens`].
+pub const SEPIA_MATRIX: ColorMatrix = [
+    [0.393f32, 0.769f32, 0.189f32],
+    [0.349f32, 0.686f32, 0.168f32],
+    [0.272f32, 0.534f32, 0.131f32],
+];
+
 pub fn sepia_lens<S>(source: S, flags: ChannelFlags) -> impl Lens<Item = Pixel>
 where
     S: Lens,
     S::Item: AsRef<Pixel>,
 {
-    source.map(move |px| map_px(px, flags))
+    color_matrix_lens(source, SEPIA_MATRIX, [0f32; 3], flags)
 }
 
 pub fn sepia(image: &Image, flags: ChannelFlags) -> Image {
@@ -33,20 +44,3 @@ pub fn sepia_par(image: &Image, threads: NonZeroUsize, flags: ChannelFlags) -> I
     let lens = sepia_lens(image.lens(), flags);
     Image::from_lens_par(lens, threads)
 }
-
-fn map_px(px: impl AsRef<Pixel>, flags: ChannelFlags) -> Pixel {
-    let px = px.as_ref();
-
-    let new_red = 0.393 * px.r() as f32 + 0.769 * px.g() as f32 + 0.189 * px.b() as f32;
-    let new_green = 0.349 * px.r() as f32 + 0.686 * px.g() as f32 + 0.168 * px.b() as f32;
-    let new_blue = 0.272 * px.r() as f32 + 0.534 * px.g() as f32 + 0.131 * px.b() as f32;
-
-    let new_red = new_red.clamp(0f32, 255f32) as u8;
-    let new_green = new_green.clamp(0f32, 255f32) as u8;
-    let new_blue = new_blue.clamp(0f32, 255f32) as u8;
-
-    let mut px = *px;
-    px.set_with_flags(new_red, new_green, new_blue, px.a(), flags);
-
-    px
-}
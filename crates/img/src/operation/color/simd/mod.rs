@@ -0,0 +1,116 @@
+//! Vectorized fast paths for per-pixel channel operations.
+//!
+//! Functions in this module work directly on [`Image`] buffer, processing [`LANES`] bytes
+//! (4 pixels) at once with SSE2 instructions on x86_64 and NEON on aarch64. Results are
+//! bit-identical to their scalar [`Lens`] counterparts, which stay the default and are used as
+//! fallback on other targets.
+//!
+//! [`Lens`]: crate::lens::Lens
+
+use crate::{
+    image::Image,
+    pixel::ChannelFlags,
+};
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+mod neon;
+#[cfg(all(target_arch = "x86_64", target_feature = "sse2"))]
+mod sse2;
+#[cfg(any(
+    all(target_arch = "x86_64", target_feature = "sse2"),
+    all(target_arch = "aarch64", target_feature = "neon")
+))]
+mod vector;
+
+/// Targets without supported vector instructions use scalar lenses.
+#[cfg(not(any(
+    all(target_arch = "x86_64", target_feature = "sse2"),
+    all(target_arch = "aarch64", target_feature = "neon")
+)))]
+mod vector {
+    pub(super) use crate::operation::color::{
+        brightness,
+        grayscale,
+        negative,
+    };
+}
+
+/// Number of `u8` lanes processed at once.
+pub const LANES: usize = 16;
+
+/// Apply negative filter to an image, vectorized version of [`negative`].
+///
+/// [`negative`]: crate::operation::color::negative
+pub fn negative_simd(image: &Image, flags: ChannelFlags) -> Image {
+    vector::negative(image, flags)
+}
+
+/// Adjust brightness of an image, vectorized version of [`brightness`].
+///
+/// [`brightness`]: crate::operation::color::brightness
+pub fn brightness_simd(image: &Image, amount: i16, flags: ChannelFlags) -> Image {
+    vector::brightness(image, amount, flags)
+}
+
+/// Apply grayscale filter to an image, vectorized version of [`grayscale`].
+///
+/// [`grayscale`]: crate::operation::color::grayscale
+pub fn grayscale_simd(image: &Image, flags: ChannelFlags) -> Image {
+    vector::grayscale(image, flags)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use crate::{
+        component::primitive::Size,
+        operation::color::{
+            brightness,
+            grayscale,
+            negative,
+        },
+    };
+
+    use super::*;
+
+    const FLAGS: [ChannelFlags; 4] =
+        [ChannelFlags::RGBA, ChannelFlags::RGB, ChannelFlags::BLUE, ChannelFlags::empty()];
+
+    fn random_image() -> Image {
+        // 7x5 has area not divisible by 4 pixels, so padding of vector chunks is exercised too.
+        Image::random(Size::new(7, 5).unwrap(), &mut SmallRng::seed_from_u64(0))
+    }
+
+    #[test]
+    fn test_negative_simd_matches_scalar() {
+        let image = random_image();
+        for flags in FLAGS {
+            assert_eq!(negative_simd(&image, flags).buffer(), negative(&image, flags).buffer());
+        }
+    }
+
+    #[test]
+    fn test_brightness_simd_matches_scalar() {
+        let image = random_image();
+        for flags in FLAGS {
+            for amount in [-300, -40, 0, 17, 255, 400] {
+                assert_eq!(
+                    brightness_simd(&image, amount, flags).buffer(),
+                    brightness(&image, amount, flags).buffer()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_grayscale_simd_matches_scalar() {
+        let image = random_image();
+        for flags in FLAGS {
+            assert_eq!(grayscale_simd(&image, flags).buffer(), grayscale(&image, flags).buffer());
+        }
+    }
+}
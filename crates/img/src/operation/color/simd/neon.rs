@@ -0,0 +1,91 @@
+use std::arch::aarch64::*;
+
+use super::{
+    LANES,
+    vector::PIXELS_PER_CHUNK,
+};
+use crate::pixel::{
+    ChannelFlags,
+    PIXEL_SIZE,
+    Pixel,
+};
+
+/// 16 `u8` lanes in a NEON register.
+#[derive(Clone, Copy)]
+pub(super) struct U8x16(uint8x16_t);
+
+impl U8x16 {
+    pub(super) fn load(pixels: &[Pixel; PIXELS_PER_CHUNK]) -> Self {
+        // SAFETY: `Pixel` is a transparent wrapper of `[u8; PIXEL_SIZE]`, so pixels are LANES
+        // contiguous bytes.
+        Self(unsafe { vld1q_u8(pixels.as_ptr().cast()) })
+    }
+
+    pub(super) fn store(self, pixels: &mut [Pixel; PIXELS_PER_CHUNK]) {
+        // SAFETY: see `load`.
+        unsafe { vst1q_u8(pixels.as_mut_ptr().cast(), self.0) }
+    }
+
+    pub(super) fn splat(value: u8) -> Self {
+        // SAFETY: this module is only compiled with NEON enabled.
+        unsafe { Self(vdupq_n_u8(value)) }
+    }
+
+    /// Create a mask with all bits set in lanes of channels specified in `flags`.
+    pub(super) fn channel_mask(flags: ChannelFlags) -> Self {
+        let channels =
+            [ChannelFlags::RED, ChannelFlags::GREEN, ChannelFlags::BLUE, ChannelFlags::ALPHA]
+                .map(|channel| if flags.contains(channel) { u8::MAX } else { 0 });
+        let lanes: [u8; LANES] = std::array::from_fn(|lane| channels[lane % PIXEL_SIZE]);
+
+        // SAFETY: array has exactly LANES bytes.
+        Self(unsafe { vld1q_u8(lanes.as_ptr()) })
+    }
+
+    pub(super) fn xor(self, other: Self) -> Self {
+        // SAFETY: this module is only compiled with NEON enabled.
+        unsafe { Self(veorq_u8(self.0, other.0)) }
+    }
+
+    pub(super) fn saturating_add(self, other: Self) -> Self {
+        // SAFETY: this module is only compiled with NEON enabled.
+        unsafe { Self(vqaddq_u8(self.0, other.0)) }
+    }
+
+    pub(super) fn saturating_sub(self, other: Self) -> Self {
+        // SAFETY: this module is only compiled with NEON enabled.
+        unsafe { Self(vqsubq_u8(self.0, other.0)) }
+    }
+
+    /// Take lanes from `on` where mask is set and from `off` elsewhere.
+    pub(super) fn select(self, on: Self, off: Self) -> Self {
+        // SAFETY: this module is only compiled with NEON enabled.
+        unsafe { Self(vbslq_u8(self.0, on.0, off.0)) }
+    }
+
+    /// Replace every channel of each pixel with its gray value, computed the same way as the
+    /// scalar grayscale filter.
+    pub(super) fn gray(self) -> Self {
+        // SAFETY: this module is only compiled with NEON enabled.
+        unsafe {
+            let pixels = vreinterpretq_u32_u8(self.0);
+            let byte = vdupq_n_u32(u8::MAX as u32);
+            let channel = |shifted: uint32x4_t| vcvtq_f32_u32(vandq_u32(shifted, byte));
+            let red = channel(pixels);
+            let green = channel(vshrq_n_u32::<8>(pixels));
+            let blue = channel(vshrq_n_u32::<16>(pixels));
+
+            // Multiplications and additions are kept separate, fused multiply-add would round
+            // differently than the scalar filter.
+            let value = vaddq_f32(
+                vaddq_f32(vmulq_n_f32(red, 0.299), vmulq_n_f32(green, 0.587)),
+                vmulq_n_f32(blue, 0.214),
+            );
+            // Conversion truncates like `as u8`, which also saturates at 255.
+            let value = vcvtq_u32_f32(vminq_f32(value, vdupq_n_f32(u8::MAX as f32)));
+            let value = vorrq_u32(value, vshlq_n_u32::<8>(value));
+
+            Self(vreinterpretq_u8_u32(vorrq_u32(value, vshlq_n_u32::<16>(value))))
+        }
+    }
+}
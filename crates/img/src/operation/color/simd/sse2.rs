@@ -0,0 +1,91 @@
+use std::arch::x86_64::*;
+
+use super::{
+    LANES,
+    vector::PIXELS_PER_CHUNK,
+};
+use crate::pixel::{
+    ChannelFlags,
+    PIXEL_SIZE,
+    Pixel,
+};
+
+/// 16 `u8` lanes in an SSE2 register.
+#[derive(Clone, Copy)]
+pub(super) struct U8x16(__m128i);
+
+impl U8x16 {
+    pub(super) fn load(pixels: &[Pixel; PIXELS_PER_CHUNK]) -> Self {
+        // SAFETY: `Pixel` is a transparent wrapper of `[u8; PIXEL_SIZE]`, so pixels are LANES
+        // contiguous bytes, and unaligned loads have no alignment requirements.
+        Self(unsafe { _mm_loadu_si128(pixels.as_ptr().cast()) })
+    }
+
+    pub(super) fn store(self, pixels: &mut [Pixel; PIXELS_PER_CHUNK]) {
+        // SAFETY: see `load`.
+        unsafe { _mm_storeu_si128(pixels.as_mut_ptr().cast(), self.0) }
+    }
+
+    pub(super) fn splat(value: u8) -> Self {
+        // SAFETY: this module is only compiled with SSE2 enabled.
+        unsafe { Self(_mm_set1_epi8(value as i8)) }
+    }
+
+    /// Create a mask with all bits set in lanes of channels specified in `flags`.
+    pub(super) fn channel_mask(flags: ChannelFlags) -> Self {
+        let channels =
+            [ChannelFlags::RED, ChannelFlags::GREEN, ChannelFlags::BLUE, ChannelFlags::ALPHA]
+                .map(|channel| if flags.contains(channel) { u8::MAX } else { 0 });
+        let lanes: [u8; LANES] = std::array::from_fn(|lane| channels[lane % PIXEL_SIZE]);
+
+        // SAFETY: array has exactly LANES bytes.
+        Self(unsafe { _mm_loadu_si128(lanes.as_ptr().cast()) })
+    }
+
+    pub(super) fn xor(self, other: Self) -> Self {
+        // SAFETY: this module is only compiled with SSE2 enabled.
+        unsafe { Self(_mm_xor_si128(self.0, other.0)) }
+    }
+
+    pub(super) fn saturating_add(self, other: Self) -> Self {
+        // SAFETY: this module is only compiled with SSE2 enabled.
+        unsafe { Self(_mm_adds_epu8(self.0, other.0)) }
+    }
+
+    pub(super) fn saturating_sub(self, other: Self) -> Self {
+        // SAFETY: this module is only compiled with SSE2 enabled.
+        unsafe { Self(_mm_subs_epu8(self.0, other.0)) }
+    }
+
+    /// Take lanes from `on` where mask is set and from `off` elsewhere.
+    pub(super) fn select(self, on: Self, off: Self) -> Self {
+        // SAFETY: this module is only compiled with SSE2 enabled.
+        unsafe { Self(_mm_or_si128(_mm_and_si128(self.0, on.0), _mm_andnot_si128(self.0, off.0))) }
+    }
+
+    /// Replace every channel of each pixel with its gray value, computed the same way as the
+    /// scalar grayscale filter.
+    pub(super) fn gray(self) -> Self {
+        // SAFETY: this module is only compiled with SSE2 enabled.
+        unsafe {
+            let byte = _mm_set1_epi32(u8::MAX as i32);
+            let channel = |shifted: __m128i| _mm_cvtepi32_ps(_mm_and_si128(shifted, byte));
+            let red = channel(self.0);
+            let green = channel(_mm_srli_epi32::<8>(self.0));
+            let blue = channel(_mm_srli_epi32::<16>(self.0));
+
+            let value = _mm_add_ps(
+                _mm_add_ps(
+                    _mm_mul_ps(_mm_set1_ps(0.299), red),
+                    _mm_mul_ps(_mm_set1_ps(0.587), green),
+                ),
+                _mm_mul_ps(_mm_set1_ps(0.214), blue),
+            );
+            // Conversion truncates like `as u8`, which also saturates at 255.
+            let value = _mm_cvttps_epi32(_mm_min_ps(value, _mm_set1_ps(u8::MAX as f32)));
+            let value = _mm_or_si128(value, _mm_slli_epi32::<8>(value));
+
+            Self(_mm_or_si128(value, _mm_slli_epi32::<16>(value)))
+        }
+    }
+}
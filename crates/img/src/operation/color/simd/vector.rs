@@ -0,0 +1,69 @@
+use super::LANES;
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+use super::neon::U8x16;
+#[cfg(all(target_arch = "x86_64", target_feature = "sse2"))]
+use super::sse2::U8x16;
+use crate::{
+    image::Image,
+    pixel::{
+        ChannelFlags,
+        PIXEL_SIZE,
+        Pixel,
+    },
+};
+
+/// Number of pixels processed at once.
+pub(super) const PIXELS_PER_CHUNK: usize = LANES / PIXEL_SIZE;
+
+pub(super) fn negative(image: &Image, flags: ChannelFlags) -> Image {
+    let mask = U8x16::channel_mask(flags);
+
+    map_chunks(image, |chunk| chunk.xor(mask))
+}
+
+pub(super) fn brightness(image: &Image, amount: i16, flags: ChannelFlags) -> Image {
+    let mask = U8x16::channel_mask(flags);
+    let delta = U8x16::splat(amount.unsigned_abs().min(u8::MAX as u16) as u8);
+
+    if amount >= 0 {
+        map_chunks(image, |chunk| mask.select(chunk.saturating_add(delta), chunk))
+    } else {
+        map_chunks(image, |chunk| mask.select(chunk.saturating_sub(delta), chunk))
+    }
+}
+
+pub(super) fn grayscale(image: &Image, flags: ChannelFlags) -> Image {
+    let mask = U8x16::channel_mask(flags);
+
+    map_chunks(image, |chunk| mask.select(chunk.gray(), chunk))
+}
+
+/// Apply `f` to every chunk of [`PIXELS_PER_CHUNK`] pixels. The trailing chunk is zero padded,
+/// lanes are independent so padding does not affect results.
+fn map_chunks<F>(image: &Image, f: F) -> Image
+where
+    F: Fn(U8x16) -> U8x16,
+{
+    let source = image.pixels();
+    let mut pixels = vec![Pixel::zero(); source.len()].into_boxed_slice();
+
+    let mut chunks = source.chunks_exact(PIXELS_PER_CHUNK);
+    let mut outputs = pixels.chunks_exact_mut(PIXELS_PER_CHUNK);
+    for (chunk, output) in (&mut chunks).zip(&mut outputs) {
+        // SAFETY: exact chunks always have PIXELS_PER_CHUNK pixels.
+        let chunk = chunk.try_into().expect("unexpected error in slice conversion");
+        let output = output.try_into().expect("unexpected error in slice conversion");
+        f(U8x16::load(chunk)).store(output);
+    }
+
+    let remainder = chunks.remainder();
+    if !remainder.is_empty() {
+        let mut padded = [Pixel::zero(); PIXELS_PER_CHUNK];
+        padded[..remainder.len()].copy_from_slice(remainder);
+        f(U8x16::load(&padded)).store(&mut padded);
+        outputs.into_remainder().copy_from_slice(&padded[..remainder.len()]);
+    }
+
+    // SAFETY: every source pixel is mapped to exactly one pixel.
+    Image::new(image.size(), pixels).expect("unexpected error in Image::new")
+}
@@ -0,0 +1,127 @@
+#[cfg(feature = "parallel")]
+use std::num::NonZeroUsize;
+
+use crate::{
+    image::Image,
+    lens::{
+        FromLens,
+        Lens,
+    },
+    pixel::{
+        ChannelFlags,
+        Pixel,
+        PixelRgbaf32,
+        srgb::{
+            from_linear,
+            to_linear,
+        },
+    },
+};
+
+/// Curve used by [`tone_map_lens`] to compress linear light values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToneMapOperator {
+    /// `x / (1 + x)`, gentle curve which keeps dark values almost unchanged.
+    Reinhard,
+    /// Narkowicz's fit of the ACES filmic curve, with higher contrast and a soft shoulder.
+    AcesFilmic,
+}
+
+impl ToneMapOperator {
+    /// Apply the curve to a linear light value.
+    fn apply(&self, value: f32) -> f32 {
+        match self {
+            ToneMapOperator::Reinhard => value / (1f32 + value),
+            ToneMapOperator::AcesFilmic => {
+                (value * (2.51 * value + 0.03)) / (value * (2.43 * value + 0.59) + 0.14)
+            }
+        }
+    }
+}
+
+/// Create a [`Lens`] compressing RGB channels of `source` with `operator`. Channels are decoded
+/// to linear light, mapped and encoded back to sRGB, so bright values are rolled off smoothly
+/// instead of clipping. Alpha is left unchanged.
+pub fn tone_map_lens<S>(source: S, operator: ToneMapOperator) -> impl Lens<Item = Pixel>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    source.map(move |px| map_px(px, operator))
+}
+
+/// Compress RGB channels of an image with `operator`, see [`tone_map_lens`].
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     operation::color::{
+///         ToneMapOperator,
+///         tone_map,
+///     },
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let image = Image::new(Size::new(1, 1)?, Box::new([Pixel::new([255, 255, 255, 255])]))?;
+/// let mapped = tone_map(&image, ToneMapOperator::Reinhard);
+///
+/// assert!(mapped.pixel(Point::zero())?.r() < 255);
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn tone_map(image: &Image, operator: ToneMapOperator) -> Image {
+    let lens = tone_map_lens(image.lens(), operator);
+    Image::from_lens(lens)
+}
+
+/// Compress RGB channels of an image with `operator` in parallel, see [`tone_map_lens`].
+#[cfg(feature = "parallel")]
+pub fn tone_map_par(image: &Image, threads: NonZeroUsize, operator: ToneMapOperator) -> Image {
+    use crate::lens::FromLensPar;
+
+    let lens = tone_map_lens(image.lens(), operator);
+    Image::from_lens_par(lens, threads)
+}
+
+fn map_px(px: impl AsRef<Pixel>, operator: ToneMapOperator) -> Pixel {
+    let mut px = *px.as_ref();
+    let map = |value: f32| from_linear(operator.apply(to_linear(value)));
+    let (r, g, b) = (map(px.r_f32()), map(px.g_f32()), map(px.b_f32()));
+    px.set_with_flags_f32(r, g, b, 0f32, ChannelFlags::RGB);
+
+    px
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gray(value: u8) -> Pixel {
+        Pixel::new([value, value, value, 255])
+    }
+
+    #[test]
+    fn test_tone_map_compresses_bright_values_smoothly() {
+        for operator in [ToneMapOperator::Reinhard, ToneMapOperator::AcesFilmic] {
+            let mapped: Vec<_> = (0..=255).map(|value| map_px(gray(value), operator).r()).collect();
+
+            assert!(mapped[255] < 255);
+            assert!(mapped.windows(2).all(|pair| pair[0] <= pair[1]));
+            // No sudden jumps, so bright values are not clipped.
+            assert!(mapped.windows(2).all(|pair| pair[1] - pair[0] <= 4));
+        }
+    }
+
+    #[test]
+    fn test_tone_map_reinhard_preserves_dark_values() {
+        for value in 0..=40 {
+            let mapped = map_px(gray(value), ToneMapOperator::Reinhard);
+
+            assert!(mapped.r().abs_diff(value) <= 2);
+            assert_eq!(mapped.a(), 255);
+        }
+    }
+}
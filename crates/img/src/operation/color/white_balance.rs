@@ -0,0 +1,149 @@
+#[cfg(feature = "parallel")]
+use std::num::NonZeroUsize;
+
+use crate::{
+    image::Image,
+    lens::{
+        FromLens,
+        Lens,
+    },
+    pixel::{
+        ChannelFlags,
+        Pixel,
+        PixelRgbaf32,
+    },
+};
+
+/// Temperature in kelvins which is treated as neutral, leaving colors unchanged.
+pub const NEUTRAL_TEMPERATURE: f32 = 6500f32;
+
+/// Range of temperatures in kelvins in which the blackbody approximation is valid, temperatures
+/// outside of it are clamped.
+const TEMPERATURE_RANGE: (f32, f32) = (1000f32, 40000f32);
+
+/// Create a [`Lens`] that shifts white balance of `source` towards a light source of
+/// `temperature_kelvin`. Temperatures below [`NEUTRAL_TEMPERATURE`] warm the image up (more
+/// red, less blue), higher temperatures cool it down.
+///
+/// RGB channels are scaled by the color of a blackbody radiator of given temperature, relative
+/// to the color at [`NEUTRAL_TEMPERATURE`]. Alpha is left unchanged.
+pub fn white_balance_lens<S>(source: S, temperature_kelvin: f32) -> impl Lens<Item = Pixel>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    let factors = channel_factors(temperature_kelvin);
+
+    source.map(move |px| map_px(px, factors))
+}
+
+/// Shift white balance of an image towards a light source of `temperature_kelvin`.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     operation::color::white_balance,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let image = Image::new(Size::new(1, 1)?, Box::new([Pixel::new([128, 128, 128, 255])]))?;
+/// let warm = white_balance(&image, 3000.0);
+/// let px = warm.pixel(Point::zero())?;
+///
+/// assert!(px.r() > px.b());
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn white_balance(image: &Image, temperature_kelvin: f32) -> Image {
+    let lens = white_balance_lens(image.lens(), temperature_kelvin);
+    Image::from_lens(lens)
+}
+
+/// Shift white balance of an image towards a light source of `temperature_kelvin` in parallel.
+#[cfg(feature = "parallel")]
+pub fn white_balance_par(image: &Image, threads: NonZeroUsize, temperature_kelvin: f32) -> Image {
+    use crate::lens::FromLensPar;
+
+    let lens = white_balance_lens(image.lens(), temperature_kelvin);
+    Image::from_lens_par(lens, threads)
+}
+
+fn map_px(px: impl AsRef<Pixel>, factors: [f32; 3]) -> Pixel {
+    let mut px = *px.as_ref();
+    let (r, g, b) = (px.r_f32() * factors[0], px.g_f32() * factors[1], px.b_f32() * factors[2]);
+    px.set_with_flags_f32(r, g, b, 0f32, ChannelFlags::RGB);
+
+    px
+}
+
+/// Get RGB channel multipliers shifting neutral white to the color of `temperature_kelvin`.
+fn channel_factors(temperature_kelvin: f32) -> [f32; 3] {
+    let target = blackbody(temperature_kelvin);
+    let neutral = blackbody(NEUTRAL_TEMPERATURE);
+
+    std::array::from_fn(|i| target[i] / neutral[i])
+}
+
+/// Approximate 0-1 normalized RGB color of a blackbody radiator of given temperature, using
+/// curves fitted to blackbody data by Tanner Helland.
+fn blackbody(temperature_kelvin: f32) -> [f32; 3] {
+    let temperature = if temperature_kelvin.is_nan() {
+        NEUTRAL_TEMPERATURE
+    } else {
+        temperature_kelvin.clamp(TEMPERATURE_RANGE.0, TEMPERATURE_RANGE.1)
+    } / 100f32;
+
+    let red = if temperature <= 66f32 {
+        255f32
+    } else {
+        329.69873 * (temperature - 60f32).powf(-0.13320476)
+    };
+    let green = if temperature <= 66f32 {
+        99.4708 * temperature.ln() - 161.11957
+    } else {
+        288.12216 * (temperature - 60f32).powf(-0.075514846)
+    };
+    let blue = if temperature >= 66f32 {
+        255f32
+    } else if temperature <= 19f32 {
+        0f32
+    } else {
+        138.51773 * (temperature - 10f32).ln() - 305.0448
+    };
+
+    // Keep channels strictly positive, so that they can be used as divisors.
+    [red, green, blue].map(|channel| channel.clamp(1f32, 255f32) / 255f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::component::primitive::Size;
+
+    use super::*;
+
+    #[test]
+    fn test_white_balance_neutral_temperature() {
+        let gray = Pixel::new([120, 120, 120, 200]);
+        let image = Image::new(Size::new(1, 1).unwrap(), Box::new([gray])).unwrap();
+
+        let balanced = white_balance(&image, NEUTRAL_TEMPERATURE);
+
+        assert_eq!(balanced.pixels()[0], gray);
+    }
+
+    #[test]
+    fn test_white_balance_warms_and_cools() {
+        let gray = Pixel::new([120, 120, 120, 200]);
+
+        let warm = map_px(gray, channel_factors(3000f32));
+        let cool = map_px(gray, channel_factors(12000f32));
+
+        assert!(warm.r() > warm.b());
+        assert!(warm.r() >= gray.r() && warm.b() < gray.b());
+        assert!(cool.b() > cool.r());
+        assert_eq!((warm.a(), cool.a()), (200, 200));
+    }
+}
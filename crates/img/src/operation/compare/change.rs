@@ -0,0 +1,170 @@
+use crate::{
+    component::kernel::morphology::{
+        MorphologyKernel,
+        MorphologyOperation,
+        StructuringElement,
+    },
+    image::{
+        Image,
+        ResultError,
+    },
+    lens::{
+        FromLens,
+        kernel::pad_for_kernel,
+    },
+    pixel::{
+        ChannelFlags,
+        Pixel,
+    },
+};
+
+/// Value of mask pixels where images differ.
+const CHANGED: Pixel = Pixel::new([255, 255, 255, 255]);
+/// Value of mask pixels where images are the same.
+const UNCHANGED: Pixel = Pixel::new([0, 0, 0, 255]);
+
+/// Create a binary mask of pixels where luminance of `a` and `b` differs by more than
+/// `threshold`, e.g. to detect motion between two frames.
+///
+/// Changed pixels are white, unchanged ones black, mask is opaque. Luminance is computed with
+/// [`Pixel::luminance_f32`] and rounded to `u8`, alpha is ignored.
+///
+/// Returns [`SizeMismatch`] if sizes of `a` and `b` differ.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     operation::compare::change_mask,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let a = Image::solid(Size::new(4, 4)?, Pixel::new([100, 100, 100, 255]));
+/// let mut b = a.clone();
+/// *b.pixel_mut(Point::new(1, 2)?)? = Pixel::new([160, 160, 160, 255]);
+///
+/// let mask = change_mask(&a, &b, 20)?;
+///
+/// assert_eq!(*mask.pixel(Point::new(1, 2)?)?, Pixel::new([255, 255, 255, 255]));
+/// assert_eq!(*mask.pixel(Point::new(2, 2)?)?, Pixel::new([0, 0, 0, 255]));
+///
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`SizeMismatch`]: crate::image::CreationError::SizeMismatch
+pub fn change_mask(a: &Image, b: &Image, threshold: u8) -> ResultError<Image> {
+    Image::zip_map(a, b, |a, b| match a.luminance().abs_diff(b.luminance()) > threshold {
+        true => CHANGED,
+        false => UNCHANGED,
+    })
+}
+
+/// Create a change mask like [`change_mask`], then clean it up with morphological opening
+/// (erosion followed by dilation) with `element`.
+///
+/// Opening removes changed regions smaller than `element`, like noise or flicker, while larger
+/// regions keep their shape. Mask is padded by repeating its edges, so it keeps the size of the
+/// images and regions touching the border are not eroded from outside.
+///
+/// Returns [`SizeMismatch`] if sizes of `a` and `b` differ or [`InvalidSize`] if `element` is too
+/// big.
+///
+/// [`SizeMismatch`]: crate::image::CreationError::SizeMismatch
+/// [`InvalidSize`]: crate::image::CreationError::InvalidSize
+pub fn change_mask_cleaned(
+    a: &Image,
+    b: &Image,
+    threshold: u8,
+    element: StructuringElement,
+) -> ResultError<Image> {
+    let mask = change_mask(a, b, threshold)?;
+
+    let erode = MorphologyKernel::new(element, MorphologyOperation::Erode, ChannelFlags::RGB)?;
+    let dilate = MorphologyKernel::new(element, MorphologyOperation::Dilate, ChannelFlags::RGB)?;
+    let eroded = Image::from_lens(pad_for_kernel(mask.lens(), erode)?);
+
+    Ok(Image::from_lens(pad_for_kernel(eroded.lens(), dilate)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use super::*;
+    use crate::{
+        component::primitive::{
+            Point,
+            Size,
+        },
+        image::CreationError,
+        lens::Lens,
+    };
+
+    /// Random background and a copy with a brighter 4x3 region at (5, 2) and a single bright
+    /// pixel at (1, 8).
+    fn frames() -> (Image, Image) {
+        let size = Size::new(12, 10).unwrap();
+        let mut rng = SmallRng::seed_from_u64(1250);
+        let background = Image::from_lens(
+            Image::random_gray(size, &mut rng).lens().map(|px| px.lerp(Pixel::zero(), 0.5)),
+        );
+
+        let mut changed = background.clone();
+        for y in 2..5 {
+            for x in 5..9 {
+                *changed.pixel_mut(Point::new(x, y).unwrap()).unwrap() = CHANGED;
+            }
+        }
+        *changed.pixel_mut(Point::new(1, 8).unwrap()).unwrap() = CHANGED;
+
+        (background, changed)
+    }
+
+    fn changed_points(mask: &Image) -> Vec<(usize, usize)> {
+        mask.lens()
+            .map_with_point(|point, px| (*px == CHANGED).then_some((point.x(), point.y())))
+            .elements()
+            .flatten()
+            .collect()
+    }
+
+    #[test]
+    fn test_mask_highlights_changed_region() {
+        let (a, b) = frames();
+
+        let mask = change_mask(&a, &b, 10).unwrap();
+
+        let mut expected = (2..5).flat_map(|y| (5..9).map(move |x| (x, y))).collect::<Vec<_>>();
+        expected.push((1, 8));
+        expected.sort_by_key(|(x, y)| (*y, *x));
+        assert_eq!(changed_points(&mask), expected);
+        assert!(mask.pixels().iter().all(|px| *px == CHANGED || *px == UNCHANGED));
+    }
+
+    #[test]
+    fn test_cleaned_mask_removes_specks() {
+        let (a, b) = frames();
+
+        let mask = change_mask_cleaned(&a, &b, 10, StructuringElement::Square(1)).unwrap();
+
+        let expected = (2..5).flat_map(|y| (5..9).map(move |x| (x, y))).collect::<Vec<_>>();
+        assert_eq!(mask.size(), a.size());
+        assert_eq!(changed_points(&mask), expected);
+    }
+
+    #[test]
+    fn test_identical_and_mismatched() {
+        let (a, _) = frames();
+
+        assert!(change_mask(&a, &a, 0).unwrap().pixels().iter().all(|px| *px == UNCHANGED));
+        assert_eq!(
+            change_mask(&a, &Image::empty(Size::new(2, 2).unwrap()), 0).unwrap_err(),
+            CreationError::SizeMismatch
+        );
+    }
+}
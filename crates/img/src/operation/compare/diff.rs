@@ -0,0 +1,172 @@
+use crate::{
+    image::{
+        Image,
+        ResultError,
+    },
+    lens::{
+        FromLens,
+        Lens,
+    },
+    operation::blend::{
+        ArithmeticOp,
+        image_arithmetic_lens,
+    },
+    pixel::{
+        PIXEL_SIZE,
+        Pixel,
+    },
+};
+
+/// Summary of differences between two images, produced by [`diff`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffReport {
+    max: [u8; PIXEL_SIZE],
+    mean: [f32; PIXEL_SIZE],
+    differing_pixels: usize,
+}
+
+impl DiffReport {
+    /// Get maximum absolute difference of each channel, in RGBA order.
+    pub fn max(&self) -> [u8; PIXEL_SIZE] {
+        self.max
+    }
+
+    /// Get mean absolute difference of each channel, in RGBA order.
+    pub fn mean(&self) -> [f32; PIXEL_SIZE] {
+        self.mean
+    }
+
+    /// Get number of pixels which differ in at least one channel.
+    pub fn differing_pixels(&self) -> usize {
+        self.differing_pixels
+    }
+
+    /// Check if images were identical.
+    pub fn is_identical(&self) -> bool {
+        self.differing_pixels == 0
+    }
+}
+
+/// Compare two images of the same size.
+///
+/// Returns [`DiffReport`] or [`SizeMismatch`] if sizes of `a` and `b` differ.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     operation::compare::diff,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let image = Image::empty(Size::new(4, 4)?);
+/// let report = diff(&image, &image)?;
+///
+/// assert!(report.is_identical());
+///
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`SizeMismatch`]: crate::image::CreationError::SizeMismatch
+pub fn diff(a: &Image, b: &Image) -> ResultError<DiffReport> {
+    let differences = image_arithmetic_lens(a.lens(), b.lens(), ArithmeticOp::Difference)?;
+
+    let mut max = [0u8; PIXEL_SIZE];
+    let mut sum = [0u64; PIXEL_SIZE];
+    let mut differing_pixels = 0;
+
+    for difference in differences.elements() {
+        let difference = *difference.buffer();
+        for channel in 0..PIXEL_SIZE {
+            max[channel] = max[channel].max(difference[channel]);
+            sum[channel] += difference[channel] as u64;
+        }
+
+        if difference != [0; PIXEL_SIZE] {
+            differing_pixels += 1;
+        }
+    }
+
+    let area = a.size().area() as f32;
+
+    Ok(DiffReport { max, mean: sum.map(|sum| sum as f32 / area), differing_pixels })
+}
+
+/// Create an image visualizing absolute difference of RGB channels of two images of the same
+/// size, [`ArithmeticOp::Difference`] with alpha dropped. Resulting image is fully opaque, so
+/// identical regions are black.
+///
+/// Returns [`SizeMismatch`] if sizes of `a` and `b` differ.
+///
+/// [`SizeMismatch`]: crate::image::CreationError::SizeMismatch
+pub fn diff_image(a: &Image, b: &Image) -> ResultError<Image> {
+    let lens = image_arithmetic_lens(a.lens(), b.lens(), ArithmeticOp::Difference)?.map(|px| {
+        let [r, g, b, _] = *px.buffer();
+        Pixel::new([r, g, b, u8::MAX])
+    });
+
+    Ok(Image::from_lens(lens))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use crate::{
+        component::primitive::{
+            Point,
+            Size,
+        },
+        image::CreationError,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_diff_identical() {
+        let image = Image::random(Size::new(8, 6).unwrap(), &mut SmallRng::seed_from_u64(0));
+
+        let report = diff(&image, &image.clone()).unwrap();
+
+        assert!(report.is_identical());
+        assert_eq!(report.max(), [0; PIXEL_SIZE]);
+        assert_eq!(report.mean(), [0f32; PIXEL_SIZE]);
+    }
+
+    #[test]
+    fn test_diff_single_pixel() {
+        let a = Image::random(Size::new(8, 6).unwrap(), &mut SmallRng::seed_from_u64(0));
+        let mut b = a.clone();
+        let point = Point::new(3, 2).unwrap();
+        let px = b.pixel_mut(point).unwrap();
+        px.set_g(px.g().wrapping_add(48));
+        let expected = px.g().abs_diff(a.pixel(point).unwrap().g());
+
+        let report = diff(&a, &b).unwrap();
+
+        assert_eq!(report.differing_pixels(), 1);
+        assert_eq!(report.max(), [0, expected, 0, 0]);
+        assert!((report.mean()[1] - expected as f32 / 48f32).abs() < 1e-6);
+
+        let visualized = diff_image(&a, &b).unwrap();
+        assert_eq!(*visualized.pixel(point).unwrap(), Pixel::new([0, expected, 0, 255]));
+        assert_eq!(
+            *visualized.pixel(Point::new(0, 0).unwrap()).unwrap(),
+            Pixel::new([0, 0, 0, 255])
+        );
+    }
+
+    #[test]
+    fn test_diff_size_mismatch() {
+        let a = Image::empty(Size::new(4, 4).unwrap());
+        let b = Image::empty(Size::new(4, 5).unwrap());
+
+        assert_eq!(diff(&a, &b).unwrap_err(), CreationError::SizeMismatch);
+        assert_eq!(diff_image(&a, &b).unwrap_err(), CreationError::SizeMismatch);
+    }
+}
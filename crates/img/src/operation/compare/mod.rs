@@ -0,0 +1,19 @@
+mod change;
+mod diff;
+mod quality;
+
+pub use change::{
+    change_mask,
+    change_mask_cleaned,
+};
+pub use diff::{
+    DiffReport,
+    diff,
+    diff_image,
+};
+pub use quality::{
+    QualityCreationError,
+    QualityCreationResult,
+    psnr,
+    ssim,
+};
@@ -0,0 +1,188 @@
+use thiserror::Error;
+
+use crate::{
+    component::{
+        primitive::{
+            Area,
+            Point,
+            Size,
+        },
+        summed_area::SummedArea,
+    },
+    gray_image::REC601_WEIGHTS,
+    image::Image,
+};
+
+/// Error returned by quality metric functions
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum QualityCreationError {
+    #[error("images have different sizes")]
+    SizeMismatch,
+    #[error("window must be non-zero and fit within the images")]
+    WindowInvalid,
+}
+
+pub type QualityCreationResult<T> = std::result::Result<T, QualityCreationError>;
+
+/// Constant stabilizing SSIM division with weak means, `(0.01 * 255)^2`.
+const SSIM_C1: f64 = 6.5025;
+/// Constant stabilizing SSIM division with weak variances, `(0.03 * 255)^2`.
+const SSIM_C2: f64 = 58.5225;
+
+/// Calculate peak signal-to-noise ratio in decibels between RGB channels of two images of the
+/// same size. Identical images give [`f32::INFINITY`].
+///
+/// Returns [`QualityCreationError::SizeMismatch`] if sizes of `a` and `b` differ.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     operation::compare::psnr,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let image = Image::empty(Size::new(4, 4)?);
+///
+/// assert_eq!(psnr(&image, &image)?, f32::INFINITY);
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn psnr(a: &Image, b: &Image) -> QualityCreationResult<f32> {
+    if a.size() != b.size() {
+        return Err(QualityCreationError::SizeMismatch);
+    }
+
+    let squared_error: u64 = a
+        .pixels()
+        .iter()
+        .zip(b.pixels())
+        .flat_map(|(px_a, px_b)| {
+            (0..3).map(move |channel| {
+                (px_a.buffer()[channel].abs_diff(px_b.buffer()[channel]) as u64).pow(2)
+            })
+        })
+        .sum();
+
+    if squared_error == 0 {
+        return Ok(f32::INFINITY);
+    }
+
+    let mse = squared_error as f64 / (3 * a.size().area()) as f64;
+
+    Ok((10f64 * (255f64 * 255f64 / mse).log10()) as f32)
+}
+
+/// Calculate mean structural similarity index of luma of two images of the same size, using
+/// uniform square windows of `window` side placed at every position fully within the images.
+/// Identical images give 1.0, lower values mean less similar images.
+///
+/// Returns [`QualityCreationError`] if sizes of `a` and `b` differ or `window` is zero or larger
+/// than the images.
+pub fn ssim(a: &Image, b: &Image, window: usize) -> QualityCreationResult<f32> {
+    if a.size() != b.size() {
+        return Err(QualityCreationError::SizeMismatch);
+    }
+
+    let size = a.size();
+    if window == 0 || window > size.width() || window > size.height() {
+        return Err(QualityCreationError::WindowInvalid);
+    }
+
+    let luma_a = a.to_gray(REC601_WEIGHTS);
+    let luma_b = b.to_gray(REC601_WEIGHTS);
+    let values = |x: u8, y: u8| {
+        let (x, y) = (x as f64, y as f64);
+        [x, y, x * x, y * y, x * y]
+    };
+    let sums = SummedArea::new(
+        luma_a.buffer().iter().zip(luma_b.buffer()).map(|(x, y)| values(*x, *y)),
+        size,
+    );
+    // SAFETY: window was checked to be non-zero and to fit within the images.
+    let window_size = Size::new(window, window).expect("unexpected error in Size::new");
+
+    let count = (window * window) as f64;
+    let mut total = 0f64;
+    let mut windows = 0;
+    for y in 0..=size.height() - window {
+        for x in 0..=size.width() - window {
+            let [sum_a, sum_b, sum_aa, sum_bb, sum_ab] = sums.sum(Area::new(
+                window_size,
+                // SAFETY: x and y are within the images, so they are valid coordinates.
+                Point::new(x, y).expect("unexpected error in Point::new"),
+            ));
+            let (mean_a, mean_b) = (sum_a / count, sum_b / count);
+            let variance_a = sum_aa / count - mean_a * mean_a;
+            let variance_b = sum_bb / count - mean_b * mean_b;
+            let covariance = sum_ab / count - mean_a * mean_b;
+
+            total += ((2f64 * mean_a * mean_b + SSIM_C1) * (2f64 * covariance + SSIM_C2))
+                / ((mean_a * mean_a + mean_b * mean_b + SSIM_C1)
+                    * (variance_a + variance_b + SSIM_C2));
+            windows += 1;
+        }
+    }
+
+    Ok((total / windows as f64) as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use crate::{
+        component::primitive::Size,
+        operation::blur::box_blur,
+        pixel::ChannelFlags,
+    };
+
+    use super::*;
+
+    fn test_image() -> Image {
+        let image = Image::random(Size::new(32, 24).unwrap(), &mut SmallRng::seed_from_u64(0));
+        // Smooth noise a bit so that the image has structure on the scale of SSIM windows.
+        box_blur(&image, 1, ChannelFlags::RGB)
+    }
+
+    #[test]
+    fn test_identical_images() {
+        let image = test_image();
+
+        assert_eq!(psnr(&image, &image).unwrap(), f32::INFINITY);
+        assert!((ssim(&image, &image, 7).unwrap() - 1f32).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_blurred_image_scores_lower() {
+        let image = test_image();
+        let slightly_blurred = box_blur(&image, 1, ChannelFlags::RGB);
+        let blurred = box_blur(&image, 3, ChannelFlags::RGB);
+
+        let psnr_slight = psnr(&image, &slightly_blurred).unwrap();
+        let psnr_strong = psnr(&image, &blurred).unwrap();
+        assert!(psnr_slight.is_finite());
+        assert!(psnr_strong < psnr_slight);
+
+        let ssim_slight = ssim(&image, &slightly_blurred, 7).unwrap();
+        let ssim_strong = ssim(&image, &blurred, 7).unwrap();
+        assert!(ssim_slight < 1f32);
+        assert!(ssim_strong < ssim_slight);
+    }
+
+    #[test]
+    fn test_invalid_params() {
+        let a = Image::empty(Size::new(4, 4).unwrap());
+        let b = Image::empty(Size::new(4, 5).unwrap());
+
+        assert_eq!(psnr(&a, &b).unwrap_err(), QualityCreationError::SizeMismatch);
+        assert_eq!(ssim(&a, &b, 3).unwrap_err(), QualityCreationError::SizeMismatch);
+        assert_eq!(ssim(&a, &a, 0).unwrap_err(), QualityCreationError::WindowInvalid);
+        assert_eq!(ssim(&a, &a, 5).unwrap_err(), QualityCreationError::WindowInvalid);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{
+    component::primitive::{
+        Area,
+        Connectivity,
+        Point,
+        Size,
+    },
+    lens::Lens,
+};
+
+/// Find bounding boxes of blobs in `mask`, which are connected components of non-zero pixels
+/// with at least `min_area` pixels.
+///
+/// Pixels are connected to their neighbors of given `connectivity`, so diagonal lines form a
+/// single blob only with [`Connectivity::Eight`]. Blobs are returned in order of their first pixel
+/// in row-major order.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     lens::value::ValueLens,
+///     operation::detection::blob::detect_blobs,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let mask = ValueLens::new(255u8, Size::new(4, 3)?);
+///
+/// let blobs = detect_blobs(&mask, 1, Connectivity::Four);
+///
+/// assert_eq!(blobs, vec![Area::new(Size::new(4, 3)?, Point::zero())]);
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn detect_blobs(
+    mask: &impl Lens<Item = u8>,
+    min_area: usize,
+    connectivity: Connectivity,
+) -> Vec<Area> {
+    let size = mask.size();
+    let width = size.width();
+    let mut unvisited = mask.elements().map(|value| value != 0).collect::<Vec<_>>();
+    let mut stack = Vec::new();
+    let mut blobs = Vec::new();
+
+    for start in 0..unvisited.len() {
+        if !unvisited[start] {
+            continue;
+        }
+        unvisited[start] = false;
+        stack.push(start);
+
+        let mut area = 0;
+        let (mut min_x, mut min_y) = (usize::MAX, usize::MAX);
+        let (mut max_x, mut max_y) = (0, 0);
+        while let Some(index) = stack.pop() {
+            // SAFETY: only indices within the mask are pushed onto the stack.
+            let point =
+                Point::from_index(index, size).expect("unexpected error in Point::from_index");
+            let (x, y) = (point.x(), point.y());
+            area += 1;
+            (min_x, min_y) = (min_x.min(x), min_y.min(y));
+            (max_x, max_y) = (max_x.max(x), max_y.max(y));
+
+            for neighbor in connectivity.neighbors(point, size) {
+                let neighbor = neighbor.y() * width + neighbor.x();
+                if unvisited[neighbor] {
+                    unvisited[neighbor] = false;
+                    stack.push(neighbor);
+                }
+            }
+        }
+
+        if area >= min_area {
+            // SAFETY: bounding box is within mask, so its size and top left point are valid.
+            let size = Size::new(max_x - min_x + 1, max_y - min_y + 1)
+                .expect("unexpected error in Size::new");
+            let top_left = Point::new(min_x, min_y).expect("unexpected error in Point::new");
+            blobs.push(Area::new(size, top_left));
+        }
+    }
+
+    blobs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        image::Image,
+        lens::value::ValueLens,
+        pixel::Pixel,
+    };
+
+    /// 10x8 mask with an L shaped blob of 7 pixels and a 2x1 blob.
+    fn two_blobs() -> Image {
+        let size = Size::new(10, 8).unwrap();
+        let pixels = (0..size.area())
+            .map(|index| {
+                let (x, y) = (index % 10, index / 10);
+                let large = (x == 2 && (1..6).contains(&y)) || (y == 5 && (3..5).contains(&x));
+                let small = y == 2 && (7..9).contains(&x);
+                let value = if large || small { 255 } else { 0 };
+                Pixel::new([value, value, value, 255])
+            })
+            .collect();
+
+        Image::new(size, pixels).unwrap()
+    }
+
+    #[test]
+    fn test_detect_blobs_min_area() {
+        let image = two_blobs();
+        let mask = image.lens().map(|px| px.r());
+
+        let blobs = detect_blobs(&mask, 3, Connectivity::Four);
+
+        assert_eq!(blobs, vec![Area::new(Size::new(3, 5).unwrap(), Point::new(2, 1).unwrap())]);
+        assert_eq!(detect_blobs(&mask, 1, Connectivity::Four).len(), 2);
+    }
+
+    #[test]
+    fn test_detect_blobs_diagonal() {
+        let size = Size::new(4, 4).unwrap();
+        let pixels = (0..size.area())
+            .map(|index| {
+                let value = if index % 4 == index / 4 { 255 } else { 0 };
+                Pixel::new([value, 0, 0, 255])
+            })
+            .collect();
+        let image = Image::new(size, pixels).unwrap();
+
+        let mask = image.lens().map(|px| px.r());
+
+        assert_eq!(
+            detect_blobs(&mask, 1, Connectivity::Eight),
+            vec![Area::new(size, Point::zero())]
+        );
+        assert_eq!(detect_blobs(&mask, 1, Connectivity::Four).len(), 4);
+    }
+
+    #[test]
+    fn test_detect_blobs_empty() {
+        let mask = ValueLens::new(0u8, Size::new(5, 5).unwrap());
+
+        assert!(detect_blobs(&mask, 0, Connectivity::Eight).is_empty());
+    }
+}
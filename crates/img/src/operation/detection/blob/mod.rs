@@ -0,0 +1,3 @@
+mod detect;
+
+pub use detect::detect_blobs;
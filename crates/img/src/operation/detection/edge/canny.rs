@@ -1,8 +1,12 @@
-use std::f32::consts::PI;
 #[cfg(feature = "parallel")]
 use std::num::NonZeroUsize;
+use std::{
+    cmp::Ordering,
+    f32::consts::PI,
+};
 
 use itertools::Itertools;
+use thiserror::Error;
 
 use crate::{
     component::{
@@ -22,7 +26,10 @@ use crate::{
             Size,
         },
     },
-    error::IndexResult,
+    error::{
+        IndexError,
+        IndexResult,
+    },
     image::Image,
     lens::{
         FromLens,
@@ -34,18 +41,114 @@ use crate::{
     },
 };
 
-// #[derive(Debug, Error)]
-// pub enum CreationError {
-//     #[error("kernel creation error: {0}")]
-//     KernelCreationError(#[from] kernel::CreationError),
-// }
-//
-// pub type CreationResult<T> = std::result::Result<T, CreationError>;
+/// Error returned by canny functions
+#[derive(Debug, Error)]
+pub enum CannyCreationError {
+    #[error("low threshold ({low}) must not be greater than high threshold ({high})")]
+    InvalidThresholds { low: f32, high: f32 },
+}
+
+pub type CannyCreationResult<T> = std::result::Result<T, CannyCreationError>;
+
+/// Number of bins used for automatic threshold selection.
+const AUTO_THRESHOLD_BINS: usize = 256;
+
+/// Ratio of low to high threshold when thresholds are selected automatically.
+const AUTO_LOW_RATIO: f32 = 0.5;
 
-pub fn canny_lens<S>(source: S) -> impl Lens<Item = Pixel>
+/// Options for Canny edge detection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CannyLensOptions {
+    low_threshold: f32,
+    high_threshold: f32,
+    auto_thresholds: bool,
+}
+
+impl Default for CannyLensOptions {
+    fn default() -> Self {
+        Self { low_threshold: 10f32, high_threshold: 20f32, auto_thresholds: false }
+    }
+}
+
+impl CannyLensOptions {
+    /// Create [`CannyLensOptions`] with default fixed thresholds.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Derive hysteresis thresholds from each image instead of using fixed ones.
+    ///
+    /// High threshold is selected with Otsu's method on the histogram of gradient magnitudes
+    /// left after non-maximum suppression, which separates weak edges from strong ones. Low
+    /// threshold is half of the high one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use img::{
+    ///     operation::detection::edge::canny,
+    ///     prelude::*,
+    /// };
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let image = Image::empty(Size::new(10, 10)?);
+    /// let edges = canny(&image, CannyLensOptions::new().auto_thresholds(true))?;
+    ///
+    /// assert_eq!(edges.size(), image.size());
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn auto_thresholds(mut self, value: bool) -> Self {
+        self.auto_thresholds = value;
+        self
+    }
+
+    /// Set low hysteresis threshold, gradient magnitudes below it are never edges.
+    ///
+    /// Ignored if [`auto_thresholds`](Self::auto_thresholds) is enabled.
+    pub fn low_threshold(mut self, value: f32) -> Self {
+        self.low_threshold = value;
+        self
+    }
+
+    /// Set high hysteresis threshold, gradient magnitudes above it are always edges.
+    ///
+    /// Ignored if [`auto_thresholds`](Self::auto_thresholds) is enabled.
+    pub fn high_threshold(mut self, value: f32) -> Self {
+        self.high_threshold = value;
+        self
+    }
+
+    /// Check that low threshold is not greater than high threshold.
+    ///
+    /// Returns [`CannyCreationError::InvalidThresholds`] otherwise, including when any of them
+    /// is NaN.
+    pub fn validate(&self) -> CannyCreationResult<()> {
+        if self.low_threshold.partial_cmp(&self.high_threshold).is_none_or(Ordering::is_gt) {
+            return Err(CannyCreationError::InvalidThresholds {
+                low: self.low_threshold,
+                high: self.high_threshold,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Create a [`Lens`] detecting edges with Canny edge detector, every color channel separately.
+///
+/// Returns [`CannyCreationError::InvalidThresholds`] if low threshold of `options` is greater
+/// than high threshold.
+pub fn canny_lens<S>(
+    source: S,
+    options: CannyLensOptions,
+) -> CannyCreationResult<impl Lens<Item = Pixel>>
 where
     S: Lens<Item = Pixel> + Clone,
 {
+    options.validate()?;
+
     let lens = value_border(
         source,
         Margin::unified(2).expect("unexpected error in Margin::unified"),
@@ -54,30 +157,39 @@ where
     .expect("TODO");
 
     // SAFETY: `Size::from_radius(2)` is always successful.
-    lens.kernel(
-        GaussianKernel::new(
-            Size::from_radius(2).expect("unexpected error in Size::from_radius"),
-            2f32,
-            ChannelFlags::RGB,
+    let lens = lens
+        .kernel(
+            GaussianKernel::new(
+                Size::from_radius(2).expect("unexpected error in Size::from_radius"),
+                2f32,
+                ChannelFlags::RGB,
+            )
+            .expect("TODO"),
         )
-        .expect("TODO"),
-    )
-    .expect("TODO")
-    .materialize()
-    .split4(
-        |s| single_channel_lens(s.map(|p| p.r())),
-        |s| single_channel_lens(s.map(|p| p.g())),
-        |s| single_channel_lens(s.map(|p| p.b())),
-        |s| s.map(|p| p.a()),
-    )
-    .map(|(r, g, b, a)| Pixel::new([r, g, b, a]))
+        .expect("TODO")
+        .materialize()
+        .split4(
+            |s| single_channel_lens(s.map(|p| p.r()), options),
+            |s| single_channel_lens(s.map(|p| p.g()), options),
+            |s| single_channel_lens(s.map(|p| p.b()), options),
+            |s| s.map(|p| p.a()),
+        )
+        .map(|(r, g, b, a)| Pixel::new([r, g, b, a]));
+
+    Ok(lens)
 }
 
 #[cfg(feature = "parallel")]
-pub fn canny_lens_par<S>(source: S, threads: NonZeroUsize) -> impl Lens<Item = Pixel>
+pub fn canny_lens_par<S>(
+    source: S,
+    threads: NonZeroUsize,
+    options: CannyLensOptions,
+) -> CannyCreationResult<impl Lens<Item = Pixel>>
 where
     S: Lens<Item = Pixel> + Clone + Send + Sync,
 {
+    options.validate()?;
+
     let lens = value_border(
         source,
         Margin::unified(2).expect("unexpected error in Margin::unified"),
@@ -86,39 +198,48 @@ where
     .expect("TODO");
 
     // SAFETY: `Size::from_radius(2)` is always successful.
-    lens.kernel(
-        GaussianKernel::new(
-            Size::from_radius(2).expect("unexpected error in Size::from_radius"),
-            2f32,
-            ChannelFlags::RGB,
+    let lens = lens
+        .kernel(
+            GaussianKernel::new(
+                Size::from_radius(2).expect("unexpected error in Size::from_radius"),
+                2f32,
+                ChannelFlags::RGB,
+            )
+            .expect("TODO"),
         )
-        .expect("TODO"),
-    )
-    .expect("TODO")
-    .materialize_par(threads)
-    .split4(
-        |s| single_channel_lens(s.map(|p| p.r())),
-        |s| single_channel_lens(s.map(|p| p.g())),
-        |s| single_channel_lens(s.map(|p| p.b())),
-        |s| s.map(|p| p.a()),
-    )
-    .map(|(r, g, b, a)| Pixel::new([r, g, b, a]))
+        .expect("TODO")
+        .materialize_par(threads)
+        .split4(
+            |s| single_channel_lens(s.map(|p| p.r()), options),
+            |s| single_channel_lens(s.map(|p| p.g()), options),
+            |s| single_channel_lens(s.map(|p| p.b()), options),
+            |s| s.map(|p| p.a()),
+        )
+        .map(|(r, g, b, a)| Pixel::new([r, g, b, a]));
+
+    Ok(lens)
 }
 
-pub fn canny(image: &Image) -> Image {
-    let lens = canny_lens(image.lens().cloned());
-    Image::from_lens(lens)
+/// Detect edges in an image, see [`canny_lens`].
+pub fn canny(image: &Image, options: CannyLensOptions) -> CannyCreationResult<Image> {
+    let lens = canny_lens(image.lens().cloned(), options)?;
+    Ok(Image::from_lens(lens))
 }
 
+/// Detect edges in an image in parallel, see [`canny_lens`].
 #[cfg(feature = "parallel")]
-pub fn canny_par(image: &Image, threads: NonZeroUsize) -> Image {
+pub fn canny_par(
+    image: &Image,
+    threads: NonZeroUsize,
+    options: CannyLensOptions,
+) -> CannyCreationResult<Image> {
     use crate::lens::FromLensPar;
 
-    let lens = canny_lens_par(image.lens().cloned(), threads);
-    Image::from_lens_par(lens, threads)
+    let lens = canny_lens_par(image.lens().cloned(), threads, options)?;
+    Ok(Image::from_lens_par(lens, threads))
 }
 
-fn single_channel_lens<S>(source: S) -> impl Lens<Item = u8>
+fn single_channel_lens<S>(source: S, options: CannyLensOptions) -> impl Lens<Item = u8>
 where
     S: Lens<Item = u8>,
 {
@@ -132,11 +253,64 @@ where
         Default::default(),
     )
     .expect("TODO");
-    let lens = non_maximum_suppression_lens(lens);
+    // Hysteresis looks at every magnitude multiple times and automatic thresholds need
+    // statistics of all of them, so magnitudes are computed only once.
+    let lens = non_maximum_suppression_lens(lens).materialize();
+    let (min, max) = if options.auto_thresholds {
+        auto_thresholds(&lens)
+    } else {
+        (options.low_threshold, options.high_threshold)
+    };
     let lens =
         value_border(lens, Margin::unified(1).expect("unexpected error in Margin::unified"), 0f32)
             .expect("TODO");
-    hysteresis_thresholding_lens(lens)
+    hysteresis_thresholding_lens(lens, min, max)
+}
+
+/// Select `(low, high)` hysteresis thresholds from non-zero gradient magnitudes using Otsu's
+/// method.
+fn auto_thresholds<S>(magnitudes: &S) -> (f32, f32)
+where
+    S: Lens<Item = f32>,
+{
+    let max = magnitudes.elements().fold(0f32, f32::max);
+    if max <= 0f32 {
+        return (f32::INFINITY, f32::INFINITY);
+    }
+
+    let bin_width = max / AUTO_THRESHOLD_BINS as f32;
+    let mut bins = [0usize; AUTO_THRESHOLD_BINS];
+    for magnitude in magnitudes.elements().filter(|magnitude| *magnitude > 0f32) {
+        let bin = ((magnitude / bin_width) as usize).min(AUTO_THRESHOLD_BINS - 1);
+        bins[bin] += 1;
+    }
+
+    let total = bins.iter().sum::<usize>() as f32;
+    let weighted_total =
+        bins.iter().enumerate().map(|(bin, count)| (bin * count) as f32).sum::<f32>();
+
+    let mut background = 0f32;
+    let mut weighted_background = 0f32;
+    let mut best = (0f32, 0);
+    for (bin, count) in bins.iter().enumerate() {
+        background += *count as f32;
+        weighted_background += (bin * count) as f32;
+        let foreground = total - background;
+        if background == 0f32 || foreground == 0f32 {
+            continue;
+        }
+
+        let mean_difference =
+            weighted_background / background - (weighted_total - weighted_background) / foreground;
+        let variance = background * foreground * mean_difference * mean_difference;
+        if variance > best.0 {
+            best = (variance, bin);
+        }
+    }
+
+    let high = (best.1 + 1) as f32 * bin_width;
+
+    (high * AUTO_LOW_RATIO, high)
 }
 
 enum GradientDirection {
@@ -165,25 +339,15 @@ where
         .expect("TODO");
     source.map(|g| (g.magnitude(), g.direction())).remap(
         |s, p| {
-            let p = p.translate(Offset::new(1, 1).expect("TODO")).expect("TODO");
-            let gradient_a = s.look(p).expect("TODO");
-            let direction = GradientDirection::from_angle(gradient_a.1);
-
-            let gradient_b = match direction {
-                GradientDirection::Horizontal => {
-                    s.look(Point::new(p.x() + 1, p.y()).expect("TODO")).expect("TODO")
-                }
-                GradientDirection::Vertical => {
-                    s.look(Point::new(p.x(), p.y() + 1).expect("TODO")).expect("TODO")
-                }
-            };
+            let p = neighbor(p, 1, 1)?;
+            let gradient_a = s.look(p)?;
 
-            let gradient_c = match direction {
+            let (gradient_b, gradient_c) = match GradientDirection::from_angle(gradient_a.1) {
                 GradientDirection::Horizontal => {
-                    s.look(Point::new(p.x() - 1, p.y()).expect("TODO")).expect("TODO")
+                    (s.look(neighbor(p, 1, 0)?)?, s.look(neighbor(p, -1, 0)?)?)
                 }
                 GradientDirection::Vertical => {
-                    s.look(Point::new(p.x(), p.y() - 1).expect("TODO")).expect("TODO")
+                    (s.look(neighbor(p, 0, 1)?)?, s.look(neighbor(p, 0, -1)?)?)
                 }
             };
 
@@ -197,6 +361,15 @@ where
     )
 }
 
+/// Get point translated by `(x, y)`, returning [`IndexError::OutOfBounds`] instead of panicking
+/// if it would be outside of valid coordinates.
+fn neighbor(point: Point, x: isize, y: isize) -> IndexResult<Point> {
+    Offset::new(x, y)
+        .ok()
+        .and_then(|offset| point.translate(offset).ok())
+        .ok_or(IndexError::OutOfBounds)
+}
+
 struct HysteresisThresholdingKernel {
     min: f32,
     max: f32,
@@ -217,14 +390,14 @@ impl Kernel<f32, u8> for HysteresisThresholdingKernel {
             return Ok(0u8);
         }
 
-        let neighbor_exists = (-1..=1)
-            .cartesian_product(-1..=1)
-            .map(|(x, y)| Offset::new(x, y))
-            .map(|offset| point.translate(offset.expect("TODO")).expect("TODO"))
-            .map(|point| lens.look(point).expect("TODO"))
-            .any(|value| value > self.max);
+        // Neighbors outside of the source are reported as errors instead of panicking.
+        for (x, y) in (-1..=1).cartesian_product(-1..=1) {
+            if lens.look(neighbor(point, x, y)?)? > self.max {
+                return Ok(255u8);
+            }
+        }
 
-        if neighbor_exists { Ok(255u8) } else { Ok(0u8) }
+        Ok(0u8)
     }
 
     fn margin(&self) -> Margin {
@@ -232,12 +405,107 @@ impl Kernel<f32, u8> for HysteresisThresholdingKernel {
     }
 }
 
-fn hysteresis_thresholding_lens<S>(source: S) -> impl Lens<Item = u8>
+fn hysteresis_thresholding_lens<S>(source: S, min: f32, max: f32) -> impl Lens<Item = u8>
 where
     S: Lens<Item = f32>,
 {
-    let min = 10f32;
-    let max = 20f32;
-
     source.kernel(HysteresisThresholdingKernel { min, max }).expect("TODO")
 }
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use crate::lens::value::ValueLens;
+
+    use super::*;
+
+    /// Create image with a bright square in the middle of dark background. Outline of the square
+    /// is halfway between them, so gradient magnitude has a single peak across the edge.
+    fn square_image(background: u8, foreground: u8) -> Image {
+        let size = Size::new(32, 32).unwrap();
+        let halfway = background / 2 + foreground / 2;
+        Image::from_lens(ValueLens::new(0u8, size).remap(
+            move |_, point| {
+                let inside = (8..24).contains(&point.x()) && (8..24).contains(&point.y());
+                let outline = [point.x(), point.y()].iter().any(|c| [8, 23].contains(c));
+                let value = match (inside, outline) {
+                    (true, true) => halfway,
+                    (true, false) => foreground,
+                    (false, _) => background,
+                };
+                Ok(Pixel::new([value, value, value, 255]))
+            },
+            size,
+        ))
+    }
+
+    fn edge_count(image: &Image) -> usize {
+        image.pixels().iter().filter(|px| px.r() == 255).count()
+    }
+
+    #[test]
+    fn test_canny_auto_thresholds_different_contrast() {
+        let options = CannyLensOptions::new().auto_thresholds(true);
+        let area = Size::new(32, 32).unwrap().area();
+
+        for (background, foreground) in [(0, 20), (0, 255)] {
+            let edges = canny(&square_image(background, foreground), options).unwrap();
+            let count = edge_count(&edges);
+
+            assert!(count > 0, "no edges for contrast {background}-{foreground}");
+            assert!(count < area / 4, "too many edges for contrast {background}-{foreground}");
+        }
+    }
+
+    #[test]
+    fn test_canny_auto_thresholds_flat_image() {
+        let options = CannyLensOptions::new().auto_thresholds(true);
+
+        assert_eq!(edge_count(&canny(&square_image(0, 0), options).unwrap()), 0);
+    }
+
+    #[test]
+    fn test_canny_minimal_images() {
+        let mut rng = SmallRng::seed_from_u64(1212);
+        for (width, height) in [(1, 1), (1, 2), (2, 1), (3, 3), (1, 7)] {
+            let image = Image::random(Size::new(width, height).unwrap(), &mut rng);
+            for options in [CannyLensOptions::new(), CannyLensOptions::new().auto_thresholds(true)]
+            {
+                assert_eq!(canny(&image, options).unwrap().size(), image.size());
+            }
+        }
+    }
+
+    #[test]
+    fn test_canny_thresholds() {
+        let image = square_image(0, 20);
+        let default = canny(&image, CannyLensOptions::new()).unwrap();
+        let explicit =
+            canny(&image, CannyLensOptions::new().low_threshold(10f32).high_threshold(20f32))
+                .unwrap();
+        let strict =
+            canny(&image, CannyLensOptions::new().low_threshold(1e6).high_threshold(1e6)).unwrap();
+
+        assert_eq!(default.pixels(), explicit.pixels());
+        assert!(edge_count(&default) > 0);
+        assert_eq!(edge_count(&strict), 0);
+    }
+
+    #[test]
+    fn test_canny_invalid_thresholds() {
+        let image = square_image(0, 20);
+
+        for (low, high) in [(30f32, 20f32), (f32::NAN, 20f32)] {
+            let options = CannyLensOptions::new().low_threshold(low).high_threshold(high);
+
+            assert!(matches!(
+                canny(&image, options),
+                Err(CannyCreationError::InvalidThresholds { .. })
+            ));
+        }
+    }
+}
@@ -0,0 +1,142 @@
+#[cfg(feature = "parallel")]
+use std::num::NonZeroUsize;
+
+use crate::{
+    component::kernel::sobel::SobelKernel,
+    gray_image::GrayImage,
+    image::Image,
+    lens::{
+        FromLens,
+        Lens,
+        kernel::pad_for_kernel,
+    },
+    pixel::Pixel,
+};
+
+/// Sobel magnitude of a step edge from 0 to 255 is `4 * 255`, scale it down to fit in `u8`.
+const ENERGY_SCALE: f32 = 4f32;
+
+/// Create [`Lens`] with gradient energy of `source`, Sobel gradient magnitude of its luma.
+///
+/// Pixels outside of `source` are clamped to the nearest edge, so the lens has the same size as
+/// `source` and flat borders have no energy. Magnitude is divided by 4, so a sharp edge between
+/// black and white has energy of 255, and results are clamped to that value.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     lens::Lens,
+///     operation::detection::edge::energy_map_lens,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let image = Image::solid(Size::new(4, 4)?, Pixel::new([120, 50, 80, 255]));
+/// let energy = energy_map_lens(image.lens());
+///
+/// assert_eq!(energy.look(Point::zero())?, 0);
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn energy_map_lens<S>(source: S) -> impl Lens<Item = u8>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    gradient_magnitude_lens(source)
+        .map(|magnitude| (magnitude / ENERGY_SCALE).round().min(255f32) as u8)
+}
+
+/// Create gradient energy map of an image, see [`energy_map_lens`].
+pub fn energy_map(image: &Image) -> GrayImage {
+    let lens = energy_map_lens(image.lens());
+    GrayImage::from_lens(lens)
+}
+
+/// Create gradient energy map of an image in parallel, see [`energy_map_lens`].
+#[cfg(feature = "parallel")]
+pub fn energy_map_par(image: &Image, threads: NonZeroUsize) -> GrayImage {
+    use crate::lens::FromLensPar;
+
+    GrayImage::from_lens_par(energy_map_lens(image.lens()), threads)
+}
+
+/// Create [`Lens`] with Sobel gradient magnitude of luma of `source`, with pixels outside of it
+/// clamped to the nearest edge, preserving its size.
+pub(crate) fn gradient_magnitude_lens<S>(source: S) -> impl Lens<Item = f32>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    // SAFETY: extending a valid image size by 1 pixel on every side is always valid.
+    pad_for_kernel(source.to_luma(), SobelKernel::new())
+        .expect("unexpected error in pad_for_kernel")
+        .map(|gradient| gradient.magnitude())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::component::primitive::{
+        Point,
+        Size,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_energy_map_smooth_gradient() {
+        let size = Size::new(32, 8).unwrap();
+        let image = Image::from_lens(Image::empty(size).lens().map_with_point(|point, _| {
+            let value = (point.x() * 4) as u8;
+            Pixel::new([value, value, value, 255])
+        }));
+
+        let energy = energy_map(&image);
+
+        assert_eq!(energy.size(), size);
+        // Interior has a constant slope of 4 per pixel, which gives magnitude of 32.
+        for y in 0..8 {
+            for x in 1..31 {
+                assert_eq!(energy.value(Point::new(x, y).unwrap()).unwrap(), 8);
+            }
+        }
+    }
+
+    #[test]
+    fn test_energy_map_edge() {
+        let size = Size::new(10, 6).unwrap();
+        let image = Image::from_lens(Image::empty(size).lens().map_with_point(|point, _| {
+            let value = if point.x() < 5 { 0 } else { 255 };
+            Pixel::new([value, value, value, 255])
+        }));
+
+        let energy = energy_map(&image);
+
+        for y in 0..6 {
+            for x in 0..10 {
+                let value = energy.value(Point::new(x, y).unwrap()).unwrap();
+                if x == 4 || x == 5 {
+                    assert_eq!(value, 255);
+                } else {
+                    assert_eq!(value, 0);
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_energy_map_par() {
+        use rand::{
+            SeedableRng,
+            rngs::SmallRng,
+        };
+
+        let image = Image::random(Size::new(33, 17).unwrap(), &mut SmallRng::seed_from_u64(1209));
+        let threads = NonZeroUsize::new(4).unwrap();
+
+        assert_eq!(energy_map_par(&image, threads).buffer(), energy_map(&image).buffer());
+    }
+}
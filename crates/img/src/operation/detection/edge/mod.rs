@@ -1,12 +1,26 @@
 mod canny;
+mod energy;
+mod sobel_color;
 
 pub use canny::{
+    CannyCreationError,
+    CannyCreationResult,
+    CannyLensOptions,
     canny,
     canny_lens,
 };
+pub(crate) use energy::gradient_magnitude_lens;
+pub use energy::{
+    energy_map,
+    energy_map_lens,
+};
+pub use sobel_color::sobel_color_lens;
 
 #[cfg(feature = "parallel")]
-pub use canny::{
-    canny_lens_par,
-    canny_par,
+pub use self::{
+    canny::{
+        canny_lens_par,
+        canny_par,
+    },
+    energy::energy_map_par,
 };
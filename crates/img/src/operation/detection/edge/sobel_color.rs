@@ -0,0 +1,123 @@
+use crate::{
+    component::{
+        kernel::{
+            Kernel,
+            sobel::SobelKernel,
+        },
+        primitive::{
+            Margin,
+            Point,
+        },
+    },
+    error::IndexResult,
+    lens::{
+        Lens,
+        kernel::CreationError,
+    },
+    pixel::Pixel,
+};
+
+/// Create [`Lens`] with Sobel gradient magnitude of `source` computed separately for every RGB
+/// channel and combined as their Euclidean norm.
+///
+/// Unlike gradients of luma, edges between colors of equal brightness, e.g. red and green, are
+/// detected too. Alpha is ignored. Like other kernel based lenses, result is smaller than
+/// `source` by 1 pixel on every side, [`CreationError`] is returned if `source` is smaller than
+/// 3x3.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     lens::Lens,
+///     operation::detection::edge::sobel_color_lens,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let image = Image::solid(Size::new(5, 5)?, Pixel::new([200, 10, 10, 255]));
+/// let magnitude = sobel_color_lens(image.lens())?;
+///
+/// assert_eq!(magnitude.size(), Size::new(3, 3)?);
+/// assert_eq!(magnitude.look(Point::zero())?, 0f32);
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn sobel_color_lens<S>(source: S) -> Result<impl Lens<Item = f32>, CreationError>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    source.map(|px| *px.as_ref()).kernel(ColorSobelKernel)
+}
+
+struct ColorSobelKernel;
+
+impl Kernel<Pixel, f32> for ColorSobelKernel {
+    fn apply<S>(&self, source: &S, point: Point) -> IndexResult<f32>
+    where
+        S: Lens<Item = Pixel>,
+    {
+        let mut sum_squares = 0f32;
+        for channel in 0..3 {
+            let gradient =
+                SobelKernel::new().apply(&source.map(move |px| px.buffer()[channel]), point)?;
+            sum_squares += gradient.magnitude().powi(2);
+        }
+
+        Ok(sum_squares.sqrt())
+    }
+
+    fn margin(&self) -> Margin {
+        SobelKernel::new().margin()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        component::primitive::Size,
+        image::Image,
+        lens::FromLens,
+        operation::detection::edge::gradient_magnitude_lens,
+    };
+
+    use super::*;
+
+    /// Red and green halves of (rounded) equal luma, split at `x = 4`.
+    fn equiluminant_edge() -> Image {
+        let image = Image::empty(Size::new(8, 6).unwrap());
+        Image::from_lens(image.lens().map_with_point(|point, _| {
+            if point.x() < 4 { Pixel::new([255, 0, 0, 255]) } else { Pixel::new([0, 130, 0, 255]) }
+        }))
+    }
+
+    #[test]
+    fn test_detects_equiluminant_edge() {
+        let image = equiluminant_edge();
+
+        let color = sobel_color_lens(image.lens()).unwrap();
+        let luma = gradient_magnitude_lens(image.lens());
+
+        assert!(luma.elements().all(|magnitude| magnitude == 0f32));
+        for y in 0..color.size().height() {
+            for x in 0..color.size().width() {
+                let magnitude = color.look(Point::new(x, y).unwrap()).unwrap();
+                // Output is offset by 1, so the edge is at x = 2 and 3.
+                if x == 2 || x == 3 {
+                    assert!(magnitude > 500f32);
+                } else {
+                    assert_eq!(magnitude, 0f32);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_too_small_source() {
+        let image = Image::empty(Size::new(2, 5).unwrap());
+
+        assert!(matches!(sobel_color_lens(image.lens()), Err(CreationError::KernelTooBigX)));
+    }
+}
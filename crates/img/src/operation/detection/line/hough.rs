@@ -0,0 +1,264 @@
+use std::f32::consts::PI;
+
+use itertools::Itertools;
+use thiserror::Error;
+
+use crate::lens::Lens;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum HoughCreationError {
+    #[error("invalid resolution, both rho and theta must be finite and positive")]
+    InvalidResolution,
+}
+
+pub type HoughCreationResult<T> = std::result::Result<T, HoughCreationError>;
+
+/// Resolution of [`HoughAccumulator`] bins.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HoughResolution {
+    rho: f32,
+    theta: f32,
+}
+
+impl HoughResolution {
+    /// Create resolution with `rho` pixels between neighboring `rho` bins and `theta` radians
+    /// between neighboring `theta` bins.
+    ///
+    /// Returns [`HoughCreationError::InvalidResolution`] if any of the values is not finite or not
+    /// positive.
+    pub fn new(rho: f32, theta: f32) -> HoughCreationResult<Self> {
+        if !(rho.is_finite() && rho > 0f32 && theta.is_finite() && theta > 0f32) {
+            return Err(HoughCreationError::InvalidResolution);
+        }
+
+        Ok(Self { rho, theta })
+    }
+
+    /// Distance between neighboring `rho` bins in pixels.
+    pub fn rho(&self) -> f32 {
+        self.rho
+    }
+
+    /// Angle between neighboring `theta` bins in radians.
+    pub fn theta(&self) -> f32 {
+        self.theta
+    }
+}
+
+impl Default for HoughResolution {
+    /// One pixel and one degree resolution.
+    fn default() -> Self {
+        Self { rho: 1f32, theta: PI / 180f32 }
+    }
+}
+
+/// Votes of edge pixels for lines in `(rho, theta)` parameter space, where each line is the set
+/// of points satisfying `x * cos(theta) + y * sin(theta) = rho`. `theta` is within `[0, PI)`,
+/// `rho` within `[-diagonal, diagonal]`.
+#[derive(Debug, Clone)]
+pub struct HoughAccumulator {
+    votes: Vec<usize>,
+    rho_bins: usize,
+    theta_bins: usize,
+    rho_offset: usize,
+    resolution: HoughResolution,
+}
+
+impl HoughAccumulator {
+    /// Get number of votes for bin closest to given `rho` and `theta`.
+    pub fn votes(&self, rho: f32, theta: f32) -> usize {
+        let rho_index = self.rho_index(rho).min(self.rho_bins - 1);
+        let theta_index =
+            ((theta / self.resolution.theta).round() as usize).min(self.theta_bins - 1);
+
+        self.votes[rho_index * self.theta_bins + theta_index]
+    }
+
+    /// Get `(rho, theta)` of lines with at least `threshold` votes which are local maxima of the
+    /// accumulator, sorted by number of votes, most voted first.
+    pub fn lines(&self, threshold: usize) -> Vec<(f32, f32)> {
+        (0..self.rho_bins)
+            .cartesian_product(0..self.theta_bins)
+            .filter(|&(rho_index, theta_index)| {
+                let index = rho_index * self.theta_bins + theta_index;
+                self.votes[index] >= threshold && self.is_local_maximum(rho_index, theta_index)
+            })
+            .map(|(rho_index, theta_index)| {
+                (self.votes[rho_index * self.theta_bins + theta_index], rho_index, theta_index)
+            })
+            .sorted_by(|a, b| b.0.cmp(&a.0))
+            .map(|(_, rho_index, theta_index)| {
+                (
+                    (rho_index as f32 - self.rho_offset as f32) * self.resolution.rho,
+                    theta_index as f32 * self.resolution.theta,
+                )
+            })
+            .collect()
+    }
+
+    fn rho_index(&self, rho: f32) -> usize {
+        ((rho / self.resolution.rho).round() as isize + self.rho_offset as isize).max(0) as usize
+    }
+
+    /// Check if bin has at least as many votes as its neighbors, ties are won by the bin with
+    /// lower index, so plateaus produce a single maximum. `theta` wraps around, with `rho`
+    /// negated, since `(rho, PI)` describes the same line as `(-rho, 0)`.
+    fn is_local_maximum(&self, rho_index: usize, theta_index: usize) -> bool {
+        let index = rho_index * self.theta_bins + theta_index;
+        let value = self.votes[index];
+
+        (-1isize..=1).cartesian_product(-1isize..=1).filter(|offset| *offset != (0, 0)).all(
+            |(dr, dt)| {
+                let mut r = rho_index as isize + dr;
+                let mut t = theta_index as isize + dt;
+                if t < 0 || t >= self.theta_bins as isize {
+                    t = t.rem_euclid(self.theta_bins as isize);
+                    r = 2 * self.rho_offset as isize - r;
+                }
+                if r < 0 || t < 0 || r >= self.rho_bins as isize || t >= self.theta_bins as isize {
+                    return true;
+                }
+
+                let neighbor = r as usize * self.theta_bins + t as usize;
+                if neighbor < index {
+                    value > self.votes[neighbor]
+                } else {
+                    value >= self.votes[neighbor]
+                }
+            },
+        )
+    }
+}
+
+/// Accumulate votes of edge pixels (non-zero values of `edges`) for lines passing through them.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     lens::value::ValueLens,
+///     operation::detection::line::{
+///         HoughResolution,
+///         hough_accumulator,
+///     },
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let edges = ValueLens::new(0u8, Size::new(10, 10)?);
+/// let accumulator = hough_accumulator(&edges, HoughResolution::default());
+///
+/// assert!(accumulator.lines(1).is_empty());
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn hough_accumulator<S>(edges: &S, resolution: HoughResolution) -> HoughAccumulator
+where
+    S: Lens<Item = u8>,
+{
+    let size = edges.size();
+    let max_rho = (size.width() as f32).hypot(size.height() as f32);
+    let rho_offset = (max_rho / resolution.rho).ceil() as usize;
+    let rho_bins = 2 * rho_offset + 1;
+    let theta_bins = ((PI / resolution.theta).round() as usize).max(1);
+    let trigonometry: Vec<_> =
+        (0..theta_bins).map(|index| (index as f32 * resolution.theta).sin_cos()).collect();
+
+    let mut accumulator = HoughAccumulator {
+        votes: vec![0; rho_bins * theta_bins],
+        rho_bins,
+        theta_bins,
+        rho_offset,
+        resolution,
+    };
+
+    for (y, row) in edges.rows().enumerate() {
+        for (x, value) in row.enumerate() {
+            if value == 0 {
+                continue;
+            }
+
+            for (theta_index, (sin, cos)) in trigonometry.iter().enumerate() {
+                let rho = x as f32 * cos + y as f32 * sin;
+                let rho_index = accumulator.rho_index(rho);
+                accumulator.votes[rho_index * theta_bins + theta_index] += 1;
+            }
+        }
+    }
+
+    accumulator
+}
+
+/// Detect straight lines in `edges` (e.g. output of Canny), returning `(rho, theta)` of lines
+/// with at least `threshold` votes, most voted first. Uses [`HoughResolution::default()`].
+///
+/// See [`HoughAccumulator`] for description of line parameters.
+pub fn hough_lines<S>(edges: &S, threshold: usize) -> Vec<(f32, f32)>
+where
+    S: Lens<Item = u8>,
+{
+    hough_accumulator(edges, HoughResolution::default()).lines(threshold)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_4;
+
+    use crate::{
+        component::primitive::Size,
+        lens::value::ValueLens,
+    };
+
+    use super::*;
+
+    fn line_image(on_line: fn(usize, usize) -> bool) -> impl Lens<Item = u8> {
+        let size = Size::new(40, 40).unwrap();
+        ValueLens::new(0u8, size)
+            .remap(move |_, point| Ok(if on_line(point.x(), point.y()) { 255 } else { 0 }), size)
+    }
+
+    #[test]
+    fn test_hough_lines_vertical() {
+        let lines = hough_lines(&line_image(|x, _| x == 10), 35);
+
+        assert_eq!(lines.len(), 1);
+        let (rho, theta) = lines[0];
+        assert_eq!(rho, 10f32);
+        assert!(theta.abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_hough_lines_diagonal() {
+        let edges = line_image(|x, y| x == y);
+        let accumulator = hough_accumulator(&edges, HoughResolution::default());
+        let lines = accumulator.lines(35);
+
+        assert_eq!(lines.len(), 1);
+        let (rho, theta) = lines[0];
+        assert!(rho.abs() <= 1f32);
+        assert!((theta - 3f32 * FRAC_PI_4).abs() < 1e-3);
+        assert_eq!(accumulator.votes(rho, theta), 40);
+    }
+
+    #[test]
+    fn test_hough_resolution_new() {
+        let resolution = HoughResolution::new(2f32, 0.5f32).unwrap();
+        assert_eq!(resolution.rho(), 2f32);
+        assert_eq!(resolution.theta(), 0.5f32);
+
+        for (rho, theta) in [
+            (0f32, 1f32),
+            (1f32, 0f32),
+            (-1f32, 1f32),
+            (1f32, -1f32),
+            (f32::NAN, 1f32),
+            (1f32, f32::INFINITY),
+        ] {
+            assert_eq!(
+                HoughResolution::new(rho, theta),
+                Err(HoughCreationError::InvalidResolution)
+            );
+        }
+    }
+}
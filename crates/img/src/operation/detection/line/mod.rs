@@ -0,0 +1,10 @@
+mod hough;
+
+pub use hough::{
+    HoughAccumulator,
+    HoughCreationError,
+    HoughCreationResult,
+    HoughResolution,
+    hough_accumulator,
+    hough_lines,
+};
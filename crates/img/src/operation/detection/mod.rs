@@ -1 +1,4 @@
+pub mod blob;
 pub mod edge;
+pub mod line;
+pub mod template;
@@ -0,0 +1,7 @@
+mod ncc;
+
+pub use ncc::{
+    MatchTemplateCreationError,
+    MatchTemplateCreationResult,
+    match_template,
+};
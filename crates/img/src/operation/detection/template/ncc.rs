@@ -0,0 +1,173 @@
+use itertools::Itertools;
+use thiserror::Error;
+
+use crate::{
+    component::primitive::Point,
+    gray_image::{
+        GrayImage,
+        REC601_WEIGHTS,
+    },
+    image::Image,
+};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum MatchTemplateCreationError {
+    #[error("template must not be larger than image")]
+    TemplateTooLarge,
+}
+
+pub type MatchTemplateCreationResult<T> = std::result::Result<T, MatchTemplateCreationError>;
+
+/// Find location of `template` within `image` using normalized cross-correlation of luminance.
+///
+/// `template` is slid over every position where it fits entirely within `image`. Score is
+/// zero-mean normalized cross-correlation in range -1 to 1, where 1 is a perfect match up to
+/// brightness and contrast. Positions where either image or template is flat score 0.
+///
+/// Returns top left [`Point`] of the best match with its score, or
+/// [`MatchTemplateCreationError`] if `template` is larger than `image` in any dimension.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     operation::detection::template::match_template,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let image = Image::empty(Size::new(10, 10)?);
+/// let template = Image::empty(Size::new(3, 3)?);
+///
+/// let (point, score) = match_template(&image, &template)?;
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn match_template(
+    image: &Image,
+    template: &Image,
+) -> MatchTemplateCreationResult<(Point, f32)> {
+    let (size, template_size) = (image.size(), template.size());
+    if template_size.width() > size.width() || template_size.height() > size.height() {
+        return Err(MatchTemplateCreationError::TemplateTooLarge);
+    }
+
+    let image = image.to_gray(REC601_WEIGHTS);
+    let template = template.to_gray(REC601_WEIGHTS);
+
+    let template_mean = mean(template.buffer());
+    let template_values: Vec<f32> =
+        template.buffer().iter().map(|value| *value as f32 - template_mean).collect();
+    let template_norm = template_values.iter().map(|value| value * value).sum::<f32>();
+
+    let best = (0..=size.height() - template_size.height())
+        .cartesian_product(0..=size.width() - template_size.width())
+        .map(|(y, x)| {
+            let score =
+                ncc_at(&image, &template_values, template_norm, template_size.width(), x, y);
+            (x, y, score)
+        })
+        .fold(None, |best: Option<(usize, usize, f32)>, current| match best {
+            Some(best) if best.2 >= current.2 => Some(best),
+            _ => Some(current),
+        })
+        .expect("unexpected empty search space");
+
+    // SAFETY: x and y are within image size.
+    let point = Point::new(best.0, best.1).expect("unexpected error in Point::new");
+
+    Ok((point, best.2))
+}
+
+fn mean(values: &[u8]) -> f32 {
+    values.iter().map(|value| *value as f32).sum::<f32>() / values.len() as f32
+}
+
+/// Calculate normalized cross-correlation of zero-mean `template_values` with window of `image`
+/// starting at `(x, y)`.
+fn ncc_at(
+    image: &GrayImage,
+    template_values: &[f32],
+    template_norm: f32,
+    template_width: usize,
+    x: usize,
+    y: usize,
+) -> f32 {
+    let width = image.size().width();
+    let window = |row: usize| {
+        let start = (y + row) * width + x;
+        &image.buffer()[start..start + template_width]
+    };
+    let rows = template_values.len() / template_width;
+
+    let window_mean = (0..rows).map(|row| mean(window(row)) * template_width as f32).sum::<f32>()
+        / template_values.len() as f32;
+
+    let (cross, window_norm) = (0..rows)
+        .flat_map(|row| window(row).iter().zip(&template_values[row * template_width..]))
+        .fold((0f32, 0f32), |(cross, norm), (value, template_value)| {
+            let value = *value as f32 - window_mean;
+            (cross + value * template_value, norm + value * value)
+        });
+
+    let denominator = (window_norm * template_norm).sqrt();
+    if denominator <= f32::EPSILON {
+        return 0f32;
+    }
+
+    cross / denominator
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use crate::{
+        component::primitive::Size,
+        lens::{
+            FromLens,
+            Lens,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_match_template_finds_embedded() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let image = Image::random(Size::new(24, 18).unwrap(), &mut rng);
+        let at = Point::new(13, 7).unwrap();
+        let template_size = Size::new(5, 4).unwrap();
+        let template = Image::from_lens(
+            image
+                .lens()
+                .remap(
+                    move |s, point| {
+                        s.look(Point::new(point.x() + at.x(), point.y() + at.y()).unwrap())
+                    },
+                    template_size,
+                )
+                .cloned(),
+        );
+
+        let (point, score) = match_template(&image, &template).unwrap();
+
+        assert_eq!(point, at);
+        assert!((score - 1f32).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_match_template_too_large() {
+        let image = Image::empty(Size::new(4, 4).unwrap());
+        let template = Image::empty(Size::new(4, 5).unwrap());
+
+        assert_eq!(
+            match_template(&image, &template).unwrap_err(),
+            MatchTemplateCreationError::TemplateTooLarge
+        );
+    }
+}
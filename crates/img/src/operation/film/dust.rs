@@ -0,0 +1,150 @@
+#[cfg(feature = "parallel")]
+use std::num::NonZeroUsize;
+
+use crate::{
+    component::primitive::Point,
+    error::IndexError,
+    image::Image,
+    lens::{
+        FromLens,
+        Lens,
+    },
+    pixel::Pixel,
+};
+
+/// Create a [`Lens`] removing dust and scratches, small specks much brighter or darker than
+/// their surroundings.
+///
+/// Each pixel is compared with per channel median of a square window with given `radius` around
+/// it, clipped to `source`. If any color channel differs from the median by more than
+/// `threshold`, color is replaced with the median, otherwise the pixel is left unchanged, so
+/// unlike a plain median filter fine detail below `threshold` is preserved. Specks are removed
+/// if they cover less than half of the window. Alpha is preserved and result has the same size
+/// as `source`.
+pub fn dust_scratches_lens<S>(source: S, radius: usize, threshold: u8) -> impl Lens<Item = Pixel>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    let size = source.size();
+    source.remap(
+        move |lens, point| {
+            if !size.contains(&point) {
+                return Err(IndexError::OutOfBounds);
+            }
+
+            let xs = point.x().saturating_sub(radius)..(point.x() + radius + 1).min(size.width());
+            let ys = point.y().saturating_sub(radius)..(point.y() + radius + 1).min(size.height());
+            let mut channels = [const { Vec::new() }; 3];
+            for y in ys {
+                for x in xs.clone() {
+                    // SAFETY: window is clipped to source.
+                    let px = *lens
+                        .look(Point::new(x, y).expect("unexpected error in Point::new"))?
+                        .as_ref();
+                    channels[0].push(px.r());
+                    channels[1].push(px.g());
+                    channels[2].push(px.b());
+                }
+            }
+            let median = channels.map(|mut values| {
+                let middle = values.len() / 2;
+                *values.select_nth_unstable(middle).1
+            });
+
+            let px = *lens.look(point)?.as_ref();
+            let color = [px.r(), px.g(), px.b()];
+            if color.iter().zip(&median).all(|(value, median)| value.abs_diff(*median) <= threshold)
+            {
+                return Ok(px);
+            }
+
+            Ok(Pixel::new([median[0], median[1], median[2], px.a()]))
+        },
+        size,
+    )
+}
+
+/// Remove dust and scratches from an image, see [`dust_scratches_lens`].
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     operation::film::dust_scratches,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let mut image = Image::solid(Size::new(5, 5)?, Pixel::new([60, 60, 60, 255]));
+/// *image.pixel_mut(Point::new(2, 2)?)? = Pixel::new([255, 255, 255, 255]);
+///
+/// let cleaned = dust_scratches(&image, 1, 30);
+///
+/// assert_eq!(*cleaned.pixel(Point::new(2, 2)?)?, Pixel::new([60, 60, 60, 255]));
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn dust_scratches(image: &Image, radius: usize, threshold: u8) -> Image {
+    Image::from_lens(dust_scratches_lens(image.lens(), radius, threshold))
+}
+
+/// Remove dust and scratches from an image in parallel, see [`dust_scratches_lens`].
+#[cfg(feature = "parallel")]
+pub fn dust_scratches_par(
+    image: &Image,
+    threads: NonZeroUsize,
+    radius: usize,
+    threshold: u8,
+) -> Image {
+    use crate::lens::FromLensPar;
+
+    Image::from_lens_par(dust_scratches_lens(image.lens(), radius, threshold), threads)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::primitive::Size;
+
+    const BACKGROUND: Pixel = Pixel::new([60, 70, 80, 255]);
+    const SPECK: Pixel = Pixel::new([250, 250, 250, 255]);
+
+    #[test]
+    fn test_removes_isolated_specks() {
+        let mut image = Image::solid(Size::new(12, 10).unwrap(), BACKGROUND);
+        for (x, y) in [(0, 0), (3, 4), (8, 2), (11, 9)] {
+            *image.pixel_mut(Point::new(x, y).unwrap()).unwrap() = SPECK;
+        }
+
+        let cleaned = dust_scratches(&image, 1, 40);
+
+        assert_eq!(cleaned.size(), image.size());
+        assert!(cleaned.pixels().iter().all(|px| *px == BACKGROUND));
+    }
+
+    #[test]
+    fn test_keeps_detail_and_edges() {
+        // Gentle gradient with a hard vertical edge at x = 6.
+        let image = Image::from_lens(
+            Image::empty(Size::new(12, 8).unwrap()).lens().map_with_point(|point, _| {
+                let base = if point.x() < 6 { 40 } else { 200 };
+                let value = base + (point.x() + point.y()) as u8 * 2;
+                Pixel::new([value, value, value, 255])
+            }),
+        );
+
+        let cleaned = dust_scratches(&image, 2, 20);
+
+        assert_eq!(cleaned.pixels(), image.pixels());
+    }
+
+    #[test]
+    fn test_zero_radius_is_identity() {
+        let mut image = Image::solid(Size::new(4, 4).unwrap(), BACKGROUND);
+        *image.pixel_mut(Point::new(1, 1).unwrap()).unwrap() = SPECK;
+
+        assert_eq!(dust_scratches(&image, 0, 0).pixels(), image.pixels());
+    }
+}
@@ -0,0 +1,166 @@
+#[cfg(feature = "parallel")]
+use std::num::NonZeroUsize;
+
+use thiserror::Error;
+
+use crate::{
+    error::IndexError,
+    image::Image,
+    lens::{
+        FromLens,
+        Lens,
+    },
+    pixel::Pixel,
+};
+
+/// Error returned by add_grain functions
+#[derive(Debug, Error)]
+pub enum GrainCreationError {
+    #[error("intensity must be finite and non-negative")]
+    InvalidIntensity,
+}
+
+pub type GrainCreationResult<T> = std::result::Result<T, GrainCreationError>;
+
+/// Create a [`Lens`] overlaying monochrome film grain on `source`.
+///
+/// Every pixel gets the same offset added to its color channels, with roughly normal
+/// distribution and standard deviation of `intensity` in midtones, falling off towards
+/// shadows and highlights like grain of a film. Noise depends only on `seed` and position of
+/// the pixel, so the result is reproducible and can be looked up in any order. Alpha is
+/// preserved.
+///
+/// Returns [`GrainCreationError::InvalidIntensity`] if `intensity` is negative or not finite.
+pub fn add_grain_lens<S>(
+    source: S,
+    intensity: f32,
+    seed: u64,
+) -> GrainCreationResult<impl Lens<Item = Pixel>>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    if !intensity.is_finite() || intensity < 0f32 {
+        return Err(GrainCreationError::InvalidIntensity);
+    }
+
+    let size = source.size();
+    let lens = source.remap(
+        move |lens, point| {
+            if !size.contains(&point) {
+                return Err(IndexError::OutOfBounds);
+            }
+
+            let px = *lens.look(point)?.as_ref();
+            let luma = px.luminance_f32();
+            let strength = 4f32 * luma * (1f32 - luma);
+            let offset = intensity * strength * normal_noise(seed, point.x(), point.y());
+
+            let channel = |value: u8| (value as f32 + offset).round().clamp(0f32, 255f32) as u8;
+            Ok(Pixel::new([channel(px.r()), channel(px.g()), channel(px.b()), px.a()]))
+        },
+        size,
+    );
+
+    Ok(lens)
+}
+
+/// Overlay film grain on an image, see [`add_grain_lens`].
+pub fn add_grain(image: &Image, intensity: f32, seed: u64) -> GrainCreationResult<Image> {
+    let lens = add_grain_lens(image.lens(), intensity, seed)?;
+    Ok(Image::from_lens(lens))
+}
+
+/// Overlay film grain on an image in parallel, see [`add_grain_lens`].
+#[cfg(feature = "parallel")]
+pub fn add_grain_par(
+    image: &Image,
+    threads: NonZeroUsize,
+    intensity: f32,
+    seed: u64,
+) -> GrainCreationResult<Image> {
+    use crate::lens::FromLensPar;
+
+    let lens = add_grain_lens(image.lens(), intensity, seed)?;
+    Ok(Image::from_lens_par(lens, threads))
+}
+
+/// Noise with zero mean and unit variance for given position, approximately normal as a sum
+/// of 4 uniform values.
+fn normal_noise(seed: u64, x: usize, y: usize) -> f32 {
+    let mut state = seed ^ ((x as u64) << 32 | y as u64 & 0xFFFF_FFFF);
+    let sum = (0..4)
+        .map(|_| {
+            state = split_mix(state);
+            (state >> 40) as f32 / (1u64 << 24) as f32
+        })
+        .sum::<f32>();
+
+    // Sum of 4 uniform values in 0-1 range has mean 2 and variance 1/3.
+    (sum - 2f32) * 3f32.sqrt()
+}
+
+/// Step of SplitMix64 generator, good enough to decorrelate neighboring positions.
+fn split_mix(state: u64) -> u64 {
+    let mut z = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::primitive::{
+        Point,
+        Size,
+    };
+
+    fn difference(a: &Image, b: &Image) -> Vec<f32> {
+        a.pixels().iter().zip(b.pixels()).map(|(a, b)| a.r() as f32 - b.r() as f32).collect()
+    }
+
+    #[test]
+    fn test_grain_reproducible() {
+        let image = Image::solid(Size::new(16, 16).unwrap(), Pixel::new([128, 128, 128, 255]));
+
+        let first = add_grain(&image, 10f32, 7).unwrap();
+        let second = add_grain(&image, 10f32, 7).unwrap();
+        let other = add_grain(&image, 10f32, 8).unwrap();
+
+        assert_eq!(first.pixels(), second.pixels());
+        assert_ne!(first.pixels(), other.pixels());
+        // Looking up a single point gives the same value as collecting the whole image.
+        let point = Point::new(5, 11).unwrap();
+        let lens = add_grain_lens(image.lens(), 10f32, 7).unwrap();
+        assert_eq!(&lens.look(point).unwrap(), first.pixel(point).unwrap());
+    }
+
+    #[test]
+    fn test_grain_strongest_in_midtones() {
+        let size = Size::new(32, 32).unwrap();
+        let deviation = |value: u8| {
+            let image = Image::solid(size, Pixel::new([value, value, value, 255]));
+            let differences = difference(&add_grain(&image, 10f32, 1).unwrap(), &image);
+            let variance =
+                differences.iter().map(|d| d * d).sum::<f32>() / differences.len() as f32;
+            variance.sqrt()
+        };
+
+        let midtones = deviation(128);
+        assert!((8f32..12f32).contains(&midtones), "{midtones}");
+        assert!(deviation(20) < midtones / 2f32);
+        assert_eq!(deviation(0), 0f32);
+    }
+
+    #[test]
+    fn test_invalid_intensity() {
+        let image = Image::empty(Size::new(2, 2).unwrap());
+
+        assert!(matches!(add_grain(&image, -1f32, 0), Err(GrainCreationError::InvalidIntensity)));
+        assert!(matches!(
+            add_grain(&image, f32::NAN, 0),
+            Err(GrainCreationError::InvalidIntensity)
+        ));
+    }
+}
@@ -0,0 +1,19 @@
+mod dust;
+mod grain;
+
+pub use dust::{
+    dust_scratches,
+    dust_scratches_lens,
+};
+pub use grain::{
+    GrainCreationError,
+    GrainCreationResult,
+    add_grain,
+    add_grain_lens,
+};
+
+#[cfg(feature = "parallel")]
+pub use self::{
+    dust::dust_scratches_par,
+    grain::add_grain_par,
+};
@@ -0,0 +1,183 @@
+#[cfg(feature = "parallel")]
+use std::num::NonZeroUsize;
+
+use crate::{
+    component::primitive::{
+        Margin,
+        Size,
+    },
+    image::Image,
+    lens::{
+        FromLens,
+        Lens,
+    },
+    operation::blur::{
+        GaussianBlurCreationResult,
+        gaussian_blur_lens,
+    },
+    pixel::{
+        AlphaMode,
+        ChannelFlags,
+        Pixel,
+    },
+};
+
+/// Value of a channel without any detail in a high-pass layer.
+pub const HIGH_PASS_NEUTRAL: u8 = 128;
+
+/// Create a [`Lens`] with high frequency detail of `source`, computed per channel selected by
+/// `flags` as `original - gaussian_blur + 128`, clamped to `0..=255`.
+///
+/// Flat areas become mid-gray (128), edges and texture deviate from it in both directions.
+/// Unlike [`gaussian_blur_lens`], size of `source` is preserved, pixels outside of it are clamped
+/// to the nearest edge before blurring. Channels not selected by `flags` are copied unchanged.
+pub fn high_pass_lens<S>(
+    source: S,
+    radius: usize,
+    sigma: f32,
+    flags: ChannelFlags,
+) -> GaussianBlurCreationResult<impl Lens<Item = Pixel>>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    let original = source.map(|px| *px.as_ref()).materialize();
+    let blurred = low_pass_lens(original.clone(), radius, sigma, flags)?;
+
+    Ok(blurred.map_with_point(move |point, blurred| {
+        // SAFETY: blurred lens has the same size as original.
+        let px = original.look(point).expect("unexpected error in Lens::look");
+        detail_px(px, blurred, flags)
+    }))
+}
+
+/// Extract high frequency detail of an image, see [`high_pass_lens`].
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     operation::frequency::high_pass,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let image = Image::solid(Size::new(8, 8)?, Pixel::new([30, 200, 90, 255]));
+/// let detail = high_pass(&image, 2, 1.5, ChannelFlags::RGB)?;
+///
+/// assert_eq!(detail.size(), image.size());
+/// assert_eq!(*detail.pixel(Point::zero())?, Pixel::new([128, 128, 128, 255]));
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn high_pass(
+    image: &Image,
+    radius: usize,
+    sigma: f32,
+    flags: ChannelFlags,
+) -> GaussianBlurCreationResult<Image> {
+    let lens = high_pass_lens(image.lens(), radius, sigma, flags)?;
+    Ok(Image::from_lens(lens))
+}
+
+/// Extract high frequency detail of an image in parallel, see [`high_pass_lens`].
+#[cfg(feature = "parallel")]
+pub fn high_pass_par(
+    image: &Image,
+    threads: NonZeroUsize,
+    radius: usize,
+    sigma: f32,
+    flags: ChannelFlags,
+) -> GaussianBlurCreationResult<Image> {
+    use crate::lens::FromLensPar;
+
+    let lens = high_pass_lens(image.lens(), radius, sigma, flags)?;
+    Ok(Image::from_lens_par(lens, threads))
+}
+
+/// Difference between `px` and its `blurred` version offset by [`HIGH_PASS_NEUTRAL`], in
+/// channels selected by `flags`.
+pub(crate) fn detail_px(px: Pixel, blurred: Pixel, flags: ChannelFlags) -> Pixel {
+    let detail = |original: u8, blurred: u8| {
+        (original as i16 - blurred as i16 + HIGH_PASS_NEUTRAL as i16).clamp(0, 255) as u8
+    };
+
+    let mut result = px;
+    result.set_with_flags(
+        detail(px.r(), blurred.r()),
+        detail(px.g(), blurred.g()),
+        detail(px.b(), blurred.b()),
+        detail(px.a(), blurred.a()),
+        flags,
+    );
+    result
+}
+
+/// Blur `source` with a gaussian kernel, preserving its size by clamping pixels outside of it to
+/// the nearest edge.
+pub(crate) fn low_pass_lens<S>(
+    source: S,
+    radius: usize,
+    sigma: f32,
+    flags: ChannelFlags,
+) -> GaussianBlurCreationResult<impl Lens<Item = Pixel>>
+where
+    S: Lens<Item = Pixel>,
+{
+    // Kernel size is validated first, so the radius always fits in a margin.
+    Size::from_radius(radius)?;
+    let margin = Margin::unified(radius).expect("unexpected error in Margin::unified");
+    let padded = source.clamp_border(margin)?;
+
+    gaussian_blur_lens(padded, radius, sigma, flags, AlphaMode::Straight)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use super::*;
+    use crate::component::primitive::Point;
+
+    #[test]
+    fn test_flat_image_is_neutral() {
+        let image = Image::solid(Size::new(9, 7).unwrap(), Pixel::new([10, 250, 77, 200]));
+
+        let detail = high_pass(&image, 3, 2f32, ChannelFlags::RGB).unwrap();
+
+        assert_eq!(detail.size(), image.size());
+        assert!(detail.pixels().iter().all(|px| *px == Pixel::new([128, 128, 128, 200])));
+    }
+
+    #[test]
+    fn test_detail_preserved_around_neutral() {
+        let size = Size::new(11, 11).unwrap();
+        let mut image = Image::solid(size, Pixel::new([100, 100, 100, 255]));
+        *image.pixel_mut(Point::new(5, 5).unwrap()).unwrap() = Pixel::new([160, 40, 100, 255]);
+
+        let detail = high_pass(&image, 2, 1f32, ChannelFlags::RGB).unwrap();
+
+        let center = detail.pixel(Point::new(5, 5).unwrap()).unwrap();
+        assert!(center.r() > 160 && center.g() < 96 && center.b() == 128);
+        // Neighbours are brighter than their blur in red, darker in green.
+        let neighbour = detail.pixel(Point::new(6, 5).unwrap()).unwrap();
+        assert!(neighbour.r() < 128 && neighbour.g() > 128);
+        // Far away from the detail everything is neutral.
+        assert_eq!(*detail.pixel(Point::zero()).unwrap(), Pixel::new([128, 128, 128, 255]));
+    }
+
+    #[test]
+    fn test_random_image_mean_is_neutral() {
+        let image =
+            Image::random_opaque(Size::new(32, 32).unwrap(), &mut SmallRng::seed_from_u64(0));
+
+        let detail = high_pass(&image, 2, 1f32, ChannelFlags::RGB).unwrap();
+
+        let mean = detail.pixels().iter().map(|px| px.r() as f32).sum::<f32>() / 1024f32;
+        assert!((mean - 128f32).abs() < 4f32);
+    }
+}
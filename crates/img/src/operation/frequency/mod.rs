@@ -0,0 +1,12 @@
+mod high_pass;
+mod separation;
+
+pub use high_pass::{
+    HIGH_PASS_NEUTRAL,
+    high_pass,
+    high_pass_lens,
+};
+pub use separation::frequency_separation;
+
+#[cfg(feature = "parallel")]
+pub use self::high_pass::high_pass_par;
@@ -0,0 +1,94 @@
+use crate::{
+    image::Image,
+    lens::{
+        FromLens,
+        Lens,
+    },
+    operation::{
+        blur::GaussianBlurCreationResult,
+        frequency::high_pass::{
+            detail_px,
+            low_pass_lens,
+        },
+    },
+    pixel::ChannelFlags,
+};
+
+/// Split an image into a low frequency layer, blurred with a gaussian kernel of given `radius`
+/// and `sigma`, and a high frequency detail layer, as in [`high_pass`](super::high_pass).
+///
+/// Layers can be retouched separately, e.g. smoothing skin tones in the low layer while keeping
+/// texture in the high one, and recombined with `low + high - 128` per channel, which reproduces
+/// the original unless detail exceeds the range of the high layer. Only RGB channels are
+/// separated, both layers keep alpha of the original. Size of the image is preserved.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     operation::frequency::frequency_separation,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let image = Image::solid(Size::new(8, 8)?, Pixel::new([30, 200, 90, 255]));
+/// let (low, high) = frequency_separation(&image, 2, 1.5)?;
+///
+/// assert_eq!(low.pixels(), image.pixels());
+/// assert_eq!(*high.pixel(Point::zero())?, Pixel::new([128, 128, 128, 255]));
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn frequency_separation(
+    image: &Image,
+    radius: usize,
+    sigma: f32,
+) -> GaussianBlurCreationResult<(Image, Image)> {
+    let low =
+        Image::from_lens(low_pass_lens(image.lens().cloned(), radius, sigma, ChannelFlags::RGB)?);
+    // SAFETY: low pass preserves size of the image.
+    let high = Image::zip_map(image, &low, |px, low| detail_px(px, low, ChannelFlags::RGB))
+        .expect("unexpected error in Image::zip_map");
+
+    Ok((low, high))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use crate::{
+        component::primitive::Size,
+        pixel::Pixel,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_recombine_reproduces_original() {
+        // Smooth gradient with mild noise, so detail stays within range of the high layer.
+        let mut rng = SmallRng::seed_from_u64(1222);
+        let noise = Image::random(Size::new(24, 16).unwrap(), &mut rng);
+        let image = Image::from_lens(noise.lens().map_with_point(|point, px| {
+            let base = (point.x() * 6 + point.y() * 2) as u8;
+            Pixel::new([base + px.r() % 32, 200 - px.g() % 32, base / 2 + px.b() % 16, px.a()])
+        }));
+
+        let (low, high) = frequency_separation(&image, 3, 2f32).unwrap();
+
+        assert_eq!(low.size(), image.size());
+        assert_eq!(high.size(), image.size());
+        for ((original, low), high) in image.pixels().iter().zip(low.pixels()).zip(high.pixels()) {
+            for channel in 0..3 {
+                let recombined = low.buffer()[channel] as i16 + high.buffer()[channel] as i16 - 128;
+                assert!((recombined - original.buffer()[channel] as i16).abs() <= 1);
+            }
+            assert_eq!(low.a(), original.a());
+            assert_eq!(high.a(), original.a());
+        }
+    }
+}
@@ -1,13 +1,27 @@
 #[cfg(feature = "parallel")]
 use std::num::NonZeroUsize;
 
+use thiserror::Error;
+
 use crate::{
-    component::primitive::{
-        Margin,
-        Offset,
-        SizeCreationError,
+    component::{
+        primitive::{
+            Margin,
+            Offset,
+            Size,
+            SizeCreationError,
+        },
+        sampler::{
+            EdgeMode,
+            sample_bilinear,
+        },
+    },
+    error::IndexError,
+    image::{
+        CreationError,
+        DIMENSION_MAX,
+        Image,
     },
-    image::Image,
     lens::{
         FromLens,
         FromLensPar,
@@ -16,6 +30,17 @@ use crate::{
     pixel::Pixel,
 };
 
+/// Error returned by crop_subpixel function
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CropSubpixelCreationError {
+    #[error("invalid crop size: {0}")]
+    Image(#[from] CreationError),
+    #[error("crop origin must be finite and within image dimension limits")]
+    InvalidOrigin,
+}
+
+pub type CropSubpixelCreationResult<T> = std::result::Result<T, CropSubpixelCreationError>;
+
 pub fn crop_lens<S>(source: S, margin: Margin) -> Result<impl Lens<Item = Pixel>, SizeCreationError>
 where
     S: Lens<Item = Pixel>,
@@ -60,6 +85,86 @@ pub fn crop_par(
     Ok(image)
 }
 
+/// Create a [`Lens`] cropping `source` to a rectangle with fractional coordinates, given as
+/// `(x, y, width, height)`.
+///
+/// Output size is `width` and `height` rounded to the nearest integer, and its pixel `(i, j)` is
+/// sampled bilinearly at `(x + i, y + j)`, so integer coordinates give exactly the same result as
+/// [`crop_lens`] and fractional ones interpolate neighboring pixels. Points outside of `source`
+/// are clamped to its nearest edge.
+///
+/// Returns [`CreationError::InvalidSize`] if rounded size is zero or too big, which includes
+/// negative and non-finite dimensions, and [`CropSubpixelCreationError::InvalidOrigin`] if `x` or
+/// `y` is not finite or its magnitude exceeds [`DIMENSION_MAX`].
+pub fn crop_subpixel_lens<S>(
+    source: S,
+    area: (f32, f32, f32, f32),
+) -> CropSubpixelCreationResult<impl Lens<Item = Pixel>>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    let (x, y, width, height) = area;
+    let size = Size::new(width.round() as usize, height.round() as usize)
+        .map_err(CreationError::InvalidSize)?;
+    if [x, y].iter().any(|value| !value.is_finite() || value.abs() > DIMENSION_MAX as f32) {
+        return Err(CropSubpixelCreationError::InvalidOrigin);
+    }
+
+    Ok(source.remap(
+        move |lens, point| {
+            if !size.contains(&point) {
+                return Err(IndexError::OutOfBounds);
+            }
+
+            Ok(sample_bilinear(lens, x + point.x() as f32, y + point.y() as f32, EdgeMode::Clamp))
+        },
+        size,
+    ))
+}
+
+/// Crop an image to a rectangle with fractional coordinates, see [`crop_subpixel_lens`].
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     operation::geometry::crop_subpixel,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let black = Pixel::new([0, 0, 0, 255]);
+/// let white = Pixel::new([255, 255, 255, 255]);
+/// let image = Image::new(Size::new(2, 1)?, Box::new([black, white]))?;
+///
+/// let cropped = crop_subpixel(&image, (0.5, 0.0, 1.0, 1.0))?;
+///
+/// assert_eq!(*cropped.pixel(Point::zero())?, Pixel::new([128, 128, 128, 255]));
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn crop_subpixel(
+    image: &Image,
+    area: (f32, f32, f32, f32),
+) -> CropSubpixelCreationResult<Image> {
+    let lens = crop_subpixel_lens(image.lens(), area)?;
+    Ok(Image::from_lens(lens))
+}
+
+/// Crop an image to a rectangle with fractional coordinates in parallel, see
+/// [`crop_subpixel_lens`].
+#[cfg(feature = "parallel")]
+pub fn crop_subpixel_par(
+    image: &Image,
+    threads: NonZeroUsize,
+    area: (f32, f32, f32, f32),
+) -> CropSubpixelCreationResult<Image> {
+    let lens = crop_subpixel_lens(image.lens(), area)?;
+    Ok(Image::from_lens_par(lens, threads))
+}
+
 #[cfg(test)]
 mod tests {
     use rand::{
@@ -67,9 +172,8 @@ mod tests {
         rngs::StdRng,
     };
 
-    use crate::prelude::Size;
-
     use super::*;
+    use crate::prelude::Point;
 
     #[test]
     fn test_crop_with_valid_margins() {
@@ -113,4 +217,73 @@ mod tests {
         let left_oob = crop(&image, Margin::new(0, 0, 0, 10).unwrap());
         assert_eq!(left_oob.unwrap_err(), SizeCreationError::WidthZero);
     }
+
+    #[test]
+    fn test_crop_subpixel_integer_matches_crop() {
+        let image = Image::random(Size::new(10, 20).unwrap(), &mut StdRng::from_seed([7u8; 32]));
+
+        let subpixel = crop_subpixel(&image, (2.0, 3.0, 5.0, 15.0)).unwrap();
+        let cropped = crop(&image, Margin::new(3, 3, 2, 2).unwrap()).unwrap();
+
+        assert_eq!(subpixel.size(), cropped.size());
+        assert_eq!(subpixel.pixels(), cropped.pixels());
+    }
+
+    #[test]
+    fn test_crop_subpixel_half_pixel_shift() {
+        let image = Image::random(Size::new(6, 6).unwrap(), &mut StdRng::from_seed([3u8; 32]));
+
+        let shifted = crop_subpixel(&image, (1.5, 2.0, 3.0, 2.0)).unwrap();
+
+        assert_eq!(shifted.size(), Size::new(3, 2).unwrap());
+        for (x, y) in [(0, 0), (2, 1)] {
+            let left = image.pixel(Point::new(x + 1, y + 2).unwrap()).unwrap();
+            let right = image.pixel(Point::new(x + 2, y + 2).unwrap()).unwrap();
+            let px = shifted.pixel(Point::new(x, y).unwrap()).unwrap();
+            for channel in 0..4 {
+                let expected =
+                    (left.buffer()[channel] as f32 + right.buffer()[channel] as f32) / 2f32;
+                assert!((px.buffer()[channel] as f32 - expected).abs() <= 0.5, "{px:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_crop_subpixel_invalid_size() {
+        let image = Image::empty(Size::new(4, 4).unwrap());
+
+        for area in [(0.0, 0.0, 0.4, 2.0), (0.0, 0.0, -3.0, 2.0), (0.0, 0.0, 2.0, f32::NAN)] {
+            assert!(matches!(
+                crop_subpixel(&image, area),
+                Err(CropSubpixelCreationError::Image(CreationError::InvalidSize(_)))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_crop_subpixel_invalid_origin() {
+        let image = Image::empty(Size::new(4, 4).unwrap());
+
+        for area in
+            [(f32::NAN, 0.0, 2.0, 2.0), (0.0, f32::INFINITY, 2.0, 2.0), (1e30, 0.0, 2.0, 2.0)]
+        {
+            assert_eq!(
+                crop_subpixel(&image, area).err(),
+                Some(CropSubpixelCreationError::InvalidOrigin)
+            );
+        }
+    }
+
+    #[test]
+    fn test_crop_subpixel_far_outside_is_clamped() {
+        let image = Image::random(Size::new(4, 4).unwrap(), &mut StdRng::from_seed([5u8; 32]));
+        let far = DIMENSION_MAX as f32;
+
+        let cropped = crop_subpixel(&image, (far, -far, 1.0, 1.0)).unwrap();
+
+        assert_eq!(
+            cropped.pixel(Point::zero()).unwrap(),
+            image.pixel(Point::new(3, 0).unwrap()).unwrap()
+        );
+    }
 }
@@ -0,0 +1,173 @@
+use crate::{
+    component::sampler::EdgeMode,
+    gray_image::REC601_WEIGHTS,
+    image::Image,
+    lens::Lens,
+    operation::geometry::rotate_arbitrary,
+};
+
+/// Step in radians of the coarse angle search, about one degree.
+const COARSE_STEP: f32 = 0.0175;
+/// Step in radians of the fine angle search around the best coarse angle.
+const FINE_STEP: f32 = 0.001;
+
+/// Straighten skewed document scan by rotating it within `±max_angle` radians.
+///
+/// See [`deskew_angle`] for how the rotation is found. The image is rotated with
+/// [`rotate_arbitrary`], pixels coming from outside of the image repeat its edges.
+pub fn deskew(image: &Image, max_angle: f32) -> Image {
+    rotate_arbitrary(image, deskew_angle(image, max_angle), EdgeMode::Clamp, false)
+}
+
+/// Find angle in radians within `±max_angle` by which rotating `image` clockwise (as done by
+/// [`rotate_arbitrary`]) straightens lines of text in it.
+///
+/// Pixels darker than the mean luma are treated as ink. For each candidate angle the ink is
+/// projected onto rows of the rotated image, and the angle giving the projection profile with
+/// the highest variance, i.e. the sharpest separation of lines and gaps, is selected. Search
+/// is done with a coarse step first and refined around the best coarse angle. Non-finite
+/// `max_angle` is treated as zero.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     operation::geometry::deskew_angle,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let image = Image::empty(Size::new(10, 10)?);
+///
+/// assert_eq!(deskew_angle(&image, 0.2), 0.0);
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn deskew_angle(image: &Image, max_angle: f32) -> f32 {
+    let max_angle = if max_angle.is_finite() { max_angle.abs() } else { 0f32 };
+    let ink = ink_points(image);
+    if ink.is_empty() {
+        return 0f32;
+    }
+
+    let coarse = search(&ink, -max_angle, max_angle, COARSE_STEP);
+    search(
+        &ink,
+        (coarse - COARSE_STEP).max(-max_angle),
+        (coarse + COARSE_STEP).min(max_angle),
+        FINE_STEP,
+    )
+}
+
+/// Get coordinates relative to the image center and weights of pixels darker than mean luma.
+fn ink_points(image: &Image) -> Vec<(f32, f32, f32)> {
+    let gray = image.to_gray(REC601_WEIGHTS);
+    let size = gray.size();
+    let mean = gray.buffer().iter().map(|value| *value as f32).sum::<f32>() / size.area() as f32;
+    let center_x = (size.width() - 1) as f32 / 2f32;
+    let center_y = (size.height() - 1) as f32 / 2f32;
+
+    gray.lens()
+        .rows()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.enumerate().filter_map(move |(x, value)| {
+                let weight = mean - value as f32;
+                (weight > 0f32).then_some((x as f32 - center_x, y as f32 - center_y, weight))
+            })
+        })
+        .collect()
+}
+
+/// Find angle from `from` to `to` range with `step` maximizing projection profile variance,
+/// ties are won by the angle closest to zero.
+fn search(ink: &[(f32, f32, f32)], from: f32, to: f32, step: f32) -> f32 {
+    let steps = ((to - from) / step).round().max(0f32) as usize;
+
+    (0..=steps)
+        .map(
+            |index| {
+                if steps == 0 { from } else { from + (to - from) * index as f32 / steps as f32 }
+            },
+        )
+        .map(|angle| (angle, profile_variance(ink, angle)))
+        .fold((0f32, f32::NEG_INFINITY), |best, (angle, variance)| {
+            if variance > best.1 || (variance == best.1 && angle.abs() < best.0.abs()) {
+                (angle, variance)
+            } else {
+                best
+            }
+        })
+        .0
+}
+
+/// Variance of ink projected onto rows of the image rotated by `angle`.
+fn profile_variance(ink: &[(f32, f32, f32)], angle: f32) -> f32 {
+    let (sin, cos) = angle.sin_cos();
+    let rows: Vec<_> =
+        ink.iter().map(|(x, y, weight)| ((sin * x + cos * y).round(), *weight)).collect();
+    let min = rows.iter().map(|(row, _)| *row).fold(f32::INFINITY, f32::min);
+    let max = rows.iter().map(|(row, _)| *row).fold(f32::NEG_INFINITY, f32::max);
+
+    let mut profile = vec![0f32; (max - min) as usize + 1];
+    for (row, weight) in rows {
+        profile[(row - min) as usize] += weight;
+    }
+
+    let mean = profile.iter().sum::<f32>() / profile.len() as f32;
+    profile.iter().map(|value| (value - mean).powi(2)).sum::<f32>() / profile.len() as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        component::primitive::Size,
+        lens::{
+            FromLens,
+            value::ValueLens,
+        },
+        pixel::Pixel,
+    };
+
+    use super::*;
+
+    const WHITE: Pixel = Pixel::new([255, 255, 255, 255]);
+    const BLACK: Pixel = Pixel::new([0, 0, 0, 255]);
+
+    /// White page with lines of black "words" separated by gaps.
+    fn text_page() -> Image {
+        let size = Size::new(160, 160).unwrap();
+        Image::from_lens(ValueLens::new(WHITE, size).remap(
+            |_, point| {
+                let (x, y) = (point.x(), point.y());
+                let in_margin = !(20..140).contains(&x) || !(20..140).contains(&y);
+                let in_line = y % 12 < 4;
+                let in_word = (x / 7 + y / 12) % 5 != 0;
+                Ok(if !in_margin && in_line && in_word { BLACK } else { WHITE })
+            },
+            size,
+        ))
+    }
+
+    #[test]
+    fn test_deskew_angle_recovers_rotation() {
+        let skew = 4f32.to_radians();
+        let skewed = rotate_arbitrary(&text_page(), skew, EdgeMode::Constant(WHITE), false);
+
+        let angle = deskew_angle(&skewed, 10f32.to_radians());
+
+        assert!((angle + skew).abs() < 0.5f32.to_radians(), "angle {}", angle.to_degrees());
+    }
+
+    #[test]
+    fn test_deskew_straightens_image() {
+        let skewed =
+            rotate_arbitrary(&text_page(), -3f32.to_radians(), EdgeMode::Constant(WHITE), false);
+
+        let straightened = deskew(&skewed, 10f32.to_radians());
+
+        assert!(deskew_angle(&straightened, 10f32.to_radians()).abs() < 0.5f32.to_radians());
+        assert!(deskew_angle(&text_page(), 10f32.to_radians()).abs() <= FINE_STEP);
+    }
+}
@@ -0,0 +1,200 @@
+use crate::{
+    component::primitive::Point,
+    image::Image,
+    lens::{
+        FromLens,
+        Lens,
+    },
+    pixel::Pixel,
+};
+
+/// Standard deviation of color difference to the guide, in RGB units, used to weight samples.
+const RANGE_SIGMA: f32 = 20f32;
+
+/// Upsample single channel `low` to the resolution of `guide` with joint bilateral upsampling.
+///
+/// Every output pixel is a weighted average of `low` values within `radius` (in `low` pixels)
+/// of its position in `low`. Weights fall off with distance and with color difference between
+/// the output pixel in `guide` and `guide` at the position of the sample, so edges of `guide`
+/// are followed instead of the blocky edges of `low`. Radius of 0 falls back to nearest
+/// neighbor. Result is an opaque grayscale image of the size of `guide`.
+///
+/// Useful for computing expensive results like masks or depth maps at low resolution.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     lens::value::ValueLens,
+///     operation::geometry::guided_upsample,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let low = ValueLens::new(200u8, Size::new(4, 4)?);
+/// let guide = Image::empty(Size::new(16, 16)?);
+///
+/// let upsampled = guided_upsample(&low, &guide, 2);
+///
+/// assert_eq!(upsampled.size(), guide.size());
+/// assert_eq!(*upsampled.pixel(Point::zero())?, Pixel::new([200, 200, 200, 255]));
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn guided_upsample(low: &impl Lens<Item = u8>, guide: &Image, radius: usize) -> Image {
+    let (low_size, size) = (low.size(), guide.size());
+    let values = low.elements().collect::<Vec<_>>();
+    let scale_x = low_size.width() as f32 / size.width() as f32;
+    let scale_y = low_size.height() as f32 / size.height() as f32;
+
+    // Guide color at the center of every low pixel.
+    let guide_samples = (0..low_size.area())
+        .map(|index| {
+            // SAFETY: index is always within low.
+            let point =
+                Point::from_index(index, low_size).expect("unexpected error in Point::from_index");
+            let x = to_high(point.x(), scale_x, size.width());
+            let y = to_high(point.y(), scale_y, size.height());
+            // SAFETY: to_high clamps coordinates to guide.
+            *guide
+                .pixel(Point::new(x, y).expect("unexpected error in Point::new"))
+                .expect("unexpected error in Image::pixel")
+        })
+        .collect::<Vec<_>>();
+
+    let spatial_sigma = (radius as f32 / 2f32).max(0.5);
+    let lens = guide.lens().map_with_point(move |point, px| {
+        let x = (point.x() as f32 + 0.5) * scale_x - 0.5;
+        let y = (point.y() as f32 + 0.5) * scale_y - 0.5;
+        let (center_x, center_y) = (
+            (x.round().max(0f32) as usize).min(low_size.width() - 1),
+            (y.round().max(0f32) as usize).min(low_size.height() - 1),
+        );
+
+        let (mut sum, mut total) = (0f32, 0f32);
+        for low_y in window(center_y, radius, low_size.height()) {
+            for low_x in window(center_x, radius, low_size.width()) {
+                let index = low_y * low_size.width() + low_x;
+                let distance_squared = (low_x as f32 - x).powi(2) + (low_y as f32 - y).powi(2);
+                let spatial = (-distance_squared / (2f32 * spatial_sigma * spatial_sigma)).exp();
+                let range = (-color_distance_squared(px, &guide_samples[index])
+                    / (2f32 * RANGE_SIGMA * RANGE_SIGMA))
+                    .exp();
+
+                sum += spatial * range * values[index] as f32;
+                total += spatial * range;
+            }
+        }
+
+        // Falls back to nearest value when every sample is too far or too different.
+        let value = match total > f32::EPSILON {
+            true => (sum / total).round().clamp(0f32, 255f32) as u8,
+            false => values[center_y * low_size.width() + center_x],
+        };
+        Pixel::new([value, value, value, 255])
+    });
+
+    Image::from_lens(lens)
+}
+
+/// Center of `low` coordinate in high resolution, clamped to `length`.
+fn to_high(low: usize, scale: f32, length: usize) -> usize {
+    let high = (low as f32 + 0.5) / scale - 0.5;
+    (high.round().max(0f32) as usize).min(length - 1)
+}
+
+/// Coordinates within `radius` of `center`, clamped to `length`.
+fn window(center: usize, radius: usize, length: usize) -> std::ops::RangeInclusive<usize> {
+    center.saturating_sub(radius)..=(center + radius).min(length - 1)
+}
+
+fn color_distance_squared(a: &Pixel, b: &Pixel) -> f32 {
+    [(a.r(), b.r()), (a.g(), b.g()), (a.b(), b.b())]
+        .iter()
+        .map(|(a, b)| (*a as f32 - *b as f32).powi(2))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        component::{
+            primitive::{
+                Scale,
+                Size,
+            },
+            sampler::EdgeMode,
+        },
+        gray_image::GrayImage,
+        operation::geometry::resize_bilinear,
+        pixel::ColorSpace,
+    };
+
+    /// 16x16 guide, black left of `x = 6` and white from it.
+    fn edge_guide() -> Image {
+        let size = Size::new(16, 16).unwrap();
+        let pixels = (0..size.area())
+            .map(|index| if index % 16 < 6 { 0 } else { 255 })
+            .map(|value| Pixel::new([value, value, value, 255]))
+            .collect();
+
+        Image::new(size, pixels).unwrap()
+    }
+
+    /// 4x4 mask of the guide computed at low resolution, second column straddles the edge.
+    fn low_mask() -> GrayImage {
+        let values = (0..16).map(|index| [0, 128, 255, 255][index % 4]).collect();
+
+        GrayImage::new(Size::new(4, 4).unwrap(), values).unwrap()
+    }
+
+    #[test]
+    fn test_follows_guide_edges() {
+        let guide = edge_guide();
+
+        let upsampled = guided_upsample(&low_mask().lens(), &guide, 2);
+
+        assert_eq!(upsampled.size(), guide.size());
+        for y in 0..16 {
+            for x in 0..16 {
+                let value = upsampled.pixel(Point::new(x, y).unwrap()).unwrap().r();
+                match x < 6 {
+                    true => assert!(value < 40, "({x}, {y}) = {value}"),
+                    false => assert!(value > 150, "({x}, {y}) = {value}"),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sharper_than_plain_upsampling() {
+        let mask = low_mask();
+        let mask_image =
+            Image::from_lens(mask.lens().map(|value| Pixel::new([value, value, value, 255])));
+
+        let guided = guided_upsample(&mask.lens(), &edge_guide(), 2);
+        let plain = resize_bilinear(
+            &mask_image,
+            Scale::new(4f32, 4f32).unwrap(),
+            EdgeMode::Clamp,
+            ColorSpace::Srgb,
+        )
+        .unwrap();
+
+        // Just left of the guide edge, plain upsampling is halfway, guided stays dark.
+        let point = Point::new(5, 8).unwrap();
+        assert!(plain.pixel(point).unwrap().r() > 100);
+        assert!(guided.pixel(point).unwrap().r() < 40);
+    }
+
+    #[test]
+    fn test_zero_radius_is_nearest() {
+        let upsampled = guided_upsample(&low_mask().lens(), &edge_guide(), 0);
+
+        assert_eq!(upsampled.pixel(Point::new(3, 0).unwrap()).unwrap().r(), 0);
+        assert_eq!(upsampled.pixel(Point::new(4, 0).unwrap()).unwrap().r(), 128);
+        assert_eq!(upsampled.pixel(Point::new(15, 0).unwrap()).unwrap().r(), 255);
+    }
+}
@@ -1,17 +1,77 @@
 mod crop;
+mod deskew;
+mod guided_upsample;
 mod resize;
+mod rotate;
+mod seam_carve;
+mod trim;
+mod warp;
 
 pub use crop::{
+    CropSubpixelCreationError,
+    CropSubpixelCreationResult,
     crop,
     crop_lens,
+    crop_subpixel,
+    crop_subpixel_lens,
 };
+pub use deskew::{
+    deskew,
+    deskew_angle,
+};
+pub use guided_upsample::guided_upsample;
 pub use resize::{
+    ResizeCreationError,
+    ResizeCreationResult,
     resize,
+    resize_bilinear,
+    resize_bilinear_lens,
     resize_lens,
 };
+pub use rotate::{
+    rotate,
+    rotate_arbitrary,
+    rotate_arbitrary_lens,
+    rotate_lens,
+};
+pub use seam_carve::{
+    SeamCarveCreationError,
+    SeamCarveCreationResult,
+    seam_carve,
+};
+pub use trim::{
+    TrimCreationError,
+    TrimCreationResult,
+    trim_transparent,
+};
+pub use warp::{
+    AFFINE_IDENTITY,
+    AffineMatrix,
+    WarpCreationError,
+    WarpCreationResult,
+    affine_inverse,
+    affine_rotation,
+    affine_scale,
+    affine_shear,
+    affine_then,
+    affine_translation,
+    warp_affine,
+    warp_affine_lens,
+};
 
 #[cfg(feature = "parallel")]
 pub use self::{
-    crop::crop_par,
-    resize::resize_par,
+    crop::{
+        crop_par,
+        crop_subpixel_par,
+    },
+    resize::{
+        resize_bilinear_par,
+        resize_par,
+    },
+    rotate::{
+        rotate_arbitrary_par,
+        rotate_par,
+    },
+    warp::warp_affine_par,
 };
@@ -4,16 +4,27 @@ use std::num::NonZeroUsize;
 use thiserror::Error;
 
 use crate::{
-    component::primitive::{
-        Scale,
-        SizeCreationError,
+    component::{
+        primitive::{
+            Scale,
+            SizeCreationError,
+        },
+        sampler::{
+            EdgeMode,
+            sample_bilinear,
+            sample_bilinear_linear,
+        },
     },
+    error::IndexError,
     image::Image,
     lens::{
         FromLens,
         Lens,
     },
-    pixel::Pixel,
+    pixel::{
+        ColorSpace,
+        Pixel,
+    },
 };
 
 #[derive(Debug, Error)]
@@ -60,6 +71,70 @@ pub fn resize_par(
     Ok(Image::from_lens_par(lens, threads))
 }
 
+/// Create a [`Lens`] resizing `source` by `scale` with bilinear interpolation.
+///
+/// Output pixel centers are mapped back into `source`, samples near the edges which reach
+/// outside of `source` are handled according to `edge_mode`. Colors are interpolated in
+/// `color_space`, [`ColorSpace::Linear`] avoids darkening fine detail when downscaling.
+pub fn resize_bilinear_lens<S>(
+    source: S,
+    scale: Scale,
+    edge_mode: EdgeMode,
+    color_space: ColorSpace,
+) -> ResizeCreationResult<impl Lens<Item = Pixel>>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    let size = scale.apply(source.size())?;
+    let inverse_scale = scale.inverse();
+
+    let lens = source.remap(
+        move |lens, point| {
+            if !size.contains(&point) {
+                return Err(IndexError::OutOfBounds);
+            }
+
+            // Shift from top left corners to centers of pixels.
+            let (x, y) = inverse_scale.sample_coords(point);
+            let x = x + 0.5 * inverse_scale.x() - 0.5;
+            let y = y + 0.5 * inverse_scale.y() - 0.5;
+
+            match color_space {
+                ColorSpace::Srgb => Ok(sample_bilinear(lens, x, y, edge_mode)),
+                ColorSpace::Linear => Ok(sample_bilinear_linear(lens, x, y, edge_mode)),
+            }
+        },
+        size,
+    );
+
+    Ok(lens)
+}
+
+pub fn resize_bilinear(
+    image: &Image,
+    scale: Scale,
+    edge_mode: EdgeMode,
+    color_space: ColorSpace,
+) -> ResizeCreationResult<Image> {
+    let lens = resize_bilinear_lens(image.lens(), scale, edge_mode, color_space)?;
+    Ok(Image::from_lens(lens))
+}
+
+#[cfg(feature = "parallel")]
+pub fn resize_bilinear_par(
+    image: &Image,
+    threads: NonZeroUsize,
+    scale: Scale,
+    edge_mode: EdgeMode,
+    color_space: ColorSpace,
+) -> ResizeCreationResult<Image> {
+    use crate::lens::FromLensPar;
+
+    let lens = resize_bilinear_lens(image.lens(), scale, edge_mode, color_space)?;
+    Ok(Image::from_lens_par(lens, threads))
+}
+
 #[cfg(test)]
 mod tests {
     use rand::{
@@ -67,7 +142,10 @@ mod tests {
         rngs::SmallRng,
     };
 
-    use crate::prelude::Size;
+    use crate::prelude::{
+        Point,
+        Size,
+    };
 
     use super::*;
 
@@ -91,4 +169,43 @@ mod tests {
         assert!(mixed.is_ok());
         assert_eq!(mixed.unwrap().size(), Size::new(5, 40).unwrap());
     }
+
+    #[test]
+    fn test_resize_bilinear_edge_modes() {
+        let white = Pixel::new([255, 255, 255, 255]);
+        let image =
+            Image::new(Size::new(2, 2).unwrap(), vec![white; 4].into_boxed_slice()).unwrap();
+        let scale = Scale::new(2f32, 2f32).unwrap();
+
+        let clamped = resize_bilinear(&image, scale, EdgeMode::Clamp, ColorSpace::Srgb).unwrap();
+        assert_eq!(clamped.size(), Size::new(4, 4).unwrap());
+        assert!(clamped.pixels().iter().all(|px| *px == white));
+
+        let constant =
+            resize_bilinear(&image, scale, EdgeMode::Constant(Pixel::zero()), ColorSpace::Srgb)
+                .unwrap();
+        let corner = constant.pixel(Point::new(0, 0).unwrap()).unwrap();
+        assert!(corner.r() < 255);
+        assert!(corner.a() < 255);
+    }
+
+    #[test]
+    fn test_resize_bilinear_linear_checkerboard() {
+        let size = Size::new(8, 8).unwrap();
+        let pixels = (0..size.area())
+            .map(|index| {
+                let (x, y) = (index % 8, index / 8);
+                let value = if (x + y) % 2 == 0 { 255 } else { 0 };
+                Pixel::new([value, value, value, 255])
+            })
+            .collect();
+        let image = Image::new(size, pixels).unwrap();
+        let scale = Scale::new(0.5, 0.5).unwrap();
+
+        let naive = resize_bilinear(&image, scale, EdgeMode::Clamp, ColorSpace::Srgb).unwrap();
+        let correct = resize_bilinear(&image, scale, EdgeMode::Clamp, ColorSpace::Linear).unwrap();
+
+        assert!(naive.pixels().iter().all(|px| px.r() == 128 && px.a() == 255));
+        assert!(correct.pixels().iter().all(|px| px.r() == 188 && px.a() == 255));
+    }
 }
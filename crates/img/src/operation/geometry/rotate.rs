@@ -0,0 +1,349 @@
+#[cfg(feature = "parallel")]
+use std::num::NonZeroUsize;
+
+use crate::{
+    component::{
+        lens::border::BorderMode,
+        primitive::Size,
+        sampler::{
+            EdgeMode,
+            sample_bilinear,
+            sample_nearest,
+        },
+    },
+    error::IndexError,
+    image::{
+        DIMENSION_MAX,
+        Image,
+    },
+    lens::{
+        FromLens,
+        Lens,
+    },
+    pixel::Pixel,
+};
+
+/// Tolerance for floating point error when computing expanded size, so that e.g. rotating by
+/// a right angle does not add a pixel.
+const EXPAND_EPSILON: f32 = 1e-3;
+
+/// Tolerance within which sine and cosine are snapped to whole numbers, so that rotating by
+/// right angles is pixel exact despite floating point error of `angle`.
+const SNAP_EPSILON: f32 = 1e-6;
+
+/// Create a [`Lens`] rotating `source` by `angle` radians clockwise around its center.
+///
+/// If `expand` is false, output keeps the size of `source` and corners of the rotated image are
+/// clipped. If it is true, output grows to contain the whole rotated image (up to
+/// [`DIMENSION_MAX`] in each dimension), centered on the canvas. Each output pixel is mapped back
+/// into `source` and sampled bilinearly, points mapped outside of `source` are handled according
+/// to `edge_mode`, so use [`EdgeMode::Constant`] to fill the area around the rotated image.
+/// Rotating by multiples of a right angle is pixel exact.
+pub fn rotate_arbitrary_lens<S>(
+    source: S,
+    angle: f32,
+    edge_mode: EdgeMode,
+    expand: bool,
+) -> impl Lens<Item = Pixel>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    let (sin, cos) = snapped_sin_cos(angle);
+    let size = if expand { expanded_size(source.size(), sin, cos) } else { source.size() };
+
+    inverse_rotation_lens(source, sin, cos, size, move |lens, x, y| {
+        sample_bilinear(lens, x, y, edge_mode)
+    })
+}
+
+/// Rotate an image by `angle` radians clockwise around its center, see
+/// [`rotate_arbitrary_lens`].
+pub fn rotate_arbitrary(image: &Image, angle: f32, edge_mode: EdgeMode, expand: bool) -> Image {
+    let lens = rotate_arbitrary_lens(image.lens(), angle, edge_mode, expand);
+    Image::from_lens(lens)
+}
+
+/// Rotate an image by `angle` radians clockwise around its center in parallel.
+#[cfg(feature = "parallel")]
+pub fn rotate_arbitrary_par(
+    image: &Image,
+    threads: NonZeroUsize,
+    angle: f32,
+    edge_mode: EdgeMode,
+    expand: bool,
+) -> Image {
+    use crate::lens::FromLensPar;
+
+    let lens = rotate_arbitrary_lens(image.lens(), angle, edge_mode, expand);
+    Image::from_lens_par(lens, threads)
+}
+
+/// Create a [`Lens`] rotating `source` by `angle` radians clockwise around its center, using
+/// nearest neighbor sampling.
+///
+/// Output has the size of the bounding box of the rotated image, which is centered on it. Each
+/// output pixel is mapped back into `source`, points mapped outside of it are handled according
+/// to `border`. Rotating by multiples of a right angle is pixel exact. See
+/// [`rotate_arbitrary_lens`] for bilinear sampling.
+///
+/// # Examples
+///
+/// ```
+/// use std::f32::consts::FRAC_PI_2;
+///
+/// use img::{
+///     component::lens::border::BorderMode,
+///     lens::{
+///         Lens,
+///         value::ValueLens,
+///     },
+///     operation::geometry::rotate_lens,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let source = ValueLens::new(Pixel::new([1, 2, 3, 255]), Size::new(6, 2)?);
+/// let rotated = rotate_lens(source, FRAC_PI_2, BorderMode::Clamp);
+///
+/// assert_eq!(rotated.size(), Size::new(2, 6)?);
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn rotate_lens<S>(source: S, angle: f32, border: BorderMode<Pixel>) -> impl Lens<Item = Pixel>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    let (sin, cos) = snapped_sin_cos(angle);
+    let size = expanded_size(source.size(), sin, cos);
+
+    inverse_rotation_lens(source, sin, cos, size, move |lens, x, y| {
+        sample_nearest(lens, x, y, border)
+    })
+}
+
+/// Rotate an image by `angle` radians clockwise around its center, filling the area around it
+/// with transparent pixels, see [`rotate_lens`].
+pub fn rotate(image: &Image, angle: f32) -> Image {
+    let lens = rotate_lens(image.lens(), angle, BorderMode::Constant(Pixel::zero()));
+    Image::from_lens(lens)
+}
+
+/// Rotate an image by `angle` radians clockwise around its center in parallel, see [`rotate`].
+#[cfg(feature = "parallel")]
+pub fn rotate_par(image: &Image, threads: NonZeroUsize, angle: f32) -> Image {
+    use crate::lens::FromLensPar;
+
+    let lens = rotate_lens(image.lens(), angle, BorderMode::Constant(Pixel::zero()));
+    Image::from_lens_par(lens, threads)
+}
+
+/// Create a [`Lens`] of `size` centered on `source` rotated by an angle with given `sin` and
+/// `cos`. Each output point is mapped back into `source` by the inverse rotation around centers
+/// of both and sampled at the resulting fractional coordinates with `sample`.
+fn inverse_rotation_lens<S, F>(
+    source: S,
+    sin: f32,
+    cos: f32,
+    size: Size,
+    sample: F,
+) -> impl Lens<Item = Pixel>
+where
+    S: Lens,
+    F: Fn(&S, f32, f32) -> Pixel,
+{
+    let source_size = source.size();
+    let source_center_x = (source_size.width() - 1) as f32 / 2f32;
+    let source_center_y = (source_size.height() - 1) as f32 / 2f32;
+    let center_x = (size.width() - 1) as f32 / 2f32;
+    let center_y = (size.height() - 1) as f32 / 2f32;
+
+    source.remap(
+        move |lens, point| {
+            if !size.contains(&point) {
+                return Err(IndexError::OutOfBounds);
+            }
+
+            let dx = point.x() as f32 - center_x;
+            let dy = point.y() as f32 - center_y;
+            let x = cos * dx + sin * dy + source_center_x;
+            let y = -sin * dx + cos * dy + source_center_y;
+
+            Ok(sample(lens, x, y))
+        },
+        size,
+    )
+}
+
+/// Sine and cosine of `angle`, snapped to whole numbers when within [`SNAP_EPSILON`] of them.
+fn snapped_sin_cos(angle: f32) -> (f32, f32) {
+    let snap = |value: f32| {
+        if (value - value.round()).abs() < SNAP_EPSILON { value.round() } else { value }
+    };
+    let (sin, cos) = angle.sin_cos();
+
+    (snap(sin), snap(cos))
+}
+
+/// Size of the bounding box of `size` rotated by an angle with given `sin` and `cos`.
+fn expanded_size(size: Size, sin: f32, cos: f32) -> Size {
+    let (width, height) = (size.width() as f32, size.height() as f32);
+    let dimension = |value: f32| ((value - EXPAND_EPSILON).ceil() as usize).clamp(1, DIMENSION_MAX);
+
+    // SAFETY: both dimensions are clamped to valid range.
+    Size::new(
+        dimension(width * cos.abs() + height * sin.abs()),
+        dimension(width * sin.abs() + height * cos.abs()),
+    )
+    .expect("unexpected error in Size::new")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::{
+        FRAC_PI_2,
+        FRAC_PI_4,
+        FRAC_PI_6,
+        PI,
+    };
+
+    use crate::{
+        component::primitive::{
+            Point,
+            Size,
+        },
+        lens::value::ValueLens,
+    };
+
+    use super::*;
+
+    const WHITE: Pixel = Pixel::new([255, 255, 255, 255]);
+    const BLACK: Pixel = Pixel::new([0, 0, 0, 255]);
+
+    #[test]
+    fn test_rotate_arbitrary_edge_modes() {
+        let image = Image::from_lens(ValueLens::new(WHITE, Size::new(20, 10).unwrap()));
+        let corner = Point::new(0, 0).unwrap();
+        let center = Point::new(10, 5).unwrap();
+
+        let clamped = rotate_arbitrary(&image, FRAC_PI_6, EdgeMode::Clamp, false);
+        assert!(clamped.pixels().iter().all(|px| *px == WHITE));
+
+        let constant = rotate_arbitrary(&image, FRAC_PI_6, EdgeMode::Constant(BLACK), false);
+        assert!(constant.pixel(corner).unwrap().r() < 32);
+        assert_eq!(constant.pixel(center).unwrap(), &WHITE);
+    }
+
+    #[test]
+    fn test_rotate_arbitrary_zero_is_identity() {
+        let image = Image::from_lens(ValueLens::new(0u8, Size::new(5, 4).unwrap()).remap(
+            |_, point| Ok(Pixel::new([point.x() as u8, point.y() as u8, 0, 255])),
+            Size::new(5, 4).unwrap(),
+        ));
+
+        let rotated = rotate_arbitrary(&image, 0f32, EdgeMode::Clamp, false);
+        assert_eq!(rotated.buffer(), image.buffer());
+    }
+
+    #[test]
+    fn test_rotate_arbitrary_expand() {
+        let image = Image::from_lens(ValueLens::new(WHITE, Size::new(20, 10).unwrap()));
+
+        let expanded = rotate_arbitrary(&image, FRAC_PI_4, EdgeMode::Constant(BLACK), true);
+
+        // Bounding box of the rotated image is (20 + 10) / sqrt(2) wide and tall.
+        assert_eq!(expanded.size(), Size::new(22, 22).unwrap());
+        assert_eq!(expanded.pixel(Point::zero()).unwrap(), &BLACK);
+        assert_eq!(expanded.pixel(Point::new(11, 11).unwrap()).unwrap(), &WHITE);
+        // Whole image is contained, so the total amount of white is preserved.
+        let white = expanded.pixels().iter().map(|px| px.r() as f32 / 255f32).sum::<f32>();
+        assert!((white - 200f32).abs() < 10f32, "white area {white}");
+    }
+
+    #[test]
+    fn test_rotate_arbitrary_crop() {
+        let image = Image::from_lens(ValueLens::new(WHITE, Size::new(20, 20).unwrap()));
+
+        let cropped = rotate_arbitrary(&image, FRAC_PI_4, EdgeMode::Constant(BLACK), false);
+
+        assert_eq!(cropped.size(), image.size());
+        for corner in [(0, 0), (19, 0), (0, 19), (19, 19)] {
+            let corner = Point::new(corner.0, corner.1).unwrap();
+            assert_eq!(cropped.pixel(corner).unwrap(), &BLACK);
+        }
+        assert_eq!(cropped.pixel(Point::new(10, 10).unwrap()).unwrap(), &WHITE);
+    }
+
+    #[test]
+    fn test_rotate_arbitrary_expand_right_angle() {
+        let image = Image::from_lens(ValueLens::new(WHITE, Size::new(20, 10).unwrap()));
+
+        let rotated = rotate_arbitrary(&image, FRAC_PI_2, EdgeMode::Clamp, true);
+
+        assert_eq!(rotated.size(), Size::new(10, 20).unwrap());
+        assert!(rotated.pixels().iter().all(|px| *px == WHITE));
+    }
+
+    /// Image with unique color at every point.
+    fn indexed(width: usize, height: usize) -> Image {
+        let size = Size::new(width, height).unwrap();
+        Image::from_lens(
+            ValueLens::new(0u8, size)
+                .remap(|_, point| Ok(Pixel::new([point.x() as u8, point.y() as u8, 0, 255])), size),
+        )
+    }
+
+    #[test]
+    fn test_rotate_right_angles_are_exact() {
+        let image = indexed(5, 3);
+        let source = |x: usize, y: usize| *image.pixel(Point::new(x, y).unwrap()).unwrap();
+
+        let quarter = rotate(&image, FRAC_PI_2);
+        let half = rotate(&image, PI);
+        let three_quarters = rotate(&image, 3f32 * FRAC_PI_2);
+
+        assert_eq!(quarter.size(), Size::new(3, 5).unwrap());
+        assert_eq!(half.size(), image.size());
+        assert_eq!(three_quarters.size(), Size::new(3, 5).unwrap());
+        for (point, px) in quarter.lens().elements().enumerate() {
+            let point = Point::from_index(point, quarter.size()).unwrap();
+            assert_eq!(*px, source(point.y(), 2 - point.x()));
+        }
+        for (point, px) in half.lens().elements().enumerate() {
+            let point = Point::from_index(point, half.size()).unwrap();
+            assert_eq!(*px, source(4 - point.x(), 2 - point.y()));
+        }
+        for (point, px) in three_quarters.lens().elements().enumerate() {
+            let point = Point::from_index(point, three_quarters.size()).unwrap();
+            assert_eq!(*px, source(4 - point.y(), point.x()));
+        }
+        assert_eq!(rotate(&image, 0f32).buffer(), image.buffer());
+    }
+
+    #[test]
+    fn test_rotate_arbitrary_right_angles_are_exact() {
+        let image = indexed(5, 3);
+
+        for angle in [FRAC_PI_2, PI, 3f32 * FRAC_PI_2] {
+            let bilinear = rotate_arbitrary(&image, angle, EdgeMode::Constant(BLACK), true);
+            assert_eq!(bilinear.buffer(), rotate(&image, angle).buffer());
+        }
+    }
+
+    #[test]
+    fn test_rotate_border_modes() {
+        let image = Image::from_lens(ValueLens::new(WHITE, Size::new(20, 10).unwrap()));
+
+        let constant =
+            Image::from_lens(rotate_lens(image.lens(), FRAC_PI_6, BorderMode::Constant(BLACK)));
+        let clamped = Image::from_lens(rotate_lens(image.lens(), FRAC_PI_6, BorderMode::Clamp));
+        let transparent = rotate(&image, FRAC_PI_4);
+
+        assert_eq!(constant.pixel(Point::zero()).unwrap(), &BLACK);
+        assert_eq!(constant.pixel(constant.size().middle()).unwrap(), &WHITE);
+        assert!(clamped.pixels().iter().all(|px| *px == WHITE));
+        assert_eq!(transparent.pixel(Point::zero()).unwrap(), &Pixel::zero());
+    }
+}
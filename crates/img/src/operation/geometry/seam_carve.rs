@@ -0,0 +1,211 @@
+use thiserror::Error;
+
+use crate::{
+    component::primitive::{
+        Point,
+        Size,
+    },
+    error::IndexError,
+    image::Image,
+    lens::{
+        FromLens,
+        Lens,
+    },
+    operation::detection::edge::gradient_magnitude_lens,
+    pixel::Pixel,
+};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SeamCarveCreationError {
+    #[error("target size must not be larger than image size")]
+    TargetTooLarge,
+}
+
+pub type SeamCarveCreationResult<T> = std::result::Result<T, SeamCarveCreationError>;
+
+/// Resize an image to `target` size by removing low-energy seams (content-aware resize).
+///
+/// Energy of each pixel is the magnitude of Sobel gradient of its luma. Vertical seams are
+/// removed first until width matches `target`, then horizontal ones. Each seam is a connected
+/// path of pixels with the lowest total energy found with dynamic programming, so regions with
+/// little detail are removed while high-energy content is preserved.
+///
+/// Returns carved [`Image`] or [`SeamCarveCreationError`] if `target` is larger than the image
+/// in any dimension.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     operation::geometry::seam_carve,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let image = Image::empty(Size::new(10, 10)?);
+/// let carved = seam_carve(&image, Size::new(8, 9)?)?;
+///
+/// assert_eq!(carved.size(), Size::new(8, 9)?);
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn seam_carve(image: &Image, target: Size) -> SeamCarveCreationResult<Image> {
+    let size = image.size();
+    if target.width() > size.width() || target.height() > size.height() {
+        return Err(SeamCarveCreationError::TargetTooLarge);
+    }
+
+    let mut carved = image.clone();
+    while carved.size().width() > target.width() {
+        carved = remove_vertical_seam(&carved);
+    }
+
+    carved = transpose(&carved);
+    while carved.size().width() > target.height() {
+        carved = remove_vertical_seam(&carved);
+    }
+
+    Ok(transpose(&carved))
+}
+
+/// Calculate energy map of `image` as row-major buffer.
+fn energy(image: &Image) -> Vec<f32> {
+    gradient_magnitude_lens(image.lens()).elements().collect()
+}
+
+/// Find and remove vertical seam with the lowest total energy.
+fn remove_vertical_seam(image: &Image) -> Image {
+    let size = image.size();
+    let (width, height) = (size.width(), size.height());
+    let energy = energy(image);
+
+    // cost[y * width + x] is the lowest total energy of a seam from the top row to (x, y)
+    let mut cost = energy.clone();
+    for y in 1..height {
+        for x in 0..width {
+            let above = (x.saturating_sub(1)..=(x + 1).min(width - 1))
+                .map(|above_x| cost[(y - 1) * width + above_x])
+                .fold(f32::INFINITY, f32::min);
+            cost[y * width + x] += above;
+        }
+    }
+
+    let mut seam = vec![0; height];
+    let last_row = &cost[(height - 1) * width..];
+    seam[height - 1] = min_index(last_row, 0..width);
+    for y in (0..height - 1).rev() {
+        let x = seam[y + 1];
+        let row = &cost[y * width..(y + 1) * width];
+        seam[y] = min_index(row, x.saturating_sub(1)..(x + 2).min(width));
+    }
+
+    // SAFETY: removing one column from image wider than target keeps width at least 1.
+    let new_size = Size::new(width - 1, height).expect("unexpected error in Size::new");
+    let pixels = image
+        .pixels()
+        .chunks(width)
+        .zip(&seam)
+        .flat_map(|(row, &seam_x)| row[..seam_x].iter().chain(&row[seam_x + 1..]))
+        .copied()
+        .collect();
+
+    // SAFETY: exactly one pixel was removed from every row.
+    Image::new(new_size, pixels).expect("unexpected error in Image::new")
+}
+
+/// Get index of the lowest value in `row` within `range`, first one wins on ties.
+fn min_index(row: &[f32], range: std::ops::Range<usize>) -> usize {
+    range
+        .fold(None, |best: Option<usize>, x| match best {
+            Some(best) if row[best] <= row[x] => Some(best),
+            _ => Some(x),
+        })
+        .expect("unexpected empty range")
+}
+
+fn transpose(image: &Image) -> Image {
+    let size = image.size().transpose();
+
+    Image::from_lens(
+        image
+            .lens()
+            .remap(
+                move |s, point| {
+                    if !size.contains(&point) {
+                        return Err(IndexError::OutOfBounds);
+                    }
+
+                    s.look(
+                        Point::new(point.y(), point.x()).expect("unexpected error in Point::new"),
+                    )
+                },
+                size,
+            )
+            .map(|px: &Pixel| *px),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use super::*;
+
+    const GRAY: Pixel = Pixel::new([128, 128, 128, 255]);
+
+    /// Create noisy image with flat gray columns 3, 4 and 5, column 4 has no gradient at all.
+    fn image_with_flat_column() -> Image {
+        let noise = Image::random(Size::new(9, 6).unwrap(), &mut SmallRng::seed_from_u64(0));
+        let pixels = noise
+            .pixels()
+            .iter()
+            .enumerate()
+            .map(|(index, px)| if (3..=5).contains(&(index % 9)) { GRAY } else { *px })
+            .collect();
+
+        Image::new(Size::new(9, 6).unwrap(), pixels).unwrap()
+    }
+
+    fn without_column(image: &Image, column: usize) -> Vec<Pixel> {
+        let width = image.size().width();
+        image
+            .pixels()
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| index % width != column)
+            .map(|(_, px)| *px)
+            .collect()
+    }
+
+    #[test]
+    fn test_seam_carve_removes_low_energy_column() {
+        let image = image_with_flat_column();
+        let carved = seam_carve(&image, Size::new(8, 6).unwrap()).unwrap();
+
+        assert_eq!(carved.size(), Size::new(8, 6).unwrap());
+        assert_eq!(carved.pixels(), without_column(&image, 4).as_slice());
+    }
+
+    #[test]
+    fn test_seam_carve_removes_low_energy_row() {
+        let image = transpose(&image_with_flat_column());
+        let carved = seam_carve(&image, Size::new(6, 8).unwrap()).unwrap();
+
+        assert_eq!(carved.size(), Size::new(6, 8).unwrap());
+        assert_eq!(transpose(&carved).pixels(), without_column(&transpose(&image), 4).as_slice());
+    }
+
+    #[test]
+    fn test_seam_carve_target_too_large() {
+        let image = Image::empty(Size::new(4, 4).unwrap());
+
+        assert_eq!(
+            seam_carve(&image, Size::new(5, 4).unwrap()).unwrap_err(),
+            SeamCarveCreationError::TargetTooLarge
+        );
+    }
+}
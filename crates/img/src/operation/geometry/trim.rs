@@ -0,0 +1,120 @@
+use thiserror::Error;
+
+use crate::{
+    component::primitive::{
+        Area,
+        Margin,
+        Point,
+        Size,
+    },
+    image::Image,
+    lens::Lens,
+    operation::geometry::crop,
+};
+
+/// Error returned by trim_transparent function
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TrimCreationError {
+    #[error("image is fully transparent")]
+    FullyTransparent,
+}
+
+pub type TrimCreationResult<T> = std::result::Result<T, TrimCreationError>;
+
+/// Crop fully transparent rows and columns from the borders of an image, e.g. before packing
+/// sprites into an atlas.
+///
+/// Returns the tightest crop containing every pixel with non-zero alpha, together with the
+/// [`Area`] it occupied in the original image, or [`TrimCreationError::FullyTransparent`] if
+/// there is no such pixel.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     operation::geometry::trim_transparent,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let mut image = Image::empty(Size::new(5, 4)?);
+/// image.pixel_mut(Point::new(2, 1)?)?.set_a(255);
+///
+/// let (trimmed, area) = trim_transparent(&image)?;
+///
+/// assert_eq!(trimmed.size(), Size::new(1, 1)?);
+/// assert_eq!(area.top_left(), Point::new(2, 1)?);
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn trim_transparent(image: &Image) -> TrimCreationResult<(Image, Area)> {
+    let size = image.size();
+    let opaque_rows: Vec<_> = image.lens().reduce_rows(|mut row| row.any(|px| px.a() != 0));
+    let opaque_columns: Vec<_> =
+        image.lens().reduce_columns(|mut column| column.any(|px| px.a() != 0));
+
+    let top = opaque_rows.iter().position(|opaque| *opaque);
+    let bottom = opaque_rows.iter().rposition(|opaque| *opaque);
+    let left = opaque_columns.iter().position(|opaque| *opaque);
+    let right = opaque_columns.iter().rposition(|opaque| *opaque);
+
+    let (Some(top), Some(bottom), Some(left), Some(right)) = (top, bottom, left, right) else {
+        return Err(TrimCreationError::FullyTransparent);
+    };
+
+    // SAFETY: bounds come from positions of opaque rows and columns, so they are within the
+    // image and describe a non-empty area.
+    let margin = Margin::new(top, size.width() - 1 - right, size.height() - 1 - bottom, left)
+        .expect("unexpected error in Margin::new");
+    let trimmed = crop(image, margin).expect("unexpected error in crop");
+    let area = Area::new(
+        Size::new(right - left + 1, bottom - top + 1).expect("unexpected error in Size::new"),
+        Point::new(left, top).expect("unexpected error in Point::new"),
+    );
+
+    Ok((trimmed, area))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_trim_transparent_border() {
+        let mut image = Image::empty(Size::new(8, 6).unwrap());
+        for (x, y) in [(2, 1), (5, 3), (3, 4)] {
+            image.pixel_mut(Point::new(x, y).unwrap()).unwrap().set_a(1);
+        }
+
+        let (trimmed, area) = trim_transparent(&image).unwrap();
+
+        assert_eq!(trimmed.size(), Size::new(4, 4).unwrap());
+        assert_eq!(area.size(), trimmed.size());
+        assert_eq!(area.top_left(), Point::new(2, 1).unwrap());
+        assert_eq!(trimmed.pixel(Point::new(3, 2).unwrap()).unwrap().a(), 1);
+    }
+
+    #[test]
+    fn test_trim_transparent_opaque_unchanged() {
+        let image = Image::random_opaque(Size::new(5, 3).unwrap(), &mut SmallRng::seed_from_u64(0));
+
+        let (trimmed, area) = trim_transparent(&image).unwrap();
+
+        assert_eq!(trimmed.buffer(), image.buffer());
+        assert_eq!(area.size(), image.size());
+        assert_eq!(area.top_left(), Point::zero());
+    }
+
+    #[test]
+    fn test_trim_transparent_fully_transparent() {
+        let image = Image::empty(Size::new(3, 3).unwrap());
+
+        assert_eq!(trim_transparent(&image).unwrap_err(), TrimCreationError::FullyTransparent);
+    }
+}
@@ -0,0 +1,254 @@
+#[cfg(feature = "parallel")]
+use std::num::NonZeroUsize;
+
+use thiserror::Error;
+
+use crate::{
+    component::{
+        primitive::Size,
+        sampler::{
+            EdgeMode,
+            sample_bilinear,
+        },
+    },
+    error::IndexError,
+    image::Image,
+    lens::{
+        FromLens,
+        Lens,
+    },
+    pixel::Pixel,
+};
+
+/// Error returned by warp_affine functions
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum WarpCreationError {
+    #[error("affine matrix is singular")]
+    SingularMatrix,
+}
+
+pub type WarpCreationResult<T> = std::result::Result<T, WarpCreationError>;
+
+/// 2x3 affine transformation matrix, mapping `(x, y)` to
+/// `(m[0][0] * x + m[0][1] * y + m[0][2], m[1][0] * x + m[1][1] * y + m[1][2])`.
+pub type AffineMatrix = [[f32; 3]; 2];
+
+/// Affine matrix which leaves every point in place.
+pub const AFFINE_IDENTITY: AffineMatrix = [[1f32, 0f32, 0f32], [0f32, 1f32, 0f32]];
+
+/// Create affine matrix moving points by `dx` and `dy`.
+pub fn affine_translation(dx: f32, dy: f32) -> AffineMatrix {
+    [[1f32, 0f32, dx], [0f32, 1f32, dy]]
+}
+
+/// Create affine matrix scaling points by `sx` and `sy` relative to origin.
+pub fn affine_scale(sx: f32, sy: f32) -> AffineMatrix {
+    [[sx, 0f32, 0f32], [0f32, sy, 0f32]]
+}
+
+/// Create affine matrix shearing points, `x` is moved by `kx * y` and `y` by `ky * x`.
+pub fn affine_shear(kx: f32, ky: f32) -> AffineMatrix {
+    [[1f32, kx, 0f32], [ky, 1f32, 0f32]]
+}
+
+/// Create affine matrix rotating points by `angle` radians clockwise around `(cx, cy)`.
+pub fn affine_rotation(angle: f32, cx: f32, cy: f32) -> AffineMatrix {
+    let (sin, cos) = angle.sin_cos();
+
+    [[cos, -sin, cx - cos * cx + sin * cy], [sin, cos, cy - sin * cx - cos * cy]]
+}
+
+/// Combine two affine matrices, resulting matrix applies `first` and then `second`.
+pub fn affine_then(first: AffineMatrix, second: AffineMatrix) -> AffineMatrix {
+    std::array::from_fn(|row| {
+        [
+            second[row][0] * first[0][0] + second[row][1] * first[1][0],
+            second[row][0] * first[0][1] + second[row][1] * first[1][1],
+            second[row][0] * first[0][2] + second[row][1] * first[1][2] + second[row][2],
+        ]
+    })
+}
+
+/// Invert an affine matrix.
+///
+/// Returns `None` if `matrix` is singular, i.e. its determinant is negligible compared to the
+/// products it is computed from, or if the inverse is not finite. Matrices with a small
+/// determinant due to strong scaling, like `affine_scale(0.001, 0.001)`, are still invertible.
+pub fn affine_inverse(matrix: AffineMatrix) -> Option<AffineMatrix> {
+    let [[a, b, c], [d, e, f]] = matrix;
+    let determinant = a * e - b * d;
+    if determinant.abs() <= f32::EPSILON * (a * e).abs().max((b * d).abs()) {
+        return None;
+    }
+
+    let (ia, ib, id, ie) = (e / determinant, -b / determinant, -d / determinant, a / determinant);
+    let inverse = [[ia, ib, -(ia * c + ib * f)], [id, ie, -(id * c + ie * f)]];
+
+    inverse.as_flattened().iter().all(|value| value.is_finite()).then_some(inverse)
+}
+
+/// Create a [`Lens`] applying affine `matrix` to `source`.
+///
+/// `matrix` maps source coordinates into output coordinates. Each output pixel is mapped back
+/// through inverse of `matrix` and sampled bilinearly, points mapped outside of `source` are
+/// handled according to `edge_mode`.
+///
+/// Returns [`WarpCreationError::SingularMatrix`] if `matrix` can't be inverted, see
+/// [`affine_inverse`].
+pub fn warp_affine_lens<S>(
+    source: S,
+    matrix: AffineMatrix,
+    output_size: Size,
+    edge_mode: EdgeMode,
+) -> WarpCreationResult<impl Lens<Item = Pixel>>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    let [[a, b, c], [d, e, f]] = affine_inverse(matrix).ok_or(WarpCreationError::SingularMatrix)?;
+
+    let lens = source.remap(
+        move |lens, point| {
+            if !output_size.contains(&point) {
+                return Err(IndexError::OutOfBounds);
+            }
+
+            let (x, y) = (point.x() as f32, point.y() as f32);
+            let (source_x, source_y) = (a * x + b * y + c, d * x + e * y + f);
+
+            Ok(sample_bilinear(lens, source_x, source_y, edge_mode))
+        },
+        output_size,
+    );
+
+    Ok(lens)
+}
+
+/// Apply affine `matrix` to an image, see [`warp_affine_lens`].
+pub fn warp_affine(
+    image: &Image,
+    matrix: AffineMatrix,
+    output_size: Size,
+    edge_mode: EdgeMode,
+) -> WarpCreationResult<Image> {
+    let lens = warp_affine_lens(image.lens(), matrix, output_size, edge_mode)?;
+    Ok(Image::from_lens(lens))
+}
+
+/// Apply affine `matrix` to an image in parallel.
+#[cfg(feature = "parallel")]
+pub fn warp_affine_par(
+    image: &Image,
+    threads: NonZeroUsize,
+    matrix: AffineMatrix,
+    output_size: Size,
+    edge_mode: EdgeMode,
+) -> WarpCreationResult<Image> {
+    use crate::lens::FromLensPar;
+
+    let lens = warp_affine_lens(image.lens(), matrix, output_size, edge_mode)?;
+    Ok(Image::from_lens_par(lens, threads))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use crate::component::primitive::Point;
+
+    use super::*;
+
+    #[test]
+    fn test_warp_affine_identity() {
+        let image = Image::random(Size::new(7, 5).unwrap(), &mut SmallRng::seed_from_u64(0));
+        let warped = warp_affine(&image, AFFINE_IDENTITY, image.size(), EdgeMode::Clamp).unwrap();
+
+        assert_eq!(warped.buffer(), image.buffer());
+    }
+
+    #[test]
+    fn test_warp_affine_translation() {
+        let image = Image::random(Size::new(7, 5).unwrap(), &mut SmallRng::seed_from_u64(0));
+        let warped = warp_affine(
+            &image,
+            affine_translation(2f32, 1f32),
+            image.size(),
+            EdgeMode::Constant(Pixel::zero()),
+        )
+        .unwrap();
+
+        for y in 0..5 {
+            for x in 0..7 {
+                let point = Point::new(x, y).unwrap();
+                let expected = if x >= 2 && y >= 1 {
+                    *image.pixel(Point::new(x - 2, y - 1).unwrap()).unwrap()
+                } else {
+                    Pixel::zero()
+                };
+                assert_eq!(warped.pixel(point).unwrap(), &expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_affine_helpers() {
+        let apply = |[[a, b, c], [d, e, f]]: AffineMatrix, (x, y): (f32, f32)| {
+            (a * x + b * y + c, d * x + e * y + f)
+        };
+        let close = |(ax, ay): (f32, f32), (bx, by): (f32, f32)| {
+            (ax - bx).abs() < 1e-4 && (ay - by).abs() < 1e-4
+        };
+
+        let rotation = affine_rotation(std::f32::consts::FRAC_PI_2, 1f32, 1f32);
+        assert!(close(apply(rotation, (2f32, 1f32)), (1f32, 2f32)));
+
+        let combined = affine_then(affine_scale(2f32, 3f32), affine_translation(1f32, -1f32));
+        assert!(close(apply(combined, (1f32, 1f32)), (3f32, 2f32)));
+
+        let inverse = affine_inverse(combined).unwrap();
+        assert!(close(apply(inverse, (3f32, 2f32)), (1f32, 1f32)));
+
+        assert!(affine_inverse(affine_scale(0f32, 1f32)).is_none());
+        assert!(affine_inverse([[1f32, 2f32, 0f32], [2f32, 4f32, 0f32]]).is_none());
+        assert!(close(apply(affine_shear(1f32, 0f32), (1f32, 2f32)), (3f32, 2f32)));
+    }
+
+    #[test]
+    fn test_warp_affine_strong_downscale() {
+        let matrix = affine_scale(0.001f32, 0.001f32);
+
+        let inverse = affine_inverse(matrix).unwrap();
+
+        assert!((inverse[0][0] - 1000f32).abs() < 1e-2);
+        assert!((inverse[1][1] - 1000f32).abs() < 1e-2);
+
+        // Every output pixel but the origin maps 1000 pixels away, which is clamped to an edge.
+        let image = Image::random(Size::new(7, 5).unwrap(), &mut SmallRng::seed_from_u64(0));
+        let warped =
+            warp_affine(&image, matrix, Size::new(2, 2).unwrap(), EdgeMode::Clamp).unwrap();
+
+        for (output, source) in
+            [((0, 0), (0, 0)), ((1, 0), (6, 0)), ((0, 1), (0, 4)), ((1, 1), (6, 4))]
+        {
+            assert_eq!(
+                warped.pixel(Point::new(output.0, output.1).unwrap()).unwrap(),
+                image.pixel(Point::new(source.0, source.1).unwrap()).unwrap(),
+            );
+        }
+    }
+
+    #[test]
+    fn test_warp_affine_singular_matrix() {
+        let image = Image::random(Size::new(7, 5).unwrap(), &mut SmallRng::seed_from_u64(0));
+
+        for edge_mode in [EdgeMode::Clamp, EdgeMode::Wrap, EdgeMode::Constant(Pixel::zero())] {
+            assert!(matches!(
+                warp_affine(&image, affine_scale(0f32, 1f32), image.size(), edge_mode),
+                Err(WarpCreationError::SingularMatrix)
+            ));
+        }
+    }
+}
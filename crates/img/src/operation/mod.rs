@@ -1,4 +1,12 @@
+pub mod blend;
 pub mod blur;
 pub mod color;
+pub mod compare;
 pub mod detection;
+pub mod film;
+pub mod frequency;
 pub mod geometry;
+pub mod morphology;
+pub mod pipeline;
+pub mod segmentation;
+pub mod stats;
@@ -0,0 +1,327 @@
+mod separable;
+
+#[cfg(feature = "parallel")]
+use std::num::NonZeroUsize;
+
+use thiserror::Error;
+
+use self::separable::square_lens;
+use crate::{
+    component::{
+        kernel::morphology::{
+            MorphologyKernel,
+            MorphologyOperation,
+            StructuringElement,
+        },
+        primitive::{
+            Connectivity,
+            Point,
+            Size,
+            SizeCreationError,
+        },
+    },
+    error::IndexResult,
+    image::Image,
+    lens::{
+        self,
+        FromLens,
+        Lens,
+    },
+    pixel::{
+        ChannelFlags,
+        Pixel,
+    },
+};
+
+/// Error returned by morphology functions
+#[derive(Debug, Error)]
+pub enum MorphologyCreationError {
+    #[error("failed to create kernel lens: {0}")]
+    KernelLens(#[from] lens::kernel::CreationError),
+    #[error("failed to create size: {0}")]
+    Size(#[from] SizeCreationError),
+}
+
+pub type MorphologyCreationResult<T> = std::result::Result<T, MorphologyCreationError>;
+
+/// Create a [`Lens`] that replaces every channel with minimum within `element`.
+///
+/// Square elements are applied as two separable passes with cost per pixel independent of
+/// their radius.
+pub fn erode_lens<S>(
+    source: S,
+    element: StructuringElement,
+    flags: ChannelFlags,
+) -> MorphologyCreationResult<impl Lens<Item = Pixel>>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    morphology_lens(source, element, MorphologyOperation::Erode, flags, square_lens)
+}
+
+/// Create a [`Lens`] that replaces every channel with maximum within `element`.
+///
+/// Square elements are applied as two separable passes with cost per pixel independent of
+/// their radius.
+pub fn dilate_lens<S>(
+    source: S,
+    element: StructuringElement,
+    flags: ChannelFlags,
+) -> MorphologyCreationResult<impl Lens<Item = Pixel>>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    morphology_lens(source, element, MorphologyOperation::Dilate, flags, square_lens)
+}
+
+/// Apply erosion to an image.
+pub fn erode(
+    image: &Image,
+    element: StructuringElement,
+    flags: ChannelFlags,
+) -> MorphologyCreationResult<Image> {
+    let lens = erode_lens(image.lens(), element, flags)?;
+    Ok(Image::from_lens(lens))
+}
+
+/// Apply dilation to an image.
+pub fn dilate(
+    image: &Image,
+    element: StructuringElement,
+    flags: ChannelFlags,
+) -> MorphologyCreationResult<Image> {
+    let lens = dilate_lens(image.lens(), element, flags)?;
+    Ok(Image::from_lens(lens))
+}
+
+/// Apply erosion to an image in parallel.
+#[cfg(feature = "parallel")]
+pub fn erode_par(
+    image: &Image,
+    threads: NonZeroUsize,
+    element: StructuringElement,
+    flags: ChannelFlags,
+) -> MorphologyCreationResult<Image> {
+    use crate::lens::FromLensPar;
+
+    let lens = morphology_lens(
+        image.lens(),
+        element,
+        MorphologyOperation::Erode,
+        flags,
+        |source, radius, operation, flags| {
+            separable::square_lens_par(source, radius, operation, flags, threads)
+        },
+    )?;
+    Ok(Image::from_lens_par(lens, threads))
+}
+
+/// Apply dilation to an image in parallel.
+#[cfg(feature = "parallel")]
+pub fn dilate_par(
+    image: &Image,
+    threads: NonZeroUsize,
+    element: StructuringElement,
+    flags: ChannelFlags,
+) -> MorphologyCreationResult<Image> {
+    use crate::lens::FromLensPar;
+
+    let lens = morphology_lens(
+        image.lens(),
+        element,
+        MorphologyOperation::Dilate,
+        flags,
+        |source, radius, operation, flags| {
+            separable::square_lens_par(source, radius, operation, flags, threads)
+        },
+    )?;
+    Ok(Image::from_lens_par(lens, threads))
+}
+
+/// Create a [`Lens`] applying `operation` within `element`, using lens built by `square` for
+/// square elements and [`MorphologyKernel`] for all the others.
+fn morphology_lens<S, Q, L>(
+    source: S,
+    element: StructuringElement,
+    operation: MorphologyOperation,
+    flags: ChannelFlags,
+    square: Q,
+) -> MorphologyCreationResult<impl Lens<Item = Pixel>>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+    Q: FnOnce(
+        S,
+        usize,
+        MorphologyOperation,
+        ChannelFlags,
+    ) -> Result<L, lens::kernel::CreationError>,
+    L: Lens<Item = Pixel>,
+{
+    let kernel = MorphologyKernel::new(element, operation, flags)?;
+
+    let lens = match element {
+        StructuringElement::Square(radius) => {
+            MorphologyLens::Square(square(source, radius, operation, flags)?)
+        }
+        StructuringElement::Neighborhood(Connectivity::Eight) => {
+            MorphologyLens::Square(square(source, 1, operation, flags)?)
+        }
+        _ => MorphologyLens::Kernel(source.kernel(kernel)?),
+    };
+
+    Ok(lens)
+}
+
+/// [`Lens`] of either implementation used by [`morphology_lens`].
+enum MorphologyLens<K, Q> {
+    Kernel(K),
+    Square(Q),
+}
+
+impl<K, Q> Lens for MorphologyLens<K, Q>
+where
+    K: Lens<Item = Pixel>,
+    Q: Lens<Item = Pixel>,
+{
+    type Item = Pixel;
+
+    fn look(&self, point: Point) -> IndexResult<Self::Item> {
+        match self {
+            MorphologyLens::Kernel(lens) => lens.look(point),
+            MorphologyLens::Square(lens) => lens.look(point),
+        }
+    }
+
+    fn size(&self) -> Size {
+        match self {
+            MorphologyLens::Kernel(lens) => lens.size(),
+            MorphologyLens::Square(lens) => lens.size(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use super::*;
+    use crate::lens::value::ValueLens;
+
+    const WHITE: Pixel = Pixel::new([255, 255, 255, 255]);
+    const BLACK: Pixel = Pixel::new([0, 0, 0, 255]);
+
+    /// White 15x15 image with black 5x5 square in the middle.
+    fn square_image() -> Image {
+        let size = Size::new(15, 15).unwrap();
+        Image::from_lens(ValueLens::new(WHITE, size).remap(
+            |s, point| {
+                s.look(point).map(|px| {
+                    if (5..10).contains(&point.x()) && (5..10).contains(&point.y()) {
+                        BLACK
+                    } else {
+                        px
+                    }
+                })
+            },
+            size,
+        ))
+    }
+
+    #[test]
+    fn test_erode_square_keeps_corners() {
+        let image =
+            erode(&square_image(), StructuringElement::Square(2), ChannelFlags::RGB).unwrap();
+
+        assert_eq!(image.size(), Size::new(11, 11).unwrap());
+        assert_eq!(image.pixel(Point::new(1, 1).unwrap()).unwrap(), &BLACK);
+        assert_eq!(image.pixel(Point::new(0, 0).unwrap()).unwrap(), &WHITE);
+    }
+
+    #[test]
+    fn test_erode_disk_rounds_corners() {
+        let image = erode(&square_image(), StructuringElement::Disk(2), ChannelFlags::RGB).unwrap();
+
+        assert_eq!(image.size(), Size::new(11, 11).unwrap());
+        assert_eq!(image.pixel(Point::new(1, 1).unwrap()).unwrap(), &WHITE);
+        assert_eq!(image.pixel(Point::new(2, 2).unwrap()).unwrap(), &BLACK);
+        assert_eq!(image.pixel(Point::new(3, 1).unwrap()).unwrap(), &BLACK);
+        assert_eq!(image.pixel(Point::new(1, 3).unwrap()).unwrap(), &BLACK);
+    }
+
+    #[test]
+    fn test_erode_neighborhood_connectivity() {
+        let four = erode(&square_image(), Connectivity::Four.into(), ChannelFlags::RGB).unwrap();
+        let eight = erode(&square_image(), Connectivity::Eight.into(), ChannelFlags::RGB).unwrap();
+
+        // Pixel diagonal to the corner of the square is only reached by 8-connected element.
+        assert_eq!(four.size(), Size::new(13, 13).unwrap());
+        assert_eq!(four.pixel(Point::new(3, 3).unwrap()).unwrap(), &WHITE);
+        assert_eq!(four.pixel(Point::new(4, 3).unwrap()).unwrap(), &BLACK);
+        assert_eq!(eight.pixel(Point::new(3, 3).unwrap()).unwrap(), &BLACK);
+    }
+
+    #[test]
+    fn test_dilate_shrinks_dark_square() {
+        let image =
+            dilate(&square_image(), StructuringElement::Square(1), ChannelFlags::RGB).unwrap();
+
+        assert_eq!(image.size(), Size::new(13, 13).unwrap());
+        assert_eq!(image.pixel(Point::new(4, 4).unwrap()).unwrap(), &WHITE);
+        assert_eq!(image.pixel(Point::new(5, 5).unwrap()).unwrap(), &BLACK);
+        assert_eq!(image.pixel(Point::new(7, 7).unwrap()).unwrap(), &BLACK);
+        assert_eq!(image.pixel(Point::new(8, 8).unwrap()).unwrap(), &WHITE);
+    }
+
+    #[test]
+    fn test_square_matches_kernel() {
+        let image = Image::random(Size::new(23, 17).unwrap(), &mut SmallRng::seed_from_u64(1208));
+
+        for radius in 0..5 {
+            for operation in [MorphologyOperation::Erode, MorphologyOperation::Dilate] {
+                let element = StructuringElement::Square(radius);
+                let kernel = MorphologyKernel::new(element, operation, ChannelFlags::RGB).unwrap();
+                let expected = Image::from_lens(image.lens().kernel(kernel).unwrap());
+                let actual = Image::from_lens(
+                    square_lens(image.lens(), radius, operation, ChannelFlags::RGB).unwrap(),
+                );
+
+                assert_eq!(actual.size(), expected.size());
+                assert_eq!(actual.pixels(), expected.pixels(), "{operation:?} {radius}");
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn test_par_matches_serial() {
+        let image = Image::random(Size::new(23, 17).unwrap(), &mut SmallRng::seed_from_u64(1208));
+        let threads = NonZeroUsize::new(3).unwrap();
+
+        for element in [StructuringElement::Square(3), Connectivity::Eight.into()] {
+            let serial = erode(&image, element, ChannelFlags::RGBA).unwrap();
+            let parallel = erode_par(&image, threads, element, ChannelFlags::RGBA).unwrap();
+            assert_eq!(serial.pixels(), parallel.pixels());
+
+            let serial = dilate(&image, element, ChannelFlags::RGBA).unwrap();
+            let parallel = dilate_par(&image, threads, element, ChannelFlags::RGBA).unwrap();
+            assert_eq!(serial.pixels(), parallel.pixels());
+        }
+    }
+
+    #[test]
+    fn test_square_too_big() {
+        let image = square_image();
+
+        assert!(matches!(
+            erode(&image, StructuringElement::Square(8), ChannelFlags::RGB),
+            Err(MorphologyCreationError::KernelLens(_))
+        ));
+        assert!(erode(&image, StructuringElement::Square(7), ChannelFlags::RGB).is_ok());
+    }
+}
@@ -0,0 +1,213 @@
+#[cfg(feature = "parallel")]
+use std::num::NonZeroUsize;
+
+use crate::{
+    component::{
+        kernel::morphology::MorphologyOperation,
+        primitive::{
+            Margin,
+            Point,
+            Size,
+        },
+    },
+    error::{
+        IndexError,
+        IndexResult,
+    },
+    lens::{
+        self,
+        Lens,
+        materialize::MaterializeLens,
+    },
+    pixel::{
+        ChannelFlags,
+        Pixel,
+    },
+};
+
+type Channels = [u8; 4];
+
+/// Apply `operation` within a square window of given `radius`, with the same result as
+/// [`MorphologyKernel`](crate::component::kernel::morphology::MorphologyKernel) with
+/// [`StructuringElement::Square`](crate::component::kernel::morphology::StructuringElement::Square).
+///
+/// Window is separated into a horizontal and a vertical pass, each using van Herk/Gil-Werman
+/// algorithm, so cost per pixel is constant instead of growing with the area of the window.
+pub(crate) fn square_lens<S>(
+    source: S,
+    radius: usize,
+    operation: MorphologyOperation,
+    flags: ChannelFlags,
+) -> Result<impl Lens<Item = Pixel>, lens::kernel::CreationError>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    square_lens_with(source, radius, operation, flags, |lines| lines.materialize())
+}
+
+/// Apply `operation` within a square window like [`square_lens`], evaluating lines of both passes
+/// using up to `threads` threads.
+#[cfg(feature = "parallel")]
+pub(crate) fn square_lens_par<S>(
+    source: S,
+    radius: usize,
+    operation: MorphologyOperation,
+    flags: ChannelFlags,
+    threads: NonZeroUsize,
+) -> Result<impl Lens<Item = Pixel>, lens::kernel::CreationError>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    square_lens_with(source, radius, operation, flags, move |lines| lines.materialize_par(threads))
+}
+
+/// Apply `operation` within a square window, evaluating every [`ExtremumLines`] with
+/// `materialize`.
+fn square_lens_with<S, M>(
+    source: S,
+    radius: usize,
+    operation: MorphologyOperation,
+    flags: ChannelFlags,
+    materialize: M,
+) -> Result<impl Lens<Item = Pixel>, lens::kernel::CreationError>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+    M: Fn(ExtremumLines) -> MaterializeLens<Vec<Channels>>,
+{
+    // SAFETY: radius was validated by the caller when creating the kernel.
+    let margin = Margin::unified(radius).expect("unexpected error in Margin::unified");
+    let size = lens::kernel::working_size(source.size(), margin)?;
+
+    let pass = |source: MaterializeLens<Channels>, horizontal: bool| {
+        let lines = ExtremumLines { source, radius, operation, horizontal };
+        let size = lines.output_size();
+        from_lines(materialize(lines), size, horizontal)
+    };
+
+    let original = source.map(|px| *px.as_ref()).materialize();
+    let extrema = pass(pass(original.clone().map(|px| *px.buffer()).materialize(), true), false);
+
+    Ok(extrema.remap(
+        move |lens, point| {
+            let result = lens.look(point)?;
+            // SAFETY: point is within the working area, so the center is within `original`.
+            let center = Point::new(point.x() + radius, point.y() + radius)
+                .expect("unexpected error in Point::new");
+            let mut px = original.look(center)?;
+            px.set_with_flags(result[0], result[1], result[2], result[3], flags);
+
+            Ok(px)
+        },
+        size,
+    ))
+}
+
+/// [`Lens`] over lines of a single horizontal or vertical pass of `operation` over `source`.
+///
+/// Every item is a whole line of extrema of windows fully within it, so lines can be evaluated
+/// independently of each other.
+struct ExtremumLines {
+    source: MaterializeLens<Channels>,
+    radius: usize,
+    operation: MorphologyOperation,
+    horizontal: bool,
+}
+
+impl ExtremumLines {
+    fn output_size(&self) -> Size {
+        let size = self.source.size();
+        let (width, height) = match self.horizontal {
+            true => (size.width() - 2 * self.radius, size.height()),
+            false => (size.width(), size.height() - 2 * self.radius),
+        };
+
+        // SAFETY: working size was validated, so both dimensions stay positive.
+        Size::new(width, height).expect("unexpected error in Size::new")
+    }
+
+    fn extremum(&self, a: Channels, b: Channels) -> Channels {
+        std::array::from_fn(|i| match self.operation {
+            MorphologyOperation::Erode => a[i].min(b[i]),
+            MorphologyOperation::Dilate => a[i].max(b[i]),
+        })
+    }
+
+    fn line(&self, line: usize) -> Vec<Channels> {
+        let size = self.source.size();
+        let length = if self.horizontal { size.width() } else { size.height() };
+        let values = (0..length)
+            .map(|position| {
+                let point = match self.horizontal {
+                    true => Point::new(position, line),
+                    false => Point::new(line, position),
+                };
+                // SAFETY: position is within the line and line is within source.
+                let point = point.expect("unexpected error in Point::new");
+                self.source.look(point).expect("unexpected error in Lens::look")
+            })
+            .collect::<Vec<_>>();
+
+        // Extrema from the start and to the end of every block of window length, any window
+        // spans at most two neighbouring blocks.
+        let window = 2 * self.radius + 1;
+        let mut prefix = values.clone();
+        let mut suffix = values;
+        for i in 1..length {
+            if !i.is_multiple_of(window) {
+                prefix[i] = self.extremum(prefix[i - 1], prefix[i]);
+            }
+        }
+        for i in (0..length - 1).rev() {
+            if !(i + 1).is_multiple_of(window) {
+                suffix[i] = self.extremum(suffix[i], suffix[i + 1]);
+            }
+        }
+
+        (0..=length - window)
+            .map(|start| self.extremum(suffix[start], prefix[start + window - 1]))
+            .collect()
+    }
+}
+
+impl Lens for ExtremumLines {
+    type Item = Vec<Channels>;
+
+    fn look(&self, point: Point) -> IndexResult<Self::Item> {
+        if !self.size().contains(&point) {
+            return Err(IndexError::OutOfBounds);
+        }
+
+        Ok(self.line(point.x()))
+    }
+
+    fn size(&self) -> Size {
+        let size = self.source.size();
+        let lines = if self.horizontal { size.height() } else { size.width() };
+
+        // SAFETY: number of lines is a dimension of a valid size.
+        Size::new(lines, 1).expect("unexpected error in Size::new")
+    }
+}
+
+/// Assemble `lines` of a pass back into a lens of `size`.
+fn from_lines(
+    lines: MaterializeLens<Vec<Channels>>,
+    size: Size,
+    horizontal: bool,
+) -> MaterializeLens<Channels> {
+    let mut result = vec![[0u8; 4]; size.area()];
+    for (line, values) in lines.elements().enumerate() {
+        for (position, value) in values.into_iter().enumerate() {
+            let index = match horizontal {
+                true => line * size.width() + position,
+                false => position * size.width() + line,
+            };
+            result[index] = value;
+        }
+    }
+
+    MaterializeLens::from_values(result, size)
+}
@@ -0,0 +1,178 @@
+use thiserror::Error;
+
+use crate::{
+    component::primitive::Scale,
+    image::Image,
+    lens::{
+        FromLens,
+        Lens,
+    },
+    operation::{
+        blur::{
+            GaussianBlurCreationError,
+            gaussian_blur_lens,
+        },
+        color::grayscale_lens,
+        geometry::{
+            ResizeCreationError,
+            resize_lens,
+        },
+    },
+    pixel::{
+        AlphaMode,
+        ChannelFlags,
+        Pixel,
+    },
+};
+
+/// Error returned by [`Pipeline`] steps
+#[derive(Debug, Error)]
+pub enum PipelineError {
+    #[error("gaussian blur failed: {0}")]
+    GaussianBlur(#[from] GaussianBlurCreationError),
+    #[error("resize failed: {0}")]
+    Resize(#[from] ResizeCreationError),
+    #[error("custom step failed: {0}")]
+    Custom(Box<dyn std::error::Error + Send + Sync>),
+}
+
+pub type PipelineResult<T> = std::result::Result<T, PipelineError>;
+
+/// Type erased [`Lens`] passed between [`Pipeline`] steps.
+pub type PipelineLens<'a> = Box<dyn Lens<Item = Pixel> + 'a>;
+
+type Step = Box<dyn for<'a> Fn(PipelineLens<'a>) -> PipelineResult<PipelineLens<'a>>>;
+
+/// Sequence of operations applied to an [`Image`] in order.
+///
+/// Every step wraps [`Lens`] produced by the previous one, so nothing is evaluated until
+/// [`Pipeline::apply`] collects the result. Operations without a dedicated method can be added
+/// with [`Pipeline::then`].
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     operation::pipeline::Pipeline,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let pipeline = Pipeline::new().grayscale().blur_gaussian(1, 1.0).resize(Scale::new(0.5, 0.5)?);
+/// let image = Image::empty(Size::new(10, 10)?);
+///
+/// assert_eq!(pipeline.len(), 3);
+/// assert_eq!(pipeline.apply(&image)?.size(), Size::new(4, 4)?);
+///
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct Pipeline {
+    steps: Vec<Step>,
+}
+
+impl Pipeline {
+    /// Create an empty [`Pipeline`], which returns images unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a custom step creating a new [`Lens`] from the result of previous steps.
+    pub fn then<F>(mut self, step: F) -> Self
+    where
+        F: for<'a> Fn(PipelineLens<'a>) -> PipelineResult<PipelineLens<'a>> + 'static,
+    {
+        self.steps.push(Box::new(step));
+        self
+    }
+
+    /// Add grayscale conversion of color channels, see [`grayscale_lens`].
+    pub fn grayscale(self) -> Self {
+        self.then(|lens| Ok(grayscale_lens(lens, ChannelFlags::RGB).boxed()))
+    }
+
+    /// Add gaussian blur of all channels, see [`gaussian_blur_lens`].
+    pub fn blur_gaussian(self, radius: usize, sigma: f32) -> Self {
+        self.then(move |lens| {
+            Ok(gaussian_blur_lens(lens, radius, sigma, ChannelFlags::RGBA, AlphaMode::Straight)?
+                .boxed())
+        })
+    }
+
+    /// Add nearest neighbor resize, see [`resize_lens`].
+    pub fn resize(self, scale: Scale) -> Self {
+        self.then(move |lens| Ok(resize_lens(lens, scale)?.boxed()))
+    }
+
+    /// Get number of steps.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Check if there are no steps.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Apply all steps to `image` in order.
+    ///
+    /// Returns the first error returned by any step.
+    pub fn apply(&self, image: &Image) -> PipelineResult<Image> {
+        let lens = self.lens(image)?;
+        Ok(Image::from_lens(lens))
+    }
+
+    fn lens<'a>(&self, image: &'a Image) -> PipelineResult<PipelineLens<'a>> {
+        self.steps.iter().try_fold(image.lens().cloned().boxed(), |lens, step| step(lens))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use super::*;
+    use crate::{
+        component::primitive::Size,
+        operation::{
+            blur::gaussian_blur,
+            color::grayscale,
+        },
+    };
+
+    #[test]
+    fn test_matches_manual_composition() {
+        let image = Image::random(Size::new(12, 9).unwrap(), &mut SmallRng::seed_from_u64(0));
+
+        let pipeline = Pipeline::new().grayscale().blur_gaussian(2, 1.5f32);
+        let manual = gaussian_blur(
+            &grayscale(&image, ChannelFlags::RGB),
+            2,
+            1.5f32,
+            ChannelFlags::RGBA,
+            AlphaMode::Straight,
+        )
+        .unwrap();
+
+        assert_eq!(pipeline.apply(&image).unwrap().pixels(), manual.pixels());
+    }
+
+    #[test]
+    fn test_custom_step_and_errors() {
+        let image = Image::empty(Size::new(4, 4).unwrap());
+
+        assert_eq!(Pipeline::new().apply(&image).unwrap().pixels(), image.pixels());
+
+        let invert = |px: Pixel| Pixel::new([255 - px.r(), 255 - px.g(), 255 - px.b(), px.a()]);
+        let inverted = Pipeline::new().then(move |lens| Ok(lens.map(invert).boxed()));
+        let expected = image.pixels().iter().copied().map(invert).collect::<Vec<_>>();
+        assert_eq!(inverted.apply(&image).unwrap().pixels(), expected);
+
+        let too_large = Pipeline::new().blur_gaussian(3, 1f32);
+        assert!(matches!(too_large.apply(&image), Err(PipelineError::GaussianBlur(_))));
+    }
+}
@@ -0,0 +1,290 @@
+use std::collections::HashMap;
+
+use itertools::iproduct;
+use thiserror::Error;
+
+use crate::{
+    component::primitive::Point,
+    image::Image,
+    pixel::Pixel,
+};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum MeanShiftCreationError {
+    #[error("color radius must be finite and positive")]
+    InvalidColorRadius,
+}
+
+pub type MeanShiftCreationResult<T> = std::result::Result<T, MeanShiftCreationError>;
+
+/// Shift smaller than this, in both color and spatial units, ends iteration for a pixel.
+const CONVERGENCE_EPSILON: f32 = 0.01;
+
+/// Segment `image` into flat color regions using mean-shift.
+///
+/// Every pixel starts at its own position and color and is repeatedly moved to the mean of
+/// pixels within `spatial_radius` (square window) whose color is closer than `color_radius`
+/// (Euclidean RGB distance), until the shift is negligible or `max_iter` iterations are done.
+/// Pixels whose resulting colors are closer than half of `color_radius` are then merged into one
+/// region, painted with the mean of their colors. Alpha is preserved.
+///
+/// Cost grows with `spatial_radius` squared, so small radii are recommended for large images.
+///
+/// Returns [`MeanShiftCreationError::InvalidColorRadius`] if `color_radius` is not finite or not
+/// positive.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     operation::segmentation::mean_shift_segment,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let image = Image::solid(Size::new(4, 4)?, Pixel::new([10, 20, 30, 255]));
+/// let segmented = mean_shift_segment(&image, 2, 20.0, 10)?;
+///
+/// assert_eq!(segmented.pixels(), image.pixels());
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn mean_shift_segment(
+    image: &Image,
+    spatial_radius: usize,
+    color_radius: f32,
+    max_iter: usize,
+) -> MeanShiftCreationResult<Image> {
+    if !color_radius.is_finite() || color_radius <= 0f32 {
+        return Err(MeanShiftCreationError::InvalidColorRadius);
+    }
+
+    let size = image.size();
+    let colors = image.pixels().iter().map(|px| [px.r(), px.g(), px.b()].map(f32::from));
+    let colors = colors.collect::<Vec<_>>();
+
+    let modes = (0..size.area())
+        .map(|index| {
+            // SAFETY: index is always less than area of the image.
+            let point =
+                Point::from_index(index, size).expect("unexpected error in Point::from_index");
+            let mut position = [point.x() as f32, point.y() as f32];
+            let mut color = colors[index];
+
+            for _ in 0..max_iter {
+                let (new_position, new_color) =
+                    shift(&colors, size.width(), position, color, spatial_radius, color_radius);
+                let moved = distance(&position, &new_position).max(distance(&color, &new_color));
+                (position, color) = (new_position, new_color);
+
+                if moved < CONVERGENCE_EPSILON {
+                    break;
+                }
+            }
+
+            color
+        })
+        .collect::<Vec<_>>();
+
+    let (labels, clusters) = cluster(&modes, color_radius / 2f32);
+
+    let pixels = image
+        .pixels()
+        .iter()
+        .zip(labels)
+        .map(|(px, label)| {
+            let [r, g, b] = clusters[label].map(|value| value.round().clamp(0f32, 255f32) as u8);
+            Pixel::new([r, g, b, px.a()])
+        })
+        .collect();
+
+    // SAFETY: one pixel is produced for every pixel of the image.
+    Ok(Image::new(size, pixels).expect("unexpected error in Image::new"))
+}
+
+/// Move `position` and `color` to the mean of pixels in the window around `position` with color
+/// within `color_radius` of `color`.
+fn shift(
+    colors: &[[f32; 3]],
+    width: usize,
+    position: [f32; 2],
+    color: [f32; 3],
+    spatial_radius: usize,
+    color_radius: f32,
+) -> ([f32; 2], [f32; 3]) {
+    let height = colors.len() / width;
+    let [center_x, center_y] = position.map(|value| value.round() as usize);
+    let xs = center_x.saturating_sub(spatial_radius)..(center_x + spatial_radius + 1).min(width);
+    let ys = center_y.saturating_sub(spatial_radius)..(center_y + spatial_radius + 1).min(height);
+
+    let mut position_sum = [0f32; 2];
+    let mut color_sum = [0f32; 3];
+    let mut count = 0f32;
+    for y in ys {
+        for x in xs.clone() {
+            let neighbor = colors[y * width + x];
+            if distance(&neighbor, &color) >= color_radius {
+                continue;
+            }
+
+            position_sum[0] += x as f32;
+            position_sum[1] += y as f32;
+            (0..3).for_each(|channel| color_sum[channel] += neighbor[channel]);
+            count += 1f32;
+        }
+    }
+
+    if count == 0f32 {
+        return (position, color);
+    }
+
+    (position_sum.map(|sum| sum / count), color_sum.map(|sum| sum / count))
+}
+
+/// Group `modes` closer than `radius` to the first mode of a group, returning label of every
+/// mode and mean color of every group.
+///
+/// First modes of groups are bucketed into a grid with cells of size `radius`, so only seeds in
+/// the neighboring cells have to be checked. `radius` must be positive. For tiny `radius` cell
+/// coordinates saturate, which only makes the cells coarser, as the distance is checked anyway.
+fn cluster(modes: &[[f32; 3]], radius: f32) -> (Vec<usize>, Vec<[f32; 3]>) {
+    let mut seeds: Vec<[f32; 3]> = Vec::new();
+    let mut grid: HashMap<[i32; 3], Vec<usize>> = HashMap::new();
+    let mut sums: Vec<([f32; 3], f32)> = Vec::new();
+    let cell = |color: &[f32; 3]| color.map(|value| (value / radius).floor() as i32);
+
+    let labels = modes
+        .iter()
+        .map(|mode| {
+            let [r, g, b] = cell(mode);
+            let nearest = iproduct!(-1..=1, -1..=1, -1..=1)
+                .filter_map(|(dr, dg, db)| {
+                    grid.get(&[r.saturating_add(dr), g.saturating_add(dg), b.saturating_add(db)])
+                })
+                .flatten()
+                .copied()
+                .filter(|&label| distance(&seeds[label], mode) < radius)
+                .min();
+
+            let label = match nearest {
+                Some(label) => label,
+                None => {
+                    seeds.push(*mode);
+                    grid.entry([r, g, b]).or_default().push(seeds.len() - 1);
+                    sums.push(([0f32; 3], 0f32));
+                    seeds.len() - 1
+                }
+            };
+
+            let (sum, count) = &mut sums[label];
+            (0..3).for_each(|channel| sum[channel] += mode[channel]);
+            *count += 1f32;
+
+            label
+        })
+        .collect();
+
+    let means = sums.into_iter().map(|(sum, count)| sum.map(|value| value / count)).collect();
+
+    (labels, means)
+}
+
+fn distance<const N: usize>(a: &[f32; N], b: &[f32; N]) -> f32 {
+    a.iter().zip(b).map(|(a, b)| (a - b) * (a - b)).sum::<f32>().sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        Rng,
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use crate::component::primitive::Size;
+
+    use super::*;
+
+    /// Left half is reddish and right half is bluish, both with a little noise.
+    fn two_regions() -> Image {
+        let mut rng = SmallRng::seed_from_u64(1213);
+        let size = Size::new(12, 8).unwrap();
+        let pixels = (0..size.area())
+            .map(|index| {
+                let base = if index % 12 < 6 { [200, 40, 40] } else { [30, 60, 210] };
+                let [r, g, b] = base.map(|value: u8| value + rng.random_range(0..6));
+                Pixel::new([r, g, b, 255])
+            })
+            .collect();
+
+        Image::new(size, pixels).unwrap()
+    }
+
+    #[test]
+    fn test_two_regions_become_flat() {
+        let image = two_regions();
+
+        let segmented = mean_shift_segment(&image, 3, 30f32, 20).unwrap();
+
+        let left = *segmented.pixel(Point::zero()).unwrap();
+        let right = *segmented.pixel(Point::new(11, 7).unwrap()).unwrap();
+        assert_ne!(left, right);
+        for (index, px) in segmented.pixels().iter().enumerate() {
+            assert_eq!(*px, if index % 12 < 6 { left } else { right });
+        }
+        assert!((200..206).contains(&left.r()) && (210..216).contains(&right.b()));
+    }
+
+    #[test]
+    fn test_converges_within_max_iter() {
+        let image = two_regions();
+
+        let converged = mean_shift_segment(&image, 3, 30f32, 20).unwrap();
+
+        assert_eq!(
+            mean_shift_segment(&image, 3, 30f32, 1000).unwrap().pixels(),
+            converged.pixels()
+        );
+    }
+
+    #[test]
+    fn test_zero_iterations_keeps_distinct_colors() {
+        let image = two_regions();
+
+        let segmented = mean_shift_segment(&image, 3, 1f32, 0).unwrap();
+
+        assert_eq!(segmented.pixels(), image.pixels());
+    }
+
+    #[test]
+    fn test_invalid_color_radius() {
+        let image = two_regions();
+
+        for color_radius in [0f32, -1f32, f32::NAN, f32::INFINITY] {
+            assert!(matches!(
+                mean_shift_segment(&image, 3, color_radius, 10),
+                Err(MeanShiftCreationError::InvalidColorRadius)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_tiny_color_radius_keeps_distinct_colors() {
+        let image = two_regions();
+
+        let segmented = mean_shift_segment(&image, 1, 1e-7, 2).unwrap();
+
+        assert_eq!(segmented.pixels(), image.pixels());
+    }
+
+    #[test]
+    fn test_cluster_matches_first_seed() {
+        let modes = [[0f32; 3], [4f32, 0f32, 0f32], [8f32, 0f32, 0f32], [9f32, 0f32, 0f32]];
+
+        let (labels, means) = cluster(&modes, 5f32);
+
+        assert_eq!(labels, vec![0, 0, 1, 1]);
+        assert_eq!(means, vec![[2f32, 0f32, 0f32], [8.5f32, 0f32, 0f32]]);
+    }
+}
@@ -0,0 +1,7 @@
+mod mean_shift;
+
+pub use mean_shift::{
+    MeanShiftCreationError,
+    MeanShiftCreationResult,
+    mean_shift_segment,
+};
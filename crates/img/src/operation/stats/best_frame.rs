@@ -0,0 +1,152 @@
+use crate::{
+    image::Image,
+    lens::Lens,
+    operation::{
+        compare::diff,
+        detection::edge::gradient_magnitude_lens,
+    },
+};
+
+/// Mean absolute difference of color channels below which a frame is considered a duplicate of
+/// the previous one.
+const DUPLICATE_THRESHOLD: f32 = 2f32;
+
+/// Pick index of the most representative frame of an animation, e.g. to use as its thumbnail.
+///
+/// Frames are scored by sharpness, the mean Sobel gradient magnitude of their luma, so blurry
+/// transitions and flat frames are avoided. A frame which is a near duplicate of the previous one
+/// (mean color difference below 2) adds nothing new and is skipped, even if it is slightly
+/// sharper, so for a held shot the index of its first frame is returned. Frames of different size
+/// than their predecessor always start a new shot. Ties are resolved in favor of the earlier
+/// frame. Returns 0 if `frames` is empty.
+///
+/// # Examples
+///
+/// ```
+/// use img::{
+///     operation::stats::best_frame,
+///     prelude::*,
+/// };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// let flat = Image::solid(Size::new(4, 4)?, Pixel::new([128, 128, 128, 255]));
+/// let mut detailed = flat.clone();
+/// *detailed.pixel_mut(Point::new(1, 1)?)? = Pixel::new([255, 255, 255, 255]);
+///
+/// assert_eq!(best_frame(&[flat, detailed]), 1);
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn best_frame<'a, I>(frames: I) -> usize
+where
+    I: IntoIterator<Item = &'a Image>,
+{
+    let mut best = (0, f32::MIN);
+    let mut previous: Option<&Image> = None;
+    for (index, frame) in frames.into_iter().enumerate() {
+        let is_duplicate = previous.is_some_and(|previous| {
+            diff(previous, frame).is_ok_and(|report| {
+                report.mean()[..3].iter().sum::<f32>() / 3f32 < DUPLICATE_THRESHOLD
+            })
+        });
+        previous = Some(frame);
+        if is_duplicate {
+            continue;
+        }
+
+        let sharpness = sharpness(frame);
+        if sharpness > best.1 {
+            best = (index, sharpness);
+        }
+    }
+
+    best.0
+}
+
+/// Mean Sobel gradient magnitude of luma of `image`.
+fn sharpness(image: &Image) -> f32 {
+    gradient_magnitude_lens(image.lens()).elements().sum::<f32>() / image.size().area() as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        component::primitive::{
+            Point,
+            Size,
+        },
+        lens::FromLens,
+        operation::blur::gaussian_blur,
+        pixel::{
+            AlphaMode,
+            ChannelFlags,
+            Pixel,
+        },
+    };
+
+    const RED: Pixel = Pixel::new([255, 0, 0, 255]);
+    const GREEN: Pixel = Pixel::new([0, 255, 0, 255]);
+
+    /// Checkerboard with 2x2 squares.
+    fn checkerboard(size: Size) -> Image {
+        Image::from_lens(Image::empty(size).lens().map_with_point(|point, _| {
+            match (point.x() / 2 + point.y() / 2) % 2 {
+                0 => Pixel::new([0, 0, 0, 255]),
+                _ => Pixel::new([255, 255, 255, 255]),
+            }
+        }))
+    }
+
+    /// Left half red, right half green.
+    fn edge(size: Size) -> Image {
+        Image::from_lens(Image::empty(size).lens().map_with_point(|point, _| {
+            match point.x() < size.width() / 2 {
+                true => RED,
+                false => GREEN,
+            }
+        }))
+    }
+
+    #[test]
+    fn test_best_frame_sharpest() {
+        let sharp = checkerboard(Size::new(16, 16).unwrap());
+        // Blur shrinks the image by radius on every side.
+        let blurred = gaussian_blur(
+            &checkerboard(Size::new(22, 22).unwrap()),
+            3,
+            1.5,
+            ChannelFlags::RGB,
+            AlphaMode::Straight,
+        )
+        .unwrap();
+        let gray = Image::solid(sharp.size(), Pixel::new([128, 128, 128, 255]));
+
+        assert_eq!(best_frame(&[gray, blurred.clone(), sharp, blurred]), 2);
+    }
+
+    #[test]
+    fn test_best_frame_skips_duplicates() {
+        let size = Size::new(8, 8).unwrap();
+        let edge = edge(size);
+        let frames = [Image::solid(size, RED), edge.clone(), edge.clone(), edge];
+
+        // Held shot is represented by its first frame.
+        assert_eq!(best_frame(&frames), 1);
+        assert_eq!(best_frame(&frames[..1]), 0);
+        assert_eq!(best_frame(&[]), 0);
+    }
+
+    #[test]
+    fn test_best_frame_skips_sharper_near_duplicate() {
+        let size = Size::new(8, 8).unwrap();
+        let edge = edge(size);
+        let mut near_duplicate = edge.clone();
+        *near_duplicate.pixel_mut(Point::new(6, 4).unwrap()).unwrap() =
+            Pixel::new([60, 255, 0, 255]);
+
+        assert!(sharpness(&near_duplicate) > sharpness(&edge));
+        assert_eq!(best_frame(&[Image::solid(size, RED), edge, near_duplicate]), 1);
+    }
+}
@@ -0,0 +1,132 @@
+#[cfg(feature = "parallel")]
+use std::num::NonZeroUsize;
+
+use thiserror::Error;
+
+use crate::{
+    component::{
+        kernel::stat::StatKernel,
+        primitive::SizeCreationError,
+    },
+    gray_image::GrayImage,
+    image::Image,
+    lens::{
+        self,
+        FromLens,
+        Lens,
+    },
+    pixel::Pixel,
+};
+
+/// Error returned by local_entropy functions
+#[derive(Debug, Error)]
+pub enum LocalEntropyCreationError {
+    #[error("failed to create size: {0}")]
+    Size(#[from] SizeCreationError),
+    #[error("failed to create kernel lens: {0}")]
+    KernelLens(#[from] lens::kernel::CreationError),
+}
+
+pub type LocalEntropyCreationResult<T> = std::result::Result<T, LocalEntropyCreationError>;
+
+/// Create a [`Lens`] where each value is Shannon entropy of luma histogram within a square
+/// window of `2 * radius + 1` side, as a measure of texture or complexity.
+///
+/// Entropy is scaled so that the largest one possible for the window, every luma value being
+/// different (or every one of 256 values appearing equally often), maps to 255. Flat regions
+/// give 0. Useful for finding detailed, in focus regions or regions of interest.
+///
+/// Like other kernel based lenses, result is smaller than `source` by `radius` on every side.
+pub fn local_entropy_lens<S>(
+    source: S,
+    radius: usize,
+) -> LocalEntropyCreationResult<impl Lens<Item = u8>>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    let kernel = StatKernel::new(radius, entropy)?;
+    Ok(source.kernel(kernel)?)
+}
+
+/// Calculate local entropy of luma of an image, see [`local_entropy_lens`].
+pub fn local_entropy(image: &Image, radius: usize) -> LocalEntropyCreationResult<GrayImage> {
+    let lens = local_entropy_lens(image.lens(), radius)?;
+    Ok(GrayImage::from_lens(lens))
+}
+
+/// Calculate local entropy of luma of an image in parallel, see [`local_entropy_lens`].
+#[cfg(feature = "parallel")]
+pub fn local_entropy_par(
+    image: &Image,
+    threads: NonZeroUsize,
+    radius: usize,
+) -> LocalEntropyCreationResult<GrayImage> {
+    use crate::lens::FromLensPar;
+
+    let lens = local_entropy_lens(image.lens(), radius)?;
+    Ok(GrayImage::from_lens_par(lens, threads))
+}
+
+/// Entropy of histogram of `values` rounded to `u8`, scaled to 0-255.
+fn entropy(values: &[f32]) -> f32 {
+    let mut histogram = [0usize; 256];
+    values.iter().for_each(|value| histogram[value.round().clamp(0f32, 255f32) as usize] += 1);
+
+    let count = values.len() as f32;
+    let entropy = histogram
+        .iter()
+        .filter(|frequency| **frequency > 0)
+        .map(|frequency| {
+            let probability = *frequency as f32 / count;
+            -probability * probability.log2()
+        })
+        .sum::<f32>();
+    let max_entropy = count.min(256f32).log2();
+
+    match max_entropy > 0f32 {
+        true => entropy / max_entropy * 255f32,
+        false => 0f32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use super::*;
+    use crate::component::primitive::{
+        Point,
+        Size,
+    };
+
+    #[test]
+    fn test_local_entropy_flat_and_noisy() {
+        let size = Size::new(20, 10).unwrap();
+        let noise = Image::random_gray(size, &mut SmallRng::seed_from_u64(0));
+        // Left half is flat gray, right half is noise.
+        let image =
+            Image::from_lens(noise.lens().map_with_point(|point, px| match point.x() < 10 {
+                true => Pixel::new([128, 128, 128, 255]),
+                false => *px,
+            }));
+
+        let entropy = local_entropy(&image, 2).unwrap();
+
+        assert_eq!(entropy.size(), Size::new(16, 6).unwrap());
+        for y in 0..6 {
+            assert_eq!(entropy.value(Point::new(3, y).unwrap()).unwrap(), 0);
+            assert!(entropy.value(Point::new(14, y).unwrap()).unwrap() > 230);
+        }
+    }
+
+    #[test]
+    fn test_entropy_two_values() {
+        // Two equally frequent values carry one bit, out of two possible for 4 values.
+        assert_eq!(entropy(&[0f32, 0f32, 255f32, 255f32]), 127.5);
+        assert_eq!(entropy(&[7f32]), 0f32);
+    }
+}
@@ -0,0 +1,146 @@
+#[cfg(feature = "parallel")]
+use std::num::NonZeroUsize;
+
+use crate::{
+    component::{
+        primitive::{
+            Area,
+            Point,
+            Size,
+        },
+        summed_area::SummedArea,
+    },
+    error::IndexError,
+    gray_image::GrayImage,
+    image::Image,
+    lens::{
+        FromLens,
+        Lens,
+    },
+    pixel::Pixel,
+};
+
+/// Create a [`Lens`] where each value is the standard deviation of luma within a square window
+/// of `2 * radius + 1` side around the point, scaled so that the largest possible deviation
+/// (half of the window black, half white) maps to 255. Flat regions give 0, detailed or noisy
+/// regions give large values, which makes the result useful as a texture mask.
+///
+/// Windows are clipped at the edges of `source`, so the size of `source` is preserved. Sums of
+/// luma and its squares are precomputed into a [`SummedArea`] table, so the cost per point does
+/// not depend on `radius`.
+pub fn local_std_dev_lens<S>(source: S, radius: usize) -> impl Lens<Item = u8>
+where
+    S: Lens,
+    S::Item: AsRef<Pixel>,
+{
+    let luma = source.map(|px| px.as_ref().luminance() as f64);
+    let size = luma.size();
+    let sums = SummedArea::new(luma.elements().map(|value| [value, value * value]), size);
+
+    luma.remap(
+        move |_, point| {
+            if !size.contains(&point) {
+                return Err(IndexError::OutOfBounds);
+            }
+
+            let left = point.x().saturating_sub(radius);
+            let top = point.y().saturating_sub(radius);
+            let right = (point.x() + radius + 1).min(size.width());
+            let bottom = (point.y() + radius + 1).min(size.height());
+            // SAFETY: window always contains `point`, so it is non-empty and within `size`.
+            let area = Area::new(
+                Size::new(right - left, bottom - top).expect("unexpected error in Size::new"),
+                Point::new(left, top).expect("unexpected error in Point::new"),
+            );
+
+            let [sum, sum_squares] = sums.sum(area);
+            let count = area.size().area() as f64;
+            let mean = sum / count;
+            let variance = (sum_squares / count - mean * mean).max(0f64);
+
+            Ok((variance.sqrt() * 2f64).round().min(255f64) as u8)
+        },
+        size,
+    )
+}
+
+/// Calculate local standard deviation of luma of an image, see [`local_std_dev_lens`].
+pub fn local_std_dev(image: &Image, radius: usize) -> GrayImage {
+    let lens = local_std_dev_lens(image.lens(), radius);
+    GrayImage::from_lens(lens)
+}
+
+/// Calculate local standard deviation of luma of an image in parallel, see
+/// [`local_std_dev_lens`].
+#[cfg(feature = "parallel")]
+pub fn local_std_dev_par(image: &Image, threads: NonZeroUsize, radius: usize) -> GrayImage {
+    use crate::lens::FromLensPar;
+
+    GrayImage::from_lens_par(local_std_dev_lens(image.lens(), radius), threads)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use crate::lens::value::ValueLens;
+
+    use super::*;
+
+    #[test]
+    fn test_local_std_dev_flat_and_textured() {
+        let size = Size::new(20, 10).unwrap();
+        let noise = Image::random_gray(size, &mut SmallRng::seed_from_u64(0));
+        // Left half is flat gray, right half is a checkerboard.
+        let image = Image::from_lens(ValueLens::new(Pixel::zero(), size).remap(
+            |_, point| {
+                let value = if point.x() < 10 {
+                    128
+                } else if (point.x() + point.y()) % 2 == 0 {
+                    255
+                } else {
+                    0
+                };
+                Ok(Pixel::new([value, value, value, 255]))
+            },
+            size,
+        ));
+
+        let deviation = local_std_dev(&image, 2);
+
+        for y in 0..10 {
+            assert_eq!(deviation.value(Point::new(2, y).unwrap()).unwrap(), 0);
+            assert!(deviation.value(Point::new(16, y).unwrap()).unwrap() > 240);
+        }
+
+        let noise_deviation = local_std_dev(&noise, 2);
+        assert!(noise_deviation.buffer().iter().all(|value| *value > 50));
+    }
+
+    #[test]
+    fn test_local_std_dev_matches_naive() {
+        let size = Size::new(7, 6).unwrap();
+        let image = Image::random_gray(size, &mut SmallRng::seed_from_u64(1));
+        let radius = 1;
+
+        let deviation = local_std_dev(&image, radius);
+
+        for point in (0..size.area()).map(|index| Point::from_index(index, size).unwrap()) {
+            let window: Vec<f64> = (0..size.area())
+                .map(|index| Point::from_index(index, size).unwrap())
+                .filter(|other| other.x().abs_diff(point.x()) <= radius)
+                .filter(|other| other.y().abs_diff(point.y()) <= radius)
+                .map(|other| image.pixel(other).unwrap().r() as f64)
+                .collect();
+            let mean = window.iter().sum::<f64>() / window.len() as f64;
+            let variance = window.iter().map(|value| (value - mean).powi(2)).sum::<f64>()
+                / window.len() as f64;
+            let expected = (variance.sqrt() * 2f64).round().min(255f64) as u8;
+
+            assert!(deviation.value(point).unwrap().abs_diff(expected) <= 1);
+        }
+    }
+}
@@ -0,0 +1,21 @@
+mod best_frame;
+mod local_entropy;
+mod local_std_dev;
+
+pub use best_frame::best_frame;
+pub use local_entropy::{
+    LocalEntropyCreationError,
+    LocalEntropyCreationResult,
+    local_entropy,
+    local_entropy_lens,
+};
+pub use local_std_dev::{
+    local_std_dev,
+    local_std_dev_lens,
+};
+
+#[cfg(feature = "parallel")]
+pub use self::{
+    local_entropy::local_entropy_par,
+    local_std_dev::local_std_dev_par,
+};
@@ -1,9 +1,13 @@
 use bitflags::bitflags;
 use rand::Rng;
 
-use crate::pixel::hsv::HsvPixel;
+use crate::{
+    gray_image::REC601_WEIGHTS,
+    pixel::hsv::HsvPixel,
+};
 
 pub mod hsv;
+pub mod srgb;
 
 /// Pixel size of an image in bytes
 ///
@@ -16,7 +20,7 @@ bitflags! {
     ///
     /// Some operations suppport this as a parameter to specify which channel should be
     /// affected.
-    #[derive(Clone, Copy, Debug)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
     pub struct ChannelFlags: u8 {
         const RED = 0b1000;
         const GREEN = 0b0100;
@@ -28,10 +32,77 @@ bitflags! {
     }
 }
 
+/// Policy of converting scaled floating point channel values to `u8`, used by
+/// [`PixelRgbaf32`] setters.
+///
+/// Every mode clamps the result to 0-255 range, NaN becomes 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoundingMode {
+    /// Round to nearest, halfway cases away from zero, `127.5` becomes `128`.
+    #[default]
+    Round,
+    /// Round down, `127.5` becomes `127`.
+    Floor,
+    /// Round up, `127.5` becomes `128`.
+    Ceil,
+    /// Drop fractional part like an `as` cast, `127.5` becomes `127`.
+    ///
+    /// Equal to [`RoundingMode::Floor`] within 0-255 range, as negative values are clamped to 0
+    /// either way.
+    Truncate,
+}
+
+impl RoundingMode {
+    /// Convert channel `value` in 0-255 range to `u8`.
+    pub fn apply(self, value: f32) -> u8 {
+        let value = match self {
+            RoundingMode::Round => value.round(),
+            RoundingMode::Floor => value.floor(),
+            RoundingMode::Ceil => value.ceil(),
+            RoundingMode::Truncate => value.trunc(),
+        };
+
+        value.clamp(0f32, 255f32) as u8
+    }
+}
+
+/// Interpretation of color channels in relation to alpha, used by operations which mix
+/// neighbouring pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AlphaMode {
+    /// Color channels are mixed as stored, independently of alpha. Color of transparent pixels
+    /// bleeds into their neighbours.
+    #[default]
+    Straight,
+    /// Color channels are multiplied by alpha before mixing and divided by the mixed alpha
+    /// afterwards, so colors are weighted by opacity.
+    Premultiplied,
+}
+
+/// Color space in which channels are interpolated, used by resampling operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorSpace {
+    /// Interpolate gamma encoded sRGB values directly.
+    #[default]
+    Srgb,
+    /// Interpolate color channels in linear light, which keeps the brightness of mixed colors
+    /// correct. Alpha is not gamma encoded and is interpolated directly.
+    Linear,
+}
+
 /// A `struct` representing RGBA pixel.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(transparent)]
 pub struct Pixel([u8; PIXEL_SIZE]);
 
+// SAFETY: Pixel is a transparent wrapper over [u8; PIXEL_SIZE], which is Zeroable.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for Pixel {}
+
+// SAFETY: Pixel is a transparent wrapper over [u8; PIXEL_SIZE], which is Pod.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for Pixel {}
+
 impl Pixel {
     /// Create a [`Pixel`] from given array.
     ///
@@ -98,6 +169,93 @@ impl Pixel {
         &self.0
     }
 
+    /// Get 0-1 normalized luminance, weighted sum of color channels using ITU-R BT.601
+    /// [`REC601_WEIGHTS`]. Alpha is ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use img::prelude::*;
+    ///
+    /// assert_eq!(Pixel::new([255, 255, 255, 0]).luminance_f32(), 1.0);
+    /// assert!((Pixel::new([0, 255, 0, 255]).luminance_f32() - 0.587).abs() < 1e-6);
+    /// ```
+    pub fn luminance_f32(&self) -> f32 {
+        (REC601_WEIGHTS[0] * self.r() as f32
+            + REC601_WEIGHTS[1] * self.g() as f32
+            + REC601_WEIGHTS[2] * self.b() as f32)
+            / 255f32
+    }
+
+    /// Get luminance in 0-255 range, [`Pixel::luminance_f32`] scaled and rounded to nearest.
+    ///
+    /// # Examples
+    /// ```
+    /// use img::prelude::*;
+    ///
+    /// assert_eq!(Pixel::new([255, 255, 255, 0]).luminance(), 255);
+    /// assert_eq!(Pixel::new([255, 0, 0, 255]).luminance(), 76);
+    /// ```
+    pub fn luminance(&self) -> u8 {
+        (self.luminance_f32() * 255f32).round() as u8
+    }
+
+    /// Linearly interpolate between `self` and `other` on every channel. `t` of 0 gives `self`,
+    /// 1 gives `other`, values outside of 0-1 range are clamped.
+    ///
+    /// # Examples
+    /// ```
+    /// use img::prelude::*;
+    /// let black = Pixel::new([0, 0, 0, 255]);
+    /// let white = Pixel::new([255, 255, 255, 255]);
+    ///
+    /// assert_eq!(black.lerp(white, 0.5), Pixel::new([128, 128, 128, 255]));
+    /// assert_eq!(black.lerp(white, 2.0), white);
+    /// ```
+    pub fn lerp(&self, other: Pixel, t: f32) -> Pixel {
+        let t = t.clamp(0f32, 1f32);
+
+        Pixel(std::array::from_fn(|i| {
+            let from = self.0[i] as f32;
+            let to = other.0[i] as f32;
+            (from + (to - from) * t).round() as u8
+        }))
+    }
+
+    /// Composite `self` over `background` with straight alpha "source over" operator.
+    ///
+    /// Resulting alpha is `a + b * (1 - a)`, where `a` and `b` are alphas of `self` and
+    /// `background`, so result is opaque whenever `background` is. Colors are averaged with
+    /// weights of their contribution to that alpha. Fully transparent result is [`Pixel::zero`].
+    ///
+    /// # Examples
+    /// ```
+    /// use img::prelude::*;
+    /// let red = Pixel::new([255, 0, 0, 128]);
+    /// let white = Pixel::new([255, 255, 255, 255]);
+    ///
+    /// assert_eq!(red.blend_over(white), Pixel::new([255, 127, 127, 255]));
+    /// ```
+    pub fn blend_over(self, background: Pixel) -> Pixel {
+        let top_alpha = self.a_f32();
+        let background_alpha = background.a_f32() * (1f32 - top_alpha);
+        let alpha = top_alpha + background_alpha;
+        if alpha == 0f32 {
+            return Pixel::zero();
+        }
+
+        let channel = |top: u8, bottom: u8| {
+            let value = (top as f32 * top_alpha + bottom as f32 * background_alpha) / alpha;
+            value.round().clamp(0f32, 255f32) as u8
+        };
+
+        Pixel([
+            channel(self.r(), background.r()),
+            channel(self.g(), background.g()),
+            channel(self.b(), background.b()),
+            (alpha * 255f32).round() as u8,
+        ])
+    }
+
     /// Set [`Pixel`] values ignoring channels not specified in `flags`.
     ///
     /// # Examples
@@ -156,10 +314,46 @@ pub trait PixelRgbaf32 {
     /// Set 0-1 normalized alpha component.
     fn set_a_f32(&mut self, value: f32);
 
+    /// Set 0-1 normalized red component, converting it with given [`RoundingMode`].
+    ///
+    /// # Examples
+    /// ```
+    /// use img::prelude::*;
+    /// let mut pixel = Pixel::zero();
+    ///
+    /// pixel.set_r_f32_with(0.5, RoundingMode::Floor);
+    ///
+    /// assert_eq!(pixel.r(), 127);
+    /// ```
+    fn set_r_f32_with(&mut self, value: f32, mode: RoundingMode) {
+        self.set_r_f32(quantize(value, mode));
+    }
+
+    /// Set 0-1 normalized green component, converting it with given [`RoundingMode`].
+    fn set_g_f32_with(&mut self, value: f32, mode: RoundingMode) {
+        self.set_g_f32(quantize(value, mode));
+    }
+
+    /// Set 0-1 normalized blue component, converting it with given [`RoundingMode`].
+    fn set_b_f32_with(&mut self, value: f32, mode: RoundingMode) {
+        self.set_b_f32(quantize(value, mode));
+    }
+
+    /// Set 0-1 normalized alpha component, converting it with given [`RoundingMode`].
+    fn set_a_f32_with(&mut self, value: f32, mode: RoundingMode) {
+        self.set_a_f32(quantize(value, mode));
+    }
+
     /// Set [`Pixel`] values ignoring channels not specified in `flags`.
     fn set_with_flags_f32(&mut self, r: f32, g: f32, b: f32, a: f32, flags: ChannelFlags);
 }
 
+/// Convert 0-1 normalized `value` with `mode` and normalize it back, so that rounding to nearest
+/// in [`PixelRgbaf32`] setters keeps the converted value.
+fn quantize(value: f32, mode: RoundingMode) -> f32 {
+    mode.apply(value * 255.0) as f32 / 255.0
+}
+
 impl PixelRgbaf32 for Pixel {
     /// Get 0-1 normalized red component.
     fn r_f32(&self) -> f32 {
@@ -275,9 +469,10 @@ impl From<HsvPixel> for Pixel {
     fn from(value: HsvPixel) -> Self {
         let c = value.value() * value.saturation();
 
-        let h = (value.hue() / 60f32) as i8;
+        let sector = (value.hue() / 60f32) % 6f32;
+        let h = sector as i8;
 
-        let x = c * (1 - (h % 2 - 1).abs()) as f32;
+        let x = c * (1f32 - (sector % 2f32 - 1f32).abs());
 
         let (r1, g1, b1) = match h {
             0 => (c, x, 0f32),
@@ -305,6 +500,53 @@ impl From<HsvPixel> for Pixel {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_blend_over() {
+        let white = Pixel::new([255, 255, 255, 255]);
+        let red = Pixel::new([255, 0, 0, 255]);
+
+        assert_eq!(
+            Pixel::new([255, 0, 0, 128]).blend_over(white),
+            Pixel::new([255, 127, 127, 255])
+        );
+        assert_eq!(red.blend_over(white), red);
+        assert_eq!(Pixel::new([10, 20, 30, 0]).blend_over(white), white);
+        assert_eq!(Pixel::zero().blend_over(Pixel::zero()), Pixel::zero());
+        // Half transparent over half transparent covers three quarters.
+        assert_eq!(
+            Pixel::new([255, 0, 0, 128]).blend_over(Pixel::new([0, 0, 255, 128])),
+            Pixel::new([170, 0, 85, 192])
+        );
+    }
+
+    #[test]
+    fn test_rounding_modes() {
+        let cases = [
+            (RoundingMode::Round, 128),
+            (RoundingMode::Floor, 127),
+            (RoundingMode::Ceil, 128),
+            (RoundingMode::Truncate, 127),
+        ];
+
+        for (mode, expected) in cases {
+            let mut pixel = Pixel::zero();
+            pixel.set_r_f32_with(0.5, mode);
+            pixel.set_g_f32_with(0.5, mode);
+            pixel.set_b_f32_with(0.5, mode);
+            pixel.set_a_f32_with(0.5, mode);
+
+            assert_eq!(pixel, Pixel::new([expected; 4]), "{mode:?}");
+            // Out of range values are clamped.
+            assert_eq!(mode.apply(-3.7), 0, "{mode:?}");
+            assert_eq!(mode.apply(300.2), 255, "{mode:?}");
+            assert_eq!(mode.apply(f32::NAN), 0, "{mode:?}");
+        }
+
+        let mut pixel = Pixel::zero();
+        pixel.set_r_f32(0.5);
+        assert_eq!(pixel.r(), 128);
+    }
+
     #[test]
     fn test_pixel_from_hsv_pixel() {
         let cases = vec![
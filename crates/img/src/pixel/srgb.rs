@@ -0,0 +1,34 @@
+//! Conversions between sRGB encoded and linear light channel values.
+//!
+//! Averaging or interpolating sRGB encoded values darkens results, so operations mixing colors
+//! can decode values with [`to_linear`], mix them and encode back with [`from_linear`].
+
+/// Decode a 0-1 normalized sRGB channel value into linear light.
+///
+/// # Examples
+///
+/// ```
+/// use img::pixel::srgb::to_linear;
+///
+/// assert_eq!(to_linear(0.0), 0.0);
+/// assert!((to_linear(1.0) - 1.0).abs() < 1e-6);
+/// assert!((to_linear(0.5) - 0.214).abs() < 1e-3);
+/// ```
+pub fn to_linear(value: f32) -> f32 {
+    if value <= 0.04045 { value / 12.92 } else { ((value + 0.055) / 1.055).powf(2.4) }
+}
+
+/// Encode a 0-1 normalized linear light channel value as sRGB.
+///
+/// # Examples
+///
+/// ```
+/// use img::pixel::srgb::from_linear;
+///
+/// assert_eq!(from_linear(0.0), 0.0);
+/// assert!((from_linear(1.0) - 1.0).abs() < 1e-6);
+/// assert!((from_linear(0.5) * 255.0 - 188.0).abs() < 0.5);
+/// ```
+pub fn from_linear(value: f32) -> f32 {
+    if value <= 0.0031308 { value * 12.92 } else { 1.055 * value.powf(1f32 / 2.4) - 0.055 }
+}
@@ -1,58 +1,173 @@
 pub use crate::{
     component::primitive::{
         Area,
+        Connectivity,
         Margin,
         Offset,
         Point,
         Scale,
         Size,
     },
+    gray_image::GrayImage,
     image::{
         DIMENSION_MAX,
         Image,
     },
+    image_view::ImageView,
     operation::{
+        blend::{
+            anaglyph,
+            composite_premultiplied,
+            focus_stack,
+            image_arithmetic,
+            laplacian_blend,
+            stack_average,
+        },
         blur::{
+            bilateral,
+            box_blur,
+            gaussian_approx,
             gaussian_blur,
             kuwahara,
+            kuwahara_generalized,
             mean_blur,
+            mean_blur_disk,
+            radial_blur,
+            separable_gaussian_blur,
         },
         color::{
+            brightness,
+            color_matrix,
+            extract_channel,
             gamma_correction,
             grayscale,
+            histogram_match,
+            linear,
             negative,
+            negative_value,
+            quantize_kmeans,
+            reduce_depth,
+            selective_color,
             sepia,
+            tone_map,
+            white_balance,
+        },
+        compare::{
+            change_mask,
+            diff,
+            diff_image,
+            psnr,
+            ssim,
+        },
+        detection::{
+            blob::detect_blobs,
+            edge::{
+                CannyLensOptions,
+                canny,
+                energy_map,
+            },
+        },
+        film::{
+            add_grain,
+            dust_scratches,
+        },
+        frequency::{
+            frequency_separation,
+            high_pass,
         },
-        detection::edge::canny,
         geometry::{
             crop,
+            crop_subpixel,
+            deskew,
+            guided_upsample,
             resize,
+            rotate,
+            rotate_arbitrary,
+            trim_transparent,
+            warp_affine,
+        },
+        morphology::{
+            dilate,
+            erode,
+        },
+        segmentation::mean_shift_segment,
+        stats::{
+            local_entropy,
+            local_std_dev,
         },
     },
     pixel::{
+        AlphaMode,
         ChannelFlags,
+        ColorSpace,
         Pixel,
         PixelRgbaf32,
+        RoundingMode,
         hsv::HsvPixel,
     },
 };
 
 #[cfg(feature = "parallel")]
 pub use crate::operation::{
+    blend::image_arithmetic_par,
     blur::{
+        bilateral_par,
+        box_blur_par,
+        gaussian_approx_par,
         gaussian_blur_par,
+        kuwahara_generalized_par,
         kuwahara_par,
+        mean_blur_disk_par,
         mean_blur_par,
+        radial_blur_par,
+        separable_gaussian_blur_par,
     },
     color::{
+        brightness_par,
+        color_matrix_par,
+        extract_channel_par,
         gamma_correction_par,
         grayscale_par,
+        histogram_match_par,
+        linear_par,
         negative_par,
+        negative_value_par,
+        reduce_depth_par,
+        selective_color_par,
         sepia_par,
+        tone_map_par,
+        white_balance_par,
     },
-    detection::edge::canny_par,
+    detection::edge::{
+        canny_par,
+        energy_map_par,
+    },
+    film::{
+        add_grain_par,
+        dust_scratches_par,
+    },
+    frequency::high_pass_par,
     geometry::{
         crop_par,
+        crop_subpixel_par,
         resize_par,
+        rotate_arbitrary_par,
+        rotate_par,
+        warp_affine_par,
+    },
+    morphology::{
+        dilate_par,
+        erode_par,
     },
+    stats::{
+        local_entropy_par,
+        local_std_dev_par,
+    },
+};
+
+#[cfg(feature = "simd")]
+pub use crate::operation::color::{
+    brightness_simd,
+    grayscale_simd,
+    negative_simd,
 };
@@ -1,16 +1,80 @@
 use std::fmt;
 
 use img::{
-    component::kernel::identity::IdentityKernel,
+    component::{
+        kernel::{
+            identity::IdentityKernel,
+            morphology::StructuringElement,
+        },
+        lens::border::BorderMode,
+        sampler::EdgeMode,
+    },
     lens::{
+        FromLens,
         Lens,
+        run_length::RunLengthMask,
+        test_pattern::{
+            TestPattern,
+            TestPatternLens,
+        },
         value::ValueLens,
     },
-    operation::color::{
-        gamma_correction_lens,
-        grayscale_lens,
-        negative_lens,
-        sepia_lens,
+    operation::{
+        blend::{
+            ArithmeticOp,
+            image_arithmetic_lens,
+            over_checkerboard_lens,
+        },
+        blur::{
+            RadialKind,
+            bilateral_lens,
+            box_blur_lens,
+            gaussian_approx_lens,
+            kuwahara_generalized_lens,
+            mean_blur_disk_lens,
+            radial_blur_lens,
+            separable_gaussian_blur_lens,
+        },
+        color::{
+            COLOR_MATRIX_IDENTITY,
+            ToneMapOperator,
+            color_matrix_lens,
+            extract_channel_lens,
+            gamma_correction_lens,
+            grayscale_lens,
+            histogram_match_lens,
+            linear_lens,
+            negative_lens,
+            negative_value_lens,
+            reduce_depth_lens,
+            selective_color_lens,
+            sepia_lens,
+            tone_map_lens,
+            white_balance_lens,
+        },
+        detection::edge::{
+            energy_map_lens,
+            sobel_color_lens,
+        },
+        film::{
+            add_grain_lens,
+            dust_scratches_lens,
+        },
+        frequency::high_pass_lens,
+        geometry::{
+            crop_subpixel_lens,
+            resize_bilinear_lens,
+            rotate_arbitrary_lens,
+            rotate_lens,
+        },
+        morphology::{
+            dilate_lens,
+            erode_lens,
+        },
+        stats::{
+            local_entropy_lens,
+            local_std_dev_lens,
+        },
     },
     prelude::*,
 };
@@ -57,6 +121,7 @@ macro_rules! test_lens {
             pub fn [<test_ $name>]() {
                 let mut rng = SmallRng::seed_from_u64(0);
                 let lens = $lens;
+                lens.assert_valid();
                 for _ in 0..$iterations {
                     assert_lens_size_sync(&lens, &mut rng);
                     assert_lens_repeated_look(&lens, &mut rng);
@@ -72,6 +137,12 @@ fn prepare_image_lens(width: usize, height: usize) -> impl Lens<Item = &'static
 
 test_lens!(image_lens, prepare_image_lens(50, 100), 100);
 
+fn prepare_test_pattern_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    TestPatternLens::new(Size::new(width, height).unwrap(), TestPattern::Circles)
+}
+
+test_lens!(test_pattern_lens, prepare_test_pattern_lens(50, 100), 100);
+
 fn prepare_map_lens(width: usize, height: usize) -> impl Lens<Item = u8> {
     prepare_test_image(width, height).lens().map(|s| s.r())
 }
@@ -87,6 +158,19 @@ fn prepare_split2_lens(
 
 test_lens!(split2_lens, prepare_split2_lens(50, 100), 100);
 
+fn prepare_map2_lens(width: usize, height: usize) -> impl Lens<Item = u8> {
+    let image = prepare_test_image(width, height);
+    image.lens().map2(image.lens().to_luma(), |px, luma| px.r().abs_diff(luma))
+}
+
+test_lens!(map2_lens, prepare_map2_lens(50, 100), 100);
+
+fn prepare_step_lens(width: usize, height: usize) -> impl Lens<Item = &'static Pixel> {
+    prepare_test_image(width, height).lens().step_by(Size::new(3, 4).unwrap())
+}
+
+test_lens!(step_lens, prepare_step_lens(50, 100), 100);
+
 fn prepare_split3_lens(
     width: usize,
     height: usize,
@@ -150,6 +234,27 @@ fn prepare_overlay_lens(width: usize, height: usize) -> impl Lens<Item = Pixel>
 
 test_lens!(overlay_lens, prepare_overlay_lens(50, 100), 100);
 
+fn prepare_color_matrix_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    color_matrix_lens(
+        prepare_test_image(width, height).lens(),
+        COLOR_MATRIX_IDENTITY,
+        [10.0, -10.0, 0.0],
+        ChannelFlags::RGB,
+    )
+}
+
+test_lens!(color_matrix_lens, prepare_color_matrix_lens(50, 100), 100);
+
+fn prepare_linear_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    linear_lens(
+        prepare_test_image(width, height).lens(),
+        [1.2, 0.8, 1.0, 1.0],
+        [0.05, 0.0, -0.1, 0.0],
+    )
+}
+
+test_lens!(linear_lens, prepare_linear_lens(50, 100), 100);
+
 fn prepare_negative_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
     negative_lens(prepare_test_image(width, height).lens(), ChannelFlags::RGBA)
 }
@@ -173,3 +278,247 @@ fn prepare_gamma_correction_lens(width: usize, height: usize) -> impl Lens<Item
 }
 
 test_lens!(gamma_correction_lens, prepare_gamma_correction_lens(50, 100), 100);
+
+fn prepare_histogram_match_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    histogram_match_lens(
+        prepare_test_image(width, height).lens(),
+        prepare_test_image(height, width).lens(),
+        ChannelFlags::RGBA,
+    )
+}
+
+test_lens!(histogram_match_lens, prepare_histogram_match_lens(50, 100), 100);
+
+fn prepare_erode_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    erode_lens(
+        prepare_test_image(width, height).lens(),
+        StructuringElement::Disk(2),
+        ChannelFlags::RGBA,
+    )
+    .unwrap()
+}
+
+test_lens!(erode_lens, prepare_erode_lens(50, 100), 100);
+
+fn prepare_dilate_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    dilate_lens(
+        prepare_test_image(width, height).lens(),
+        StructuringElement::Square(3),
+        ChannelFlags::RGBA,
+    )
+    .unwrap()
+}
+
+test_lens!(dilate_lens, prepare_dilate_lens(50, 100), 100);
+
+fn prepare_rotate_arbitrary_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    rotate_arbitrary_lens(prepare_test_image(width, height).lens(), 0.5, EdgeMode::Reflect, false)
+}
+
+test_lens!(rotate_arbitrary_lens, prepare_rotate_arbitrary_lens(50, 100), 100);
+
+fn prepare_rotate_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    rotate_lens(prepare_test_image(width, height).lens(), 0.3, BorderMode::Reflect)
+}
+
+test_lens!(rotate_lens, prepare_rotate_lens(50, 100), 100);
+
+fn prepare_resize_bilinear_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    resize_bilinear_lens(
+        prepare_test_image(width, height).lens(),
+        Scale::new(0.7, 1.3).unwrap(),
+        EdgeMode::Clamp,
+        ColorSpace::Linear,
+    )
+    .unwrap()
+}
+
+test_lens!(resize_bilinear_lens, prepare_resize_bilinear_lens(50, 100), 100);
+
+fn prepare_box_blur_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    box_blur_lens(prepare_test_image(width, height).lens(), 3, ChannelFlags::RGBA)
+}
+
+test_lens!(box_blur_lens, prepare_box_blur_lens(50, 100), 100);
+
+fn prepare_mean_blur_disk_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    mean_blur_disk_lens(prepare_test_image(width, height).lens(), 3, ChannelFlags::RGBA).unwrap()
+}
+
+test_lens!(mean_blur_disk_lens, prepare_mean_blur_disk_lens(50, 100), 100);
+
+fn prepare_bilateral_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    bilateral_lens(prepare_test_image(width, height).lens(), 2, 2f32, 30f32).unwrap()
+}
+
+test_lens!(bilateral_lens, prepare_bilateral_lens(50, 100), 100);
+
+fn prepare_separable_gaussian_blur_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    separable_gaussian_blur_lens(
+        prepare_test_image(width, height).lens(),
+        3,
+        1.5,
+        ChannelFlags::RGBA,
+    )
+    .unwrap()
+}
+
+test_lens!(separable_gaussian_blur_lens, prepare_separable_gaussian_blur_lens(50, 100), 100);
+
+fn prepare_gaussian_approx_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    gaussian_approx_lens(prepare_test_image(width, height).lens(), 2.5, ChannelFlags::RGBA).unwrap()
+}
+
+test_lens!(gaussian_approx_lens, prepare_gaussian_approx_lens(50, 100), 100);
+
+fn prepare_negative_value_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    negative_value_lens(prepare_test_image(width, height).lens())
+}
+
+test_lens!(negative_value_lens, prepare_negative_value_lens(50, 100), 100);
+
+fn prepare_extract_channel_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    extract_channel_lens(prepare_test_image(width, height).lens(), ChannelFlags::GREEN).unwrap()
+}
+
+test_lens!(extract_channel_lens, prepare_extract_channel_lens(50, 100), 100);
+
+fn prepare_reduce_depth_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    reduce_depth_lens(prepare_test_image(width, height).lens(), 3, ChannelFlags::RGB).unwrap()
+}
+
+test_lens!(reduce_depth_lens, prepare_reduce_depth_lens(50, 100), 100);
+
+fn prepare_high_pass_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    high_pass_lens(prepare_test_image(width, height).lens(), 2, 1.5, ChannelFlags::RGB).unwrap()
+}
+
+test_lens!(high_pass_lens, prepare_high_pass_lens(50, 100), 100);
+
+fn prepare_kuwahara_generalized_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    kuwahara_generalized_lens(prepare_test_image(width, height).lens(), 2, 8).unwrap()
+}
+
+test_lens!(kuwahara_generalized_lens, prepare_kuwahara_generalized_lens(50, 100), 100);
+
+fn prepare_radial_blur_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    let center = Point::new(width / 3, height / 2).unwrap();
+    radial_blur_lens(prepare_test_image(width, height).lens(), center, 0.2, RadialKind::Zoom)
+        .unwrap()
+}
+
+test_lens!(radial_blur_lens, prepare_radial_blur_lens(50, 100), 100);
+
+fn prepare_add_grain_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    add_grain_lens(prepare_test_image(width, height).lens(), 12.0, 1238).unwrap()
+}
+
+test_lens!(add_grain_lens, prepare_add_grain_lens(50, 100), 100);
+
+fn prepare_dust_scratches_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    dust_scratches_lens(prepare_test_image(width, height).lens(), 2, 30)
+}
+
+test_lens!(dust_scratches_lens, prepare_dust_scratches_lens(50, 100), 100);
+
+fn prepare_crop_subpixel_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    let area = (3.25, 7.5, width as f32 / 2.0, height as f32 / 3.0);
+    crop_subpixel_lens(prepare_test_image(width, height).lens(), area).unwrap()
+}
+
+test_lens!(crop_subpixel_lens, prepare_crop_subpixel_lens(50, 100), 100);
+
+fn prepare_sobel_color_lens(width: usize, height: usize) -> impl Lens<Item = u32> {
+    // Magnitudes are compared bitwise, as f32 is not Eq.
+    sobel_color_lens(prepare_test_image(width, height).lens()).unwrap().map(f32::to_bits)
+}
+
+test_lens!(sobel_color_lens, prepare_sobel_color_lens(50, 100), 100);
+
+fn prepare_energy_map_lens(width: usize, height: usize) -> impl Lens<Item = u8> {
+    energy_map_lens(prepare_test_image(width, height).lens())
+}
+
+test_lens!(energy_map_lens, prepare_energy_map_lens(50, 100), 100);
+
+fn prepare_to_luma_lens(width: usize, height: usize) -> impl Lens<Item = u8> {
+    prepare_test_image(width, height).lens().to_luma()
+}
+
+test_lens!(to_luma_lens, prepare_to_luma_lens(50, 100), 100);
+
+fn prepare_map_with_point_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    prepare_test_image(width, height).lens().map_with_point(|point, px| {
+        let mut px = *px;
+        px.set_g(point.x() as u8);
+        px
+    })
+}
+
+test_lens!(map_with_point_lens, prepare_map_with_point_lens(50, 100), 100);
+
+fn prepare_local_std_dev_lens(width: usize, height: usize) -> impl Lens<Item = u8> {
+    local_std_dev_lens(prepare_test_image(width, height).lens(), 3)
+}
+
+test_lens!(local_std_dev_lens, prepare_local_std_dev_lens(50, 100), 100);
+
+fn prepare_local_entropy_lens(width: usize, height: usize) -> impl Lens<Item = u8> {
+    local_entropy_lens(prepare_test_image(width, height).lens(), 2).unwrap()
+}
+
+test_lens!(local_entropy_lens, prepare_local_entropy_lens(50, 100), 100);
+
+fn prepare_white_balance_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    white_balance_lens(prepare_test_image(width, height).lens(), 4000.0)
+}
+
+test_lens!(white_balance_lens, prepare_white_balance_lens(50, 100), 100);
+
+fn prepare_selective_color_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    selective_color_lens(prepare_test_image(width, height).lens(), 120.0, 40.0, 0.2, 0.1)
+}
+
+test_lens!(selective_color_lens, prepare_selective_color_lens(50, 100), 100);
+
+fn prepare_tone_map_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    tone_map_lens(prepare_test_image(width, height).lens(), ToneMapOperator::AcesFilmic)
+}
+
+test_lens!(tone_map_lens, prepare_tone_map_lens(50, 100), 100);
+
+fn prepare_over_checkerboard_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    over_checkerboard_lens(
+        prepare_test_image(width, height).lens(),
+        8,
+        Pixel::new([204, 204, 204, 255]),
+        Pixel::new([153, 153, 153, 255]),
+    )
+    .unwrap()
+}
+
+test_lens!(over_checkerboard_lens, prepare_over_checkerboard_lens(50, 100), 100);
+
+fn prepare_image_arithmetic_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    let image = prepare_test_image(width, height);
+    let inverted = image.lens().map(|px| Pixel::new(px.buffer().map(|value| !value)));
+    image_arithmetic_lens(image.lens(), inverted, ArithmeticOp::Multiply).unwrap()
+}
+
+test_lens!(image_arithmetic_lens, prepare_image_arithmetic_lens(50, 100), 100);
+
+fn prepare_run_length_mask(width: usize, height: usize) -> impl Lens<Item = u8> {
+    let image = prepare_test_image(width, height);
+    RunLengthMask::from_lens(image.lens().map(|px| if px.r() > 127 { 255 } else { 0 }))
+}
+
+test_lens!(run_length_mask, prepare_run_length_mask(50, 100), 100);
+
+fn prepare_inspect_lens(width: usize, height: usize) -> impl Lens<Item = Pixel> {
+    prepare_test_image(width, height)
+        .lens()
+        .cloned()
+        .inspect(move |point, _| assert!(point.x() < width))
+}
+
+test_lens!(inspect_lens, prepare_inspect_lens(50, 100), 100);